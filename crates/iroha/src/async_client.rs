@@ -0,0 +1,401 @@
+//! Asynchronous client, to be used from within a `tokio` runtime.
+//!
+//! Unlike [`Client`], it doesn't block the thread on requests and reuses the connections to the peer between them,
+//! so it is meant to be created once and cloned wherever needed.
+
+use std::{
+    fmt::{self, Debug},
+    num::NonZeroU64,
+    pin::Pin,
+    str::FromStr,
+    sync::Arc,
+    task::{Context, Poll},
+};
+
+use eyre::{eyre, Result, WrapErr};
+use futures_util::{
+    stream::{self, BoxStream},
+    Stream, StreamExt,
+};
+use http::header::{HeaderMap, HeaderName, HeaderValue, ACCEPT};
+use iroha_telemetry::metrics::Status;
+use iroha_torii_shared::uri as torii_uri;
+use iroha_version::prelude::*;
+use parity_scale_codec::{DecodeAll, Encode};
+
+use crate::{
+    client::{
+        events_api::AsyncEventStream, join_torii_url, query_subscription_api, tx_status_filters,
+        wait_for_tx_status, Client, QueryResult, StatusResponseHandler, TransactionResponseHandler,
+    },
+    config::Config,
+    crypto::HashOf,
+    data_model::{
+        block::SignedBlock,
+        events::pipeline::TransactionStatus,
+        isi::Instruction,
+        prelude::*,
+        query::{
+            builder::{AsyncQueryExecutor, AsyncQueryIterator, HasTypedBatchIter, QueryBuilder},
+            parameters::ForwardCursor,
+            Query, QueryOutputBatchBoxTuple, QueryWithParams, SingularQuery, SingularQueryBox,
+            SingularQueryOutputBox,
+        },
+    },
+    http::Response,
+    query::{decode_iterable_query_response, decode_singular_query_response, QueryError},
+};
+
+/// Asynchronous Iroha client with the same settings as the [`Client`] it is constructed from
+#[derive(Debug, Clone)]
+pub struct AsyncClient {
+    client: Arc<Client>,
+    http: reqwest::Client,
+}
+
+impl AsyncClient {
+    /// Constructor for client from configuration
+    ///
+    /// # Errors
+    /// Fails if the HTTP client can't be built with the configured TLS settings
+    pub fn new(configuration: Config) -> Result<Self> {
+        Self::from_client(Client::new(configuration))
+    }
+
+    /// Construct an asynchronous client with the headers and TLS settings of the `client`
+    ///
+    /// # Errors
+    /// Fails if the headers are invalid or the HTTP client can't be built with the TLS settings
+    pub fn from_client(client: Client) -> Result<Self> {
+        let mut headers = HeaderMap::new();
+        for (name, value) in &client.headers {
+            headers.insert(
+                HeaderName::from_str(name)
+                    .wrap_err_with(|| format!("Failed to parse header name {name}"))?,
+                HeaderValue::from_str(value)
+                    .wrap_err_with(|| format!("Failed to parse value of header {name}"))?,
+            );
+        }
+
+        let mut builder = reqwest::Client::builder().default_headers(headers);
+        if let Some(tls) = &client.tls {
+            builder = tls.configure(builder);
+        }
+        let http = builder
+            .build()
+            .wrap_err("Failed to build the HTTP client")?;
+
+        Ok(Self {
+            client: Arc::new(client),
+            http,
+        })
+    }
+
+    /// The blocking client this one is constructed from, e.g. to build and sign transactions
+    pub fn client(&self) -> &Client {
+        &self.client
+    }
+
+    async fn send(&self, request: reqwest::RequestBuilder) -> Result<Response<Vec<u8>>> {
+        let response = request.send().await?;
+
+        let mut builder = Response::builder()
+            .status(response.status())
+            .version(response.version());
+        if let Some(headers) = builder.headers_mut() {
+            headers.clone_from(response.headers());
+        }
+        let body = response.bytes().await?;
+
+        builder
+            .body(body.to_vec())
+            .wrap_err("Failed to construct the response")
+    }
+
+    /// Instructions API entry point. Submits one Iroha Special Instruction to `Iroha` peers.
+    /// Returns submitted transaction's hash or error string.
+    ///
+    /// # Errors
+    /// Fails if sending transaction to peer fails or if it response with error
+    pub async fn submit<I: Instruction>(&self, isi: I) -> Result<HashOf<SignedTransaction>> {
+        self.submit_all([isi]).await
+    }
+
+    /// Instructions API entry point. Submits several Iroha Special Instructions to `Iroha` peers.
+    /// Returns submitted transaction's hash or error string.
+    ///
+    /// # Errors
+    /// Fails if sending transaction to peer fails or if it response with error
+    pub async fn submit_all<I: Instruction>(
+        &self,
+        instructions: impl IntoIterator<Item = I>,
+    ) -> Result<HashOf<SignedTransaction>> {
+        let transaction = self
+            .client
+            .build_transaction(instructions, Metadata::default());
+        self.submit_transaction(&transaction).await
+    }
+
+    /// Submit a prebuilt transaction.
+    /// Returns submitted transaction's hash or error string.
+    ///
+    /// # Errors
+    /// Fails if sending transaction to peer fails or if it response with error
+    pub async fn submit_transaction(
+        &self,
+        transaction: &SignedTransaction,
+    ) -> Result<HashOf<SignedTransaction>> {
+        iroha_logger::trace!(tx=?transaction, "Submitting");
+        let hash = transaction.hash();
+        let request = self
+            .http
+            .post(join_torii_url(
+                &self.client.torii_url,
+                torii_uri::TRANSACTION,
+            ))
+            .body(transaction.encode_versioned());
+
+        let response = self
+            .send(request)
+            .await
+            .wrap_err_with(|| format!("Failed to send transaction with hash {hash:?}"))?;
+        TransactionResponseHandler::handle(&response)?;
+        Ok(hash)
+    }
+
+    /// Submits several Iroha Special Instructions and waits until the transaction is either rejected or committed.
+    /// See [`Self::submit_transaction_and_wait`].
+    ///
+    /// # Errors
+    /// Fails if sending transaction to peer fails or if it response with error
+    pub async fn submit_all_and_wait<I: Instruction>(
+        &self,
+        instructions: impl IntoIterator<Item = I>,
+    ) -> Result<TransactionStatus> {
+        let transaction = self
+            .client
+            .build_transaction(instructions, Metadata::default());
+        self.submit_transaction_and_wait(&transaction).await
+    }
+
+    /// Submit the prebuilt transaction and wait for its final status:
+    /// [`TransactionStatus::Approved`] once it is committed,
+    /// [`TransactionStatus::Rejected`] with the rejection reason,
+    /// [`TransactionStatus::Expired`] or [`TransactionStatus::Evicted`].
+    ///
+    /// # Errors
+    /// Fails if sending the transaction fails or the status isn't known
+    /// within the timeout configured with `transaction.status_timeout_ms`
+    pub async fn submit_transaction_and_wait(
+        &self,
+        transaction: &SignedTransaction,
+    ) -> Result<TransactionStatus> {
+        let hash = transaction.hash();
+        let timeout = self.client.transaction_status_timeout;
+        let deadline = tokio::time::Instant::now() + timeout;
+
+        // the events are subscribed to before submitting, so that none of them is missed
+        let mut events =
+            tokio::time::timeout_at(deadline, self.listen_for_events(tx_status_filters(hash)))
+                .await
+                .map_err(Into::into)
+                .and_then(std::convert::identity)
+                .wrap_err("Failed to establish event listener connection")?;
+
+        let result = async {
+            self.submit_transaction(transaction).await?;
+            tokio::time::timeout_at(deadline, wait_for_tx_status(&mut events))
+                .await
+                .wrap_err_with(|| {
+                    eyre!(
+                        "haven't got tx confirmation within {timeout:?} (configured with `transaction.status_timeout_ms`)"
+                    )
+                })?
+        }
+        .await;
+        events.close().await;
+        result
+    }
+
+    /// Connect (through `WebSocket`) to listen for `Iroha` `pipeline` and `data` events.
+    ///
+    /// # Errors
+    /// Fails if the connection can't be established
+    pub async fn listen_for_events(
+        &self,
+        event_filters: impl IntoIterator<Item = impl Into<EventFilterBox>> + Send,
+    ) -> Result<AsyncEventStream> {
+        self.client.listen_for_events_async(event_filters).await
+    }
+
+    /// Connect (through `WebSocket`) to listen for `Iroha` blocks starting from the `height`
+    ///
+    /// # Errors
+    /// Fails if the connection can't be established
+    pub async fn listen_for_blocks(
+        &self,
+        height: NonZeroU64,
+    ) -> Result<impl Stream<Item = Result<SignedBlock>>> {
+        self.client.listen_for_blocks_async(height).await
+    }
+
+    /// Connect (through `WebSocket`) to subscribe to the results of an iterable `query`,
+    /// see [`Client::listen_for_query`].
+    ///
+    /// # Errors
+    /// Fails if the connection can't be established
+    pub async fn listen_for_query(
+        &self,
+        query: QueryWithParams,
+    ) -> Result<query_subscription_api::AsyncQuerySubscriptionStream> {
+        self.client.listen_for_query_async(query).await
+    }
+
+    /// Gets network status seen from the peer
+    ///
+    /// # Errors
+    /// Fails if sending request or decoding fails
+    pub async fn get_status(&self) -> Result<Status> {
+        let request = self
+            .http
+            .get(join_torii_url(&self.client.torii_url, torii_uri::STATUS))
+            .header(ACCEPT, "application/x-parity-scale");
+
+        let response = self.send(request).await?;
+        let body = StatusResponseHandler::handle(&response)?;
+        DecodeAll::decode_all(&mut body.as_slice()).map_err(|err| eyre!("{err}"))
+    }
+
+    async fn send_query(&self, request: QueryRequest) -> Result<Response<Vec<u8>>> {
+        let query = request
+            .with_authority(self.client.account.clone())
+            .sign(&self.client.key_pair);
+        let request = self
+            .http
+            .post(join_torii_url(&self.client.torii_url, torii_uri::QUERY))
+            .body(query.encode());
+
+        self.send(request).await
+    }
+
+    #[expect(clippy::type_complexity)]
+    fn into_batch(
+        self,
+        response: &Response<Vec<u8>>,
+    ) -> QueryResult<(QueryOutputBatchBoxTuple, u64, Option<AsyncQueryCursor>)> {
+        let (batch, remaining_items, cursor) =
+            decode_iterable_query_response(response)?.into_parts();
+        let cursor = cursor.map(|cursor| AsyncQueryCursor {
+            client: self,
+            cursor,
+        });
+
+        Ok((batch, remaining_items, cursor))
+    }
+
+    /// Execute a singular query and return the result
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the query execution fails.
+    pub async fn query_single<Q>(&self, query: Q) -> QueryResult<Q::Output>
+    where
+        Q: SingularQuery,
+        SingularQueryBox: From<Q>,
+        Q::Output: TryFrom<SingularQueryOutputBox>,
+        <Q::Output as TryFrom<SingularQueryOutputBox>>::Error: Debug,
+    {
+        let result = self
+            .execute_singular_query(SingularQueryBox::from(query))
+            .await?;
+
+        Ok(result
+            .try_into()
+            .expect("BUG: iroha returned unexpected type in singular query"))
+    }
+
+    /// Build an iterable query and return a builder object.
+    ///
+    /// The query is executed with [`QueryBuilder::execute_async`], and its results can be consumed as a [`QueryStream`].
+    pub fn query<Q>(&self, query: Q) -> QueryBuilder<Self, Q, Q::Item>
+    where
+        Q: Query,
+    {
+        QueryBuilder::new(self, query)
+    }
+}
+
+/// An iterable query cursor for use in the asynchronous client
+#[derive(Debug)]
+pub struct AsyncQueryCursor {
+    client: AsyncClient,
+    cursor: ForwardCursor,
+}
+
+impl AsyncQueryCursor {
+    /// The raw cursor to fetch the next batch of results.
+    pub fn raw(&self) -> &ForwardCursor {
+        &self.cursor
+    }
+}
+
+impl AsyncQueryExecutor for AsyncClient {
+    type Cursor = AsyncQueryCursor;
+    type Error = QueryError;
+
+    async fn execute_singular_query(
+        &self,
+        query: SingularQueryBox,
+    ) -> Result<SingularQueryOutputBox, Self::Error> {
+        let response = self.send_query(QueryRequest::Singular(query)).await?;
+        decode_singular_query_response(&response)
+    }
+
+    async fn start_query(
+        &self,
+        query: QueryWithParams,
+    ) -> Result<(QueryOutputBatchBoxTuple, u64, Option<Self::Cursor>), Self::Error> {
+        let response = self.send_query(QueryRequest::Start(query)).await?;
+        self.clone().into_batch(&response)
+    }
+
+    async fn continue_query(
+        cursor: Self::Cursor,
+    ) -> Result<(QueryOutputBatchBoxTuple, u64, Option<Self::Cursor>), Self::Error> {
+        let AsyncQueryCursor { client, cursor } = cursor;
+        let response = client.send_query(QueryRequest::Continue(cursor)).await?;
+        client.into_batch(&response)
+    }
+}
+
+/// Stream of the results of an iterable query, fetching the next batch once the current one is consumed
+pub struct QueryStream<T>(BoxStream<'static, QueryResult<T>>);
+
+impl<T> From<AsyncQueryIterator<AsyncClient, T>> for QueryStream<T>
+where
+    T: HasTypedBatchIter + Send + 'static,
+    T::TypedBatchIter: Send,
+{
+    fn from(iter: AsyncQueryIterator<AsyncClient, T>) -> Self {
+        Self(
+            stream::try_unfold(iter, |mut iter| async move {
+                Ok(iter.try_next().await?.map(|item| (item, iter)))
+            })
+            .boxed(),
+        )
+    }
+}
+
+impl<T> Debug for QueryStream<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("QueryStream").finish_non_exhaustive()
+    }
+}
+
+impl<T> Stream for QueryStream<T> {
+    type Item = QueryResult<T>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.0.poll_next_unpin(cx)
+    }
+}
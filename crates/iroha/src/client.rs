@@ -23,7 +23,10 @@ use parity_scale_codec::DecodeAll;
 use rand::Rng;
 use url::Url;
 
-use self::{blocks_api::AsyncBlockStream, events_api::AsyncEventStream};
+use self::{
+    blocks_api::AsyncBlockStream, events_api::AsyncEventStream,
+    query_subscription_api::AsyncQuerySubscriptionStream,
+};
 pub use crate::query::QueryError;
 use crate::{
     config::Config,
@@ -36,11 +39,12 @@ use crate::{
         },
         isi::Instruction,
         prelude::*,
+        query::QueryWithParams,
         transaction::TransactionBuilder,
         ChainId,
     },
     http::{Method as HttpMethod, RequestBuilder, Response, StatusCode},
-    http_default::{self, DefaultRequestBuilder, WebSocketError, WebSocketMessage},
+    http_default::{self, ClientTls, DefaultRequestBuilder, WebSocketError, WebSocketMessage},
 };
 
 const APPLICATION_JSON: &str = "application/json";
@@ -50,10 +54,10 @@ pub type QueryResult<T> = core::result::Result<T, QueryError>;
 
 /// Phantom struct that handles Transaction API HTTP response
 #[derive(Clone, Copy)]
-struct TransactionResponseHandler;
+pub(crate) struct TransactionResponseHandler;
 
 impl TransactionResponseHandler {
-    fn handle(resp: &Response<Vec<u8>>) -> Result<()> {
+    pub(crate) fn handle(resp: &Response<Vec<u8>>) -> Result<()> {
         if resp.status() == StatusCode::OK {
             Ok(())
         } else {
@@ -71,7 +75,7 @@ impl TransactionResponseHandler {
 pub struct StatusResponseHandler;
 
 impl StatusResponseHandler {
-    fn handle(resp: &Response<Vec<u8>>) -> Result<&Vec<u8>> {
+    pub(crate) fn handle(resp: &Response<Vec<u8>>) -> Result<&Vec<u8>> {
         if resp.status() != StatusCode::OK {
             return Err(ResponseReport::with_msg("Unexpected status response", resp)
                 .unwrap_or_else(core::convert::identity)
@@ -138,6 +142,8 @@ pub struct Client {
     /// If `true` add nonce, which makes different hashes for
     /// transactions which occur repeatedly and/or simultaneously
     pub add_transaction_nonce: bool,
+    /// Custom TLS settings of the connections
+    pub tls: Option<ClientTls>,
 }
 
 /// Representation of `Iroha` client.
@@ -162,6 +168,7 @@ impl Client {
             transaction_add_nonce,
             transaction_ttl,
             transaction_status_timeout,
+            tls,
         }: Config,
         mut headers: HashMap<String, String>,
     ) -> Self {
@@ -185,6 +192,7 @@ impl Client {
             account,
             headers,
             add_transaction_nonce: transaction_add_nonce,
+            tls,
         }
     }
 
@@ -217,6 +225,62 @@ impl Client {
             .sign(self.key_pair.private_key())
     }
 
+    /// Builds transaction out of supplied instructions or wasm,
+    /// which replaces the pending `transaction` in the queue of the peer.
+    ///
+    /// The replacement takes the sequence number of `transaction`,
+    /// so only one of them can be committed.
+    ///
+    /// # Errors
+    /// Fails if `transaction` isn't submitted by this client's account or has no sequence number
+    pub fn build_replacement<Exec: Into<Executable>>(
+        &self,
+        transaction: &SignedTransaction,
+        instructions: Exec,
+        metadata: Metadata,
+    ) -> Result<SignedTransaction> {
+        if transaction.authority() != &self.account {
+            return Err(eyre!(
+                "Only transactions of the account {} can be replaced",
+                self.account
+            ));
+        }
+        let sequence = transaction
+            .sequence()
+            .ok_or_else(|| eyre!("Only transactions with a sequence number can be replaced"))?;
+
+        let mut tx_builder = TransactionBuilder::new(self.chain.clone(), self.account.clone())
+            .with_executable(instructions.into())
+            .with_metadata(metadata);
+        if let Some(transaction_ttl) = self.transaction_ttl {
+            tx_builder.set_ttl(transaction_ttl);
+        }
+        tx_builder.set_sequence(sequence);
+
+        Ok(tx_builder.sign(self.key_pair.private_key()))
+    }
+
+    /// Cancel the pending `transaction` by replacing it with a transaction
+    /// which only logs the cancellation.
+    /// The cancelling transaction is gossiped and replaces `transaction` on other peers as well,
+    /// and `transaction` is rejected in the block if the cancelling one is committed first.
+    /// Returns the hash of the cancelling transaction.
+    ///
+    /// # Errors
+    /// - Fails if `transaction` can't be replaced, see [`Self::build_replacement`]
+    /// - Fails if sending transaction to peer fails or if it response with error
+    pub fn cancel_transaction(
+        &self,
+        transaction: &SignedTransaction,
+    ) -> Result<HashOf<SignedTransaction>> {
+        let log = Log::new(
+            Level::INFO,
+            format!("Cancel transaction {}", transaction.hash()),
+        );
+        let cancellation = self.build_replacement(transaction, [log], Metadata::default())?;
+        self.submit_transaction(&cancellation)
+    }
+
     /// Signs transaction
     ///
     /// # Errors
@@ -286,6 +350,7 @@ impl Client {
         iroha_logger::trace!(tx=?transaction, "Submitting");
         let (req, hash) = self.prepare_transaction_request::<DefaultRequestBuilder>(transaction);
         let response = req
+            .tls(self.tls.as_ref())
             .build()?
             .send()
             .wrap_err_with(|| format!("Failed to send transaction with hash {hash:?}"))?;
@@ -340,12 +405,7 @@ impl Client {
 
         rt.block_on(async {
             let mut event_iterator = {
-                let filters = vec![
-                    TransactionEventFilter::default().for_hash(hash).into(),
-                    PipelineEventFilterBox::from(
-                        BlockEventFilter::default().for_status(BlockStatus::Applied),
-                    ),
-                ];
+                let filters = tx_status_filters(hash);
 
                 let event_iterator_result =
                     tokio::time::timeout_at(deadline, self.listen_for_events_async(filters))
@@ -378,37 +438,13 @@ impl Client {
         event_iterator: &mut AsyncEventStream,
         hash: HashOf<SignedTransaction>,
     ) -> Result<HashOf<SignedTransaction>> {
-        let mut block_height = None;
-
-        while let Some(event) = event_iterator.next().await {
-            if let EventBox::Pipeline(this_event) = event? {
-                match this_event {
-                    PipelineEventBox::Transaction(transaction_event) => {
-                        match transaction_event.status() {
-                            TransactionStatus::Queued => {}
-                            TransactionStatus::Approved => {
-                                block_height = transaction_event.block_height();
-                            }
-                            TransactionStatus::Rejected(reason) => {
-                                return Err((Clone::clone(&**reason)).into());
-                            }
-                            TransactionStatus::Expired => return Err(eyre!("Transaction expired")),
-                        }
-                    }
-                    PipelineEventBox::Block(block_event) => {
-                        if Some(block_event.header().height()) == block_height {
-                            if let BlockStatus::Applied = block_event.status() {
-                                return Ok(hash);
-                            }
-                        }
-                    }
-                }
-            }
+        match wait_for_tx_status(event_iterator).await? {
+            TransactionStatus::Approved => Ok(hash),
+            TransactionStatus::Rejected(reason) => Err((*reason).into()),
+            TransactionStatus::Expired => Err(eyre!("Transaction expired")),
+            TransactionStatus::Evicted => Err(eyre!("Transaction evicted from the queue")),
+            TransactionStatus::Queued => unreachable!("queued status is not final"),
         }
-
-        Err(eyre!(
-            "Connection dropped without `Committed` or `Rejected` event"
-        ))
     }
 
     /// Lower-level Instructions API entry point.
@@ -497,7 +533,7 @@ impl Client {
         &self,
         event_filters: impl IntoIterator<Item = impl Into<EventFilterBox>>,
     ) -> Result<impl Iterator<Item = Result<EventBox>>> {
-        events_api::EventIterator::new(self.events_handler(event_filters)?)
+        events_api::EventIterator::new(self.events_handler(event_filters)?, self.tls.as_ref())
     }
 
     /// Connect asynchronously (through `WebSocket`) to listen for `Iroha` `pipeline` and `data` events.
@@ -509,7 +545,8 @@ impl Client {
         &self,
         event_filters: impl IntoIterator<Item = impl Into<EventFilterBox>> + Send,
     ) -> Result<AsyncEventStream> {
-        events_api::AsyncEventStream::new(self.events_handler(event_filters)?).await
+        events_api::AsyncEventStream::new(self.events_handler(event_filters)?, self.tls.as_ref())
+            .await
     }
 
     /// Constructs an Events API handler. With it, you can use any WS client you want.
@@ -537,7 +574,7 @@ impl Client {
         &self,
         height: NonZeroU64,
     ) -> Result<impl Iterator<Item = Result<SignedBlock>>> {
-        blocks_api::BlockIterator::new(self.blocks_handler(height)?)
+        blocks_api::BlockIterator::new(self.blocks_handler(height)?, self.tls.as_ref())
     }
 
     /// Connect asynchronously (through `WebSocket`) to listen for `Iroha` blocks
@@ -546,7 +583,7 @@ impl Client {
     /// - Forwards from [`Self::events_handler`]
     /// - Forwards from `blocks_api::BlockIterator::new`
     pub async fn listen_for_blocks_async(&self, height: NonZeroU64) -> Result<AsyncBlockStream> {
-        blocks_api::AsyncBlockStream::new(self.blocks_handler(height)?).await
+        blocks_api::AsyncBlockStream::new(self.blocks_handler(height)?, self.tls.as_ref()).await
     }
 
     /// Construct a handler for Blocks API. With this handler you can use any WS client you want.
@@ -562,6 +599,59 @@ impl Client {
         )
     }
 
+    /// Connect (through `WebSocket`) to subscribe to the results of an iterable `query`.
+    ///
+    /// The initial results come first, followed by the changes of the results after each committed block.
+    ///
+    /// # Errors
+    /// - Forwards from [`Self::query_subscription_handler`]
+    /// - Forwards from `query_subscription_api::QuerySubscriptionIterator::new`
+    pub fn listen_for_query(
+        &self,
+        query: QueryWithParams,
+    ) -> Result<impl Iterator<Item = Result<QuerySubscriptionMessage>>> {
+        query_subscription_api::QuerySubscriptionIterator::new(
+            self.query_subscription_handler(query)?,
+            self.tls.as_ref(),
+        )
+    }
+
+    /// Connect asynchronously (through `WebSocket`) to subscribe to the results of an iterable `query`.
+    ///
+    /// # Errors
+    /// - Forwards from [`Self::query_subscription_handler`]
+    /// - Forwards from `query_subscription_api::AsyncQuerySubscriptionStream::new`
+    pub async fn listen_for_query_async(
+        &self,
+        query: QueryWithParams,
+    ) -> Result<AsyncQuerySubscriptionStream> {
+        query_subscription_api::AsyncQuerySubscriptionStream::new(
+            self.query_subscription_handler(query)?,
+            self.tls.as_ref(),
+        )
+        .await
+    }
+
+    /// Construct a handler for Query Subscription API. With this handler you can use any WS client you want.
+    ///
+    /// # Errors
+    /// - if handler construction fails
+    #[inline]
+    pub fn query_subscription_handler(
+        &self,
+        query: QueryWithParams,
+    ) -> Result<query_subscription_api::flow::Init> {
+        let query = QueryRequest::Start(query)
+            .with_authority(self.account.clone())
+            .sign(&self.key_pair);
+
+        query_subscription_api::flow::Init::new(
+            query,
+            self.headers.clone(),
+            join_torii_url(&self.torii_url, torii_uri::QUERY_SUBSCRIPTION),
+        )
+    }
+
     /// Get value of config on peer
     ///
     /// # Errors
@@ -573,6 +663,7 @@ impl Client {
         )
         .headers(&self.headers)
         .header(http::header::CONTENT_TYPE, APPLICATION_JSON)
+        .tls(self.tls.as_ref())
         .build()?
         .send()?;
 
@@ -597,6 +688,7 @@ impl Client {
             .headers(&self.headers)
             .header(http::header::CONTENT_TYPE, APPLICATION_JSON)
             .body(body)
+            .tls(self.tls.as_ref())
             .build()?
             .send()?;
 
@@ -619,7 +711,7 @@ impl Client {
         let req = self
             .prepare_status_request::<DefaultRequestBuilder>()
             .header(http::header::ACCEPT, "application/x-parity-scale");
-        let resp = req.build()?.send()?;
+        let resp = req.tls(self.tls.as_ref()).build()?.send()?;
         let scaled_resp = StatusResponseHandler::handle(&resp).cloned()?;
         DecodeAll::decode_all(&mut scaled_resp.as_slice()).map_err(|err| eyre!("{err}"))
     }
@@ -647,6 +739,7 @@ impl Client {
         )
         .headers(&self.headers)
         .header(http::header::CONTENT_TYPE, APPLICATION_JSON)
+        .tls(self.tls.as_ref())
         .build()?
         .send()?;
 
@@ -661,6 +754,55 @@ impl Client {
     }
 }
 
+/// Wait for the final status of the transaction the `event_iterator` is filtered for,
+/// which is either [`TransactionStatus::Approved`] once its block is applied,
+/// [`TransactionStatus::Rejected`], [`TransactionStatus::Expired`]
+/// or [`TransactionStatus::Evicted`].
+pub(crate) async fn wait_for_tx_status(
+    event_iterator: &mut AsyncEventStream,
+) -> Result<TransactionStatus> {
+    let mut block_height = None;
+
+    while let Some(event) = event_iterator.next().await {
+        if let EventBox::Pipeline(this_event) = event? {
+            match this_event {
+                PipelineEventBox::Transaction(transaction_event) => {
+                    match transaction_event.status() {
+                        TransactionStatus::Queued => {}
+                        TransactionStatus::Approved => {
+                            block_height = transaction_event.block_height();
+                        }
+                        status @ (TransactionStatus::Rejected(_)
+                        | TransactionStatus::Expired
+                        | TransactionStatus::Evicted) => {
+                            return Ok(status.clone());
+                        }
+                    }
+                }
+                PipelineEventBox::Block(block_event) => {
+                    if Some(block_event.header().height()) == block_height {
+                        if let BlockStatus::Applied = block_event.status() {
+                            return Ok(TransactionStatus::Approved);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    Err(eyre!(
+        "Connection dropped without `Committed` or `Rejected` event"
+    ))
+}
+
+/// Event filters to wait for the final status of the transaction with the `hash`, see [`wait_for_tx_status`]
+pub(crate) fn tx_status_filters(hash: HashOf<SignedTransaction>) -> Vec<PipelineEventFilterBox> {
+    vec![
+        TransactionEventFilter::default().for_hash(hash).into(),
+        PipelineEventFilterBox::from(BlockEventFilter::default().for_status(BlockStatus::Applied)),
+    ]
+}
+
 pub(crate) fn join_torii_url(url: &Url, path: &str) -> Url {
     // This is needed to prevent "https://iroha-peer.jp/peer1/".join("/query") == "https://iroha-peer.jp/query"
     let path = path.strip_prefix('/').unwrap_or(path);
@@ -702,6 +844,7 @@ pub mod stream_api {
         /// - Message is an error
         pub fn new<I: Init<DefaultWebSocketRequestBuilder>>(
             handler: I,
+            tls: Option<&ClientTls>,
         ) -> Result<SyncIterator<I::Next>> {
             trace!("Creating `SyncIterator`");
             let InitData {
@@ -710,7 +853,7 @@ pub mod stream_api {
                 next: next_handler,
             } = Init::<http_default::DefaultWebSocketRequestBuilder>::init(handler);
 
-            let mut stream = req.build()?.connect()?;
+            let mut stream = req.tls(tls).build()?.connect()?;
             stream.send(WebSocketMessage::Binary(first_message))?;
 
             trace!("`SyncIterator` created successfully");
@@ -786,6 +929,7 @@ pub mod stream_api {
         #[allow(clippy::future_not_send)]
         pub async fn new<I: Init<DefaultWebSocketRequestBuilder>>(
             handler: I,
+            tls: Option<&ClientTls>,
         ) -> Result<AsyncStream<I::Next>> {
             trace!("Creating `AsyncStream`");
             let InitData {
@@ -794,7 +938,7 @@ pub mod stream_api {
                 next: next_handler,
             } = Init::<http_default::DefaultWebSocketRequestBuilder>::init(handler);
 
-            let mut stream = req.build()?.connect_async().await?;
+            let mut stream = req.tls(tls).build()?.connect_async().await?;
             stream.send(WebSocketMessage::Binary(first_message)).await?;
 
             trace!("`AsyncStream` created successfully");
@@ -999,6 +1143,85 @@ mod blocks_api {
     pub type AsyncBlockStream = stream_api::AsyncStream<flow::Events>;
 }
 
+/// Logic related to Query Subscription API client implementation.
+pub mod query_subscription_api {
+    use super::*;
+    use crate::http::ws::{
+        conn_flow::{Events as FlowEvents, Init as FlowInit, InitData},
+        transform_ws_url,
+    };
+
+    /// Query Subscription API flow. For documentation and usage examples, refer to [`crate::http::ws::conn_flow`].
+    pub mod flow {
+        use super::*;
+        use crate::data_model::query::SignedQuery;
+
+        /// Initialization struct for Query Subscription API flow.
+        pub struct Init {
+            /// Signed query to subscribe to
+            query: SignedQuery,
+            /// HTTP request headers
+            headers: HashMap<String, String>,
+            /// TORII URL
+            url: Url,
+        }
+
+        impl Init {
+            /// Construct new item with provided query, headers and url.
+            ///
+            /// # Errors
+            /// If [`transform_ws_url`] fails.
+            #[inline]
+            pub(in super::super) fn new(
+                query: SignedQuery,
+                headers: HashMap<String, String>,
+                url: Url,
+            ) -> Result<Self> {
+                Ok(Self {
+                    query,
+                    headers,
+                    url: transform_ws_url(url)?,
+                })
+            }
+        }
+
+        impl<R: RequestBuilder> FlowInit<R> for Init {
+            type Next = Events;
+
+            fn init(self) -> InitData<R, Self::Next> {
+                let Self {
+                    query,
+                    headers,
+                    url,
+                } = self;
+
+                let msg = QuerySubscriptionRequest::new(query).encode();
+                InitData::new(R::new(HttpMethod::GET, url).headers(headers), msg, Events)
+            }
+        }
+
+        /// Events handler for Query Subscription API flow
+        #[derive(Debug, Copy, Clone)]
+        pub struct Events;
+
+        impl FlowEvents for Events {
+            type Event = QuerySubscriptionMessage;
+
+            fn message(&self, message: Vec<u8>) -> Result<Self::Event> {
+                Ok(QuerySubscriptionMessage::decode_all(
+                    &mut message.as_slice(),
+                )?)
+            }
+        }
+    }
+
+    /// Iterator for getting query subscription messages from the `WebSocket` stream.
+    pub(super) type QuerySubscriptionIterator = stream_api::SyncIterator<flow::Events>;
+
+    /// Async stream for getting query subscription messages from the `WebSocket` stream.
+    pub type AsyncQuerySubscriptionStream = stream_api::AsyncStream<flow::Events>;
+}
+
 #[cfg(test)]
 mod tests {
     use iroha_test_samples::gen_account_in;
@@ -1025,6 +1248,7 @@ mod tests {
             transaction_add_nonce: false,
             transaction_ttl: Duration::from_secs(5),
             transaction_status_timeout: Duration::from_secs(10),
+            tls: None,
         }
     }
 
@@ -15,6 +15,7 @@ use url::Url;
 use crate::{
     crypto::KeyPair,
     data_model::{prelude::*, ChainId},
+    http_default::ClientTls,
 };
 
 mod user;
@@ -71,6 +72,8 @@ pub struct Config {
     pub transaction_ttl: Duration,
     pub transaction_status_timeout: Duration,
     pub transaction_add_nonce: bool,
+    #[serde(skip)]
+    pub tls: Option<ClientTls>,
 }
 
 /// An error type for [`Config::load`]
@@ -214,6 +217,22 @@ mod tests {
         assert_eq!(config.torii_api_url.as_str(), "http://127.0.0.1/peer-1/");
     }
 
+    #[test]
+    fn client_certificate_requires_key() {
+        let error = ConfigReader::new()
+            .with_toml_source(TomlSource::inline(config_sample()))
+            .with_env(MockEnv::from([("TLS_CERT_FILE", "client.pem")]))
+            .read_and_complete::<user::Root>()
+            .unwrap()
+            .parse()
+            .expect_err("key file is missing");
+
+        assert_contains!(
+            format!("{error:?}"),
+            "Client certificate requires both the certificate and the key files"
+        );
+    }
+
     #[test]
     fn invalid_toml_file_is_handled_properly() {
         use std::io::Write;
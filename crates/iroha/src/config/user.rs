@@ -1,5 +1,7 @@
 //! User configuration view.
 
+use std::path::PathBuf;
+
 use error_stack::{Report, ResultExt};
 use iroha_config_base::{
     attach::ConfigValueAndOrigin,
@@ -12,6 +14,7 @@ use crate::{
     config::BasicAuth,
     crypto::{KeyPair, PrivateKey, PublicKey},
     data_model::prelude::{AccountId, ChainId, DomainId},
+    http_default::ClientTls,
 };
 
 /// Root of the user configuration
@@ -27,6 +30,8 @@ pub struct Root {
     pub account: Account,
     #[config(nested)]
     pub transaction: Transaction,
+    #[config(nested)]
+    pub tls: Tls,
 }
 
 #[derive(thiserror::Error, Debug)]
@@ -37,6 +42,10 @@ pub enum ParseError {
     KeyPair,
     #[error("Unsupported URL scheme: `{scheme}`")]
     UnsupportedUrlScheme { scheme: String },
+    #[error("Client certificate requires both the certificate and the key files")]
+    IncompleteClientCertificate,
+    #[error("Failed to load TLS settings")]
+    Tls,
 }
 
 impl Root {
@@ -62,6 +71,7 @@ impl Root {
                     status_timeout_ms: tx_timeout,
                     nonce: tx_add_nonce,
                 },
+            tls,
         } = self;
 
         let mut emitter = Emitter::new();
@@ -99,6 +109,8 @@ impl Root {
             url
         };
 
+        let tls = tls.parse(&mut emitter);
+
         let (public_key, public_key_origin) = public_key.into_tuple();
         let (private_key, private_key_origin) = private_key.into_tuple();
         let account_id = AccountId::new(domain_id, public_key.clone());
@@ -119,6 +131,7 @@ impl Root {
             transaction_ttl: tx_ttl.into_value().get(),
             transaction_status_timeout: tx_timeout.into_value().get(),
             transaction_add_nonce: tx_add_nonce,
+            tls,
         })
     }
 }
@@ -144,3 +157,54 @@ pub struct Transaction {
     #[config(default = "super::DEFAULT_TRANSACTION_NONCE")]
     pub nonce: bool,
 }
+
+/// Custom TLS settings of the connections to the peer
+#[derive(Debug, Clone, ReadConfig)]
+pub struct Tls {
+    /// PEM file with the certificate authorities trusted to issue the peer certificates,
+    /// instead of the default ones
+    #[config(env = "TLS_CA_FILE")]
+    pub ca_file: Option<WithOrigin<PathBuf>>,
+    /// PEM file with the client certificate chain, presented to the peers requiring it
+    #[config(env = "TLS_CERT_FILE")]
+    pub cert_file: Option<WithOrigin<PathBuf>>,
+    /// PEM file with the private key of the client certificate
+    #[config(env = "TLS_KEY_FILE")]
+    pub key_file: Option<WithOrigin<PathBuf>>,
+}
+
+impl Tls {
+    fn parse(self, emitter: &mut Emitter<ParseError>) -> Option<ClientTls> {
+        let ca_file = self.ca_file.as_ref().map(WithOrigin::resolve_relative_path);
+        let certificate = match (&self.cert_file, &self.key_file) {
+            (Some(cert_file), Some(key_file)) => Some((
+                cert_file.resolve_relative_path(),
+                key_file.resolve_relative_path(),
+            )),
+            (None, None) => None,
+            (cert_file, key_file) => {
+                let file = cert_file
+                    .as_ref()
+                    .or(key_file.as_ref())
+                    .expect("one is set");
+                emitter.emit(
+                    Report::new(ParseError::IncompleteClientCertificate)
+                        .attach_printable(file.clone().into_attachment().display_path()),
+                );
+                return None;
+            }
+        };
+        if ca_file.is_none() && certificate.is_none() {
+            return None;
+        }
+
+        ClientTls::from_files(
+            ca_file.as_deref(),
+            certificate
+                .as_ref()
+                .map(|(cert_file, key_file)| (cert_file.as_path(), key_file.as_path())),
+        )
+        .map_err(|error| Report::new(ParseError::Tls).attach_printable(format!("{error:#}")))
+        .ok_or_emit(emitter)
+    }
+}
@@ -1,5 +1,5 @@
 //! Defaults for various items used in communication over http(s).
-use std::net::TcpStream;
+use std::{net::TcpStream, path::Path};
 
 use attohttpc::{
     body as atto_body, RequestBuilder as AttoHttpRequestBuilder, Response as AttoHttpResponse,
@@ -12,6 +12,153 @@ use url::Url;
 
 use crate::http::{Method, RequestBuilder, Response};
 
+#[cfg(any(
+    feature = "tls-rustls-native-roots",
+    feature = "tls-rustls-webpki-roots"
+))]
+mod rustls_config {
+    use std::{fs, io, sync::Arc};
+
+    use rustls::{
+        pki_types::{CertificateDer, PrivateKeyDer},
+        ClientConfig, RootCertStore,
+    };
+
+    use super::*;
+
+    fn open(path: &Path) -> Result<io::BufReader<fs::File>> {
+        fs::File::open(path)
+            .map(io::BufReader::new)
+            .wrap_err_with(|| format!("Failed to open {}", path.display()))
+    }
+
+    fn read_certs(path: &Path) -> Result<Vec<CertificateDer<'static>>> {
+        let certs = rustls_pemfile::certs(&mut open(path)?)
+            .collect::<Result<Vec<_>, _>>()
+            .wrap_err_with(|| format!("Failed to read certificates from {}", path.display()))?;
+        if certs.is_empty() {
+            return Err(eyre!("No certificates found in {}", path.display()));
+        }
+        Ok(certs)
+    }
+
+    fn read_key(path: &Path) -> Result<PrivateKeyDer<'static>> {
+        rustls_pemfile::private_key(&mut open(path)?)
+            .wrap_err_with(|| format!("Failed to read the private key from {}", path.display()))?
+            .ok_or_else(|| eyre!("No private key found in {}", path.display()))
+    }
+
+    #[cfg(feature = "tls-rustls-native-roots")]
+    fn default_roots(roots: &mut RootCertStore) -> Result<()> {
+        let certs = rustls_native_certs::load_native_certs()
+            .wrap_err("Failed to load system certificates")?;
+        roots.add_parsable_certificates(certs);
+        Ok(())
+    }
+
+    #[cfg(not(feature = "tls-rustls-native-roots"))]
+    #[allow(clippy::unnecessary_wraps)]
+    fn default_roots(roots: &mut RootCertStore) -> Result<()> {
+        roots.extend(webpki_roots::TLS_SERVER_ROOTS.iter().cloned());
+        Ok(())
+    }
+
+    pub fn load(
+        ca_file: Option<&Path>,
+        certificate: Option<(&Path, &Path)>,
+    ) -> Result<Arc<ClientConfig>> {
+        let mut roots = RootCertStore::empty();
+        if let Some(ca_file) = ca_file {
+            for cert in read_certs(ca_file)? {
+                roots
+                    .add(cert)
+                    .wrap_err_with(|| format!("Invalid certificate in {}", ca_file.display()))?;
+            }
+        } else {
+            default_roots(&mut roots)?;
+        }
+
+        let builder = ClientConfig::builder().with_root_certificates(roots);
+        let config = if let Some((cert_file, key_file)) = certificate {
+            builder
+                .with_client_auth_cert(read_certs(cert_file)?, read_key(key_file)?)
+                .wrap_err("Client certificate doesn't match the private key or is invalid")?
+        } else {
+            builder.with_no_client_auth()
+        };
+        Ok(Arc::new(config))
+    }
+}
+
+/// TLS settings of the connections to Iroha: the trusted certificate authorities and the client certificate.
+#[derive(Debug, Clone)]
+pub struct ClientTls {
+    #[cfg(any(
+        feature = "tls-rustls-native-roots",
+        feature = "tls-rustls-webpki-roots"
+    ))]
+    config: std::sync::Arc<rustls::ClientConfig>,
+}
+
+impl ClientTls {
+    /// Load the certificate authorities trusted to issue the peer certificates from the PEM `ca_file`,
+    /// or use the default ones if it is `None`.
+    /// The client presents the `certificate`, a pair of PEM certificate and key files, if it is set.
+    ///
+    /// # Errors
+    /// Fails if the files can't be read or contain invalid certificates.
+    /// Also fails if the client is built without one of the `tls-rustls-*` features.
+    #[cfg(any(
+        feature = "tls-rustls-native-roots",
+        feature = "tls-rustls-webpki-roots"
+    ))]
+    pub fn from_files(ca_file: Option<&Path>, certificate: Option<(&Path, &Path)>) -> Result<Self> {
+        Ok(Self {
+            config: rustls_config::load(ca_file, certificate)?,
+        })
+    }
+
+    /// Load the certificate authorities trusted to issue the peer certificates from the PEM `ca_file`,
+    /// or use the default ones if it is `None`.
+    /// The client presents the `certificate`, a pair of PEM certificate and key files, if it is set.
+    ///
+    /// # Errors
+    /// Fails if the files can't be read or contain invalid certificates.
+    /// Also fails if the client is built without one of the `tls-rustls-*` features.
+    #[cfg(not(any(
+        feature = "tls-rustls-native-roots",
+        feature = "tls-rustls-webpki-roots"
+    )))]
+    pub fn from_files(
+        _ca_file: Option<&Path>,
+        _certificate: Option<(&Path, &Path)>,
+    ) -> Result<Self> {
+        Err(eyre!(
+            "Custom TLS settings require the client to be built with one of the `tls-rustls-*` features"
+        ))
+    }
+
+    /// Apply the settings to the builder of the asynchronous client.
+    #[cfg(any(
+        feature = "tls-rustls-native-roots",
+        feature = "tls-rustls-webpki-roots"
+    ))]
+    pub(crate) fn configure(&self, builder: reqwest::ClientBuilder) -> reqwest::ClientBuilder {
+        // reqwest is kept on the rustls release of the blocking client to accept its configuration
+        builder.use_preconfigured_tls(rustls::ClientConfig::clone(&self.config))
+    }
+
+    /// Apply the settings to the builder of the asynchronous client.
+    #[cfg(not(any(
+        feature = "tls-rustls-native-roots",
+        feature = "tls-rustls-webpki-roots"
+    )))]
+    #[allow(clippy::unused_self)]
+    pub(crate) fn configure(&self, builder: reqwest::ClientBuilder) -> reqwest::ClientBuilder {
+        builder
+    }
+}
+
 type Bytes = Vec<u8>;
 type AttoHttpRequestBuilderWithBytes = AttoHttpRequestBuilder<atto_body::Bytes<Bytes>>;
 
@@ -39,6 +186,26 @@ impl DefaultRequestBuilder {
         }
     }
 
+    /// Use the custom `tls` settings for the connection, if any.
+    #[must_use]
+    pub fn tls(self, tls: Option<&ClientTls>) -> Self {
+        #[cfg(any(
+            feature = "tls-rustls-native-roots",
+            feature = "tls-rustls-webpki-roots"
+        ))]
+        if let Some(tls) = tls {
+            let config = tls.config.clone();
+            return self.and_then(|builder| Ok(builder.client_config(config)));
+        }
+        #[cfg(not(any(
+            feature = "tls-rustls-native-roots",
+            feature = "tls-rustls-webpki-roots"
+        )))]
+        let _ = tls;
+
+        self
+    }
+
     /// Build request by consuming self.
     pub fn build(self) -> Result<DefaultRequest> {
         self.inner
@@ -97,7 +264,7 @@ impl RequestBuilder for DefaultRequestBuilder {
 }
 
 /// Request builder built on top of [`http::request::Builder`]. Used for `WebSocket` connections.
-pub struct DefaultWebSocketRequestBuilder(Result<http::request::Builder>);
+pub struct DefaultWebSocketRequestBuilder(Result<http::request::Builder>, Option<ClientTls>);
 
 impl DefaultWebSocketRequestBuilder {
     /// Same as [`DefaultRequestBuilder::and_then`].
@@ -105,7 +272,13 @@ impl DefaultWebSocketRequestBuilder {
     where
         F: FnOnce(http::request::Builder) -> Result<http::request::Builder>,
     {
-        Self(self.0.and_then(func))
+        Self(self.0.and_then(func), self.1)
+    }
+
+    /// Use the custom `tls` settings for the connection, if any.
+    #[must_use]
+    pub fn tls(self, tls: Option<&ClientTls>) -> Self {
+        Self(self.0, tls.cloned())
     }
 
     /// Consumes itself to build request.
@@ -121,22 +294,63 @@ impl DefaultWebSocketRequestBuilder {
         {
             request.headers_mut().entry(header).or_insert(value.clone());
         }
-        Ok(DefaultWebSocketStreamRequest(request))
+        Ok(DefaultWebSocketStreamRequest(request, self.1))
     }
 }
 
 /// `WebSocket` request built by [`DefaultWebSocketRequestBuilder`]
-pub struct DefaultWebSocketStreamRequest(http::Request<()>);
+pub struct DefaultWebSocketStreamRequest(http::Request<()>, Option<ClientTls>);
 
 impl DefaultWebSocketStreamRequest {
     /// Open [`WebSocketStream`] synchronously.
     pub fn connect(self) -> Result<WebSocketStream> {
+        #[cfg(any(
+            feature = "tls-rustls-native-roots",
+            feature = "tls-rustls-webpki-roots"
+        ))]
+        if let Some(tls) = self.1 {
+            let uri = self.0.uri();
+            let host = uri.host().ok_or_else(|| eyre!("Missing host in {uri}"))?;
+            let port = uri
+                .port_u16()
+                .unwrap_or(if uri.scheme_str() == Some("wss") {
+                    443
+                } else {
+                    80
+                });
+            let socket = TcpStream::connect((host.trim_matches(['[', ']']), port))
+                .wrap_err_with(|| format!("Failed to connect to {uri}"))?;
+            let (stream, _) = tungstenite::client_tls_with_config(
+                self.0,
+                socket,
+                None,
+                Some(tungstenite::Connector::Rustls(tls.config)),
+            )
+            .map_err(|error| eyre!("WebSocket handshake failed: {error}"))?;
+            return Ok(stream);
+        }
+
         let (stream, _) = tungstenite::connect(self.0)?;
         Ok(stream)
     }
 
     /// Open [`AsyncWebSocketStream`].
     pub async fn connect_async(self) -> Result<AsyncWebSocketStream> {
+        #[cfg(any(
+            feature = "tls-rustls-native-roots",
+            feature = "tls-rustls-webpki-roots"
+        ))]
+        if let Some(tls) = self.1 {
+            let (stream, _) = tokio_tungstenite::connect_async_tls_with_config(
+                self.0,
+                None,
+                false,
+                Some(tokio_tungstenite::Connector::Rustls(tls.config)),
+            )
+            .await?;
+            return Ok(stream);
+        }
+
         let (stream, _) = tokio_tungstenite::connect_async(self.0).await?;
         Ok(stream)
     }
@@ -144,13 +358,14 @@ impl DefaultWebSocketStreamRequest {
 
 impl RequestBuilder for DefaultWebSocketRequestBuilder {
     fn new(method: Method, url: Url) -> Self {
-        Self(Ok(http::Request::builder()
-            .method(method)
-            .uri(url.as_ref())))
+        Self(
+            Ok(http::Request::builder().method(method).uri(url.as_ref())),
+            None,
+        )
     }
 
     fn param<K, V: ?Sized>(self, _key: K, _val: &V) -> Self {
-        Self(self.0.and(Err(eyre!("No params expected"))))
+        Self(self.0.and(Err(eyre!("No params expected"))), self.1)
     }
 
     fn header<N: AsRef<str>, V: ToString + ?Sized>(self, name: N, value: &V) -> Self {
@@ -196,3 +411,171 @@ impl TryFrom<ClientResponse> for Response<Bytes> {
             })
     }
 }
+
+#[cfg(all(
+    test,
+    any(
+        feature = "tls-rustls-native-roots",
+        feature = "tls-rustls-webpki-roots"
+    )
+))]
+mod tests {
+    use std::{
+        io::{BufRead as _, BufReader, Write as _},
+        net::TcpListener,
+        sync::Arc,
+        thread,
+    };
+
+    use iroha_test_samples::sample_tls_path;
+    use rustls::{
+        pki_types::CertificateDer, server::WebPkiClientVerifier, RootCertStore, ServerConfig,
+        ServerConnection, StreamOwned,
+    };
+
+    use super::*;
+
+    type Stream = StreamOwned<ServerConnection, TcpStream>;
+
+    fn sample_certs(name: &str) -> Vec<CertificateDer<'static>> {
+        let pem = std::fs::read(sample_tls_path(name)).unwrap();
+        rustls_pemfile::certs(&mut pem.as_slice())
+            .collect::<Result<_, _>>()
+            .unwrap()
+    }
+
+    fn sample_identity(name: &str) -> (std::path::PathBuf, std::path::PathBuf) {
+        (
+            sample_tls_path(format!("{name}.crt")),
+            sample_tls_path(format!("{name}.key")),
+        )
+    }
+
+    /// Serve the connections on a local port with the sample server certificate.
+    /// Clients have to present a certificate issued by the sample authority if `client_auth` is set.
+    fn serve(client_auth: bool, respond: fn(Stream)) -> Url {
+        let pem = std::fs::read(sample_tls_path("server.key")).unwrap();
+        let key = rustls_pemfile::private_key(&mut pem.as_slice())
+            .unwrap()
+            .unwrap();
+        let builder = ServerConfig::builder();
+        let builder = if client_auth {
+            let mut roots = RootCertStore::empty();
+            roots.add_parsable_certificates(sample_certs("ca.crt"));
+            builder.with_client_cert_verifier(
+                WebPkiClientVerifier::builder(Arc::new(roots))
+                    .build()
+                    .unwrap(),
+            )
+        } else {
+            builder.with_no_client_auth()
+        };
+        let config = Arc::new(
+            builder
+                .with_single_cert(sample_certs("server.crt"), key)
+                .unwrap(),
+        );
+
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("https://{}/", listener.local_addr().unwrap())
+            .parse()
+            .unwrap();
+        thread::spawn(move || {
+            for stream in listener.incoming().flatten() {
+                let connection = ServerConnection::new(Arc::clone(&config)).unwrap();
+                respond(StreamOwned::new(connection, stream));
+            }
+        });
+        url
+    }
+
+    /// Answer the request with `ok`, the rejected handshakes fail the reads and writes
+    fn respond_ok(mut stream: Stream) {
+        let mut request = BufReader::new(&mut stream);
+        let mut line = String::new();
+        // the request headers end with an empty line
+        while request.read_line(&mut line).is_ok_and(|read| read > 0) && line != "\r\n" {
+            line.clear();
+        }
+        let _ = stream
+            .write_all(b"HTTP/1.1 200 OK\r\nContent-Length: 2\r\nConnection: close\r\n\r\nok");
+        stream.conn.send_close_notify();
+        let _ = stream.flush();
+    }
+
+    fn respond_over_websocket(stream: Stream) {
+        if let Ok(mut websocket) = tungstenite::accept(stream) {
+            let _ = websocket.send(WebSocketMessage::text("ok"));
+            let _ = websocket.close(None);
+        }
+    }
+
+    fn get(url: &Url, tls: Option<&ClientTls>) -> Result<Vec<u8>> {
+        let response = DefaultRequestBuilder::new(Method::GET, url.clone())
+            .tls(tls)
+            .build()?
+            .send()?;
+        Ok(response.into_body())
+    }
+
+    fn get_async(url: &Url, tls: &ClientTls) -> Result<String> {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()?;
+        runtime.block_on(async {
+            let client = tls.configure(reqwest::Client::builder()).build()?;
+            Ok(client.get(url.clone()).send().await?.text().await?)
+        })
+    }
+
+    #[test]
+    fn peers_are_verified_with_custom_authority() -> Result<()> {
+        let url = serve(false, respond_ok);
+        let tls = ClientTls::from_files(Some(&sample_tls_path("ca.crt")), None)?;
+
+        assert_eq!(get(&url, Some(&tls))?, b"ok");
+        assert_eq!(get_async(&url, &tls)?, "ok");
+        // the sample authority isn't among the default ones
+        assert!(get(&url, None).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn client_certificate_is_presented_to_peers() -> Result<()> {
+        let url = serve(true, respond_ok);
+        let ca_file = sample_tls_path("ca.crt");
+
+        let (cert_file, key_file) = sample_identity("client");
+        let tls = ClientTls::from_files(Some(&ca_file), Some((&cert_file, &key_file)))?;
+        assert_eq!(get(&url, Some(&tls))?, b"ok");
+        assert_eq!(get_async(&url, &tls)?, "ok");
+
+        let anonymous = ClientTls::from_files(Some(&ca_file), None)?;
+        assert!(get(&url, Some(&anonymous)).is_err());
+        let (cert_file, key_file) = sample_identity("untrusted_client");
+        let untrusted = ClientTls::from_files(Some(&ca_file), Some((&cert_file, &key_file)))?;
+        assert!(get(&url, Some(&untrusted)).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn websocket_connections_use_custom_tls() -> Result<()> {
+        let mut url = serve(true, respond_over_websocket);
+        url.set_scheme("wss").unwrap();
+        let (cert_file, key_file) = sample_identity("client");
+        let tls = ClientTls::from_files(
+            Some(&sample_tls_path("ca.crt")),
+            Some((&cert_file, &key_file)),
+        )?;
+
+        let mut websocket = DefaultWebSocketRequestBuilder::new(Method::GET, url)
+            .tls(Some(&tls))
+            .build()?
+            .connect()?;
+        assert_eq!(websocket.read()?, WebSocketMessage::text("ok"));
+
+        Ok(())
+    }
+}
@@ -23,7 +23,7 @@ use crate::{
         ValidationFail,
     },
     http::{Method as HttpMethod, RequestBuilder},
-    http_default::DefaultRequestBuilder,
+    http_default::{ClientTls, DefaultRequestBuilder},
 };
 
 #[derive(Debug)]
@@ -32,6 +32,7 @@ struct ClientQueryRequestHead {
     headers: HashMap<String, String>,
     account_id: AccountId,
     key_pair: KeyPair,
+    tls: Option<ClientTls>,
 }
 
 impl ClientQueryRequestHead {
@@ -47,6 +48,7 @@ impl ClientQueryRequestHead {
         )
         .headers(self.headers.clone())
         .body(query.encode())
+        .tls(self.tls.as_ref())
     }
 }
 
@@ -89,7 +91,7 @@ fn decode_query_response(resp: &http::Response<Vec<u8>>) -> QueryResult<QueryRes
     }
 }
 
-fn decode_singular_query_response(
+pub(crate) fn decode_singular_query_response(
     resp: &http::Response<Vec<u8>>,
 ) -> QueryResult<SingularQueryOutputBox> {
     let QueryResponse::Singular(resp) = decode_query_response(resp)? else {
@@ -101,7 +103,9 @@ fn decode_singular_query_response(
     Ok(resp)
 }
 
-fn decode_iterable_query_response(resp: &http::Response<Vec<u8>>) -> QueryResult<QueryOutput> {
+pub(crate) fn decode_iterable_query_response(
+    resp: &http::Response<Vec<u8>>,
+) -> QueryResult<QueryOutput> {
     let QueryResponse::Iterable(resp) = decode_query_response(resp)? else {
         return Err(eyre!(
             "Got unexpected type of query response from the node (expected iterable)"
@@ -121,6 +125,16 @@ pub struct QueryCursor {
     cursor: ForwardCursor,
 }
 
+impl QueryCursor {
+    /// The raw cursor to fetch the next batch of results.
+    ///
+    /// Its [`ForwardCursor::continuation`] can be persisted to resume the query later, possibly against another peer,
+    /// by building the query again with [`QueryBuilder::continue_after`].
+    pub fn raw(&self) -> &ForwardCursor {
+        &self.cursor
+    }
+}
+
 /// Different errors as a result of query response handling
 #[derive(Debug, thiserror::Error, displaydoc::Display)]
 pub enum QueryError {
@@ -210,6 +224,7 @@ impl Client {
             headers: self.headers.clone(),
             account_id: self.account.clone(),
             key_pair: self.key_pair.clone(),
+            tls: self.tls.clone(),
         }
     }
 
@@ -246,6 +261,9 @@ impl Client {
     ///
     /// You probably do not want to use this function, but rather use the [`Self::query`] method to make a query and iterate over its results.
     ///
+    /// Only the peer that started the query can continue it. To resume the query elsewhere,
+    /// start it again with the [`ForwardCursor::continuation`] of the cursor.
+    ///
     /// # Errors
     ///
     /// Returns an error if the query execution fails.
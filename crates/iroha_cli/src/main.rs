@@ -344,7 +344,7 @@ fn color_mode() -> ColorMode {
 }
 
 mod filter {
-    use iroha::data_model::query::dsl::CompoundPredicate;
+    use iroha::data_model::query::dsl::{Aggregate, CompoundPredicate};
 
     use super::*;
 
@@ -355,6 +355,16 @@ mod filter {
         pub predicate: CompoundPredicate<Domain>,
     }
 
+    #[derive(clap::Args, Debug)]
+    pub struct DomainAggregate {
+        /// Aggregation specified as a JSON5 string
+        #[arg(value_parser = parse_json5::<Aggregate<Domain>>)]
+        pub aggregate: Aggregate<Domain>,
+        /// Only aggregate entries matching the filtering condition specified as a JSON5 string
+        #[arg(long, value_parser = parse_json5::<CompoundPredicate<Domain>>)]
+        pub predicate: Option<CompoundPredicate<Domain>>,
+    }
+
     #[derive(clap::Args, Debug)]
     pub struct AccountFilter {
         /// Filtering condition specified as a JSON5 string
@@ -362,6 +372,16 @@ mod filter {
         pub predicate: CompoundPredicate<Account>,
     }
 
+    #[derive(clap::Args, Debug)]
+    pub struct AccountAggregate {
+        /// Aggregation specified as a JSON5 string
+        #[arg(value_parser = parse_json5::<Aggregate<Account>>)]
+        pub aggregate: Aggregate<Account>,
+        /// Only aggregate entries matching the filtering condition specified as a JSON5 string
+        #[arg(long, value_parser = parse_json5::<CompoundPredicate<Account>>)]
+        pub predicate: Option<CompoundPredicate<Account>>,
+    }
+
     #[derive(clap::Args, Debug)]
     pub struct AssetFilter {
         /// Filtering condition specified as a JSON5 string
@@ -369,6 +389,16 @@ mod filter {
         pub predicate: CompoundPredicate<Asset>,
     }
 
+    #[derive(clap::Args, Debug)]
+    pub struct AssetAggregate {
+        /// Aggregation specified as a JSON5 string
+        #[arg(value_parser = parse_json5::<Aggregate<Asset>>)]
+        pub aggregate: Aggregate<Asset>,
+        /// Only aggregate entries matching the filtering condition specified as a JSON5 string
+        #[arg(long, value_parser = parse_json5::<CompoundPredicate<Asset>>)]
+        pub predicate: Option<CompoundPredicate<Asset>>,
+    }
+
     #[derive(clap::Args, Debug)]
     pub struct AssetDefinitionFilter {
         /// Filtering condition specified as a JSON5 string
@@ -376,12 +406,32 @@ mod filter {
         pub predicate: CompoundPredicate<AssetDefinition>,
     }
 
+    #[derive(clap::Args, Debug)]
+    pub struct AssetDefinitionAggregate {
+        /// Aggregation specified as a JSON5 string
+        #[arg(value_parser = parse_json5::<Aggregate<AssetDefinition>>)]
+        pub aggregate: Aggregate<AssetDefinition>,
+        /// Only aggregate entries matching the filtering condition specified as a JSON5 string
+        #[arg(long, value_parser = parse_json5::<CompoundPredicate<AssetDefinition>>)]
+        pub predicate: Option<CompoundPredicate<AssetDefinition>>,
+    }
+
     #[derive(clap::Args, Debug)]
     pub struct NftFilter {
         /// Filtering condition specified as a JSON5 string
         #[arg(value_parser = parse_json5::<CompoundPredicate<Nft>>)]
         pub predicate: CompoundPredicate<Nft>,
     }
+
+    #[derive(clap::Args, Debug)]
+    pub struct NftAggregate {
+        /// Aggregation specified as a JSON5 string
+        #[arg(value_parser = parse_json5::<Aggregate<Nft>>)]
+        pub aggregate: Aggregate<Nft>,
+        /// Only aggregate entries matching the filtering condition specified as a JSON5 string
+        #[arg(long, value_parser = parse_json5::<CompoundPredicate<Nft>>)]
+        pub predicate: Option<CompoundPredicate<Nft>>,
+    }
 }
 
 mod events {
@@ -517,7 +567,7 @@ mod blocks {
 }
 
 macro_rules! impl_list {
-    ($filter:ty, $query:expr) => {
+    ($filter:ty, $aggregate:ty, $query:expr) => {
         #[derive(clap::Subcommand, Debug)]
         pub enum List {
             /// List all IDs, or full entries when `--verbose` is specified
@@ -528,6 +578,8 @@ macro_rules! impl_list {
             },
             /// Filter by a given predicate
             Filter($filter),
+            /// Count entries, or compute the sum, minimum or maximum of a numeric field, optionally per group
+            Aggregate($aggregate),
         }
 
         impl Run for List {
@@ -548,6 +600,26 @@ macro_rules! impl_list {
                         let view = query.filter(filter.predicate).execute_all()?;
                         context.print_data(&view)?;
                     }
+                    List::Aggregate(args) => {
+                        use iroha::data_model::query::{
+                            dsl::{CompoundPredicate, SelectorTuple},
+                            parameters::QueryParams,
+                            QueryWithFilter, QueryWithParams,
+                        };
+
+                        let query = QueryWithParams::new(
+                            QueryWithFilter::new(
+                                $query,
+                                args.predicate.unwrap_or(CompoundPredicate::PASS),
+                                SelectorTuple::default(),
+                                Some(args.aggregate),
+                                Vec::new(),
+                            )
+                            .into(),
+                            QueryParams::default(),
+                        );
+                        crate::query::print_iterable(context, &client, query)?;
+                    }
                 }
                 Ok(())
             }
@@ -635,7 +707,7 @@ mod domain {
         pub id: DomainId,
     }
 
-    impl_list!(filter::DomainFilter, FindDomains);
+    impl_list!(filter::DomainFilter, filter::DomainAggregate, FindDomains);
 }
 
 mod account {
@@ -796,7 +868,11 @@ mod account {
         pub role: RoleId,
     }
 
-    impl_list!(filter::AccountFilter, FindAccounts);
+    impl_list!(
+        filter::AccountFilter,
+        filter::AccountAggregate,
+        FindAccounts
+    );
 }
 
 mod asset {
@@ -864,6 +940,8 @@ mod asset {
     }
 
     mod definition {
+        use std::num::NonZeroU32;
+
         use iroha::data_model::asset::{AssetDefinition, AssetDefinitionId};
 
         use super::*;
@@ -881,6 +959,8 @@ mod asset {
             Unregister(Id),
             /// Transfer ownership of an asset definition
             Transfer(Transfer),
+            /// Replace the supply cap and the balance limit of an asset definition
+            Limits(Limits),
             /// Read and write metadata
             #[command(subcommand)]
             Meta(metadata::asset_definition::Command),
@@ -905,6 +985,15 @@ mod asset {
                         if args.mint_once {
                             entry = entry.mintable_once();
                         }
+                        if let Some(times) = args.mint_limit {
+                            entry = entry.mintable_limited(times);
+                        }
+                        if let Some(max_supply) = args.max_supply {
+                            entry = entry.with_max_supply(max_supply);
+                        }
+                        if let Some(max_balance) = args.max_balance {
+                            entry = entry.with_max_balance(max_balance);
+                        }
                         let instruction = iroha::data_model::isi::Register::asset_definition(entry);
                         context
                             .finish([instruction])
@@ -925,6 +1014,19 @@ mod asset {
                             .finish([instruction])
                             .wrap_err("Failed to transfer asset definition")
                     }
+                    Limits(args) => {
+                        let mut instruction =
+                            iroha::data_model::isi::SetAssetDefinitionLimits::new(args.id);
+                        if let Some(max_supply) = args.max_supply {
+                            instruction = instruction.with_max_supply(max_supply);
+                        }
+                        if let Some(max_balance) = args.max_balance {
+                            instruction = instruction.with_max_balance(max_balance);
+                        }
+                        context
+                            .finish([instruction])
+                            .wrap_err("Failed to set asset definition limits")
+                    }
                     Meta(cmd) => cmd.run(context),
                 }
             }
@@ -938,11 +1040,33 @@ mod asset {
             /// Disables minting after the first instance
             #[arg(short, long)]
             pub mint_once: bool,
+            /// Disables minting after the given number of instances
+            #[arg(long, conflicts_with = "mint_once")]
+            pub mint_limit: Option<NonZeroU32>,
+            /// Maximum total supply of the asset
+            #[arg(long)]
+            pub max_supply: Option<Numeric>,
+            /// Maximum quantity of the asset a single account can hold
+            #[arg(long)]
+            pub max_balance: Option<Numeric>,
             /// Numeric scale of the asset. No value means unconstrained.
             #[arg(short, long)]
             pub scale: Option<u32>,
         }
 
+        #[derive(clap::Args, Debug)]
+        pub struct Limits {
+            /// Asset definition in the format "asset#domain"
+            #[arg(short, long)]
+            pub id: AssetDefinitionId,
+            /// Maximum total supply of the asset. No value lifts the cap.
+            #[arg(long)]
+            pub max_supply: Option<Numeric>,
+            /// Maximum quantity of the asset a single account can hold. No value lifts the limit.
+            #[arg(long)]
+            pub max_balance: Option<Numeric>,
+        }
+
         #[derive(clap::Args, Debug)]
         pub struct Transfer {
             /// Asset definition in the format "asset#domain"
@@ -963,7 +1087,11 @@ mod asset {
             pub id: AssetDefinitionId,
         }
 
-        impl_list!(filter::AssetDefinitionFilter, FindAssetsDefinitions);
+        impl_list!(
+            filter::AssetDefinitionFilter,
+            filter::AssetDefinitionAggregate,
+            FindAssetsDefinitions
+        );
     }
 
     #[derive(clap::Args, Debug)]
@@ -996,7 +1124,7 @@ mod asset {
         pub quantity: Numeric,
     }
 
-    impl_list!(filter::AssetFilter, FindAssets);
+    impl_list!(filter::AssetFilter, filter::AssetAggregate, FindAssets);
 }
 
 mod nft {
@@ -1115,7 +1243,7 @@ mod nft {
         pub key: Name,
     }
 
-    impl_list!(filter::NftFilter, FindNfts);
+    impl_list!(filter::NftFilter, filter::NftAggregate, FindNfts);
 }
 
 mod peer {
@@ -1508,7 +1636,7 @@ mod multisig {
 }
 
 mod query {
-    use iroha::data_model::query::AnyQueryBox;
+    use iroha::data_model::query::{AnyQueryBox, QueryWithParams};
 
     use super::*;
 
@@ -1541,47 +1669,54 @@ mod query {
 
                     context.print_data(&result)
                 }
-                AnyQueryBox::Iterable(query) => {
-                    // we can't really do type-erased iterable queries in a nice way right now...
-                    use iroha::data_model::query::builder::QueryExecutor;
+                AnyQueryBox::Iterable(query) => print_iterable(context, &client, query),
+            }
+        }
+    }
 
-                    let (mut accumulated_batch, _remaining_items, mut continue_cursor) =
-                        client.start_query(query)?;
+    /// Executes a type-erased iterable query, printing each result as an array of the selected values
+    pub fn print_iterable<C: RunContext>(
+        context: &mut C,
+        client: &Client,
+        query: QueryWithParams,
+    ) -> Result<()> {
+        // we can't really do type-erased iterable queries in a nice way right now...
+        use iroha::data_model::query::builder::QueryExecutor;
 
-                    while let Some(cursor) = continue_cursor {
-                        let (next_batch, _remaining_items, next_continue_cursor) =
-                            <Client as QueryExecutor>::continue_query(cursor)?;
+        let (mut accumulated_batch, _remaining_items, mut continue_cursor) =
+            client.start_query(query)?;
 
-                        accumulated_batch.extend(next_batch);
-                        continue_cursor = next_continue_cursor;
-                    }
+        while let Some(cursor) = continue_cursor {
+            let (next_batch, _remaining_items, next_continue_cursor) =
+                <Client as QueryExecutor>::continue_query(cursor)?;
 
-                    // for efficiency reasons iroha encodes query results in a columnar format,
-                    // so we need to transpose the batch to get the format that is more natural for humans
-                    let mut batches = vec![Vec::new(); accumulated_batch.len()];
-                    for batch in accumulated_batch {
-                        // downcast to json and extract the actual array
-                        // dynamic typing is just easier to use here than introducing a bunch of new types only for iroha_cli
-                        let batch = serde_json::to_value(batch)?;
-                        let serde_json::Value::Object(batch) = batch else {
-                            panic!("Expected the batch serialization to be a JSON object");
-                        };
-                        let (_ty, batch) = batch
-                            .into_iter()
-                            .next()
-                            .expect("Expected the batch to have exactly one key");
-                        let serde_json::Value::Array(batch_vec) = batch else {
-                            panic!("Expected the batch payload to be a JSON array");
-                        };
-                        for (target, value) in batches.iter_mut().zip(batch_vec) {
-                            target.push(value);
-                        }
-                    }
+            accumulated_batch.extend(next_batch);
+            continue_cursor = next_continue_cursor;
+        }
 
-                    context.print_data(&batches)
-                }
+        // for efficiency reasons iroha encodes query results in a columnar format,
+        // so we need to transpose the batch to get the format that is more natural for humans
+        let mut batches = vec![Vec::new(); accumulated_batch.len()];
+        for batch in accumulated_batch {
+            // downcast to json and extract the actual array
+            // dynamic typing is just easier to use here than introducing a bunch of new types only for iroha_cli
+            let batch = serde_json::to_value(batch)?;
+            let serde_json::Value::Object(batch) = batch else {
+                panic!("Expected the batch serialization to be a JSON object");
+            };
+            let (_ty, batch) = batch
+                .into_iter()
+                .next()
+                .expect("Expected the batch to have exactly one key");
+            let serde_json::Value::Array(batch_vec) = batch else {
+                panic!("Expected the batch payload to be a JSON array");
+            };
+            for (target, value) in batches.iter_mut().zip(batch_vec) {
+                target.push(value);
             }
         }
+
+        context.print_data(&batches)
     }
 }
 
@@ -26,9 +26,31 @@ pub enum InitMode {
     Fast,
 }
 
+/// Compression of blocks in Kura segments.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Default,
+    strum::EnumString,
+    strum::Display,
+    DeserializeFromStr,
+    SerializeDisplay,
+)]
+#[strum(serialize_all = "snake_case")]
+pub enum Compression {
+    /// Blocks are stored as is.
+    #[default]
+    None,
+    /// Every block is compressed with zstd.
+    Zstd,
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::kura::InitMode;
+    use crate::kura::{Compression, InitMode};
 
     #[test]
     fn init_mode_display_reprs() {
@@ -37,4 +59,12 @@ mod tests {
         assert_eq!("strict".parse::<InitMode>().unwrap(), InitMode::Strict);
         assert_eq!("fast".parse::<InitMode>().unwrap(), InitMode::Fast);
     }
+
+    #[test]
+    fn compression_display_reprs() {
+        assert_eq!(format!("{}", Compression::None), "none");
+        assert_eq!(format!("{}", Compression::Zstd), "zstd");
+        assert_eq!("none".parse::<Compression>().unwrap(), Compression::None);
+        assert_eq!("zstd".parse::<Compression>().unwrap(), Compression::Zstd);
+    }
 }
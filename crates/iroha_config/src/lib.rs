@@ -8,6 +8,7 @@ pub mod client_api;
 pub mod kura;
 pub mod logger;
 pub mod parameters;
+pub mod queue;
 pub mod snapshot;
 
 /// Enables tracing of configuration via [`stderrlog`].
@@ -11,6 +11,7 @@ use error_stack::{Result, ResultExt};
 use iroha_config_base::{read::ConfigReader, toml::TomlSource, util::Bytes, WithOrigin};
 use iroha_crypto::{KeyPair, PublicKey};
 use iroha_data_model::{
+    account::AccountId,
     peer::{Peer, PeerId},
     ChainId, Identifiable,
 };
@@ -19,7 +20,7 @@ use url::Url;
 pub use user::{DevTelemetry, Logger, Snapshot};
 
 use crate::{
-    kura::InitMode,
+    kura::{Compression as KuraCompression, InitMode},
     parameters::{defaults, user},
 };
 
@@ -80,6 +81,7 @@ pub struct Network {
     pub address: WithOrigin<SocketAddr>,
     pub public_address: WithOrigin<SocketAddr>,
     pub idle_timeout: Duration,
+    pub read_replicas: Vec<PublicKey>,
 }
 
 /// Parsed genesis configuration
@@ -94,11 +96,14 @@ pub struct Genesis {
 }
 
 #[allow(missing_docs)]
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone)]
 pub struct Queue {
     pub capacity: NonZeroUsize,
     pub capacity_per_user: NonZeroUsize,
     pub transaction_time_to_live: Duration,
+    pub ordering: QueueOrdering,
+    pub priority_key: Name,
+    pub evict_when_full: bool,
 }
 
 #[allow(missing_docs)]
@@ -107,6 +112,9 @@ pub struct Kura {
     pub init_mode: InitMode,
     pub store_dir: WithOrigin<PathBuf>,
     pub blocks_in_memory: NonZeroUsize,
+    pub segment_size: Bytes<u64>,
+    pub compression: KuraCompression,
+    pub blocks_to_keep: Option<NonZeroUsize>,
     pub debug_output_new_blocks: bool,
 }
 
@@ -116,6 +124,9 @@ impl Default for Queue {
             transaction_time_to_live: defaults::queue::TRANSACTION_TIME_TO_LIVE,
             capacity: defaults::queue::CAPACITY,
             capacity_per_user: defaults::queue::CAPACITY_PER_USER,
+            ordering: QueueOrdering::default(),
+            priority_key: defaults::queue::PRIORITY_KEY.parse().expect("valid name"),
+            evict_when_full: false,
         }
     }
 }
@@ -123,6 +134,7 @@ impl Default for Queue {
 #[derive(Debug, Copy, Clone)]
 #[allow(missing_docs)]
 pub struct Sumeragi {
+    pub read_replica: bool,
     pub debug_force_soft_fork: bool,
 }
 
@@ -186,6 +198,39 @@ pub struct TransactionGossiper {
 pub struct Torii {
     pub address: WithOrigin<SocketAddr>,
     pub max_content_len: Bytes<u64>,
+    pub query_gateway_account: Option<AccountId>,
+    pub query_subscription_capacity: NonZeroUsize,
+    pub query_subscription_capacity_per_user: NonZeroUsize,
+    pub rate_limit: ToriiRateLimit,
+    pub tls: Option<ToriiTls>,
+}
+
+/// TLS termination of the Torii connections
+#[derive(Debug, Clone)]
+#[allow(missing_docs)]
+pub struct ToriiTls {
+    pub cert_file: WithOrigin<PathBuf>,
+    pub key_file: WithOrigin<PathBuf>,
+    pub client_ca_file: Option<WithOrigin<PathBuf>>,
+    pub reload_period: Duration,
+}
+
+/// Limits of the requests accepted by Torii from a single client, per route.
+/// `None` means the route is not limited.
+#[derive(Debug, Clone, Copy, Default)]
+#[allow(missing_docs)]
+pub struct ToriiRateLimit {
+    pub transactions: Option<RateLimit>,
+    pub queries: Option<RateLimit>,
+    pub streams: Option<RateLimit>,
+}
+
+/// Token bucket refilled with `per_second` tokens every second and holding up to `burst` tokens.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[allow(missing_docs)]
+pub struct RateLimit {
+    pub per_second: NonZeroU32,
+    pub burst: NonZeroU32,
 }
 
 /// Complete configuration needed to start regular telemetry.
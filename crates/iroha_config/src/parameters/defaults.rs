@@ -17,15 +17,18 @@ pub mod queue {
     pub const CAPACITY_PER_USER: NonZeroUsize = nonzero!(2_usize.pow(16));
     // 24 hours
     pub const TRANSACTION_TIME_TO_LIVE: Duration = Duration::from_secs(24 * 60 * 60);
+    pub const PRIORITY_KEY: &str = "priority";
 }
 
 pub mod kura {
     use std::num::NonZeroUsize;
 
+    use iroha_config_base::util::Bytes;
     use nonzero_ext::nonzero;
 
     pub const STORE_DIR: &str = "./storage";
     pub const BLOCKS_IN_MEMORY: NonZeroUsize = nonzero!(128_usize);
+    pub const SEGMENT_SIZE: Bytes<u64> = Bytes(2_u64.pow(20) * 128);
 }
 
 pub mod network {
@@ -46,6 +49,9 @@ pub mod snapshot {
     pub const STORE_DIR: &str = "./storage/snapshot";
     // 10 mins
     pub const CREATE_EVERY: Duration = Duration::from_secs(10 * 60);
+    pub const DELTAS_PER_BASE: u32 = 5;
+    pub const HISTORY_SIZE: u32 = 0;
+    pub const HISTORY_CACHE_SIZE: u32 = 4;
 }
 
 pub mod torii {
@@ -58,6 +64,9 @@ pub mod torii {
     pub const QUERY_IDLE_TIME: Duration = Duration::from_secs(10);
     pub const QUERY_STORE_CAPACITY: NonZeroUsize = nonzero!(128usize);
     pub const QUERY_STORE_CAPACITY_PER_USER: NonZeroUsize = nonzero!(128usize);
+    pub const QUERY_SUBSCRIPTION_CAPACITY: NonZeroUsize = nonzero!(256usize);
+    pub const QUERY_SUBSCRIPTION_CAPACITY_PER_USER: NonZeroUsize = nonzero!(16usize);
+    pub const TLS_RELOAD_PERIOD: Duration = Duration::from_secs(30);
 }
 
 pub mod telemetry {
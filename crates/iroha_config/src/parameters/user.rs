@@ -17,7 +17,7 @@ use std::{
     path::PathBuf,
 };
 
-use error_stack::{Result, ResultExt};
+use error_stack::{Report, Result, ResultExt};
 use iroha_config_base::{
     attach::ConfigValueAndOrigin,
     env::FromEnvStr,
@@ -25,15 +25,16 @@ use iroha_config_base::{
     ReadConfig, WithOrigin,
 };
 use iroha_crypto::{PrivateKey, PublicKey};
-use iroha_data_model::{peer::Peer, ChainId, Level};
+use iroha_data_model::{account::AccountId, name::Name, peer::Peer, ChainId, Level};
 use iroha_primitives::{addr::SocketAddr, unique_vec::UniqueVec};
 use serde::Deserialize;
 use url::Url;
 
 use crate::{
-    kura::InitMode as KuraInitMode,
+    kura::{Compression as KuraCompression, InitMode as KuraInitMode},
     logger::{Directives, Format as LoggerFormat},
     parameters::{actual, defaults},
+    queue::Ordering as QueueOrdering,
     snapshot::Mode as SnapshotMode,
 };
 
@@ -86,6 +87,10 @@ pub struct Root {
 pub enum ParseError {
     #[error("Failed to construct the key pair")]
     BadKeyPair,
+    #[error("Torii TLS requires both the certificate and the key files")]
+    IncompleteToriiTls,
+    #[error("Read replica requires other trusted peers to follow")]
+    ReadReplicaWithoutTrustedPeers,
 }
 
 impl Root {
@@ -131,10 +136,13 @@ impl Root {
         let queue = self.queue;
         let snapshot = self.snapshot;
         let dev_telemetry = self.dev_telemetry;
-        let (torii, live_query_store) = self.torii.parse();
+        let (torii, live_query_store) = self.torii.parse(&mut emitter);
         let telemetry = self.telemetry.map(actual::Telemetry::from);
 
         let sumeragi = self.sumeragi.parse();
+        if sumeragi.read_replica && !trusted_peers.value().contains_other_trusted_peers() {
+            emitter.emit(Report::new(ParseError::ReadReplicaWithoutTrustedPeers));
+        }
 
         emitter.into_result()?;
 
@@ -196,6 +204,17 @@ pub struct Kura {
         default = "defaults::kura::BLOCKS_IN_MEMORY"
     )]
     pub blocks_in_memory: NonZeroUsize,
+    /// Size of a single segment file with blocks.
+    /// A block bigger than this takes a whole segment on its own.
+    #[config(default = "defaults::kura::SEGMENT_SIZE")]
+    pub segment_size: Bytes<u64>,
+    #[config(env = "KURA_COMPRESSION", default)]
+    pub compression: KuraCompression,
+    /// Number of the latest blocks kept in the storage.
+    /// Older blocks are removed once covered by a state snapshot.
+    /// All blocks are kept if not set.
+    #[config(env = "KURA_BLOCKS_TO_KEEP")]
+    pub blocks_to_keep: Option<NonZeroUsize>,
     #[config(nested)]
     pub debug: KuraDebug,
 }
@@ -206,6 +225,9 @@ impl Kura {
             init_mode,
             store_dir,
             blocks_in_memory,
+            segment_size,
+            compression,
+            blocks_to_keep,
             debug:
                 KuraDebug {
                     output_new_blocks: debug_output_new_blocks,
@@ -216,6 +238,9 @@ impl Kura {
             init_mode,
             store_dir,
             blocks_in_memory,
+            segment_size,
+            compression,
+            blocks_to_keep,
             debug_output_new_blocks,
         }
     }
@@ -229,6 +254,10 @@ pub struct KuraDebug {
 
 #[derive(Debug, Clone, Copy, ReadConfig)]
 pub struct Sumeragi {
+    /// Follow the chain as a non-voting observer: sync and verify committed blocks
+    /// without ever creating or signing them. Such a peer must not be registered.
+    #[config(env = "SUMERAGI_READ_REPLICA", default)]
+    pub read_replica: bool,
     #[config(nested)]
     pub debug: SumeragiDebug,
 }
@@ -256,10 +285,12 @@ impl Default for TrustedPeers {
 impl Sumeragi {
     fn parse(self) -> actual::Sumeragi {
         let Self {
+            read_replica,
             debug: SumeragiDebug { force_soft_fork },
         } = self;
 
         actual::Sumeragi {
+            read_replica,
             debug_force_soft_fork: force_soft_fork,
         }
     }
@@ -291,6 +322,9 @@ pub struct Network {
     /// Duration of time after which connection with peer is terminated if peer is idle
     #[config(default = "defaults::network::IDLE_TIMEOUT.into()")]
     pub idle_timeout_ms: DurationMs,
+    /// Public keys of read-replica peers which are allowed to connect without being part of the topology
+    #[config(default)]
+    pub read_replicas: Vec<PublicKey>,
 }
 
 impl Network {
@@ -309,6 +343,7 @@ impl Network {
             transaction_gossip_size,
             transaction_gossip_period_ms: transaction_gossip_period,
             idle_timeout_ms: idle_timeout,
+            read_replicas,
         } = self;
 
         (
@@ -316,6 +351,7 @@ impl Network {
                 address,
                 public_address,
                 idle_timeout: idle_timeout.get(),
+                read_replicas,
             },
             actual::BlockSync {
                 gossip_period: block_gossip_period.get(),
@@ -341,6 +377,18 @@ pub struct Queue {
     /// The transaction will be dropped after this time if it is still in the queue.
     #[config(default = "defaults::queue::TRANSACTION_TIME_TO_LIVE.into()")]
     pub transaction_time_to_live_ms: DurationMs,
+    /// Order in which transactions are taken from the queue into blocks.
+    #[config(default)]
+    pub ordering: QueueOrdering,
+    /// Key of the transaction metadata holding an unsigned integer priority.
+    /// Used by the `priority` ordering, transactions without it have the lowest priority.
+    /// The priority is only taken into account for authorities granted `CanPrioritizeTransactions`.
+    #[config(default = "defaults::queue::PRIORITY_KEY.parse().expect(\"valid name\")")]
+    pub priority_key: Name,
+    /// Evict the transaction ranked the lowest by the ordering when the queue is full,
+    /// instead of rejecting a new transaction ranked higher.
+    #[config(default)]
+    pub evict_when_full: bool,
 }
 
 impl Queue {
@@ -349,11 +397,17 @@ impl Queue {
             capacity,
             capacity_per_user,
             transaction_time_to_live_ms: transaction_time_to_live,
+            ordering,
+            priority_key,
+            evict_when_full,
         } = self;
         actual::Queue {
             capacity,
             capacity_per_user,
             transaction_time_to_live: transaction_time_to_live.0,
+            ordering,
+            priority_key,
+            evict_when_full,
         }
     }
 }
@@ -465,6 +519,18 @@ pub struct Snapshot {
         env = "SNAPSHOT_STORE_DIR"
     )]
     pub store_dir: WithOrigin<PathBuf>,
+    /// How many delta snapshots are written on top of a base snapshot before a new base is made.
+    /// `0` disables delta snapshots, so every snapshot is a full one.
+    #[config(default = "defaults::snapshot::DELTAS_PER_BASE")]
+    pub deltas_per_base: u32,
+    /// How many full snapshots are kept to answer queries about past states.
+    /// `0` disables the history.
+    #[config(default = "defaults::snapshot::HISTORY_SIZE")]
+    pub history_size: u32,
+    /// How many states rebuilt from the history are kept in memory to answer subsequent queries.
+    /// `0` disables the cache, so every query about a past state rebuilds it.
+    #[config(default = "defaults::snapshot::HISTORY_CACHE_SIZE")]
+    pub history_cache_size: u32,
 }
 
 #[derive(Debug, ReadConfig)]
@@ -481,13 +547,108 @@ pub struct Torii {
     /// The upper limit of the number of live queries for a single user.
     #[config(default = "defaults::torii::QUERY_STORE_CAPACITY_PER_USER")]
     pub query_store_capacity_per_user: NonZeroUsize,
+    /// The upper limit of the number of query subscriptions.
+    #[config(default = "defaults::torii::QUERY_SUBSCRIPTION_CAPACITY")]
+    pub query_subscription_capacity: NonZeroUsize,
+    /// The upper limit of the number of query subscriptions for a single user.
+    #[config(default = "defaults::torii::QUERY_SUBSCRIPTION_CAPACITY_PER_USER")]
+    pub query_subscription_capacity_per_user: NonZeroUsize,
+    /// The account executing the queries received as JSON.
+    /// The JSON query gateway is disabled if not set.
+    pub query_gateway_account: Option<AccountId>,
+    #[config(nested)]
+    pub rate_limit: ToriiRateLimit,
+    #[config(nested)]
+    pub tls: ToriiTls,
+}
+
+/// TLS termination of the Torii connections.
+/// Torii serves TLS if both the certificate and the key files are set, and plain HTTP otherwise.
+#[derive(Debug, Clone, ReadConfig)]
+pub struct ToriiTls {
+    /// PEM file with the certificate chain of the peer
+    #[config(env = "TORII_TLS_CERT_FILE")]
+    pub cert_file: Option<WithOrigin<PathBuf>>,
+    /// PEM file with the private key of the certificate
+    #[config(env = "TORII_TLS_KEY_FILE")]
+    pub key_file: Option<WithOrigin<PathBuf>>,
+    /// PEM file with the certificates of the authorities issuing client certificates.
+    /// If set, only the clients presenting a certificate issued by one of them can connect.
+    #[config(env = "TORII_TLS_CLIENT_CA_FILE")]
+    pub client_ca_file: Option<WithOrigin<PathBuf>>,
+    /// How often the files are checked for changes, which are picked up without a restart
+    /// once the files stay unchanged for a whole period
+    #[config(default = "defaults::torii::TLS_RELOAD_PERIOD.into()")]
+    pub reload_period_ms: DurationMs,
+}
+
+impl ToriiTls {
+    fn parse(self, emitter: &mut Emitter<ParseError>) -> Option<actual::ToriiTls> {
+        match (self.cert_file, self.key_file) {
+            (Some(cert_file), Some(key_file)) => Some(actual::ToriiTls {
+                cert_file,
+                key_file,
+                client_ca_file: self.client_ca_file,
+                reload_period: self.reload_period_ms.get(),
+            }),
+            (None, None) => None,
+            (cert_file, key_file) => {
+                let mut report = Report::new(ParseError::IncompleteToriiTls);
+                if let Some(file) = cert_file.or(key_file) {
+                    report = report.attach_printable(file.into_attachment().display_path());
+                }
+                emitter.emit(report);
+                None
+            }
+        }
+    }
+}
+
+/// Limits of the requests accepted from a single remote address or a single signing account.
+///
+/// Each route is limited with a token bucket refilled at the given rate per second,
+/// allowing bursts of up to the given size. Routes without a rate are not limited.
+#[derive(Debug, Clone, Copy, ReadConfig)]
+pub struct ToriiRateLimit {
+    pub transactions_per_sec: Option<NonZeroU32>,
+    /// Defaults to `transactions_per_sec`
+    pub transactions_burst: Option<NonZeroU32>,
+    pub queries_per_sec: Option<NonZeroU32>,
+    /// Defaults to `queries_per_sec`
+    pub queries_burst: Option<NonZeroU32>,
+    /// Limits opening the event, block and query subscription streams
+    pub streams_per_sec: Option<NonZeroU32>,
+    /// Defaults to `streams_per_sec`
+    pub streams_burst: Option<NonZeroU32>,
+}
+
+impl ToriiRateLimit {
+    fn parse(self) -> actual::ToriiRateLimit {
+        let limit = |per_second: Option<NonZeroU32>, burst: Option<NonZeroU32>| {
+            per_second.map(|per_second| actual::RateLimit {
+                per_second,
+                burst: burst.unwrap_or(per_second),
+            })
+        };
+
+        actual::ToriiRateLimit {
+            transactions: limit(self.transactions_per_sec, self.transactions_burst),
+            queries: limit(self.queries_per_sec, self.queries_burst),
+            streams: limit(self.streams_per_sec, self.streams_burst),
+        }
+    }
 }
 
 impl Torii {
-    fn parse(self) -> (actual::Torii, actual::LiveQueryStore) {
+    fn parse(self, emitter: &mut Emitter<ParseError>) -> (actual::Torii, actual::LiveQueryStore) {
         let torii = actual::Torii {
             address: self.address,
             max_content_len: self.max_content_len,
+            query_gateway_account: self.query_gateway_account,
+            query_subscription_capacity: self.query_subscription_capacity,
+            query_subscription_capacity_per_user: self.query_subscription_capacity_per_user,
+            rate_limit: self.rate_limit.parse(),
+            tls: self.tls.parse(emitter),
         };
 
         let query = actual::LiveQueryStore {
@@ -0,0 +1,48 @@
+//! Configuration tools related to the transaction queue specifically.
+
+use serde_with::{DeserializeFromStr, SerializeDisplay};
+
+/// Order in which transactions are taken from the queue into blocks.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Default,
+    strum::EnumString,
+    strum::Display,
+    DeserializeFromStr,
+    SerializeDisplay,
+)]
+#[strum(serialize_all = "snake_case")]
+pub enum Ordering {
+    /// Transactions are taken in the order of their arrival.
+    #[default]
+    Fifo,
+    /// Transactions of different authorities are taken in turns.
+    RoundRobin,
+    /// Transactions with the higher priority in their metadata are taken first.
+    ///
+    /// Only authorities granted `CanPrioritizeTransactions` may raise the priority
+    /// of their transactions.
+    Priority,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::queue::Ordering;
+
+    #[test]
+    fn ordering_display_reprs() {
+        assert_eq!(format!("{}", Ordering::Fifo), "fifo");
+        assert_eq!(format!("{}", Ordering::RoundRobin), "round_robin");
+        assert_eq!(format!("{}", Ordering::Priority), "priority");
+        assert_eq!("fifo".parse::<Ordering>().unwrap(), Ordering::Fifo);
+        assert_eq!(
+            "round_robin".parse::<Ordering>().unwrap(),
+            Ordering::RoundRobin
+        );
+        assert_eq!("priority".parse::<Ordering>().unwrap(), Ordering::Priority);
+    }
+}
@@ -123,6 +123,7 @@ fn minimal_config_snapshot() {
                     },
                 },
                 idle_timeout: 60s,
+                read_replicas: [],
             },
             genesis: Genesis {
                 public_key: PublicKey(
@@ -143,6 +144,15 @@ fn minimal_config_snapshot() {
                 max_content_len: Bytes(
                     16777216,
                 ),
+                query_gateway_account: None,
+                query_subscription_capacity: 256,
+                query_subscription_capacity_per_user: 16,
+                rate_limit: ToriiRateLimit {
+                    transactions: None,
+                    queries: None,
+                    streams: None,
+                },
+                tls: None,
             },
             kura: Kura {
                 init_mode: Strict,
@@ -153,9 +163,15 @@ fn minimal_config_snapshot() {
                     },
                 },
                 blocks_in_memory: 128,
+                segment_size: Bytes(
+                    134217728,
+                ),
+                compression: None,
+                blocks_to_keep: None,
                 debug_output_new_blocks: false,
             },
             sumeragi: Sumeragi {
+                read_replica: false,
                 debug_force_soft_fork: false,
             },
             block_sync: BlockSync {
@@ -180,6 +196,9 @@ fn minimal_config_snapshot() {
                 capacity: 65536,
                 capacity_per_user: 65536,
                 transaction_time_to_live: 86400s,
+                ordering: Fifo,
+                priority_key: "priority",
+                evict_when_full: false,
             },
             snapshot: Snapshot {
                 mode: ReadWrite,
@@ -192,6 +211,9 @@ fn minimal_config_snapshot() {
                         id: ParameterId(snapshot.store_dir),
                     },
                 },
+                deltas_per_base: 5,
+                history_size: 0,
+                history_cache_size: 4,
             },
             telemetry: None,
             dev_telemetry: DevTelemetry {
@@ -240,6 +262,28 @@ fn extra_fields() {
     assert_contains!(format!("{error:?}"), "unknown parameter: `foo`");
 }
 
+#[test]
+fn tls_certificate_without_key() {
+    let error = load_config_from_fixtures("bad.torii_tls_without_key.toml")
+        .expect_err("should fail without the key file");
+
+    assert_contains!(
+        format!("{error:?}"),
+        "Torii TLS requires both the certificate and the key files"
+    );
+}
+
+#[test]
+fn read_replica_without_trusted_peers() {
+    let error = load_config_from_fixtures("bad.read_replica_without_trusted_peers.toml")
+        .expect_err("should fail without peers to follow");
+
+    assert_contains!(
+        format!("{error:?}"),
+        "Read replica requires other trusted peers to follow"
+    );
+}
+
 /// Aims the purpose of checking that every single provided env variable is consumed and parsed
 /// into a valid config.
 #[test]
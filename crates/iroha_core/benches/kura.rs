@@ -3,7 +3,10 @@
 use criterion::{criterion_group, criterion_main, Criterion};
 use iroha_config::{
     base::WithOrigin,
-    parameters::{actual::Kura as Config, defaults::kura::BLOCKS_IN_MEMORY},
+    parameters::{
+        actual::Kura as Config,
+        defaults::kura::{BLOCKS_IN_MEMORY, SEGMENT_SIZE},
+    },
 };
 use iroha_core::{
     block::*,
@@ -17,7 +20,7 @@ use iroha_crypto::KeyPair;
 use iroha_data_model::prelude::*;
 use iroha_futures::supervisor::ShutdownSignal;
 use iroha_test_samples::gen_account_in;
-use tokio::{fs, runtime::Runtime};
+use tokio::runtime::Runtime;
 
 async fn measure_block_size_for_n_executors(n_executors: u32) {
     let dir = tempfile::tempdir().expect("Could not create tempfile.");
@@ -25,6 +28,9 @@ async fn measure_block_size_for_n_executors(n_executors: u32) {
         init_mode: iroha_config::kura::InitMode::Strict,
         debug_output_new_blocks: false,
         blocks_in_memory: BLOCKS_IN_MEMORY,
+        segment_size: SEGMENT_SIZE,
+        compression: iroha_config::kura::Compression::None,
+        blocks_to_keep: None,
         store_dir: WithOrigin::inline(dir.path().to_path_buf()),
     };
     let chain_id = ChainId::from("00000000-0000-0000-0000-000000000000");
@@ -78,8 +84,7 @@ async fn measure_block_size_for_n_executors(n_executors: u32) {
     block_store.create_files_if_they_do_not_exist().unwrap();
     block_store.append_block_to_chain(&block.into()).unwrap();
 
-    let metadata = fs::metadata(dir.path().join("blocks.data")).await.unwrap();
-    let file_size = metadata.len();
+    let file_size = block_store.read_block_index(0).unwrap().length;
     println!("For {n_executors} executors: {file_size} bytes");
 }
 
@@ -77,6 +77,8 @@ pub enum BlockValidationError {
         /// Actual value
         actual: Option<HashOf<BlockHeader>>,
     },
+    /// Leader seed is missing, unexpected or not revealed by the leader
+    InvalidLeaderSeed,
     /// Mismatch between the actual and expected height of the previous block. Expected: {expected}, actual: {actual}
     PrevBlockHeightMismatch {
         /// Expected value
@@ -112,12 +114,12 @@ pub enum BlockValidationError {
 /// Error during signature verification
 #[derive(Debug, displaydoc::Display, Clone, Copy, PartialEq, Eq, Error)]
 pub enum SignatureVerificationError {
-    /// The block doesn't have enough valid signatures to be committed (`votes_count` out of `min_votes_for_commit`)
+    /// The block doesn't have enough valid signatures to be committed (votes weight `votes_weight` out of `quorum_weight`)
     NotEnoughSignatures {
-        /// Current number of signatures
-        votes_count: usize,
-        /// Minimal required number of signatures
-        min_votes_for_commit: usize,
+        /// Current voting weight of the signatures
+        votes_weight: u64,
+        /// Minimal required voting weight of the signatures
+        quorum_weight: u64,
     },
     /// Block signatory doesn't correspond to any in topology
     UnknownSignatory,
@@ -127,6 +129,8 @@ pub enum SignatureVerificationError {
     ProxyTailMissing,
     /// The block doesn't have leader signature
     LeaderMissing,
+    /// The block carries individual signatures next to its commit certificate
+    SignaturesWithCertificate,
     /// Miscellaneous
     Other,
 }
@@ -142,7 +146,7 @@ pub enum InvalidGenesisError {
     ContainsErrors,
     /// Genesis transaction must contain instructions
     NotInstructions,
-    /// Genesis block must have 1 to 5 transactions (executor upgrade, parameters, ordinary instructions, wasm trigger registrations, initial topology)
+    /// Genesis block must have 1 to 6 transactions (executor upgrade, parameters, ordinary instructions, wasm trigger registrations, initial topology, peer weights)
     BadTransactionsAmount,
     /// First transaction must contain single `Upgrade` instruction to set executor
     MustUpgrade,
@@ -248,6 +252,8 @@ mod pending {
                 view_change_index: view_change_index
                     .try_into()
                     .expect("View change index should fit into u32"),
+                // Set once the leader reveals the seed upon signing the block
+                leader_seed: None,
             }
         }
 
@@ -282,7 +288,16 @@ mod chained {
 
     impl BlockBuilder<Chained> {
         /// Sign this block and get [`NewBlock`].
-        pub fn sign(self, private_key: &PrivateKey) -> WithEvents<NewBlock> {
+        ///
+        /// Reveals the [`LeaderSeed`] of the signing leader unless it's the genesis block.
+        pub fn sign(mut self, private_key: &PrivateKey) -> WithEvents<NewBlock> {
+            let leader_seed = self
+                .0
+                .header
+                .prev_block_hash
+                .and_then(|prev_block_hash| LeaderSeed::new(private_key, prev_block_hash));
+            self.0.header.leader_seed = leader_seed.as_ref().map(HashOf::new);
+
             let signature =
                 BlockSignature::new(0, SignatureOf::from_hash(private_key, self.0.header.hash()));
 
@@ -290,6 +305,7 @@ mod chained {
                 signature,
                 header: self.0.header,
                 transactions: self.0.transactions,
+                leader_seed,
             })
         }
     }
@@ -307,6 +323,7 @@ mod new {
         pub(super) signature: BlockSignature,
         pub(super) header: BlockHeader,
         pub(super) transactions: Vec<AcceptedTransaction>,
+        pub(super) leader_seed: Option<LeaderSeed>,
     }
 
     impl NewBlock {
@@ -344,6 +361,7 @@ mod new {
                 signature,
                 header,
                 transactions: self.transactions,
+                leader_seed: self.leader_seed,
             }
         }
     }
@@ -355,6 +373,7 @@ mod new {
                 block.header,
                 // FIXME: transmute somehow
                 block.transactions.into_iter().map(Into::into).collect(),
+                block.leader_seed,
             )
         }
     }
@@ -409,6 +428,40 @@ mod valid {
             Ok(())
         }
 
+        fn verify_leader_seed(
+            block: &SignedBlock,
+            topology: &Topology,
+        ) -> Result<(), BlockValidationError> {
+            use BlockValidationError::InvalidLeaderSeed;
+            let Some(prev_block_hash) = block.header().prev_block_hash else {
+                // Genesis block has no leader to reveal a seed
+                if block.leader_seed().is_some() || block.header().leader_seed.is_some() {
+                    return Err(InvalidLeaderSeed);
+                }
+                return Ok(());
+            };
+            let leader = topology.leader().public_key();
+
+            let expected_leader_seed = match block.leader_seed() {
+                Some(leader_seed) => {
+                    leader_seed
+                        .verify(leader, prev_block_hash)
+                        .map_err(|_err| InvalidLeaderSeed)?;
+                    Some(HashOf::new(leader_seed))
+                }
+                // Otherwise a leader could choose between its seed and none
+                None if LeaderSeed::is_revealed_with(leader.algorithm()) => {
+                    return Err(InvalidLeaderSeed)
+                }
+                None => None,
+            };
+            if block.header().leader_seed != expected_leader_seed {
+                return Err(InvalidLeaderSeed);
+            }
+
+            Ok(())
+        }
+
         fn verify_validator_signatures(
             block: &SignedBlock,
             topology: &Topology,
@@ -481,6 +534,58 @@ mod valid {
 
             Ok(())
         }
+
+        fn verify_commit_certificate(
+            block: &SignedBlock,
+            certificate: &BlockCommitCertificate,
+            topology: &Topology,
+        ) -> Result<(), SignatureVerificationError> {
+            use SignatureVerificationError::{
+                LeaderMissing, ProxyTailMissing, SignaturesWithCertificate, UnknownSignatory,
+                UnknownSignature,
+            };
+
+            // Individual signatures aren't verified once aggregated,
+            // so they must not be smuggled in next to the certificate
+            if block.signatures().next().is_some() {
+                return Err(SignaturesWithCertificate);
+            }
+
+            let mut signatories = Vec::new();
+            for index in certificate.signatories() {
+                let signatory = usize::try_from(index)
+                    .ok()
+                    .and_then(|index| topology.as_ref().get(index))
+                    .ok_or(UnknownSignatory)?;
+
+                match topology.role(signatory) {
+                    Role::Leader | Role::ValidatingPeer | Role::ProxyTail => {}
+                    Role::ObservingPeer if topology.view_change_index() >= 1 => {}
+                    Role::ObservingPeer | Role::Undefined => return Err(UnknownSignatory),
+                }
+                signatories.push(signatory.public_key());
+            }
+
+            let is_signatory = |index: usize| {
+                certificate
+                    .signatories()
+                    .any(|signatory| usize::try_from(signatory).is_ok_and(|s| s == index))
+            };
+            if !is_signatory(topology.leader_index()) {
+                return Err(LeaderMissing);
+            }
+            if topology.is_consensus_required().is_some()
+                && !is_signatory(topology.proxy_tail_index())
+            {
+                return Err(ProxyTailMissing);
+            }
+
+            certificate
+                .signature()
+                .verify_aggregate_hash(signatories, block.payload().header.hash())
+                .map_err(|_err| UnknownSignature)
+        }
+
         /// Validate the given block, apply resulting state changes,
         /// and record any transaction errors back into the block.
         pub fn validate(
@@ -593,25 +698,29 @@ mod valid {
                 });
             }
 
+            Self::verify_leader_seed(block, topology)?;
+
             if block.header().is_genesis() {
                 check_genesis_block(block, genesis_account)?;
             } else {
-                let prev_block_time = if soft_fork {
+                let prev_block = if soft_fork {
                     state.prev_block()
                 } else {
                     state.latest_block()
                 }
-                .expect("INTERNAL BUG: Genesis not committed")
-                .header()
-                .creation_time();
+                .expect("INTERNAL BUG: Genesis not committed");
 
-                if block.header().creation_time() <= prev_block_time {
+                if block.header().creation_time() <= prev_block.header().creation_time() {
                     return Err(BlockValidationError::BlockInThePast);
                 }
 
-                Self::verify_leader_signature(block, topology)?;
-                Self::verify_validator_signatures(block, topology)?;
-                Self::verify_no_undefined_signatures(block, topology)?;
+                if let Some(certificate) = block.commit_certificate() {
+                    Self::verify_commit_certificate(block, certificate, topology)?;
+                } else {
+                    Self::verify_leader_signature(block, topology)?;
+                    Self::verify_validator_signatures(block, topology)?;
+                    Self::verify_no_undefined_signatures(block, topology)?;
+                }
             }
 
             let (max_clock_drift, tx_params) = {
@@ -862,13 +971,17 @@ mod valid {
             topology: &Topology,
         ) -> Result<(), SignatureVerificationError> {
             if !block.header().is_genesis() {
-                Self::verify_proxy_tail_signature(block, topology)?;
-
-                let votes_count = block.signatures().len();
-                if votes_count < topology.min_votes_for_commit() {
+                let votes_weight = if let Some(certificate) = block.commit_certificate() {
+                    Self::verify_commit_certificate(block, certificate, topology)?;
+                    topology.signatories_weight(certificate.signatories())
+                } else {
+                    Self::verify_proxy_tail_signature(block, topology)?;
+                    topology.signatories_weight(block.signatories())
+                };
+                if votes_weight < topology.quorum_weight() {
                     return Err(SignatureVerificationError::NotEnoughSignatures {
-                        votes_count,
-                        min_votes_for_commit: topology.min_votes_for_commit(),
+                        votes_weight,
+                        quorum_weight: topology.quorum_weight(),
                     });
                 }
             }
@@ -903,6 +1016,7 @@ mod valid {
                 result_merkle_root: None,
                 creation_time_ms: 0,
                 view_change_index: 0,
+                leader_seed: None,
             };
             f(&mut header);
             let unverified_block = BlockBuilder(Chained {
@@ -920,6 +1034,7 @@ mod valid {
                     .into_iter()
                     .map(Into::into)
                     .collect(),
+                unverified_block.leader_seed,
             ))
         }
     }
@@ -954,7 +1069,7 @@ mod valid {
             .map_err(|_| InvalidGenesisError::InvalidSignature)?;
 
         let transactions = block.payload().transactions.as_slice();
-        if transactions.is_empty() || transactions.len() > 5 {
+        if transactions.is_empty() || transactions.len() > 6 {
             return Err(InvalidGenesisError::BadTransactionsAmount);
         }
         for (i, transaction) in transactions.iter().enumerate() {
@@ -975,11 +1090,51 @@ mod valid {
 
     #[cfg(test)]
     mod tests {
-        use iroha_crypto::SignatureOf;
+        use iroha_crypto::{Algorithm, SignatureOf};
 
         use super::*;
         use crate::sumeragi::network_topology::test_topology_with_keys;
 
+        #[test]
+        fn leader_seed_verification() {
+            let key_pairs =
+                core::iter::repeat_with(|| KeyPair::random_with_algorithm(Algorithm::BlsNormal))
+                    .take(4)
+                    .collect::<Vec<_>>();
+            let topology = test_topology_with_keys(&key_pairs);
+            let prev_block_hash =
+                HashOf::from_untyped_unchecked(Hash::prehashed([2; Hash::LENGTH]));
+            let new_block = |leader: &KeyPair| {
+                ValidBlock::new_dummy_and_modify_header(leader.private_key(), |header| {
+                    header.prev_block_hash = Some(prev_block_hash);
+                })
+            };
+
+            let block = new_block(&key_pairs[0]);
+            assert!(block.as_ref().leader_seed().is_some());
+            assert_eq!(
+                ValidBlock::verify_leader_seed(block.as_ref(), &topology),
+                Ok(())
+            );
+
+            // Seed revealed by a peer other than the leader
+            let block = new_block(&key_pairs[1]);
+            assert_eq!(
+                ValidBlock::verify_leader_seed(block.as_ref(), &topology),
+                Err(BlockValidationError::InvalidLeaderSeed)
+            );
+
+            // Seed withheld by the leader
+            let block = new_block(&key_pairs[0]);
+            let signature = block.as_ref().signatures().next().unwrap().clone();
+            let block =
+                SignedBlock::presigned(signature, block.as_ref().header(), Vec::new(), None);
+            assert_eq!(
+                ValidBlock::verify_leader_seed(&block, &topology),
+                Err(BlockValidationError::InvalidLeaderSeed)
+            );
+        }
+
         #[test]
         fn signature_verification_ok() {
             let key_pairs = core::iter::repeat_with(KeyPair::random)
@@ -1011,6 +1166,152 @@ mod valid {
             let _ = block.commit(&topology).unpack(|_| {}).unwrap();
         }
 
+        /// Check that a block is committed once signatures reach the quorum weight
+        #[test]
+        fn signature_verification_weighted_ok() {
+            let key_pairs = core::iter::repeat_with(KeyPair::random)
+                .take(7)
+                .collect::<Vec<_>>();
+            let mut topology = test_topology_with_keys(&key_pairs);
+            let leader = topology.leader().clone();
+            topology.set_weights(
+                [(leader, nonzero_ext::nonzero!(4_u64))]
+                    .into_iter()
+                    .collect(),
+            );
+            // Total weight is 10, so the quorum weight of 7 is reached by the first 4 peers
+            assert_eq!(topology.min_votes_for_commit(), 4);
+
+            let mut block = ValidBlock::new_dummy(key_pairs[0].private_key());
+            let payload = block.0.payload().clone();
+            key_pairs
+                .iter()
+                .enumerate()
+                .take(topology.min_votes_for_commit())
+                // Skip leader since already singed
+                .skip(1)
+                .filter(|(i, _)| *i != 3) // Skip proxy tail
+                .map(|(i, key_pair)| {
+                    BlockSignature::new(
+                        i as u64,
+                        SignatureOf::from_hash(key_pair.private_key(), payload.header.hash()),
+                    )
+                })
+                .try_for_each(|signature| block.add_signature(signature, &topology))
+                .expect("Failed to add signatures");
+
+            block.sign(&key_pairs[3], &topology);
+
+            let _ = block.commit(&topology).unpack(|_| {}).unwrap();
+        }
+
+        fn committed_block(key_pairs: &[KeyPair], topology: &Topology) -> CommittedBlock {
+            let mut block = ValidBlock::new_dummy(key_pairs[0].private_key());
+            key_pairs
+                .iter()
+                .take(topology.min_votes_for_commit())
+                // Skip leader since already singed
+                .skip(1)
+                .for_each(|key_pair| block.sign(key_pair, topology));
+
+            block.commit(topology).unpack(|_| {}).unwrap()
+        }
+
+        #[test]
+        fn commit_certificate_verification_ok() {
+            let key_pairs =
+                core::iter::repeat_with(|| KeyPair::random_with_algorithm(Algorithm::BlsNormal))
+                    .take(7)
+                    .collect::<Vec<_>>();
+            let topology = test_topology_with_keys(&key_pairs);
+
+            let mut block = committed_block(&key_pairs, &topology);
+            let signatories = block.as_ref().signatories().collect::<Vec<_>>();
+            block.aggregate_signatures(&topology);
+
+            assert_eq!(block.as_ref().signatures().len(), 0);
+            assert!(block.as_ref().commit_certificate().is_some());
+            assert_eq!(
+                block.as_ref().signatories().collect::<Vec<_>>(),
+                signatories
+            );
+            ValidBlock::is_commit(block.as_ref(), &topology).unwrap();
+        }
+
+        #[test]
+        fn commit_certificate_with_concealed_signatory_fails() {
+            let key_pairs =
+                core::iter::repeat_with(|| KeyPair::random_with_algorithm(Algorithm::BlsNormal))
+                    .take(7)
+                    .collect::<Vec<_>>();
+            let topology = test_topology_with_keys(&key_pairs);
+
+            let mut block = committed_block(&key_pairs, &topology);
+            block.aggregate_signatures(&topology);
+            let certificate = block.as_ref().commit_certificate().unwrap();
+
+            // Conceal one of the validators whose signature was aggregated
+            let forged_certificate = BlockCommitCertificate::new(
+                certificate.signatories().filter(|&index| index != 2),
+                certificate.signature().clone(),
+            );
+            assert_eq!(
+                ValidBlock::verify_commit_certificate(
+                    block.as_ref(),
+                    &forged_certificate,
+                    &topology
+                ),
+                Err(SignatureVerificationError::UnknownSignature)
+            );
+        }
+
+        #[test]
+        fn commit_certificate_with_forged_signature_fails() {
+            let key_pairs =
+                core::iter::repeat_with(|| KeyPair::random_with_algorithm(Algorithm::BlsNormal))
+                    .take(7)
+                    .collect::<Vec<_>>();
+            let topology = test_topology_with_keys(&key_pairs);
+
+            let mut block = committed_block(&key_pairs, &topology);
+            block.aggregate_signatures(&topology);
+            let certificate = block.as_ref().commit_certificate().unwrap().clone();
+
+            // Attach a signature of a validator who didn't vote for the block
+            let outsider = (0..7)
+                .find(|&index| {
+                    certificate
+                        .signatories()
+                        .all(|signatory| signatory != index)
+                })
+                .unwrap();
+            let forger = KeyPair::random_with_algorithm(Algorithm::BlsNormal);
+            block
+                .0
+                 .0
+                .sign(forger.private_key(), usize::try_from(outsider).unwrap());
+
+            assert!(!block.as_ref().signatories().any(|index| index == outsider));
+            assert_eq!(
+                ValidBlock::is_commit(block.as_ref(), &topology),
+                Err(SignatureVerificationError::SignaturesWithCertificate)
+            );
+        }
+
+        #[test]
+        fn non_bls_signatures_are_not_aggregated() {
+            let key_pairs = core::iter::repeat_with(KeyPair::random)
+                .take(7)
+                .collect::<Vec<_>>();
+            let topology = test_topology_with_keys(&key_pairs);
+
+            let mut block = committed_block(&key_pairs, &topology);
+            block.aggregate_signatures(&topology);
+
+            assert!(block.as_ref().commit_certificate().is_none());
+            ValidBlock::is_commit(block.as_ref(), &topology).unwrap();
+        }
+
         #[test]
         fn signature_verification_consensus_not_required_ok() {
             let key_pairs = core::iter::repeat_with(KeyPair::random)
@@ -1037,8 +1338,8 @@ mod valid {
             assert_eq!(
                 block.commit(&topology).unpack(|_| {}).unwrap_err().1,
                 SignatureVerificationError::NotEnoughSignatures {
-                    votes_count: 2,
-                    min_votes_for_commit: topology.min_votes_for_commit(),
+                    votes_weight: 2,
+                    quorum_weight: topology.quorum_weight(),
                 }
                 .into()
             )
@@ -1105,6 +1406,28 @@ mod commit {
         }
     }
 
+    impl CommittedBlock {
+        /// Aggregate the block signatures into a commit certificate
+        /// if all peers in the `topology` the block was committed with use the same BLS algorithm.
+        pub fn aggregate_signatures(&mut self, topology: &Topology) {
+            if self.as_ref().header().is_genesis() {
+                return;
+            }
+
+            if topology.aggregation_algorithm().is_some() {
+                let public_keys = topology
+                    .as_ref()
+                    .iter()
+                    .map(PeerId::public_key)
+                    .collect::<Vec<_>>();
+                let block = &mut self.0 .0;
+                block
+                    .aggregate_signatures(&public_keys)
+                    .expect("INTERNAL BUG: Failed to aggregate verified block signatures");
+            }
+        }
+    }
+
     #[cfg(test)]
     impl AsMut<SignedBlock> for CommittedBlock {
         fn as_mut(&mut self) -> &mut SignedBlock {
@@ -1252,6 +1575,7 @@ mod event {
 
 #[cfg(test)]
 mod tests {
+    use iroha_crypto::Algorithm;
     use iroha_data_model::prelude::*;
     use iroha_genesis::GENESIS_DOMAIN_ID;
     use iroha_test_samples::gen_account_in;
@@ -1277,6 +1601,49 @@ mod tests {
         assert_eq!(valid_block.0.hash(), committed_block.as_ref().hash())
     }
 
+    #[test]
+    fn header_records_leader_seed() {
+        let peer_key_pair = KeyPair::random_with_algorithm(Algorithm::BlsNormal);
+        let prev_block = ValidBlock::new_dummy(peer_key_pair.private_key());
+        let prev_block: &SignedBlock = prev_block.as_ref();
+
+        let unverified_block = BlockBuilder::new(Vec::new())
+            .chain(0, Some(prev_block))
+            .sign(peer_key_pair.private_key())
+            .unpack(|_| {});
+        let leader_seed = unverified_block
+            .leader_seed
+            .expect("BLS leader reveals a seed");
+        leader_seed
+            .verify(peer_key_pair.public_key(), prev_block.hash())
+            .expect("Seed is revealed by the leader");
+        assert_eq!(
+            unverified_block.header.leader_seed,
+            Some(HashOf::new(&leader_seed))
+        );
+
+        let genesis = BlockBuilder::new(Vec::new())
+            .chain(0, None)
+            .sign(peer_key_pair.private_key())
+            .unpack(|_| {});
+        assert_eq!(genesis.header.leader_seed, None);
+        assert!(genesis.leader_seed.is_none());
+    }
+
+    #[test]
+    fn leader_seed_is_only_revealed_with_bls_keys() {
+        let peer_key_pair = KeyPair::random_with_algorithm(Algorithm::Ed25519);
+        let prev_block = ValidBlock::new_dummy(peer_key_pair.private_key());
+        let prev_block: &SignedBlock = prev_block.as_ref();
+
+        let unverified_block = BlockBuilder::new(Vec::new())
+            .chain(0, Some(prev_block))
+            .sign(peer_key_pair.private_key())
+            .unpack(|_| {});
+        assert_eq!(unverified_block.header.leader_seed, None);
+        assert!(unverified_block.leader_seed.is_none());
+    }
+
     #[tokio::test]
     async fn should_reject_due_to_repetition() {
         let chain_id = ChainId::from("00000000-0000-0000-0000-000000000000");
@@ -55,6 +55,8 @@ pub struct BlockSynchronizer {
     network: IrohaNetwork,
     state: Arc<State>,
     seen_blocks: BTreeSet<(NonZeroUsize, HashOf<BlockHeader>)>,
+    /// Peers which don't store the blocks following the latest height
+    pruned_peers: BTreeSet<PeerId>,
     latest_height: usize,
 }
 
@@ -100,25 +102,40 @@ impl BlockSynchronizer {
         // This guards against a softfork and adds general redundancy.
         if now_height == self.latest_height {
             self.seen_blocks.clear();
+        } else {
+            self.pruned_peers.clear();
         }
         self.latest_height = now_height;
 
         self.seen_blocks
             .retain(|(height, _hash)| height.get() >= now_height);
 
-        if let Some(random_peer) = self.network.online_peers(Self::random_peer) {
+        let random_peer = self
+            .network
+            .online_peers(|peers| Self::random_peer(peers, &self.pruned_peers));
+        if let Some(random_peer) = random_peer {
             self.request_latest_blocks_from_peer(random_peer.id().clone())
                 .await;
+        } else {
+            // Peers might have synchronized meanwhile, so all of them are asked again next time
+            self.pruned_peers.clear();
         }
     }
 
-    /// Get a random online peer.
+    /// Get a random online peer which isn't `excluded`.
     #[allow(clippy::disallowed_types)]
-    fn random_peer(peers: &std::collections::HashSet<Peer>) -> Option<Peer> {
+    fn random_peer(
+        peers: &std::collections::HashSet<Peer>,
+        excluded: &BTreeSet<PeerId>,
+    ) -> Option<Peer> {
         use rand::{seq::IteratorRandom, SeedableRng};
 
         let rng = &mut rand::rngs::StdRng::from_entropy();
-        peers.iter().choose(rng).cloned()
+        peers
+            .iter()
+            .filter(|peer| !excluded.contains(peer.id()))
+            .choose(rng)
+            .cloned()
     }
 
     /// Sends request for latest blocks to a chosen peer
@@ -158,6 +175,7 @@ impl BlockSynchronizer {
             network,
             state,
             seen_blocks: BTreeSet::new(),
+            pruned_peers: BTreeSet::new(),
             latest_height: 0,
         }
     }
@@ -214,6 +232,25 @@ pub mod message {
         }
     }
 
+    /// Message variant to tell the peer that the requested blocks were pruned
+    #[derive(Debug, Clone, Decode, Encode)]
+    pub struct BlocksPruned {
+        /// Peer id
+        pub peer_id: PeerId,
+        /// Height of the first block still stored by the peer
+        pub first_height: NonZeroUsize,
+    }
+
+    impl BlocksPruned {
+        /// Construct [`BlocksPruned`].
+        pub const fn new(peer_id: PeerId, first_height: NonZeroUsize) -> Self {
+            Self {
+                peer_id,
+                first_height,
+            }
+        }
+    }
+
     /// Message's variants that are used by peers to communicate in the process of consensus.
     #[derive(Debug, Clone, Decode, Encode, FromVariant)]
     pub enum Message {
@@ -221,6 +258,8 @@ pub mod message {
         GetBlocksAfter(GetBlocksAfter),
         /// The response to `GetBlocksAfter`. Contains the requested blocks and the id of the peer who shared them.
         ShareBlocks(ShareBlocks),
+        /// The response to `GetBlocksAfter` if the requested blocks are no longer stored by the peer.
+        BlocksPruned(BlocksPruned),
     }
 
     impl Message {
@@ -260,6 +299,24 @@ pub mod message {
                         nonzero_ext::nonzero!(1_usize)
                     };
 
+                    let pruned_blocks_count = block_sync.kura.pruned_blocks_count();
+                    if start_height.get() <= pruned_blocks_count {
+                        trace!(
+                            peer=%peer_id,
+                            height=%start_height,
+                            "Requested blocks are pruned"
+                        );
+                        let first_height = NonZeroUsize::new(pruned_blocks_count + 1)
+                            .expect("INTERNAL BUG: Blockchain height overflow");
+                        Message::BlocksPruned(BlocksPruned::new(
+                            block_sync.peer.id.clone(),
+                            first_height,
+                        ))
+                        .send_to(&block_sync.network, peer_id.clone())
+                        .await;
+                        return;
+                    }
+
                     let blocks = block_sync
                         .state
                         .view()
@@ -293,6 +350,27 @@ pub mod message {
                         block_sync.sumeragi.incoming_block_message(msg);
                     }
                 }
+                Message::BlocksPruned(BlocksPruned {
+                    peer_id,
+                    first_height,
+                }) => {
+                    warn!(
+                        peer=%peer_id,
+                        %first_height,
+                        "Peer has pruned the requested blocks, requesting them from another peer"
+                    );
+                    block_sync.pruned_peers.insert(peer_id.clone());
+                    let next_peer = block_sync.network.online_peers(|peers| {
+                        BlockSynchronizer::random_peer(peers, &block_sync.pruned_peers)
+                    });
+                    if let Some(next_peer) = next_peer {
+                        block_sync
+                            .request_latest_blocks_from_peer(next_peer.id().clone())
+                            .await;
+                    } else {
+                        warn!("None of the online peers stores the requested blocks");
+                    }
+                }
             }
         }
 
@@ -268,6 +268,29 @@ impl Executor {
         *self = Self::UserProvided(loaded_executor);
         Ok(())
     }
+
+    /// Original wasm of the user-provided executor, if any.
+    pub(crate) fn raw_executor(&self) -> Option<&data_model_executor::Executor> {
+        match self {
+            Self::Initial => None,
+            Self::UserProvided(loaded_executor) => Some(&loaded_executor.raw_executor),
+        }
+    }
+
+    /// Restore executor from the wasm returned by [`Self::raw_executor`]
+    /// without running its migration.
+    ///
+    /// # Errors
+    ///
+    /// Failed to load `raw_executor`
+    pub(crate) fn from_raw_executor(
+        engine: &wasmtime::Engine,
+        raw_executor: Option<data_model_executor::Executor>,
+    ) -> Result<Self, wasm::error::Error> {
+        raw_executor.map_or(Ok(Self::Initial), |raw_executor| {
+            LoadedExecutor::load(engine, raw_executor).map(Self::UserProvided)
+        })
+    }
 }
 
 /// [`Executor`] with [`Module`](wasmtime::Module) for execution.
@@ -140,6 +140,18 @@ impl TransactionGossiper {
                     }) => {
                         iroha_logger::trace!(tx = %tx.as_ref().as_ref().hash(), "Transaction already in the queue, ignoring...")
                     }
+                    Err(crate::queue::Failure {
+                        tx,
+                        err: crate::queue::Error::Superseded,
+                    }) => {
+                        iroha_logger::trace!(tx = %tx.as_ref().as_ref().hash(), "Transaction is superseded by a newer one, ignoring...")
+                    }
+                    Err(crate::queue::Failure {
+                        tx,
+                        err: crate::queue::Error::SequenceUsed,
+                    }) => {
+                        iroha_logger::debug!(tx = %tx.as_ref().as_ref().hash(), "Transaction sequence number is already used, ignoring...")
+                    }
                     Err(crate::queue::Failure { tx, err }) => {
                         iroha_logger::error!(?err, tx = %tx.as_ref().as_ref().hash(), "Failed to enqueue transaction.")
                     }
@@ -2,6 +2,8 @@
 //! logic.  [`Kura`] is the main entity which should be used to store
 //! new [`Block`](iroha_data_model::block::SignedBlock)s on the
 //! blockchain.
+//!
+//! Blocks are stored in segment files of a fixed size, see [`BlockStore`].
 use std::{
     fmt::Debug,
     io::{BufWriter, Read, Seek, SeekFrom, Write},
@@ -12,8 +14,11 @@ use std::{
 };
 
 use iroha_config::{
-    kura::InitMode,
-    parameters::{actual::Kura as Config, defaults::kura::BLOCKS_IN_MEMORY},
+    kura::{Compression, InitMode},
+    parameters::{
+        actual::Kura as Config,
+        defaults::kura::{BLOCKS_IN_MEMORY, SEGMENT_SIZE},
+    },
 };
 use iroha_crypto::{Hash, HashOf};
 use iroha_data_model::block::{BlockHeader, SignedBlock};
@@ -22,6 +27,7 @@ use iroha_logger::prelude::*;
 use iroha_version::scale::{DecodeVersioned, EncodeVersioned};
 use parity_scale_codec::DecodeAll;
 use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
 
 use crate::block::CommittedBlock;
 
@@ -32,8 +38,24 @@ impl From<CommittedBlock> for Arc<SignedBlock> {
 }
 
 const INDEX_FILE_NAME: &str = "blocks.index";
-const DATA_FILE_NAME: &str = "blocks.data";
 const HASHES_FILE_NAME: &str = "blocks.hashes";
+const CHECKSUMS_FILE_NAME: &str = "blocks.checksums";
+const MANIFEST_FILE_NAME: &str = "blocks.manifest";
+const MANIFEST_TMP_FILE_NAME: &str = "blocks.manifest.tmp";
+const SEGMENTS_DIR_NAME: &str = "segments";
+const SEGMENT_FILE_EXTENSION: &str = "data";
+/// Single data file holding all blocks in the layout preceding segments
+const LEGACY_DATA_FILE_NAME: &str = "blocks.data";
+/// Directory where the store is rebuilt while migrating from the legacy layout
+const MIGRATION_DIR_NAME: &str = "migration.tmp";
+
+/// Current version of the block store layout, see [`Manifest`].
+const LAYOUT_VERSION: u32 = 1;
+/// Magic bytes of a zstd frame.
+/// Never clash with versioned blocks since those start with the version byte.
+const ZSTD_MAGIC: [u8; 4] = [0x28, 0xB5, 0x2F, 0xFD];
+/// Compression level used for blocks.
+const COMPRESSION_LEVEL: i32 = 3;
 
 const SIZE_OF_BLOCK_HASH: u64 = Hash::LENGTH as u64;
 
@@ -51,6 +73,8 @@ pub struct Kura {
     blocks_in_memory: NonZeroUsize,
     /// Amount of blocks loaded during initialization
     init_block_count: usize,
+    /// Number of the latest blocks kept in the storage, all blocks are kept if [`None`]
+    blocks_to_keep: Option<NonZeroUsize>,
 }
 
 type BlockData = Vec<(HashOf<BlockHeader>, Option<Arc<SignedBlock>>)>;
@@ -66,7 +90,9 @@ impl Kura {
     /// path.
     pub fn new(config: &Config) -> Result<(Arc<Self>, BlockCount)> {
         let store_dir = config.store_dir.resolve_relative_path();
-        let mut block_store = BlockStore::new(&store_dir);
+        let mut block_store = BlockStore::new(&store_dir)
+            .with_segment_size(config.segment_size.get())
+            .with_compression(config.compression);
         block_store.create_files_if_they_do_not_exist()?;
 
         let block_plain_text_path = config
@@ -83,6 +109,7 @@ impl Kura {
             block_plain_text_path,
             blocks_in_memory: config.blocks_in_memory,
             init_block_count: block_count,
+            blocks_to_keep: config.blocks_to_keep,
         });
 
         Ok((kura, BlockCount(block_count)))
@@ -97,6 +124,7 @@ impl Kura {
             block_plain_text_path: None,
             blocks_in_memory: BLOCKS_IN_MEMORY,
             init_block_count: 0,
+            blocks_to_keep: None,
         })
     }
 
@@ -162,34 +190,41 @@ impl Kura {
     ) -> Result<Vec<HashOf<BlockHeader>>, Error> {
         let mut block_hashes = Vec::with_capacity(block_index_count);
 
-        let mut block_indices = vec![BlockIndex::default(); block_index_count];
-        block_store.read_block_indices(0, &mut block_indices)?;
-
-        let mut prev_block_hash = None;
-        for block in block_indices {
-            // This is re-allocated every iteration. This could cause a problem.
-            let mut block_data_buffer = vec![0_u8; block.length.try_into()?];
-
-            match block_store.read_block_data(block.start, &mut block_data_buffer) {
-                Ok(()) => match SignedBlock::decode_all_versioned(&block_data_buffer) {
-                    Ok(decoded_block) => {
-                        if prev_block_hash != decoded_block.header().prev_block_hash {
-                            error!(expected=?prev_block_hash, actual=?decoded_block.header().prev_block_hash,
-                                "Block has wrong previous block hash. Not reading any blocks beyond this height."
-                            );
-                            break;
-                        }
-                        let decoded_block_hash = decoded_block.hash();
-                        block_hashes.push(decoded_block_hash);
-                        prev_block_hash = Some(decoded_block_hash);
-                    }
-                    Err(error) => {
-                        error!(?error, "Encountered malformed block. Not reading any blocks beyond this height.");
+        // Pruned blocks can't be verified, so their hashes are taken as is
+        let first_block = usize::try_from(block_store.first_block())?.min(block_index_count);
+        block_hashes.extend(block_store.read_block_hashes(0, first_block)?);
+
+        let mut block_indices = vec![BlockIndex::default(); block_index_count - first_block];
+        block_store.read_block_indices(first_block as u64, &mut block_indices)?;
+
+        let mut prev_block_hash = block_hashes.last().copied();
+        for (block_height, block) in (first_block as u64..).zip(block_indices) {
+            match block_store.read_block_at(block_height, block) {
+                Ok(decoded_block) => {
+                    if prev_block_hash != decoded_block.header().prev_block_hash {
+                        error!(expected=?prev_block_hash, actual=?decoded_block.header().prev_block_hash,
+                            "Block has wrong previous block hash. Not reading any blocks beyond this height."
+                        );
                         break;
                     }
-                },
+                    let decoded_block_hash = decoded_block.hash();
+                    block_hashes.push(decoded_block_hash);
+                    prev_block_hash = Some(decoded_block_hash);
+                }
+                Err(
+                    error @ (Error::Codec(_)
+                    | Error::Version(_)
+                    | Error::Compression(_)
+                    | Error::Checksum(_)),
+                ) => {
+                    error!(
+                        ?error,
+                        "Encountered malformed block. Not reading any blocks beyond this height."
+                    );
+                    break;
+                }
                 Err(error) => {
-                    error!(?error, "Malformed block index or corrupted block data file. Not reading any blocks beyond this height.");
+                    error!(?error, "Malformed block index or corrupted block segment. Not reading any blocks beyond this height.");
                     break;
                 }
             }
@@ -326,20 +361,13 @@ impl Kura {
         }
 
         let block_store = self.block_store.lock();
-        let BlockIndex { start, length } = block_store
-            .read_block_index(block_index as u64)
-            .expect("INTERNAL BUG: Failed to read block index from disk.");
-
-        let mut block_buf = vec![
-            0_u8;
-            usize::try_from(length)
-                .expect("INTERNAL BUG: index_len didn't fit in 32-bits")
-        ];
-        block_store
-            .read_block_data(start, &mut block_buf)
-            .expect("INTERNAL BUG: Failed to read block data.");
-        let block = SignedBlock::decode_all_versioned(&block_buf)
-            .expect("INTERNAL BUG: Failed to decode block");
+        if (block_index as u64) < block_store.first_block() {
+            return None;
+        }
+        let block = block_store
+            .read_block(block_index as u64)
+            .expect("INTERNAL BUG: Failed to read block from disk.");
+        drop(block_store);
 
         let block_arc = Arc::new(block);
         // Only last N blocks should be kept in memory
@@ -381,6 +409,37 @@ impl Kura {
     pub fn blocks_count(&self) -> usize {
         self.block_data.lock().len()
     }
+
+    /// Returns count of the oldest blocks removed from the storage.
+    /// Only hashes of such blocks are known, the blocks themselves can't be loaded.
+    pub fn pruned_blocks_count(&self) -> usize {
+        usize::try_from(self.block_store.lock().first_block())
+            .expect("INTERNAL BUG: block count exceeds usize::MAX")
+    }
+
+    /// Whether Kura is configured to keep only the latest blocks.
+    pub fn prunes_blocks(&self) -> bool {
+        self.blocks_to_keep.is_some()
+    }
+
+    /// Remove the oldest blocks from the storage if Kura is configured to keep only the latest ones.
+    ///
+    /// Only blocks below `snapshot_height` are removed, since the state can't be restored without
+    /// the blocks not covered by a snapshot. The block at `snapshot_height` itself is kept as well.
+    pub fn prune_blocks(&self, snapshot_height: usize) {
+        let Some(blocks_to_keep) = self.blocks_to_keep else {
+            return;
+        };
+        let prune_below = self
+            .blocks_count()
+            .saturating_sub(blocks_to_keep.get())
+            .min(snapshot_height.saturating_sub(1));
+
+        match self.block_store.lock().prune_below(prune_below as u64) {
+            Ok(first_block) => debug!(first_block, "Pruned old blocks"),
+            Err(error) => error!(?error, "Failed to prune old blocks"),
+        }
+    }
 }
 
 /// Loaded block count
@@ -389,9 +448,81 @@ pub struct BlockCount(pub usize);
 
 /// An implementation of a block store for `Kura`
 /// that uses `std::fs`, the default IO file in Rust.
+///
+/// Block data is split into segment files of a fixed size. Locations in the
+/// index file are offsets in the continuous space formed by all segments.
+/// Blocks never cross segment boundaries, a block bigger than the segment size
+/// occupies a whole segment on its own.
 #[derive(Debug)]
 pub struct BlockStore {
     path_to_blockchain: PathBuf,
+    /// Size of a single segment file, taken from the manifest for an existing store
+    segment_size: u64,
+    /// Compression of the blocks being appended
+    compression: Compression,
+    /// Height (counting from 0) of the first block which isn't pruned
+    first_block: u64,
+}
+
+/// Layout of the block store, persisted next to the block files.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+struct Manifest {
+    /// Version of the layout
+    version: u32,
+    /// Size of a single segment file in bytes
+    segment_size: u64,
+    /// Height (counting from 0) of the first block which isn't pruned
+    #[serde(default)]
+    first_block: u64,
+    /// Compression of the blocks being appended.
+    /// Compression of every stored block is kept in the checksums file.
+    #[serde(default)]
+    compression: Compression,
+}
+
+/// Record of the checksums file describing how a block is stored
+///
+/// Records are absent for blocks written before the checksums file was introduced,
+/// which is represented by a zeroed record.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct BlockChecksum {
+    /// CRC32 of the block data as stored
+    crc: u32,
+    /// Compression of the block data
+    compression: Compression,
+}
+
+impl BlockChecksum {
+    const SIZE: u64 = 5;
+
+    fn new(block_data: &[u8], compression: Compression) -> Self {
+        Self {
+            crc: crc32fast::hash(block_data),
+            compression,
+        }
+    }
+
+    fn to_bytes(self) -> [u8; Self::SIZE as usize] {
+        let mut bytes = [0; Self::SIZE as usize];
+        bytes[..4].copy_from_slice(&self.crc.to_le_bytes());
+        bytes[4] = match self.compression {
+            Compression::None => 1,
+            Compression::Zstd => 2,
+        };
+        bytes
+    }
+
+    /// Parse the record of the block at `block_height`, `None` if the record is absent.
+    fn from_bytes(bytes: [u8; Self::SIZE as usize], block_height: u64) -> Result<Option<Self>> {
+        let compression = match bytes[4] {
+            0 => return Ok(None),
+            1 => Compression::None,
+            2 => Compression::Zstd,
+            _ => return Err(Error::Checksum(block_height)),
+        };
+        let crc = u32::from_le_bytes(bytes[..4].try_into().expect("4 bytes"));
+        Ok(Some(Self { crc, compression }))
+    }
 }
 
 #[derive(Default, Debug, Clone, Copy)]
@@ -429,9 +560,46 @@ impl BlockStore {
     pub fn new(store_path: impl AsRef<Path>) -> Self {
         Self {
             path_to_blockchain: store_path.as_ref().to_path_buf(),
+            segment_size: SEGMENT_SIZE.get(),
+            compression: Compression::None,
+            first_block: 0,
         }
     }
 
+    /// Set the size of segment files for a newly created store.
+    ///
+    /// An existing store keeps the size recorded in its manifest.
+    #[must_use]
+    pub fn with_segment_size(mut self, segment_size: u64) -> Self {
+        self.segment_size = segment_size.max(1);
+        self
+    }
+
+    /// Set the compression of the blocks being appended.
+    ///
+    /// Blocks already in the store are read regardless of how they were written.
+    #[must_use]
+    pub fn with_compression(mut self, compression: Compression) -> Self {
+        self.compression = compression;
+        self
+    }
+
+    /// Use the layout recorded in the manifest of an existing store
+    /// without creating or migrating any files.
+    ///
+    /// # Errors
+    /// - The manifest is missing, i.e. there is no store or it is in the legacy layout
+    /// - IO Error
+    pub fn load_layout(&mut self) -> Result<()> {
+        let manifest = self
+            .read_manifest()?
+            .ok_or_else(|| Error::MissingManifest(self.path_to_blockchain.clone()))?;
+        self.segment_size = manifest.segment_size;
+        self.first_block = manifest.first_block;
+        self.compression = manifest.compression;
+        Ok(())
+    }
+
     /// Read a series of block indices from the block index file and
     /// attempt to fill all of `dest_buffer`.
     ///
@@ -566,9 +734,12 @@ impl BlockStore {
     }
 
     /// Read block data starting from the
-    /// `start_location_in_data_file` in data file in order to fill
+    /// `start_location_in_data_file` in block segments in order to fill
     /// `dest_buffer`.
     ///
+    /// The data is returned as stored, i.e. possibly compressed.
+    /// Use [`Self::read_block`] to get a decoded block.
+    ///
     /// # Errors
     /// IO Error.
     pub fn read_block_data(
@@ -576,19 +747,133 @@ impl BlockStore {
         start_location_in_data_file: u64,
         dest_buffer: &mut [u8],
     ) -> Result<()> {
-        let path = self.path_to_blockchain.join(DATA_FILE_NAME);
-        let mut data_file = std::fs::OpenOptions::new()
+        let (path, offset) = self.locate(start_location_in_data_file);
+        let mut segment_file = std::fs::OpenOptions::new()
             .read(true)
             .open(path.clone())
             .add_err_context(&path)?;
-        data_file
-            .seek(SeekFrom::Start(start_location_in_data_file))
+        segment_file
+            .seek(SeekFrom::Start(offset))
+            .add_err_context(&path)?;
+        segment_file
+            .read_exact(dest_buffer)
             .add_err_context(&path)?;
-        data_file.read_exact(dest_buffer).add_err_context(&path)?;
 
         Ok(())
     }
 
+    /// Read and decode the block at `block_height`.
+    ///
+    /// # Errors
+    /// - IO Error
+    /// - Stored block is malformed or doesn't match its checksum
+    pub fn read_block(&self, block_height: u64) -> Result<SignedBlock> {
+        let index = self.read_block_index(block_height)?;
+        self.read_block_at(block_height, index)
+    }
+
+    /// Read the block at `block_height` stored at `index`, verify its checksum,
+    /// decompress it if needed and decode it.
+    ///
+    /// Blocks without a checksum, written before checksums were introduced,
+    /// are taken as is, their compression is recognized by the data itself.
+    ///
+    /// # Errors
+    /// - IO Error
+    /// - Stored block is malformed or doesn't match its checksum
+    pub fn read_block_at(&self, block_height: u64, index: BlockIndex) -> Result<SignedBlock> {
+        let mut block_data = vec![0_u8; index.length.try_into()?];
+        self.read_block_data(index.start, &mut block_data)?;
+        let compression = match self.read_block_checksum(block_height)? {
+            Some(checksum) => {
+                if checksum != BlockChecksum::new(&block_data, checksum.compression) {
+                    return Err(Error::Checksum(block_height));
+                }
+                checksum.compression
+            }
+            None if block_data.starts_with(&ZSTD_MAGIC) => Compression::Zstd,
+            None => Compression::None,
+        };
+        if compression == Compression::Zstd {
+            block_data = zstd::decode_all(block_data.as_slice()).map_err(Error::Compression)?;
+        }
+        Ok(SignedBlock::decode_all_versioned(&block_data)?)
+    }
+
+    /// Read the checksum of the block at `block_height`, if there is one.
+    ///
+    /// # Errors
+    /// - IO Error
+    /// - The checksum record is malformed
+    fn read_block_checksum(&self, block_height: u64) -> Result<Option<BlockChecksum>> {
+        let path = self.path_to_blockchain.join(CHECKSUMS_FILE_NAME);
+        let mut checksums_file = match std::fs::OpenOptions::new().read(true).open(&path) {
+            Ok(file) => file,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(err) => return Err(Error::IO(err, path)),
+        };
+        let start_location = block_height * BlockChecksum::SIZE;
+        if start_location + BlockChecksum::SIZE
+            > checksums_file.metadata().add_err_context(&path)?.len()
+        {
+            return Ok(None);
+        }
+        let mut buffer = [0; BlockChecksum::SIZE as usize];
+        checksums_file
+            .seek(SeekFrom::Start(start_location))
+            .and_then(|_| checksums_file.read_exact(&mut buffer))
+            .add_err_context(&path)?;
+        BlockChecksum::from_bytes(buffer, block_height)
+    }
+
+    /// Write the checksum of a single block at the specified `block_height`,
+    /// extending the checksums file if necessary.
+    ///
+    /// # Errors
+    /// IO Error.
+    fn write_block_checksum(&mut self, block_height: u64, checksum: BlockChecksum) -> Result<()> {
+        let path = self.path_to_blockchain.join(CHECKSUMS_FILE_NAME);
+        let mut checksums_file = std::fs::OpenOptions::new()
+            .write(true)
+            .truncate(false)
+            .create(true)
+            .open(path.clone())
+            .add_err_context(&path)?;
+        let start_location = block_height * BlockChecksum::SIZE;
+        if start_location + BlockChecksum::SIZE
+            > checksums_file.metadata().add_err_context(&path)?.len()
+        {
+            checksums_file
+                .set_len(start_location + BlockChecksum::SIZE)
+                .add_err_context(&path)?;
+        }
+        checksums_file
+            .seek(SeekFrom::Start(start_location))
+            .add_err_context(&path)?;
+        checksums_file
+            .write_all(&checksum.to_bytes())
+            .add_err_context(&path)?;
+        Ok(())
+    }
+
+    /// Cut the checksums file down to `block_count` records.
+    ///
+    /// # Errors
+    /// IO Error.
+    fn truncate_checksums(&self, block_count: u64) -> Result<()> {
+        let path = self.path_to_blockchain.join(CHECKSUMS_FILE_NAME);
+        let checksums_file = match std::fs::OpenOptions::new().write(true).open(&path) {
+            Ok(file) => file,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(()),
+            Err(err) => return Err(Error::IO(err, path)),
+        };
+        let len = checksums_file.metadata().add_err_context(&path)?.len();
+        checksums_file
+            .set_len((block_count * BlockChecksum::SIZE).min(len))
+            .add_err_context(&path)?;
+        Ok(())
+    }
+
     /// Write the index of a single block at the specified `block_height`.
     /// If `block_height` is beyond the end of the index file, attempt to
     /// extend the index file.
@@ -642,12 +927,12 @@ impl BlockStore {
             .add_err_context(&path)?;
         let new_byte_size = new_count * BlockIndex::SIZE;
         index_file.set_len(new_byte_size).add_err_context(&path)?;
-        Ok(())
+        self.truncate_checksums(new_count)
     }
 
-    /// Write `block_data` into the data file starting at
-    /// `start_location_in_data_file`. Extend the file if
-    /// necessary.
+    /// Write `block_data` into the block segments starting at
+    /// `start_location_in_data_file`. Create or extend the segment
+    /// file if necessary.
     ///
     /// # Errors
     /// IO Error.
@@ -656,22 +941,23 @@ impl BlockStore {
         start_location_in_data_file: u64,
         block_data: &[u8],
     ) -> Result<()> {
-        let path = self.path_to_blockchain.join(DATA_FILE_NAME);
-        let mut data_file = std::fs::OpenOptions::new()
+        let (path, offset) = self.locate(start_location_in_data_file);
+        let mut segment_file = std::fs::OpenOptions::new()
             .write(true)
+            .truncate(false)
+            .create(true)
             .open(path.clone())
             .add_err_context(&path)?;
-        if start_location_in_data_file + block_data.len() as u64
-            > data_file.metadata().add_err_context(&path)?.len()
+        if offset + block_data.len() as u64 > segment_file.metadata().add_err_context(&path)?.len()
         {
-            data_file
-                .set_len(start_location_in_data_file + block_data.len() as u64)
+            segment_file
+                .set_len(offset + block_data.len() as u64)
                 .add_err_context(&path)?;
         }
-        data_file
-            .seek(SeekFrom::Start(start_location_in_data_file))
+        segment_file
+            .seek(SeekFrom::Start(offset))
             .add_err_context(&path)?;
-        data_file.write_all(block_data).add_err_context(&path)?;
+        segment_file.write_all(block_data).add_err_context(&path)?;
         Ok(())
     }
 
@@ -727,23 +1013,21 @@ impl BlockStore {
         Ok(())
     }
 
-    /// Create the index and data files if they do not
-    /// already exist.
+    /// Create the index, hashes and manifest files and the segments
+    /// directory if they do not already exist.
+    ///
+    /// A store in the legacy layout, with all blocks in a single data file,
+    /// is migrated to segments in place, see [`Self::migrate_legacy_layout`].
     ///
     /// # Errors
     /// Fails if any of the files don't exist and couldn't be
-    /// created.
+    /// created or if the migration fails.
     pub fn create_files_if_they_do_not_exist(&mut self) -> Result<()> {
         std::fs::create_dir_all(&*self.path_to_blockchain)
             .map_err(|e| Error::MkDir(e, self.path_to_blockchain.clone()))?;
+        self.migrate_legacy_layout()?;
+
         let path = self.path_to_blockchain.join(INDEX_FILE_NAME);
-        std::fs::OpenOptions::new()
-            .write(true)
-            .truncate(false)
-            .create(true)
-            .open(path.clone())
-            .add_err_context(&path)?;
-        let path = self.path_to_blockchain.join(DATA_FILE_NAME);
         std::fs::OpenOptions::new()
             .write(true)
             .truncate(false)
@@ -757,27 +1041,44 @@ impl BlockStore {
             .create(true)
             .open(path.clone())
             .add_err_context(&path)?;
+        let path = self.segments_dir();
+        std::fs::create_dir_all(&path).map_err(|e| Error::MkDir(e, path))?;
+
+        match self.read_manifest()? {
+            Some(manifest) => {
+                self.segment_size = manifest.segment_size;
+                self.first_block = manifest.first_block;
+                if manifest.compression != self.compression {
+                    self.write_manifest()?;
+                }
+            }
+            None => self.write_manifest()?,
+        }
         Ok(())
     }
 
-    /// Append `block_data` to this block store. First write
-    /// the data to the data file and then create a new index
-    /// for it in the index file.
+    /// Append `block` to this block store. First write
+    /// the data to the segment and then create a new index
+    /// and checksum for it.
     ///
     /// # Errors
     /// Fails if any of the required platform-specific functions
     /// fail.
     pub fn append_block_to_chain(&mut self, block: &SignedBlock) -> Result<()> {
-        let bytes = block.encode_versioned();
+        let bytes = self.encode_block(block)?;
         let new_block_height = self.read_index_count()?;
         let start_location_in_data_file = if new_block_height == 0 {
             0
         } else {
             let ultimate_block = self.read_block_index(new_block_height - 1)?;
-            ultimate_block.start + ultimate_block.length
+            self.next_block_start(ultimate_block, bytes.len() as u64)
         };
 
         self.write_block_data(start_location_in_data_file, &bytes)?;
+        self.write_block_checksum(
+            new_block_height,
+            BlockChecksum::new(&bytes, self.compression),
+        )?;
         self.write_block_index(
             new_block_height,
             start_location_in_data_file,
@@ -791,7 +1092,7 @@ impl BlockStore {
     /// Prune the block storage to the given height
     ///
     /// Removes block entries higher than the given height from
-    /// the data file, index file, and hashes file.
+    /// the segments, index, hashes and checksums files.
     ///
     /// This function **does not** fail if the data in files is behind
     /// the given height.
@@ -802,8 +1103,13 @@ impl BlockStore {
     /// # Errors
     ///
     /// - If files do not exist (call [`Self::create_files_if_they_do_not_exist`])
+    /// - The given height is below the first block which isn't pruned
     /// - Other IO errors
+    #[allow(clippy::integer_division)]
     pub fn prune(&self, height: u64) -> Result<()> {
+        if height < self.first_block {
+            return Err(Error::CutPrunedBlocks(height));
+        }
         let last_block_index: Option<BlockIndex>;
 
         {
@@ -830,16 +1136,326 @@ impl BlockStore {
             let new_len = (SIZE_OF_BLOCK_HASH * height).min(len);
             file.try_io(|f| f.set_len(new_len))?;
         }
+        self.truncate_checksums(height)?;
 
-        {
-            let mut file = FileWrap::open_read_write(self.path_to_blockchain.join(DATA_FILE_NAME))?;
-            let len = file.try_io(|f| f.metadata().map(|x| x.len()))?;
-            let new_len = last_block_index.map_or(0, |x| x.start + x.length).min(len);
-            file.try_io(|f| f.set_len(new_len))?;
+        // (segment, end of the last block in it)
+        let last_block_end = last_block_index.map(|x| {
+            (
+                x.start / self.segment_size,
+                x.start % self.segment_size + x.length,
+            )
+        });
+        for segment in self.read_segments()? {
+            let path = self.segment_path(segment);
+            match last_block_end {
+                Some((last_segment, _)) if segment < last_segment => {}
+                Some((last_segment, end)) if segment == last_segment => {
+                    let mut file = FileWrap::open_read_write(path)?;
+                    let len = file.try_io(|f| f.metadata().map(|x| x.len()))?;
+                    file.try_io(|f| f.set_len(end.min(len)))?;
+                }
+                _ => std::fs::remove_file(&path).add_err_context(&path)?,
+            }
         }
 
         Ok(())
     }
+
+    /// Height (counting from 0) of the first block which isn't pruned.
+    pub fn first_block(&self) -> u64 {
+        self.first_block
+    }
+
+    /// Prune the oldest blocks below the given height
+    ///
+    /// Removes segments containing only blocks below `block_height`.
+    /// Blocks sharing a segment with the first kept block are kept as well.
+    /// Indices and hashes of the pruned blocks remain, so heights of the
+    /// other blocks don't change. The latest block is never pruned.
+    ///
+    /// Returns the height (counting from 0) of the first block which isn't pruned.
+    ///
+    /// # Errors
+    /// IO Error.
+    #[allow(clippy::integer_division)]
+    pub fn prune_below(&mut self, block_height: u64) -> Result<u64> {
+        let block_height = block_height.min(self.read_index_count()?.saturating_sub(1));
+        if block_height <= self.first_block {
+            return Ok(self.first_block);
+        }
+
+        let first_segment = self.read_block_index(block_height)?.start / self.segment_size;
+        let mut block_indices =
+            vec![BlockIndex::default(); (block_height - self.first_block).try_into()?];
+        self.read_block_indices(self.first_block, &mut block_indices)?;
+        let first_block = self.first_block
+            + block_indices.partition_point(|index| index.start / self.segment_size < first_segment)
+                as u64;
+        if first_block == self.first_block {
+            return Ok(first_block);
+        }
+
+        // Blocks are marked as pruned first, leftover segments are removed on the next pruning
+        self.first_block = first_block;
+        self.write_manifest()?;
+        for segment in self.read_segments()? {
+            if segment < first_segment {
+                let path = self.segment_path(segment);
+                std::fs::remove_file(&path).add_err_context(&path)?;
+            }
+        }
+
+        Ok(first_block)
+    }
+
+    /// Numbers of the segment files present in the store, in ascending order.
+    ///
+    /// # Errors
+    /// IO Error.
+    pub fn read_segments(&self) -> Result<Vec<u64>> {
+        let path = self.segments_dir();
+        let mut segments = Vec::new();
+        for entry in std::fs::read_dir(&path).add_err_context(&path)? {
+            let segment_path = entry.add_err_context(&path)?.path();
+            if segment_path.extension().and_then(|ext| ext.to_str()) != Some(SEGMENT_FILE_EXTENSION)
+            {
+                continue;
+            }
+            if let Some(segment) = segment_path
+                .file_stem()
+                .and_then(|stem| stem.to_str())
+                .and_then(|stem| stem.parse().ok())
+            {
+                segments.push(segment);
+            }
+        }
+        segments.sort_unstable();
+        Ok(segments)
+    }
+
+    fn segments_dir(&self) -> PathBuf {
+        self.path_to_blockchain.join(SEGMENTS_DIR_NAME)
+    }
+
+    fn segment_path(&self, segment: u64) -> PathBuf {
+        self.segments_dir()
+            .join(format!("{segment:010}.{SEGMENT_FILE_EXTENSION}"))
+    }
+
+    /// Path to the segment containing `location` and the offset of `location` in it.
+    #[allow(clippy::integer_division)]
+    fn locate(&self, location: u64) -> (PathBuf, u64) {
+        (
+            self.segment_path(location / self.segment_size),
+            location % self.segment_size,
+        )
+    }
+
+    /// Location of the block of `length` bytes following the block at `prev`.
+    #[allow(clippy::integer_division)]
+    fn next_block_start(&self, prev: BlockIndex, length: u64) -> u64 {
+        let segment_size = self.segment_size;
+        let mut start = prev.start + prev.length;
+        // Previous block took a segment on its own
+        if start / segment_size != prev.start / segment_size {
+            start = start.div_ceil(segment_size) * segment_size;
+        }
+        let offset = start % segment_size;
+        if offset != 0 && offset + length > segment_size {
+            start += segment_size - offset;
+        }
+        start
+    }
+
+    fn encode_block(&self, block: &SignedBlock) -> Result<Vec<u8>> {
+        let bytes = block.encode_versioned();
+        match self.compression {
+            Compression::None => Ok(bytes),
+            Compression::Zstd => {
+                zstd::encode_all(bytes.as_slice(), COMPRESSION_LEVEL).map_err(Error::Compression)
+            }
+        }
+    }
+
+    fn read_manifest(&self) -> Result<Option<Manifest>> {
+        let path = self.path_to_blockchain.join(MANIFEST_FILE_NAME);
+        let bytes = match std::fs::read(&path) {
+            Ok(bytes) => bytes,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(err) => return Err(Error::IO(err, path)),
+        };
+        let manifest: Manifest =
+            serde_json::from_slice(&bytes).map_err(|e| Error::Manifest(e, path))?;
+        if manifest.version != LAYOUT_VERSION {
+            return Err(Error::UnsupportedLayoutVersion(manifest.version));
+        }
+        Ok(Some(manifest))
+    }
+
+    fn write_manifest(&self) -> Result<()> {
+        let manifest = Manifest {
+            version: LAYOUT_VERSION,
+            segment_size: self.segment_size,
+            first_block: self.first_block,
+            compression: self.compression,
+        };
+        let tmp_path = self.path_to_blockchain.join(MANIFEST_TMP_FILE_NAME);
+        std::fs::write(
+            &tmp_path,
+            serde_json::to_vec_pretty(&manifest).expect("INTERNAL BUG: Manifest is serializable"),
+        )
+        .add_err_context(&tmp_path)?;
+        let path = self.path_to_blockchain.join(MANIFEST_FILE_NAME);
+        std::fs::rename(&tmp_path, &path).add_err_context(&path)?;
+        Ok(())
+    }
+
+    /// Move blocks of a store in the legacy layout, where all of them are kept
+    /// in a single data file, into segments.
+    ///
+    /// The store is rebuilt in a separate directory first and every block is read
+    /// back and checked against the legacy one. Moving its manifest into place
+    /// commits the migration, after which the rebuilt files replace the legacy ones.
+    /// An interrupted migration is restarted or finished on the next call.
+    ///
+    /// If any block can't be migrated, the rebuilt store is discarded and
+    /// the legacy files are kept untouched.
+    ///
+    /// Only the layout is migrated, blocks are moved as they are. Blocks written before
+    /// the block header gained the commit certificate and the leader seed, and transactions
+    /// gained the account sequence, can't be decoded anymore and can't be converted either,
+    /// since that would change their hashes and invalidate their signatures. Such a store
+    /// fails to migrate and the peer has to start from a fresh chain.
+    fn migrate_legacy_layout(&mut self) -> Result<()> {
+        let migration_dir = self.path_to_blockchain.join(MIGRATION_DIR_NAME);
+        let manifest_path = self.path_to_blockchain.join(MANIFEST_FILE_NAME);
+        let legacy_data_path = self.path_to_blockchain.join(LEGACY_DATA_FILE_NAME);
+
+        if manifest_path.exists() {
+            if migration_dir.exists() {
+                self.finish_migration(&migration_dir)?;
+            }
+            return Ok(());
+        }
+        if !legacy_data_path.exists() {
+            return Ok(());
+        }
+
+        info!("Migrating block store to segments");
+        if migration_dir.exists() {
+            std::fs::remove_dir_all(&migration_dir).add_err_context(&migration_dir)?;
+        }
+        let mut migrated = BlockStore::new(&migration_dir)
+            .with_segment_size(self.segment_size)
+            .with_compression(self.compression);
+        let block_count = match self.migrate_legacy_blocks(&mut migrated) {
+            Ok(block_count) => block_count,
+            Err(error) => {
+                error!(
+                    ?error,
+                    "Failed to migrate block store to segments. Legacy files are kept."
+                );
+                if let Err(error) = std::fs::remove_dir_all(&migration_dir) {
+                    warn!(
+                        ?error,
+                        "Failed to remove the partially migrated block store"
+                    );
+                }
+                return Err(error);
+            }
+        };
+
+        // From now on the store is in the new layout
+        std::fs::rename(migration_dir.join(MANIFEST_FILE_NAME), &manifest_path)
+            .add_err_context(&manifest_path)?;
+        self.finish_migration(&migration_dir)?;
+        info!(block_count, "Block store migrated to segments");
+
+        Ok(())
+    }
+
+    /// Copy all blocks of the legacy store into `migrated` and verify the copies.
+    ///
+    /// Returns the number of migrated blocks.
+    ///
+    /// # Errors
+    /// - IO Error
+    /// - Any legacy block is malformed or its copy doesn't match it
+    fn migrate_legacy_blocks(&self, migrated: &mut BlockStore) -> Result<u64> {
+        migrated.create_files_if_they_do_not_exist()?;
+
+        let legacy_data_path = self.path_to_blockchain.join(LEGACY_DATA_FILE_NAME);
+        let block_count = self.read_index_count()?;
+        let legacy_hashes =
+            self.read_block_hashes(0, self.read_hashes_count()?.min(block_count).try_into()?)?;
+        let mut legacy_data_file = std::fs::OpenOptions::new()
+            .read(true)
+            .open(legacy_data_path.clone())
+            .add_err_context(&legacy_data_path)?;
+
+        let mut block_hashes = Vec::with_capacity(block_count.try_into()?);
+        for block_height in 0..block_count {
+            let migration_error =
+                |error: Error| Error::LegacyMigration(block_height, Box::new(error));
+            let BlockIndex { start, length } = self.read_block_index(block_height)?;
+            let mut block_data = vec![0_u8; length.try_into()?];
+            legacy_data_file
+                .seek(SeekFrom::Start(start))
+                .and_then(|_| legacy_data_file.read_exact(&mut block_data))
+                .add_err_context(&legacy_data_path)
+                .map_err(migration_error)?;
+            let block = SignedBlock::decode_all_versioned(&block_data)
+                .map_err(|error| migration_error(error.into()))?;
+            let block_hash = block.hash();
+            let recorded_hash = usize::try_from(block_height)
+                .ok()
+                .and_then(|height| legacy_hashes.get(height));
+            if recorded_hash.is_some_and(|recorded_hash| *recorded_hash != block_hash) {
+                return Err(Error::MigratedBlockMismatch(block_height));
+            }
+            migrated.append_block_to_chain(&block)?;
+            block_hashes.push(block_hash);
+        }
+
+        for (block_height, block_hash) in (0..).zip(block_hashes) {
+            let migrated_block = migrated
+                .read_block(block_height)
+                .map_err(|error| Error::LegacyMigration(block_height, Box::new(error)))?;
+            if migrated_block.hash() != block_hash {
+                return Err(Error::MigratedBlockMismatch(block_height));
+            }
+        }
+
+        Ok(block_count)
+    }
+
+    /// Replace the legacy files with the ones rebuilt in `migration_dir`.
+    fn finish_migration(&self, migration_dir: &Path) -> Result<()> {
+        let migrated_segments_dir = migration_dir.join(SEGMENTS_DIR_NAME);
+        if migrated_segments_dir.exists() {
+            let segments_dir = self.segments_dir();
+            if segments_dir.exists() {
+                std::fs::remove_dir_all(&segments_dir).add_err_context(&segments_dir)?;
+            }
+            std::fs::rename(&migrated_segments_dir, &segments_dir)
+                .add_err_context(&segments_dir)?;
+        }
+        for file_name in [INDEX_FILE_NAME, HASHES_FILE_NAME, CHECKSUMS_FILE_NAME] {
+            let migrated_path = migration_dir.join(file_name);
+            if migrated_path.exists() {
+                let path = self.path_to_blockchain.join(file_name);
+                std::fs::rename(&migrated_path, &path).add_err_context(&path)?;
+            }
+        }
+
+        let legacy_data_path = self.path_to_blockchain.join(LEGACY_DATA_FILE_NAME);
+        if legacy_data_path.exists() {
+            std::fs::remove_file(&legacy_data_path).add_err_context(&legacy_data_path)?;
+        }
+        std::fs::remove_dir_all(migration_dir).add_err_context(&migration_dir.to_path_buf())?;
+
+        Ok(())
+    }
 }
 
 /// Helper to reduce boilerplate of file ops
@@ -894,6 +1510,24 @@ pub enum Error {
     IntConversion(#[from] std::num::TryFromIntError),
     /// Blocks count differs hashes file and index file
     HashesFileHeightMismatch,
+    /// Failed to decode versioned block
+    Version(#[from] iroha_version::error::Error),
+    /// Failed to compress or decompress block
+    Compression(#[source] std::io::Error),
+    /// Malformed block store manifest at {1:?}
+    Manifest(#[source] serde_json::Error, PathBuf),
+    /// Unsupported block store layout version: {0}
+    UnsupportedLayoutVersion(u32),
+    /// Block store manifest not found in {0:?}, the store is either absent or in the legacy layout
+    MissingManifest(PathBuf),
+    /// Block at height {0} doesn't match its checksum
+    Checksum(u64),
+    /// Can't cut the store down to {0} blocks since the blocks below the first kept one are pruned
+    CutPrunedBlocks(u64),
+    /// Failed to migrate the block at height {0} from the legacy layout, legacy files are kept
+    LegacyMigration(u64, #[source] Box<Error>),
+    /// Migrated block at height {0} doesn't match the legacy one, legacy files are kept
+    MigratedBlockMismatch(u64),
 }
 
 trait AddErrContextExt<T> {
@@ -914,7 +1548,7 @@ impl<T> AddErrContextExt<T> for Result<T, std::io::Error> {
 mod tests {
     use std::{str::FromStr, thread, time::Duration};
 
-    use iroha_config::parameters::defaults::kura::BLOCKS_IN_MEMORY;
+    use iroha_config::parameters::defaults::kura::{BLOCKS_IN_MEMORY, SEGMENT_SIZE};
     use iroha_crypto::KeyPair;
     use iroha_data_model::{
         account::Account,
@@ -1117,6 +1751,9 @@ mod tests {
                 temp_dir.path().to_str().unwrap().into(),
             ),
             blocks_in_memory: BLOCKS_IN_MEMORY,
+            segment_size: SEGMENT_SIZE,
+            compression: Compression::None,
+            blocks_to_keep: None,
             debug_output_new_blocks: false,
         })
         .unwrap();
@@ -1147,6 +1784,9 @@ mod tests {
                     temp_dir.path().to_str().unwrap().into(),
                 ),
                 blocks_in_memory: BLOCKS_IN_MEMORY,
+                segment_size: SEGMENT_SIZE,
+                compression: Compression::None,
+                blocks_to_keep: None,
                 debug_output_new_blocks: false,
             })
             .unwrap();
@@ -1199,6 +1839,9 @@ mod tests {
                 temp_dir.path().to_str().unwrap().into(),
             ),
             blocks_in_memory: BLOCKS_IN_MEMORY,
+            segment_size: SEGMENT_SIZE,
+            compression: Compression::None,
+            blocks_to_keep: None,
             debug_output_new_blocks: false,
         })
         .unwrap();
@@ -1427,4 +2070,196 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    #[allow(clippy::integer_division)]
+    fn blocks_do_not_cross_segment_boundaries() -> eyre::Result<()> {
+        let temp = TempDir::new()?;
+        let mut blocks = DummyBlocks::new();
+        let block_size = blocks.next().encode_versioned().len() as u64;
+        let segment_size = block_size * 5 / 2;
+        let mut store = BlockStore::new(temp.path()).with_segment_size(segment_size);
+        store.create_files_if_they_do_not_exist()?;
+
+        store.append_block_to_chain(&blocks.get(0).unwrap())?;
+        for _ in 1..10 {
+            store.append_block_to_chain(&blocks.next())?;
+        }
+
+        for i in 0..10 {
+            let BlockIndex { start, length } = store.read_block_index(i)?;
+            assert_eq!(start / segment_size, (start + length - 1) / segment_size);
+            assert_eq!(store.read_block(i)?, *blocks.get(i as usize).unwrap());
+        }
+        assert!(store.read_segments()?.len() >= 4);
+
+        store.prune(3)?;
+        let BlockIndex { start, .. } = store.read_block_index(2)?;
+        assert_eq!(
+            store.read_segments()?.last().copied(),
+            Some(start / segment_size)
+        );
+        for i in 3..10 {
+            store.append_block_to_chain(&blocks.get(i).unwrap())?;
+        }
+        for i in 0..10 {
+            assert_eq!(store.read_block(i as u64)?, *blocks.get(i).unwrap());
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    #[allow(clippy::integer_division)]
+    fn old_blocks_are_pruned() -> eyre::Result<()> {
+        let temp = TempDir::new()?;
+        let mut blocks = DummyBlocks::new();
+        let block_size = blocks.next().encode_versioned().len() as u64;
+        let segment_size = block_size * 5 / 2;
+        let mut store = BlockStore::new(temp.path()).with_segment_size(segment_size);
+        store.create_files_if_they_do_not_exist()?;
+
+        store.append_block_to_chain(&blocks.get(0).unwrap())?;
+        for _ in 1..10 {
+            store.append_block_to_chain(&blocks.next())?;
+        }
+
+        let first_block = store.prune_below(7)?;
+        assert!(0 < first_block && first_block <= 7);
+        let BlockIndex { start, .. } = store.read_block_index(first_block)?;
+        assert_eq!(
+            store.read_segments()?.first().copied(),
+            Some(start / segment_size)
+        );
+
+        // Pruning is persisted and hashes of the pruned blocks are kept
+        let mut store = BlockStore::new(temp.path());
+        store.create_files_if_they_do_not_exist()?;
+        assert_eq!(store.first_block(), first_block);
+        assert_eq!(store.read_index_count()?, 10);
+        assert_eq!(store.read_block_hashes(0, 10)?.len(), 10);
+        assert!(store.read_block(0).is_err());
+        for i in first_block..10 {
+            assert_eq!(store.read_block(i)?, *blocks.get(i as usize).unwrap());
+        }
+
+        // Pruning below the already pruned blocks does nothing
+        assert_eq!(store.prune_below(1)?, first_block);
+
+        Ok(())
+    }
+
+    #[test]
+    fn compressed_blocks_are_read_back() -> eyre::Result<()> {
+        let temp = TempDir::new()?;
+        let mut store = BlockStore::new(temp.path()).with_compression(Compression::Zstd);
+        store.create_files_if_they_do_not_exist()?;
+
+        let mut blocks = DummyBlocks::new();
+        for _ in 0..3 {
+            store.append_block_to_chain(&blocks.next())?;
+        }
+        // Compression can be changed between restarts
+        let mut store = BlockStore::new(temp.path());
+        store.create_files_if_they_do_not_exist()?;
+        store.append_block_to_chain(&blocks.next())?;
+
+        for i in 0..4 {
+            assert_eq!(store.read_block(i as u64)?, *blocks.get(i).unwrap());
+        }
+        let BlockIndex { start, length } = store.read_block_index(0)?;
+        let mut data = vec![0_u8; length.try_into()?];
+        store.read_block_data(start, &mut data)?;
+        assert!(data.starts_with(&ZSTD_MAGIC));
+
+        Ok(())
+    }
+
+    #[test]
+    fn corrupted_block_is_detected_by_checksum() -> eyre::Result<()> {
+        let temp = TempDir::new()?;
+        let mut store = BlockStore::new(temp.path());
+        store.create_files_if_they_do_not_exist()?;
+
+        let mut blocks = DummyBlocks::new();
+        for _ in 0..3 {
+            store.append_block_to_chain(&blocks.next())?;
+        }
+        let BlockIndex { start, length } = store.read_block_index(1)?;
+        let mut data = vec![0_u8; length.try_into()?];
+        store.read_block_data(start, &mut data)?;
+        *data.last_mut().unwrap() ^= 0xFF;
+        store.write_block_data(start, &data)?;
+
+        assert!(matches!(store.read_block(1), Err(Error::Checksum(1))));
+        assert_eq!(store.read_block(2)?, *blocks.get(2).unwrap());
+
+        Ok(())
+    }
+
+    /// Write `count` blocks into `path` in the legacy layout.
+    fn write_legacy_store(
+        path: &Path,
+        blocks: &mut DummyBlocks,
+        count: u64,
+    ) -> eyre::Result<Vec<u8>> {
+        let mut legacy_data = Vec::new();
+        let mut legacy_store = BlockStore::new(path);
+        for i in 0..count {
+            let bytes = blocks.next().encode_versioned();
+            legacy_store.write_block_index(i, legacy_data.len() as u64, bytes.len() as u64)?;
+            legacy_store.write_block_hash(i, blocks.get(i as usize).unwrap().hash())?;
+            legacy_data.extend(bytes);
+        }
+        std::fs::write(path.join(LEGACY_DATA_FILE_NAME), &legacy_data)?;
+        Ok(legacy_data)
+    }
+
+    #[test]
+    fn legacy_layout_is_migrated() -> eyre::Result<()> {
+        let temp = TempDir::new()?;
+        let mut blocks = DummyBlocks::new();
+        write_legacy_store(temp.path(), &mut blocks, 5)?;
+
+        let mut store = BlockStore::new(temp.path()).with_compression(Compression::Zstd);
+        store.create_files_if_they_do_not_exist()?;
+
+        assert!(!temp.path().join(LEGACY_DATA_FILE_NAME).exists());
+        assert!(!temp.path().join(MIGRATION_DIR_NAME).exists());
+        assert!(temp.path().join(MANIFEST_FILE_NAME).exists());
+        assert_eq!(store.read_index_count()?, 5);
+        assert_eq!(store.read_hashes_count()?, 5);
+        for i in 0..5 {
+            assert_eq!(store.read_block(i as u64)?, *blocks.get(i).unwrap());
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn malformed_legacy_store_is_kept() -> eyre::Result<()> {
+        let temp = TempDir::new()?;
+        let mut blocks = DummyBlocks::new();
+        let mut legacy_data = write_legacy_store(temp.path(), &mut blocks, 5)?;
+        let BlockIndex { start, .. } = BlockStore::new(temp.path()).read_block_index(3)?;
+        legacy_data.truncate(start.try_into()?);
+        legacy_data.push(0);
+        std::fs::write(temp.path().join(LEGACY_DATA_FILE_NAME), &legacy_data)?;
+
+        let mut store = BlockStore::new(temp.path());
+        assert!(matches!(
+            store.create_files_if_they_do_not_exist(),
+            Err(Error::LegacyMigration(3, _))
+        ));
+
+        assert_eq!(
+            std::fs::read(temp.path().join(LEGACY_DATA_FILE_NAME))?,
+            legacy_data
+        );
+        assert!(!temp.path().join(MIGRATION_DIR_NAME).exists());
+        assert!(!temp.path().join(MANIFEST_FILE_NAME).exists());
+        assert_eq!(store.read_index_count()?, 5);
+
+        Ok(())
+    }
 }
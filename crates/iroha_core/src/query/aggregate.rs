@@ -0,0 +1,214 @@
+//! Module with [`aggregate`] function evaluating [`Aggregate`]s over query results.
+
+use std::collections::BTreeMap;
+
+use iroha_data_model::{
+    prelude::Numeric,
+    query::{
+        dsl::{Aggregate, AggregateFunction, EvaluateSelector, HasProjection, SelectorMarker},
+        error::QueryExecutionFail,
+        QueryOutputBatchBox, QueryOutputBatchBoxTuple,
+    },
+};
+use parity_scale_codec::Encode;
+
+/// Result of evaluating an [`Aggregate`].
+#[derive(Debug)]
+pub struct AggregatedRows {
+    /// A tuple of empty batches with the types of the rows
+    pub empty: QueryOutputBatchBoxTuple,
+    /// Rows in the order their groups were first encountered.
+    ///
+    /// Each row is a tuple of single-element batches: the grouping key followed by the aggregated value.
+    pub rows: Vec<QueryOutputBatchBoxTuple>,
+}
+
+/// Folds the items into a single value per group.
+///
+/// # Errors
+///
+/// - A selector fails to project an item
+/// - A selected value is not [`Numeric`]
+/// - An aggregated value overflows
+pub fn aggregate<T>(
+    items: impl Iterator<Item = T>,
+    aggregate: &Aggregate<T>,
+) -> Result<AggregatedRows, QueryExecutionFail>
+where
+    T: HasProjection<SelectorMarker, AtomType = ()> + 'static,
+    T::Projection: EvaluateSelector<T>,
+{
+    let mut empty = aggregate
+        .group_by
+        .iter()
+        .map(|selector| selector.project_clone(core::iter::empty()))
+        .collect::<Result<Vec<_>, _>>()?;
+    empty.push(QueryOutputBatchBox::Numeric(Vec::new()));
+
+    // group keys are compared by their encoding, as type-erased batches don't implement `Ord`
+    let mut group_indices = BTreeMap::<Vec<u8>, usize>::new();
+    let mut groups = Vec::<(Vec<QueryOutputBatchBox>, Option<Numeric>)>::new();
+    if aggregate.group_by.is_empty() {
+        groups.push((Vec::new(), None));
+        group_indices.insert(Vec::<QueryOutputBatchBox>::new().encode(), 0);
+    }
+
+    for item in items {
+        let key = aggregate
+            .group_by
+            .iter()
+            .map(|selector| selector.project_clone(core::iter::once(&item)))
+            .collect::<Result<Vec<_>, _>>()?;
+        let index = *group_indices.entry(key.encode()).or_insert_with(|| {
+            groups.push((key, None));
+            groups.len() - 1
+        });
+        let accumulator = &mut groups[index].1;
+
+        *accumulator = Some(match &aggregate.function {
+            AggregateFunction::Count => accumulator
+                .unwrap_or(Numeric::ZERO)
+                .checked_add(Numeric::ONE)
+                .ok_or(QueryExecutionFail::AggregateOverflow)?,
+            AggregateFunction::Sum(selector) => accumulator
+                .unwrap_or(Numeric::ZERO)
+                .checked_add(select_numeric(selector, &item)?)
+                .ok_or(QueryExecutionFail::AggregateOverflow)?,
+            AggregateFunction::Min(selector) => {
+                let value = select_numeric(selector, &item)?;
+                accumulator.map_or(value, |min| min.min(value))
+            }
+            AggregateFunction::Max(selector) => {
+                let value = select_numeric(selector, &item)?;
+                accumulator.map_or(value, |max| max.max(value))
+            }
+        });
+    }
+
+    let rows = groups
+        .into_iter()
+        .filter_map(|(key, value)| {
+            let value = match (&aggregate.function, value) {
+                (_, Some(value)) => value,
+                // nothing to count or to sum is still a result
+                (AggregateFunction::Count | AggregateFunction::Sum(_), None) => Numeric::ZERO,
+                (AggregateFunction::Min(_) | AggregateFunction::Max(_), None) => return None,
+            };
+
+            let mut row = key;
+            row.push(QueryOutputBatchBox::Numeric(vec![value]));
+            Some(QueryOutputBatchBoxTuple::new(row))
+        })
+        .collect();
+
+    Ok(AggregatedRows {
+        empty: QueryOutputBatchBoxTuple::new(empty),
+        rows,
+    })
+}
+
+fn select_numeric<T>(selector: &T::Projection, item: &T) -> Result<Numeric, QueryExecutionFail>
+where
+    T: HasProjection<SelectorMarker, AtomType = ()> + 'static,
+    T::Projection: EvaluateSelector<T>,
+{
+    match selector.project_clone(core::iter::once(item))? {
+        QueryOutputBatchBox::Numeric(values) => values
+            .into_iter()
+            .next()
+            .ok_or(QueryExecutionFail::NonNumericAggregate),
+        _ => Err(QueryExecutionFail::NonNumericAggregate),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use iroha_data_model::prelude::*;
+    use iroha_test_samples::{ALICE_ID, BOB_ID};
+
+    use super::*;
+
+    fn assets() -> Vec<Asset> {
+        [
+            ("rose#wonderland", &*ALICE_ID, numeric!(13)),
+            ("rose#wonderland", &*BOB_ID, numeric!(7)),
+            ("tulip#wonderland", &*ALICE_ID, numeric!(5)),
+        ]
+        .into_iter()
+        .map(|(definition, account, value)| {
+            Asset::new(
+                AssetId::new(definition.parse().unwrap(), account.clone()),
+                value,
+            )
+        })
+        .collect()
+    }
+
+    #[test]
+    fn ungrouped() {
+        let sum = Aggregate::<Asset>::sum_with(|asset| asset.value);
+        assert_eq!(
+            aggregate(assets().into_iter(), &sum).unwrap().rows,
+            vec![QueryOutputBatchBoxTuple::new(vec![
+                QueryOutputBatchBox::Numeric(vec![numeric!(25)])
+            ])]
+        );
+
+        let count = Aggregate::<Asset>::count();
+        assert_eq!(
+            aggregate(core::iter::empty(), &count).unwrap().rows,
+            vec![QueryOutputBatchBoxTuple::new(vec![
+                QueryOutputBatchBox::Numeric(vec![numeric!(0)])
+            ])]
+        );
+
+        let max = Aggregate::<Asset>::max_with(|asset| asset.value);
+        assert!(aggregate(core::iter::empty(), &max)
+            .unwrap()
+            .rows
+            .is_empty());
+    }
+
+    #[test]
+    fn grouped() {
+        let total_per_definition = Aggregate::<Asset>::sum_with(|asset| asset.value)
+            .group_by_with(|asset| asset.id.definition);
+        let aggregated = aggregate(assets().into_iter(), &total_per_definition).unwrap();
+
+        assert_eq!(
+            aggregated.empty,
+            QueryOutputBatchBoxTuple::new(vec![
+                QueryOutputBatchBox::AssetDefinitionId(Vec::new()),
+                QueryOutputBatchBox::Numeric(Vec::new()),
+            ])
+        );
+        assert_eq!(
+            aggregated.rows,
+            vec![
+                QueryOutputBatchBoxTuple::new(vec![
+                    QueryOutputBatchBox::AssetDefinitionId(vec!["rose#wonderland"
+                        .parse()
+                        .unwrap()]),
+                    QueryOutputBatchBox::Numeric(vec![numeric!(20)]),
+                ]),
+                QueryOutputBatchBoxTuple::new(vec![
+                    QueryOutputBatchBox::AssetDefinitionId(vec!["tulip#wonderland"
+                        .parse()
+                        .unwrap()]),
+                    QueryOutputBatchBox::Numeric(vec![numeric!(5)]),
+                ]),
+            ]
+        );
+    }
+
+    #[test]
+    fn non_numeric_values_are_rejected() {
+        // selects the whole asset instead of its value
+        let sum = Aggregate::<Asset>::new(AggregateFunction::Sum(AssetProjection::Atom(())));
+
+        assert_eq!(
+            aggregate(assets().into_iter(), &sum).unwrap_err(),
+            QueryExecutionFail::NonNumericAggregate
+        );
+    }
+}
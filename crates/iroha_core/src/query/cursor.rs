@@ -7,6 +7,7 @@ use iroha_data_model::{
     query::{
         dsl::{EvaluateSelector, HasProjection, SelectorMarker},
         error::QueryExecutionFail,
+        parameters::QueryContinuation,
         QueryOutputBatchBox, QueryOutputBatchBoxTuple,
     },
 };
@@ -37,31 +38,39 @@ where
     Ok(QueryOutputBatchBoxTuple { tuple: batch_tuple })
 }
 
+fn no_continuation<T>(
+    _item: &T,
+    _position: NonZeroU64,
+) -> Result<Option<QueryContinuation>, QueryExecutionFail> {
+    Ok(None)
+}
+
 trait BatchedTrait {
     fn next_batch(
         &mut self,
         cursor: u64,
     ) -> Result<(QueryOutputBatchBoxTuple, Option<NonZeroU64>), QueryExecutionFail>;
+    fn cursor(&self) -> Option<u64>;
+    fn continuation(&self) -> Option<&QueryContinuation>;
     fn remaining(&self) -> u64;
 }
 
-struct BatchedInner<I>
-where
-    I: ExactSizeIterator,
-    I::Item: HasProjection<SelectorMarker, AtomType = ()>,
-{
+struct BatchedInner<I, F, K> {
     iter: I,
-    selector: SelectorTuple<I::Item>,
+    /// Converts a batch of items into the type-erased batch tuple
+    evaluate: F,
+    /// Builds the continuation after an item at the given position, if the results can be resumed
+    continue_after: K,
     batch_size: NonZeroU64,
     cursor: Option<u64>,
+    continuation: Option<QueryContinuation>,
 }
 
-impl<I> BatchedTrait for BatchedInner<I>
+impl<I, F, K> BatchedTrait for BatchedInner<I, F, K>
 where
     I: ExactSizeIterator,
-    I::Item: HasProjection<SelectorMarker, AtomType = ()> + 'static,
-    <I::Item as HasProjection<SelectorMarker>>::Projection: EvaluateSelector<I::Item>,
-    QueryOutputBatchBox: From<Vec<I::Item>>,
+    F: Fn(Vec<I::Item>) -> Result<QueryOutputBatchBoxTuple, QueryExecutionFail>,
+    K: Fn(&I::Item, NonZeroU64) -> Result<Option<QueryContinuation>, QueryExecutionFail>,
 {
     fn next_batch(
         &mut self,
@@ -96,20 +105,27 @@ where
             )
             .collect();
 
-        // evaluate the requested projections
-        let batch = evaluate_selector_tuple(batch, &self.selector)?;
-
         // did we get enough elements to continue?
         if current_batch_size >= expected_batch_size {
-            self.cursor = Some(
+            let next_cursor = NonZeroU64::new(
                 cursor
                     .checked_add(current_batch_size as u64)
                     .expect("Cursor size should never reach the platform limit"),
-            );
+            )
+            .expect("Cursor is never 0");
+            self.continuation = match batch.last() {
+                Some(last) => (self.continue_after)(last, next_cursor)?,
+                None => None,
+            };
+            self.cursor = Some(next_cursor.get());
         } else {
+            self.continuation = None;
             self.cursor = None;
         }
 
+        // evaluate the requested projections
+        let batch = (self.evaluate)(batch)?;
+
         Ok((
             batch,
             self.cursor
@@ -117,6 +133,14 @@ where
         ))
     }
 
+    fn cursor(&self) -> Option<u64> {
+        self.cursor
+    }
+
+    fn continuation(&self) -> Option<&QueryContinuation> {
+        self.continuation.as_ref()
+    }
+
     fn remaining(&self) -> u64 {
         self.iter.len() as u64
     }
@@ -142,13 +166,64 @@ impl ErasedQueryIterator {
         <I::Item as HasProjection<SelectorMarker>>::Projection:
             EvaluateSelector<I::Item> + Send + Sync,
         QueryOutputBatchBox: From<Vec<I::Item>>,
+    {
+        Self::resumable(iter, selector, batch_size, 0, no_continuation)
+    }
+
+    /// Creates a new erased query iterator over the results following the first `position` ones.
+    ///
+    /// `continue_after` builds the continuation to resume the results after an item at the given position,
+    /// returning `None` if the results can't be resumed.
+    pub fn resumable<I, K>(
+        iter: I,
+        selector: SelectorTuple<I::Item>,
+        batch_size: NonZeroU64,
+        position: u64,
+        continue_after: K,
+    ) -> Self
+    where
+        I: ExactSizeIterator + Send + Sync + 'static,
+        I::Item: HasProjection<SelectorMarker, AtomType = ()> + 'static,
+        <I::Item as HasProjection<SelectorMarker>>::Projection:
+            EvaluateSelector<I::Item> + Send + Sync,
+        QueryOutputBatchBox: From<Vec<I::Item>>,
+        K: Fn(&I::Item, NonZeroU64) -> Result<Option<QueryContinuation>, QueryExecutionFail>
+            + Send
+            + Sync
+            + 'static,
     {
         Self {
             inner: Box::new(BatchedInner {
                 iter,
-                selector,
+                evaluate: move |batch: Vec<I::Item>| evaluate_selector_tuple(batch, &selector),
+                continue_after,
+                batch_size,
+                cursor: Some(position),
+                continuation: None,
+            }),
+        }
+    }
+
+    /// Creates a new erased query iterator over already evaluated rows, each being a tuple of single-element batches.
+    ///
+    /// `empty` is a tuple of empty batches of the same types as the rows, used as a base for each batch.
+    pub fn from_rows<I>(rows: I, empty: QueryOutputBatchBoxTuple, batch_size: NonZeroU64) -> Self
+    where
+        I: ExactSizeIterator<Item = QueryOutputBatchBoxTuple> + Send + Sync + 'static,
+    {
+        Self {
+            inner: Box::new(BatchedInner {
+                iter: rows,
+                evaluate: move |rows: Vec<QueryOutputBatchBoxTuple>| {
+                    let mut batch = empty.clone();
+                    rows.into_iter().for_each(|row| batch.extend(row));
+                    Ok(batch)
+                },
+                // aggregated rows have no ids to resume after
+                continue_after: no_continuation::<QueryOutputBatchBoxTuple>,
                 batch_size,
                 cursor: Some(0),
+                continuation: None,
             }),
         }
     }
@@ -170,6 +245,16 @@ impl ErasedQueryIterator {
         self.inner.next_batch(cursor)
     }
 
+    /// Returns the position of the next batch, or `None` if the iterator is drained.
+    pub fn cursor(&self) -> Option<u64> {
+        self.inner.cursor()
+    }
+
+    /// Returns the continuation to resume the results after the last returned batch, if there is one.
+    pub fn continuation(&self) -> Option<&QueryContinuation> {
+        self.inner.continuation()
+    }
+
     /// Returns the number of remaining elements in the iterator.
     ///
     /// You should not rely on the reported amount being correct for safety, same as [`ExactSizeIterator::len`].
@@ -1,5 +1,8 @@
 //! This module contains [`store::LiveQueryStore`] and helpers.
 
+pub mod aggregate;
 pub mod cursor;
 pub mod pagination;
+pub mod sorting;
 pub mod store;
+pub mod subscription;
@@ -0,0 +1,309 @@
+//! Module with [`sort`] function ordering query results by [`SortKey`]s.
+
+use std::{cmp::Ordering, num::NonZeroU64};
+
+use iroha_data_model::{
+    prelude::Json,
+    query::{
+        dsl::{EvaluateSelector, HasProjection, SelectorMarker, SortKey},
+        error::QueryExecutionFail,
+        parameters::{QueryContinuation, SortOrder, Sorting},
+        QueryOutputBatchBox, QueryOutputBatchBoxTuple,
+    },
+};
+
+use crate::smartcontracts::query::SortableQueryOutput;
+
+/// Sorts the items by the values selected by `sort_by`, then by the metadata key from `sorting`.
+///
+/// The sorting is stable, so the items with equal keys keep their relative order.
+/// Items lacking the metadata key are placed last regardless of the direction.
+///
+/// # Errors
+///
+/// - A selector fails to project an item
+/// - A selected value can't be ordered
+pub fn sort<T>(
+    items: impl Iterator<Item = T>,
+    sort_by: &[SortKey<T>],
+    sorting: &Sorting,
+) -> Result<Vec<T>, QueryExecutionFail>
+where
+    T: SortableQueryOutput + HasProjection<SelectorMarker, AtomType = ()> + 'static,
+    T::Projection: EvaluateSelector<T>,
+{
+    // check the selected types upfront, so that an unorderable key is rejected even if there are no results
+    for key in sort_by {
+        let empty = key.selector.project_clone(core::iter::empty())?;
+        if compare(&empty, &empty).is_none() {
+            return Err(QueryExecutionFail::UnorderedSortKey);
+        }
+    }
+
+    let mut keyed = items
+        .map(|item| {
+            let selected = sort_by
+                .iter()
+                .map(|key| key.selector.project_clone(core::iter::once(&item)))
+                .collect::<Result<Vec<_>, _>>()?;
+            let metadata = sorting
+                .sort_by_metadata_key
+                .as_ref()
+                .and_then(|key| item.get_metadata_sorting_key(key));
+
+            Ok((selected, metadata, item))
+        })
+        .collect::<Result<Vec<_>, QueryExecutionFail>>()?;
+
+    keyed.sort_by(|(left, left_metadata, _), (right, right_metadata, _)| {
+        sort_by
+            .iter()
+            .zip(left.iter().zip(right))
+            .map(|(key, (l, r))| {
+                // values selected by the same selector are always of the same type
+                key.order.apply(compare(l, r).unwrap_or(Ordering::Equal))
+            })
+            .fold(Ordering::Equal, Ordering::then)
+            .then_with(|| {
+                compare_metadata(
+                    left_metadata.as_ref(),
+                    right_metadata.as_ref(),
+                    sorting.order,
+                )
+            })
+    });
+
+    Ok(keyed.into_iter().map(|(_, _, item)| item).collect())
+}
+
+/// Builds the continuation to resume the results read at `height` after the `item`,
+/// which is returned at the `position`.
+///
+/// Returns `None` if the item has no id to order by.
+///
+/// # Errors
+///
+/// A selector fails to project the item
+pub fn continuation_after<T>(
+    item: &T,
+    height: u64,
+    position: NonZeroU64,
+    sort_by: &[SortKey<T>],
+    sorting: &Sorting,
+) -> Result<Option<QueryContinuation>, QueryExecutionFail>
+where
+    T: SortableQueryOutput + HasProjection<SelectorMarker, AtomType = ()> + 'static,
+    T::Projection: EvaluateSelector<T>,
+{
+    let Some(id) = item.continuation_id() else {
+        return Ok(None);
+    };
+    let tuple = sort_by
+        .iter()
+        .map(|key| key.selector.project_clone(core::iter::once(item)))
+        .collect::<Result<Vec<_>, _>>()?;
+    let metadata = sorting
+        .sort_by_metadata_key
+        .as_ref()
+        .and_then(|key| item.get_metadata_sorting_key(key));
+
+    Ok(Some(QueryContinuation {
+        height,
+        position,
+        sort_keys: QueryOutputBatchBoxTuple { tuple },
+        metadata,
+        id,
+    }))
+}
+
+/// Checks whether the `item` is ordered after the `continuation`,
+/// with the results sorted by the keys as in [`sort`] and by their ids last.
+///
+/// # Errors
+///
+/// - A selector fails to project the item
+/// - The item has no id to order by
+/// - The continuation wasn't taken from the results of the same query
+pub fn is_after<T>(
+    item: &T,
+    continuation: &QueryContinuation,
+    sort_by: &[SortKey<T>],
+    sorting: &Sorting,
+) -> Result<bool, QueryExecutionFail>
+where
+    T: SortableQueryOutput + HasProjection<SelectorMarker, AtomType = ()> + 'static,
+    T::Projection: EvaluateSelector<T>,
+{
+    let key = continuation_after(
+        item,
+        continuation.height,
+        continuation.position,
+        sort_by,
+        sorting,
+    )?
+    .ok_or(QueryExecutionFail::CursorMismatch)?;
+    if key.sort_keys.tuple.len() != continuation.sort_keys.tuple.len() {
+        return Err(QueryExecutionFail::CursorMismatch);
+    }
+
+    let mut ordering = Ordering::Equal;
+    for (sort_key, (l, r)) in sort_by.iter().zip(
+        key.sort_keys
+            .tuple
+            .iter()
+            .zip(&continuation.sort_keys.tuple),
+    ) {
+        let selected = compare(l, r).ok_or(QueryExecutionFail::CursorMismatch)?;
+        ordering = ordering.then(sort_key.order.apply(selected));
+    }
+    let ordering = ordering
+        .then_with(|| {
+            compare_metadata(
+                key.metadata.as_ref(),
+                continuation.metadata.as_ref(),
+                sorting.order,
+            )
+        })
+        .then(compare(&key.id, &continuation.id).ok_or(QueryExecutionFail::CursorMismatch)?);
+
+    Ok(ordering == Ordering::Greater)
+}
+
+fn compare_metadata(left: Option<&Json>, right: Option<&Json>, order: SortOrder) -> Ordering {
+    match (left, right) {
+        (Some(l), Some(r)) => order.apply(l.cmp(r)),
+        (Some(_), None) => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+        (None, None) => Ordering::Equal,
+    }
+}
+
+macro_rules! compare_batches {
+    ($left:expr, $right:expr; $($variant:ident),* $(,)?) => {
+        match ($left, $right) {
+            $(
+                (QueryOutputBatchBox::$variant(l), QueryOutputBatchBox::$variant(r)) => Some(l.cmp(r)),
+            )*
+            _ => None,
+        }
+    };
+}
+
+/// Compares batches of the same orderable type. Returns `None` otherwise.
+fn compare(left: &QueryOutputBatchBox, right: &QueryOutputBatchBox) -> Option<Ordering> {
+    compare_batches!(
+        left, right;
+        PublicKey,
+        String,
+        Metadata,
+        Json,
+        Numeric,
+        Name,
+        DomainId,
+        Domain,
+        AccountId,
+        Account,
+        AssetId,
+        Asset,
+        AssetDefinitionId,
+        AssetDefinition,
+        NftId,
+        Nft,
+        Role,
+        Parameter,
+        Permission,
+        CommittedTransaction,
+        TransactionResult,
+        TransactionResultHash,
+        TransactionEntrypoint,
+        TransactionEntrypointHash,
+        Peer,
+        RoleId,
+        TriggerId,
+        Trigger,
+        Block,
+        BlockHeader,
+        BlockHeaderHash,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use iroha_data_model::prelude::*;
+    use iroha_test_samples::{ALICE_ID, BOB_ID};
+    use nonzero_ext::nonzero;
+
+    use super::*;
+
+    fn asset(definition: &str, account: &AccountId, value: Numeric) -> Asset {
+        Asset::new(
+            AssetId::new(definition.parse().unwrap(), account.clone()),
+            value,
+        )
+    }
+
+    #[test]
+    fn sorts_by_multiple_keys() {
+        let assets = vec![
+            asset("rose#wonderland", &ALICE_ID, numeric!(5)),
+            asset("tulip#wonderland", &ALICE_ID, numeric!(13)),
+            asset("rose#wonderland", &BOB_ID, numeric!(13)),
+        ];
+        let sort_by = [
+            SortKey::<Asset>::build(SortOrder::Descending, |asset| asset.value),
+            SortKey::<Asset>::build(SortOrder::Ascending, |asset| asset.id.definition),
+        ];
+
+        let sorted = sort(assets.clone().into_iter(), &sort_by, &Sorting::default()).unwrap();
+
+        assert_eq!(
+            sorted,
+            vec![assets[2].clone(), assets[1].clone(), assets[0].clone()]
+        );
+    }
+
+    #[test]
+    fn continuation_resumes_sorted_results() {
+        let assets = vec![
+            asset("rose#wonderland", &ALICE_ID, numeric!(5)),
+            asset("tulip#wonderland", &ALICE_ID, numeric!(13)),
+            asset("rose#wonderland", &BOB_ID, numeric!(13)),
+        ];
+        let sort_by = [
+            SortKey::<Asset>::build(SortOrder::Descending, |asset| asset.value),
+            SortKey::<Asset>::build(SortOrder::Ascending, |asset| asset.id.definition),
+        ];
+        let sorting = Sorting::default();
+        let sorted = sort(assets.into_iter(), &sort_by, &sorting).unwrap();
+
+        let continuation = continuation_after(&sorted[1], 1, nonzero!(2_u64), &sort_by, &sorting)
+            .unwrap()
+            .expect("assets have ids");
+        let after = sorted
+            .iter()
+            .map(|asset| is_after(asset, &continuation, &sort_by, &sorting).unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(after, [false, false, true]);
+
+        // the continuation of a query sorted differently
+        let unsorted = continuation_after(&sorted[1], 1, nonzero!(2_u64), &[], &sorting)
+            .unwrap()
+            .expect("assets have ids");
+        assert_eq!(
+            is_after(&sorted[2], &unsorted, &sort_by, &sorting).unwrap_err(),
+            QueryExecutionFail::CursorMismatch
+        );
+    }
+
+    #[test]
+    fn unorderable_keys_are_rejected() {
+        let sort_by = [SortKey::<Trigger>::build(SortOrder::Ascending, |trigger| {
+            trigger.action
+        })];
+
+        assert_eq!(
+            sort(core::iter::empty(), &sort_by, &Sorting::default()).unwrap_err(),
+            QueryExecutionFail::UnorderedSortKey
+        );
+    }
+}
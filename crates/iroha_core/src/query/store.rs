@@ -12,7 +12,7 @@ use iroha_data_model::{
     account::AccountId,
     query::{
         error::QueryExecutionFail,
-        parameters::{ForwardCursor, QueryId},
+        parameters::{ForwardCursor, QueryContinuation, QueryId},
         QueryOutput, QueryOutputBatchBoxTuple,
     },
 };
@@ -155,7 +155,15 @@ impl LiveQueryStore {
         &self,
         query_id: QueryId,
         cursor: NonZeroU64,
-    ) -> Result<(QueryOutputBatchBoxTuple, u64, Option<NonZeroU64>), QueryExecutionFail> {
+    ) -> Result<
+        (
+            QueryOutputBatchBoxTuple,
+            u64,
+            Option<NonZeroU64>,
+            Option<QueryContinuation>,
+        ),
+        QueryExecutionFail,
+    > {
         trace!(%query_id, "Advancing existing query");
         let QueryInfo {
             mut live_query,
@@ -164,10 +172,11 @@ impl LiveQueryStore {
         } = self.remove(&query_id).ok_or(QueryExecutionFail::NotFound)?;
         let (next_batch, next_cursor) = live_query.next_batch(cursor.get())?;
         let remaining = live_query.remaining();
+        let continuation = live_query.continuation().cloned();
         if next_cursor.is_some() {
             self.insert(query_id, live_query, authority);
         }
-        Ok((next_batch, remaining, next_cursor))
+        Ok((next_batch, remaining, next_cursor, continuation))
     }
 
     fn check_capacity(&self, authority: &AccountId) -> Result<(), QueryExecutionFail> {
@@ -206,6 +215,8 @@ impl LiveQueryStoreHandle {
 
     /// Construct a batched response from a post-processed query output.
     ///
+    /// The cursors continue from the position the output starts at, if it is resumed from a continuation.
+    ///
     /// # Errors
     ///
     /// - Returns [`QueryExecutionFail::CapacityLimit`] if [`LiveQueryStore`] capacity is reached,
@@ -217,11 +228,12 @@ impl LiveQueryStoreHandle {
     ) -> Result<QueryOutput, QueryExecutionFail> {
         let query_id = uuid::Uuid::new_v4().to_string();
 
-        let curr_cursor = 0;
+        let curr_cursor = live_query.cursor().unwrap_or_default();
         let (batch, next_cursor) = live_query.next_batch(curr_cursor)?;
 
         // NOTE: we are checking remaining items _after_ the first batch is taken
         let remaining_items = live_query.remaining();
+        let continuation = live_query.continuation().cloned();
 
         // if the cursor is `None` - the query has ended, we can remove it from the store
         if next_cursor.is_some() {
@@ -233,6 +245,31 @@ impl LiveQueryStoreHandle {
             remaining_items,
             query_id,
             next_cursor,
+            continuation,
+        ))
+    }
+
+    /// Construct a response with the first batch of a post-processed query output,
+    /// not keeping the rest of it.
+    ///
+    /// The returned cursor can't be continued,
+    /// the query has to be started again from its continuation instead.
+    ///
+    /// # Errors
+    ///
+    /// Throws up query output handling errors.
+    pub fn handle_iter_start_detached(
+        mut live_query: ErasedQueryIterator,
+    ) -> Result<QueryOutput, QueryExecutionFail> {
+        let curr_cursor = live_query.cursor().unwrap_or_default();
+        let (batch, next_cursor) = live_query.next_batch(curr_cursor)?;
+
+        Ok(Self::construct_query_response(
+            batch,
+            live_query.remaining(),
+            uuid::Uuid::new_v4().to_string(),
+            next_cursor,
+            live_query.continuation().cloned(),
         ))
     }
 
@@ -244,9 +281,9 @@ impl LiveQueryStoreHandle {
     ///   or if cursor position doesn't match or cannot continue.
     pub fn handle_iter_continue(
         &self,
-        ForwardCursor { query, cursor }: ForwardCursor,
+        ForwardCursor { query, cursor, .. }: ForwardCursor,
     ) -> Result<QueryOutput, QueryExecutionFail> {
-        let (batch, remaining, next_cursor) =
+        let (batch, remaining, next_cursor, continuation) =
             self.store.get_query_next_batch(query.clone(), cursor)?;
 
         Ok(Self::construct_query_response(
@@ -254,6 +291,7 @@ impl LiveQueryStoreHandle {
             remaining,
             query,
             next_cursor,
+            continuation,
         ))
     }
 
@@ -267,6 +305,7 @@ impl LiveQueryStoreHandle {
         remaining_items: u64,
         query_id: QueryId,
         cursor: Option<NonZeroU64>,
+        continuation: Option<QueryContinuation>,
     ) -> QueryOutput {
         QueryOutput::new(
             batch,
@@ -274,6 +313,7 @@ impl LiveQueryStoreHandle {
             cursor.map(|cursor| ForwardCursor {
                 query: query_id,
                 cursor,
+                continuation,
             }),
         )
     }
@@ -282,9 +322,13 @@ impl LiveQueryStoreHandle {
 #[cfg(test)]
 mod tests {
     use iroha_data_model::{
+        domain::Domain,
         permission::Permission,
         prelude::SelectorTuple,
-        query::parameters::{FetchSize, Pagination, QueryParams, Sorting},
+        query::{
+            parameters::{FetchSize, Pagination, QueryParams, Sorting},
+            QueryOutputBatchBox,
+        },
     };
     use iroha_primitives::json::Json;
     use iroha_test_samples::ALICE_ID;
@@ -308,6 +352,8 @@ mod tests {
                 pagination,
                 sorting,
                 fetch_size,
+                at_height: None,
+                continuation: None,
             };
 
             // it's not important which type we use here, just to test the flow
@@ -316,6 +362,8 @@ mod tests {
             let query_output = crate::smartcontracts::query::apply_query_postprocessing(
                 query_output,
                 SelectorTuple::default(),
+                None,
+                Vec::new(),
                 &query_params,
             )
             .unwrap();
@@ -341,4 +389,73 @@ mod tests {
             assert_eq!(counter, 100, "failed on {i} iteration");
         }
     }
+
+    #[test]
+    fn query_resumes_after_continuation() {
+        let threaded_rt = tokio::runtime::Runtime::new().unwrap();
+        let query_handle = threaded_rt.block_on(async { LiveQueryStore::start_test() });
+
+        let domains = |names: &[&str]| {
+            names
+                .iter()
+                .map(|name| Domain::new(name.parse().unwrap()).build(&ALICE_ID))
+                .collect::<Vec<_>>()
+        };
+        let live_query = |domains: Vec<Domain>, params: &QueryParams| {
+            crate::smartcontracts::query::apply_query_postprocessing(
+                domains.into_iter(),
+                SelectorTuple::default(),
+                None,
+                Vec::new(),
+                params,
+            )
+            .unwrap()
+        };
+        let batch = |domains: Vec<Domain>| QueryOutputBatchBoxTuple {
+            tuple: vec![QueryOutputBatchBox::Domain(domains)],
+        };
+
+        let names = (0..25).map(|i| format!("d{i:02}")).collect::<Vec<_>>();
+        let names = names.iter().map(String::as_str).collect::<Vec<_>>();
+        let query_params = QueryParams {
+            fetch_size: FetchSize::new(Some(nonzero!(10_u64))),
+            ..QueryParams::default()
+        };
+
+        let (_, _, cursor) = query_handle
+            .handle_iter_start(live_query(domains(&names), &query_params), &ALICE_ID)
+            .unwrap()
+            .into_parts();
+        let continuation = cursor
+            .unwrap()
+            .continuation
+            .expect("domains can be resumed");
+        assert_eq!(continuation.position, nonzero!(10_u64));
+
+        // the query is started again, e.g. on another peer
+        let resumed_params = QueryParams {
+            continuation: Some(continuation),
+            ..query_params
+        };
+        let (resumed, remaining_items, next_cursor) = query_handle
+            .handle_iter_start(live_query(domains(&names), &resumed_params), &ALICE_ID)
+            .unwrap()
+            .into_parts();
+        assert_eq!(resumed, batch(domains(&names[10..20])));
+        assert_eq!(remaining_items, 5);
+        assert_eq!(next_cursor.unwrap().cursor, nonzero!(20_u64));
+
+        // results removed before and added after the continuation don't shift the rest
+        let mut changed = names.clone();
+        changed.remove(3);
+        changed.insert(9, "d09a");
+        let (resumed, remaining_items, _) = query_handle
+            .handle_iter_start(live_query(domains(&changed), &resumed_params), &ALICE_ID)
+            .unwrap()
+            .into_parts();
+        let mut expected = vec!["d09a"];
+        expected.extend(&names[10..19]);
+        assert_eq!(resumed, batch(domains(&expected)));
+        assert_eq!(remaining_items, 6);
+    }
 }
@@ -0,0 +1,1171 @@
+//! Module with [`QuerySubscription`] tracking the results of an iterable query as blocks are committed.
+
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    num::{NonZeroU64, NonZeroUsize},
+};
+
+use iroha_data_model::{
+    block::{BlockHeader, SignedBlock},
+    prelude::*,
+    query::{
+        dsl::{CompoundPredicate, EvaluateSelector, HasProjection, SelectorMarker},
+        error::QueryExecutionFail,
+        parameters::{Pagination, Sorting, DEFAULT_FETCH_SIZE, MAX_FETCH_SIZE},
+        subscription::QueryResultsDelta,
+        CommittedTransaction, QueryBox, QueryOutputBatchBoxTuple, QueryRequest, QueryWithFilter,
+        QueryWithParams,
+    },
+};
+use mv::storage::StorageReadOnly;
+use parity_scale_codec::{Decode, Encode};
+
+use crate::{
+    smartcontracts::{
+        isi::{block::consume_stored_blocks, tx::committed_transactions},
+        triggers::set::SetReadOnly,
+        ValidQuery,
+    },
+    state::{StateReadOnly, WorldReadOnly},
+};
+/// Identifies the query output items, so that a changed item can be told apart from a removed and an inserted one.
+pub trait SubscribableQueryOutput {
+    /// Get the key identifying the item among the results of a query
+    fn subscription_key(&self) -> Vec<u8>;
+}
+
+macro_rules! impl_subscribable_by_id {
+    ($($ty:ty),* $(,)?) => {$(
+        impl SubscribableQueryOutput for $ty {
+            fn subscription_key(&self) -> Vec<u8> {
+                self.id().encode()
+            }
+        }
+    )*};
+}
+
+macro_rules! impl_subscribable_by_value {
+    ($($ty:ty),* $(,)?) => {$(
+        impl SubscribableQueryOutput for $ty {
+            fn subscription_key(&self) -> Vec<u8> {
+                self.encode()
+            }
+        }
+    )*};
+}
+
+impl_subscribable_by_id!(Domain, Account, Asset, AssetDefinition, Nft, Role, Trigger);
+impl_subscribable_by_value!(RoleId, PeerId, Permission, TriggerId);
+
+impl SubscribableQueryOutput for CommittedTransaction {
+    fn subscription_key(&self) -> Vec<u8> {
+        (&self.block_hash, &self.entrypoint_hash).encode()
+    }
+}
+
+impl SubscribableQueryOutput for SignedBlock {
+    fn subscription_key(&self) -> Vec<u8> {
+        self.hash().encode()
+    }
+}
+
+impl SubscribableQueryOutput for BlockHeader {
+    fn subscription_key(&self) -> Vec<u8> {
+        self.hash().encode()
+    }
+}
+
+/// Selected results of a query, each row being a tuple of single-element batches
+struct Snapshot {
+    /// A tuple of empty batches with the types of the rows
+    empty: QueryOutputBatchBoxTuple,
+    /// Rows in the order of the query results, along with the keys of their items
+    rows: Vec<(Vec<u8>, QueryOutputBatchBoxTuple)>,
+}
+
+impl Snapshot {
+    fn evaluate<I>(iter: I, selector: &SelectorTuple<I::Item>) -> Result<Self, QueryExecutionFail>
+    where
+        I: Iterator<Item: SubscribableQueryOutput>,
+        I::Item: HasProjection<SelectorMarker, AtomType = ()> + 'static,
+        <I::Item as HasProjection<SelectorMarker>>::Projection: EvaluateSelector<I::Item>,
+    {
+        let empty = QueryOutputBatchBoxTuple::new(
+            selector
+                .iter()
+                .map(|selector| selector.project_clone(core::iter::empty()))
+                .collect::<Result<_, _>>()?,
+        );
+        let rows = iter
+            .map(|item| Ok((item.subscription_key(), select(selector, &item)?)))
+            .collect::<Result<_, QueryExecutionFail>>()?;
+
+        Ok(Self { empty, rows })
+    }
+}
+
+/// Projects the `item` with the `selector` into a row of single-element batches
+fn select<T>(
+    selector: &SelectorTuple<T>,
+    item: &T,
+) -> Result<QueryOutputBatchBoxTuple, QueryExecutionFail>
+where
+    T: HasProjection<SelectorMarker, AtomType = ()> + 'static,
+    <T as HasProjection<SelectorMarker>>::Projection: EvaluateSelector<T>,
+{
+    let row = selector
+        .iter()
+        .map(|selector| selector.project_clone(core::iter::once(item)))
+        .collect::<Result<_, _>>()?;
+
+    Ok(QueryOutputBatchBoxTuple::new(row))
+}
+
+/// Change of the world state reported by an event, as the id of the changed item
+#[derive(Debug, Clone)]
+enum Change {
+    Domain(DomainId),
+    Account(AccountId),
+    AssetDefinition(AssetDefinitionId),
+    Asset(AssetId),
+    Nft(NftId),
+    Role(RoleId),
+    Peer(PeerId),
+    Trigger(TriggerId),
+    /// The domain was deleted along with its accounts, assets, asset definitions, NFTs and triggers
+    DomainDeleted(DomainId),
+    /// The account was deleted along with its assets, NFTs and triggers
+    AccountDeleted(AccountId),
+    /// The asset definition was deleted along with its assets
+    AssetDefinitionDeleted(AssetDefinitionId),
+}
+
+impl Change {
+    fn of_event(event: &EventBox) -> Option<Self> {
+        let EventBox::Data(event) = event else {
+            // trigger repetitions are spent without emitting data events
+            return match event {
+                EventBox::TriggerCompleted(event) => {
+                    Some(Self::Trigger(event.trigger_id().clone()))
+                }
+                _ => None,
+            };
+        };
+
+        let change = match event {
+            DataEvent::Domain(DomainEvent::Deleted(id)) => Self::DomainDeleted(id.clone()),
+            DataEvent::Domain(DomainEvent::Account(AccountEvent::Deleted(id))) => {
+                Self::AccountDeleted(id.clone())
+            }
+            DataEvent::Domain(DomainEvent::AssetDefinition(AssetDefinitionEvent::Deleted(id))) => {
+                Self::AssetDefinitionDeleted(id.clone())
+            }
+            DataEvent::Domain(DomainEvent::Account(AccountEvent::Asset(event))) => {
+                Self::Asset(event.origin().clone())
+            }
+            DataEvent::Domain(DomainEvent::Account(event)) => Self::Account(event.origin().clone()),
+            DataEvent::Domain(DomainEvent::AssetDefinition(event)) => {
+                Self::AssetDefinition(event.origin().clone())
+            }
+            DataEvent::Domain(DomainEvent::Nft(event)) => Self::Nft(event.origin().clone()),
+            DataEvent::Domain(event) => Self::Domain(event.origin().clone()),
+            DataEvent::Role(event) => Self::Role(event.origin().clone()),
+            DataEvent::Peer(event) => Self::Peer(event.origin().clone()),
+            DataEvent::Trigger(event) => Self::Trigger(event.origin().clone()),
+            DataEvent::Configuration(_) | DataEvent::Executor(_) => return None,
+        };
+
+        Some(change)
+    }
+
+    /// Whether the change removes items that emit no events of their own
+    fn is_cascading(&self) -> bool {
+        matches!(
+            self,
+            Self::DomainDeleted(_) | Self::AccountDeleted(_) | Self::AssetDefinitionDeleted(_)
+        )
+    }
+}
+
+/// Query whose results are tracked item by item, so that only the items changed by the events are looked up again
+trait TrackedQuery: ValidQuery {
+    /// Id of the item among the results, encoded into the key of its row
+    type Id: Encode + Decode + Ord;
+
+    /// Id of the item the `change` refers to, if the item is one of the query items
+    fn changed_id(&self, change: &Change) -> Option<Self::Id>;
+
+    /// Whether the cascading `change` may have removed the item with the `id`
+    fn is_removed_by(&self, _id: &Self::Id, _change: &Change) -> bool {
+        false
+    }
+
+    /// Looks up the item with the `id`, if it exists and satisfies the `predicate`
+    fn lookup(
+        &self,
+        id: &Self::Id,
+        predicate: &CompoundPredicate<Self::Item>,
+        state: &impl StateReadOnly,
+    ) -> Option<Self::Item>;
+}
+
+impl TrackedQuery for FindDomains {
+    type Id = DomainId;
+
+    fn changed_id(&self, change: &Change) -> Option<Self::Id> {
+        match change {
+            Change::Domain(id) | Change::DomainDeleted(id) => Some(id.clone()),
+            _ => None,
+        }
+    }
+
+    fn lookup(
+        &self,
+        id: &Self::Id,
+        predicate: &CompoundPredicate<Domain>,
+        state: &impl StateReadOnly,
+    ) -> Option<Domain> {
+        let domain = state.world().domain(id).ok()?;
+        predicate.applies(domain).then(|| domain.clone())
+    }
+}
+
+impl TrackedQuery for FindAccounts {
+    type Id = AccountId;
+
+    fn changed_id(&self, change: &Change) -> Option<Self::Id> {
+        match change {
+            Change::Account(id) | Change::AccountDeleted(id) => Some(id.clone()),
+            _ => None,
+        }
+    }
+
+    fn is_removed_by(&self, id: &Self::Id, change: &Change) -> bool {
+        matches!(change, Change::DomainDeleted(domain) if id.domain == *domain)
+    }
+
+    fn lookup(
+        &self,
+        id: &Self::Id,
+        predicate: &CompoundPredicate<Account>,
+        state: &impl StateReadOnly,
+    ) -> Option<Account> {
+        let account = state.world().account(id).ok()?;
+        predicate
+            .applies_to_entry(&account)
+            .then(|| account.to_owned())
+    }
+}
+
+impl TrackedQuery for FindAccountsWithAsset {
+    type Id = AccountId;
+
+    fn changed_id(&self, change: &Change) -> Option<Self::Id> {
+        match change {
+            Change::Account(id) | Change::AccountDeleted(id) => Some(id.clone()),
+            Change::Asset(id) if id.definition == self.asset_definition => Some(id.account.clone()),
+            _ => None,
+        }
+    }
+
+    fn is_removed_by(&self, id: &Self::Id, change: &Change) -> bool {
+        match change {
+            Change::DomainDeleted(domain) => {
+                id.domain == *domain || self.asset_definition.domain == *domain
+            }
+            Change::AssetDefinitionDeleted(definition) => *definition == self.asset_definition,
+            _ => false,
+        }
+    }
+
+    fn lookup(
+        &self,
+        id: &Self::Id,
+        predicate: &CompoundPredicate<Account>,
+        state: &impl StateReadOnly,
+    ) -> Option<Account> {
+        let world = state.world();
+        world
+            .assets()
+            .get(&AssetId::new(self.asset_definition.clone(), id.clone()))?;
+        let account = world.account(id).ok()?;
+        predicate
+            .applies_to_entry(&account)
+            .then(|| account.to_owned())
+    }
+}
+
+impl TrackedQuery for FindAssets {
+    type Id = AssetId;
+
+    fn changed_id(&self, change: &Change) -> Option<Self::Id> {
+        match change {
+            Change::Asset(id) => Some(id.clone()),
+            _ => None,
+        }
+    }
+
+    fn is_removed_by(&self, id: &Self::Id, change: &Change) -> bool {
+        match change {
+            Change::DomainDeleted(domain) => {
+                id.account.domain == *domain || id.definition.domain == *domain
+            }
+            Change::AccountDeleted(account) => id.account == *account,
+            Change::AssetDefinitionDeleted(definition) => id.definition == *definition,
+            _ => false,
+        }
+    }
+
+    fn lookup(
+        &self,
+        id: &Self::Id,
+        predicate: &CompoundPredicate<Asset>,
+        state: &impl StateReadOnly,
+    ) -> Option<Asset> {
+        let asset = state.world().asset(id).ok()?;
+        predicate.applies_to_entry(&asset).then(|| asset.to_owned())
+    }
+}
+
+impl TrackedQuery for FindAssetsDefinitions {
+    type Id = AssetDefinitionId;
+
+    fn changed_id(&self, change: &Change) -> Option<Self::Id> {
+        match change {
+            Change::AssetDefinition(id) | Change::AssetDefinitionDeleted(id) => Some(id.clone()),
+            _ => None,
+        }
+    }
+
+    fn is_removed_by(&self, id: &Self::Id, change: &Change) -> bool {
+        matches!(change, Change::DomainDeleted(domain) if id.domain == *domain)
+    }
+
+    fn lookup(
+        &self,
+        id: &Self::Id,
+        predicate: &CompoundPredicate<AssetDefinition>,
+        state: &impl StateReadOnly,
+    ) -> Option<AssetDefinition> {
+        let definition = state.world().asset_definition(id).ok()?;
+        predicate.applies(&definition).then_some(definition)
+    }
+}
+
+impl TrackedQuery for FindNfts {
+    type Id = NftId;
+
+    fn changed_id(&self, change: &Change) -> Option<Self::Id> {
+        match change {
+            Change::Nft(id) => Some(id.clone()),
+            _ => None,
+        }
+    }
+
+    fn is_removed_by(&self, id: &Self::Id, change: &Change) -> bool {
+        match change {
+            Change::DomainDeleted(domain) => id.domain == *domain,
+            // the owner of the NFT is not a part of its id
+            Change::AccountDeleted(_) => true,
+            _ => false,
+        }
+    }
+
+    fn lookup(
+        &self,
+        id: &Self::Id,
+        predicate: &CompoundPredicate<Nft>,
+        state: &impl StateReadOnly,
+    ) -> Option<Nft> {
+        let nft = state.world().nft(id).ok()?;
+        predicate.applies_to_entry(&nft).then(|| nft.to_owned())
+    }
+}
+
+impl TrackedQuery for FindRoles {
+    type Id = RoleId;
+
+    fn changed_id(&self, change: &Change) -> Option<Self::Id> {
+        match change {
+            Change::Role(id) => Some(id.clone()),
+            _ => None,
+        }
+    }
+
+    fn lookup(
+        &self,
+        id: &Self::Id,
+        predicate: &CompoundPredicate<Role>,
+        state: &impl StateReadOnly,
+    ) -> Option<Role> {
+        let role = state.world().roles().get(id)?;
+        predicate.applies(role).then(|| role.clone())
+    }
+}
+
+impl TrackedQuery for FindRoleIds {
+    type Id = RoleId;
+
+    fn changed_id(&self, change: &Change) -> Option<Self::Id> {
+        match change {
+            Change::Role(id) => Some(id.clone()),
+            _ => None,
+        }
+    }
+
+    fn lookup(
+        &self,
+        id: &Self::Id,
+        predicate: &CompoundPredicate<RoleId>,
+        state: &impl StateReadOnly,
+    ) -> Option<RoleId> {
+        state.world().roles().get(id)?;
+        predicate.applies(id).then(|| id.clone())
+    }
+}
+
+impl TrackedQuery for FindPeers {
+    type Id = PeerId;
+
+    fn changed_id(&self, change: &Change) -> Option<Self::Id> {
+        match change {
+            Change::Peer(id) => Some(id.clone()),
+            _ => None,
+        }
+    }
+
+    fn lookup(
+        &self,
+        id: &Self::Id,
+        predicate: &CompoundPredicate<PeerId>,
+        state: &impl StateReadOnly,
+    ) -> Option<PeerId> {
+        let peer = state.world().peers().into_iter().find(|peer| *peer == id)?;
+        predicate.applies(peer).then(|| peer.clone())
+    }
+}
+
+impl TrackedQuery for FindActiveTriggerIds {
+    type Id = TriggerId;
+
+    fn changed_id(&self, change: &Change) -> Option<Self::Id> {
+        match change {
+            Change::Trigger(id) => Some(id.clone()),
+            _ => None,
+        }
+    }
+
+    fn is_removed_by(&self, _id: &Self::Id, change: &Change) -> bool {
+        // the authority of the trigger is not a part of its id
+        matches!(change, Change::DomainDeleted(_) | Change::AccountDeleted(_))
+    }
+
+    fn lookup(
+        &self,
+        id: &Self::Id,
+        predicate: &CompoundPredicate<TriggerId>,
+        state: &impl StateReadOnly,
+    ) -> Option<TriggerId> {
+        state.world().triggers().inspect_by_id(id, |_| ())?;
+        predicate.applies(id).then(|| id.clone())
+    }
+}
+
+impl TrackedQuery for FindTriggers {
+    type Id = TriggerId;
+
+    fn changed_id(&self, change: &Change) -> Option<Self::Id> {
+        match change {
+            Change::Trigger(id) => Some(id.clone()),
+            _ => None,
+        }
+    }
+
+    fn is_removed_by(&self, _id: &Self::Id, change: &Change) -> bool {
+        // the authority of the trigger is not a part of its id
+        matches!(change, Change::DomainDeleted(_) | Change::AccountDeleted(_))
+    }
+
+    fn lookup(
+        &self,
+        id: &Self::Id,
+        predicate: &CompoundPredicate<Trigger>,
+        state: &impl StateReadOnly,
+    ) -> Option<Trigger> {
+        let triggers = state.world().triggers();
+        let action = triggers.inspect_by_id(id, |action| action.clone_and_box())?;
+        let trigger = Trigger::new(id.clone(), triggers.get_original_action(action).into());
+        predicate.applies(&trigger).then_some(trigger)
+    }
+}
+
+/// Looks up the items of the `query` affected by the `changes`,
+/// returning their new rows by their keys, or [`None`] for the items that don't match the query anymore
+fn look_up_changes<Q>(
+    query: &QueryWithFilter<Q>,
+    changes: &[Change],
+    known: &BTreeMap<Vec<u8>, QueryOutputBatchBoxTuple>,
+    state: &impl StateReadOnly,
+) -> Result<Vec<(Vec<u8>, Option<QueryOutputBatchBoxTuple>)>, QueryExecutionFail>
+where
+    Q: TrackedQuery,
+    Q::Item: HasProjection<SelectorMarker, AtomType = ()> + 'static,
+    <Q::Item as HasProjection<SelectorMarker>>::Projection: EvaluateSelector<Q::Item>,
+{
+    let mut ids = changes
+        .iter()
+        .filter_map(|change| query.query.changed_id(change))
+        .collect::<BTreeSet<_>>();
+    let cascading = changes
+        .iter()
+        .filter(|change| change.is_cascading())
+        .collect::<Vec<_>>();
+    if !cascading.is_empty() {
+        ids.extend(
+            known
+                .keys()
+                .filter_map(|key| Q::Id::decode(&mut key.as_slice()).ok())
+                .filter(|id| {
+                    cascading
+                        .iter()
+                        .any(|change| query.query.is_removed_by(id, change))
+                }),
+        );
+    }
+
+    ids.into_iter()
+        .map(|id| {
+            let row = query
+                .query
+                .lookup(&id, &query.predicate, state)
+                .map(|item| select(&query.selector, &item))
+                .transpose()?;
+
+            Ok((id.encode(), row))
+        })
+        .collect()
+}
+
+/// Results of an iterable query kept up to date with the state.
+///
+/// After a block, only the items changed by its events are looked up again,
+/// and the changes are reported as a [`QueryResultsDelta`].
+/// The queries over the blocks and transactions only report the items of the new blocks.
+#[derive(Debug)]
+pub struct QuerySubscription {
+    authority: AccountId,
+    query: QueryWithParams,
+    empty: QueryOutputBatchBoxTuple,
+    /// Last known results, by the keys of their items.
+    /// Not kept for the queries over the blocks and transactions, as these are never changed nor removed.
+    results: BTreeMap<Vec<u8>, QueryOutputBatchBoxTuple>,
+    /// Height of the state the results were last evaluated at
+    height: usize,
+    /// Changes relevant to the query made by the blocks the results are not up to date with yet
+    changes: Vec<Change>,
+    /// Whether the results have to be evaluated again in full, e.g. because some events were missed
+    invalidated: bool,
+}
+
+impl QuerySubscription {
+    /// Executes the query against the `state`, returning the subscription along with the initial results split into batches.
+    ///
+    /// The query is expected to be already validated for the `authority`.
+    ///
+    /// # Errors
+    ///
+    /// - The query parameters are not applicable to subscriptions
+    /// - The query execution fails
+    pub fn start(
+        authority: AccountId,
+        query: QueryWithParams,
+        state: &impl StateReadOnly,
+    ) -> Result<(Self, Vec<QueryOutputBatchBoxTuple>), QueryExecutionFail> {
+        let params = &query.params;
+        if params.pagination != Pagination::default()
+            || params.sorting != Sorting::default()
+            || params.at_height.is_some()
+            || params.continuation.is_some()
+        {
+            return Err(QueryExecutionFail::InvalidSubscriptionParameters);
+        }
+        let fetch_size = params.fetch_size.fetch_size.unwrap_or(DEFAULT_FETCH_SIZE);
+        if fetch_size > MAX_FETCH_SIZE {
+            return Err(QueryExecutionFail::FetchSizeTooBig);
+        }
+
+        let Snapshot { empty, rows } = evaluate_query(&query.query, state)?;
+
+        let batch_size =
+            usize::try_from(fetch_size.get()).expect("`u32` should always fit into `usize`");
+        let batches = rows
+            .chunks(batch_size)
+            .map(|chunk| {
+                let mut batch = empty.clone();
+                chunk.iter().for_each(|(_, row)| batch.extend(row.clone()));
+                batch
+            })
+            .collect();
+        let results = if is_append_only(&query.query) {
+            BTreeMap::new()
+        } else {
+            rows.into_iter().collect()
+        };
+
+        let subscription = Self {
+            authority,
+            query,
+            empty,
+            results,
+            height: state.height(),
+            changes: Vec::new(),
+            invalidated: false,
+        };
+
+        Ok((subscription, batches))
+    }
+
+    /// Height of the block the results are up to date with
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Takes note of an event produced by a committed block
+    pub fn observe(&mut self, event: &EventBox) {
+        if self.invalidated {
+            return;
+        }
+        if let EventBox::Data(DataEvent::Executor(_)) = event {
+            // the upgraded executor may have migrated the data without emitting events
+            self.invalidate();
+            return;
+        }
+        if let Some(change) = Change::of_event(event) {
+            if is_affected_by(&self.query.query, &change) {
+                self.changes.push(change);
+            }
+        }
+    }
+
+    /// Takes note of events produced by committed blocks that were missed
+    pub fn invalidate(&mut self) {
+        self.invalidated = true;
+        self.changes = Vec::new();
+    }
+
+    /// Brings the results up to date with the `state`, returning the changes if there are any.
+    ///
+    /// The query is validated for the authority again, as its permissions might have changed.
+    ///
+    /// # Errors
+    ///
+    /// - The query is not permitted for the authority anymore
+    /// - The query execution fails
+    pub fn update(
+        &mut self,
+        state: &impl StateReadOnly,
+    ) -> Result<Option<QueryResultsDelta>, ValidationFail> {
+        if state.height() <= self.height {
+            return Ok(None);
+        }
+        let updated_from = self.height;
+        self.height = state.height();
+        let changes = core::mem::take(&mut self.changes);
+        let invalidated = core::mem::take(&mut self.invalidated);
+        let query = &self.query.query;
+        if !(invalidated || !changes.is_empty() || is_append_only(query)) {
+            return Ok(None);
+        }
+
+        state.world().executor().validate_query(
+            state,
+            &self.authority,
+            &QueryRequest::Start(self.query.clone()),
+        )?;
+
+        let delta = if is_append_only(query) {
+            let Snapshot { rows, .. } = evaluate_appended(query, updated_from, state)?;
+            let mut inserted = self.empty.clone();
+            rows.into_iter().for_each(|(_, row)| inserted.extend(row));
+            self.delta(
+                inserted,
+                self.empty.clone(),
+                self.empty.clone(),
+                self.empty.clone(),
+            )
+        } else {
+            let changed = if invalidated {
+                None
+            } else {
+                look_up_query_changes(query, &changes, &self.results, state)?
+            };
+            match changed {
+                Some(changed) => self.apply_changes(changed),
+                None => {
+                    let Snapshot { rows, .. } = evaluate_query(query, state)?;
+                    self.replace_results(rows)
+                }
+            }
+        };
+
+        Ok(delta)
+    }
+
+    /// Replaces the known results with the `rows`, returning the changes if there are any
+    fn replace_results(
+        &mut self,
+        rows: Vec<(Vec<u8>, QueryOutputBatchBoxTuple)>,
+    ) -> Option<QueryResultsDelta> {
+        let mut changed = self
+            .results
+            .keys()
+            .map(|key| (key.clone(), None))
+            .collect::<BTreeMap<_, _>>();
+        changed.extend(rows.into_iter().map(|(key, row)| (key, Some(row))));
+
+        self.apply_changes(changed)
+    }
+
+    /// Applies the new rows of the `changed` items to the known results, returning the changes if there are any.
+    /// Items without a row are removed.
+    fn apply_changes(
+        &mut self,
+        changed: impl IntoIterator<Item = (Vec<u8>, Option<QueryOutputBatchBoxTuple>)>,
+    ) -> Option<QueryResultsDelta> {
+        let mut inserted = self.empty.clone();
+        let mut updated = self.empty.clone();
+        let mut replaced = self.empty.clone();
+        let mut removed = self.empty.clone();
+        for (key, row) in changed {
+            match (self.results.remove(&key), row) {
+                (None, Some(row)) => {
+                    inserted.extend(row.clone());
+                    self.results.insert(key, row);
+                }
+                (Some(old), Some(row)) => {
+                    if old != row {
+                        updated.extend(row.clone());
+                        replaced.extend(old);
+                    }
+                    self.results.insert(key, row);
+                }
+                (Some(old), None) => removed.extend(old),
+                (None, None) => {}
+            }
+        }
+
+        self.delta(inserted, updated, replaced, removed)
+    }
+
+    fn delta(
+        &self,
+        inserted: QueryOutputBatchBoxTuple,
+        updated: QueryOutputBatchBoxTuple,
+        replaced: QueryOutputBatchBoxTuple,
+        removed: QueryOutputBatchBoxTuple,
+    ) -> Option<QueryResultsDelta> {
+        if inserted.is_empty() && updated.is_empty() && removed.is_empty() {
+            return None;
+        }
+
+        Some(QueryResultsDelta {
+            height: NonZeroU64::new(self.height as u64).expect("Updated after a committed block"),
+            inserted,
+            updated,
+            replaced,
+            removed,
+        })
+    }
+}
+
+/// Whether the results of the query only grow with new blocks, e.g. because they are the blocks themselves
+fn is_append_only(query: &QueryBox) -> bool {
+    matches!(
+        query,
+        QueryBox::FindTransactions(_) | QueryBox::FindBlocks(_) | QueryBox::FindBlockHeaders(_)
+    )
+}
+
+/// Whether the change may affect the results of the query
+fn is_affected_by(query: &QueryBox, change: &Change) -> bool {
+    match query {
+        QueryBox::FindDomains(q) => q.query.changed_id(change).is_some(),
+        QueryBox::FindAccounts(q) => q.query.changed_id(change).is_some() || change.is_cascading(),
+        QueryBox::FindAssets(q) => q.query.changed_id(change).is_some() || change.is_cascading(),
+        QueryBox::FindAssetsDefinitions(q) => {
+            q.query.changed_id(change).is_some() || change.is_cascading()
+        }
+        QueryBox::FindNfts(q) => q.query.changed_id(change).is_some() || change.is_cascading(),
+        QueryBox::FindAccountsWithAsset(q) => {
+            q.query.changed_id(change).is_some() || change.is_cascading()
+        }
+        QueryBox::FindRoles(q) => q.query.changed_id(change).is_some(),
+        QueryBox::FindRoleIds(q) => q.query.changed_id(change).is_some(),
+        QueryBox::FindPeers(q) => q.query.changed_id(change).is_some(),
+        QueryBox::FindActiveTriggerIds(q) => {
+            q.query.changed_id(change).is_some() || change.is_cascading()
+        }
+        QueryBox::FindTriggers(q) => q.query.changed_id(change).is_some() || change.is_cascading(),
+        QueryBox::FindPermissionsByAccountId(q) => match change {
+            Change::Account(id) | Change::AccountDeleted(id) => *id == q.query.id,
+            Change::DomainDeleted(domain) => q.query.id.domain == *domain,
+            _ => false,
+        },
+        QueryBox::FindRolesByAccountId(q) => match change {
+            Change::Account(id) | Change::AccountDeleted(id) => *id == q.query.id,
+            Change::DomainDeleted(domain) => q.query.id.domain == *domain,
+            // deleting a role revokes it from every account
+            Change::Role(_) => true,
+            _ => false,
+        },
+        QueryBox::FindTransactions(_) | QueryBox::FindBlocks(_) | QueryBox::FindBlockHeaders(_) => {
+            false
+        }
+    }
+}
+
+/// Looks up the items of the query affected by the `changes`,
+/// or returns [`None`] if the query has to be evaluated again in full
+fn look_up_query_changes(
+    query: &QueryBox,
+    changes: &[Change],
+    known: &BTreeMap<Vec<u8>, QueryOutputBatchBoxTuple>,
+    state: &impl StateReadOnly,
+) -> Result<Option<Vec<(Vec<u8>, Option<QueryOutputBatchBoxTuple>)>>, QueryExecutionFail> {
+    let changed = match query {
+        QueryBox::FindDomains(q) => look_up_changes(q, changes, known, state)?,
+        QueryBox::FindAccounts(q) => look_up_changes(q, changes, known, state)?,
+        QueryBox::FindAssets(q) => look_up_changes(q, changes, known, state)?,
+        QueryBox::FindAssetsDefinitions(q) => look_up_changes(q, changes, known, state)?,
+        QueryBox::FindNfts(q) => look_up_changes(q, changes, known, state)?,
+        QueryBox::FindAccountsWithAsset(q) => look_up_changes(q, changes, known, state)?,
+        QueryBox::FindRoles(q) => look_up_changes(q, changes, known, state)?,
+        QueryBox::FindRoleIds(q) => look_up_changes(q, changes, known, state)?,
+        QueryBox::FindPeers(q) => look_up_changes(q, changes, known, state)?,
+        QueryBox::FindActiveTriggerIds(q) => look_up_changes(q, changes, known, state)?,
+        QueryBox::FindTriggers(q) => look_up_changes(q, changes, known, state)?,
+        // the permissions and roles of a single account are few, so they are evaluated again in full
+        QueryBox::FindPermissionsByAccountId(_)
+        | QueryBox::FindRolesByAccountId(_)
+        | QueryBox::FindTransactions(_)
+        | QueryBox::FindBlocks(_)
+        | QueryBox::FindBlockHeaders(_) => return Ok(None),
+    };
+
+    Ok(Some(changed))
+}
+
+/// Evaluates the query over the blocks committed after the given height only, most recent first
+fn evaluate_appended(
+    query: &QueryBox,
+    height: usize,
+    state: &impl StateReadOnly,
+) -> Result<Snapshot, QueryExecutionFail> {
+    let from = NonZeroUsize::new(height + 1).expect("Incremented height is never zero");
+    match query {
+        QueryBox::FindTransactions(q) => Snapshot::evaluate(
+            state
+                .all_blocks(from)
+                .rev()
+                .flat_map(|block| committed_transactions(&block))
+                .filter(|tx| q.predicate.applies(tx)),
+            &q.selector,
+        ),
+        QueryBox::FindBlocks(q) => Snapshot::evaluate(
+            state
+                .all_blocks(from)
+                .rev()
+                .filter(|block| q.predicate.applies(block))
+                .map(|block| (*block).clone()),
+            &q.selector,
+        ),
+        QueryBox::FindBlockHeaders(q) => Snapshot::evaluate(
+            state
+                .all_blocks(from)
+                .rev()
+                .map(|block| block.header())
+                .filter(|header| q.predicate.applies(header)),
+            &q.selector,
+        ),
+        _ => unreachable!("Only the queries over the blocks are append-only"),
+    }
+}
+
+#[allow(clippy::too_many_lines)] // not much we can do, we _need_ to list all the box types here
+fn evaluate_query(
+    query: &QueryBox,
+    state: &impl StateReadOnly,
+) -> Result<Snapshot, QueryExecutionFail> {
+    match query {
+        QueryBox::FindDomains(q) => {
+            check_subscribable(q.aggregate.is_none() && q.sort_by.is_empty())?;
+            Snapshot::evaluate(
+                ValidQuery::execute(q.query.clone(), q.predicate.clone(), state)?,
+                &q.selector,
+            )
+        }
+        QueryBox::FindAccounts(q) => {
+            check_subscribable(q.aggregate.is_none() && q.sort_by.is_empty())?;
+            Snapshot::evaluate(
+                ValidQuery::execute(q.query.clone(), q.predicate.clone(), state)?,
+                &q.selector,
+            )
+        }
+        QueryBox::FindAssets(q) => {
+            check_subscribable(q.aggregate.is_none() && q.sort_by.is_empty())?;
+            Snapshot::evaluate(
+                ValidQuery::execute(q.query.clone(), q.predicate.clone(), state)?,
+                &q.selector,
+            )
+        }
+        QueryBox::FindAssetsDefinitions(q) => {
+            check_subscribable(q.aggregate.is_none() && q.sort_by.is_empty())?;
+            Snapshot::evaluate(
+                ValidQuery::execute(q.query.clone(), q.predicate.clone(), state)?,
+                &q.selector,
+            )
+        }
+        QueryBox::FindNfts(q) => {
+            check_subscribable(q.aggregate.is_none() && q.sort_by.is_empty())?;
+            Snapshot::evaluate(
+                ValidQuery::execute(q.query.clone(), q.predicate.clone(), state)?,
+                &q.selector,
+            )
+        }
+        QueryBox::FindRoles(q) => {
+            check_subscribable(q.aggregate.is_none() && q.sort_by.is_empty())?;
+            Snapshot::evaluate(
+                ValidQuery::execute(q.query.clone(), q.predicate.clone(), state)?,
+                &q.selector,
+            )
+        }
+        QueryBox::FindRoleIds(q) => {
+            check_subscribable(q.aggregate.is_none() && q.sort_by.is_empty())?;
+            Snapshot::evaluate(
+                ValidQuery::execute(q.query.clone(), q.predicate.clone(), state)?,
+                &q.selector,
+            )
+        }
+        QueryBox::FindPermissionsByAccountId(q) => {
+            check_subscribable(q.aggregate.is_none() && q.sort_by.is_empty())?;
+            Snapshot::evaluate(
+                ValidQuery::execute(q.query.clone(), q.predicate.clone(), state)?,
+                &q.selector,
+            )
+        }
+        QueryBox::FindRolesByAccountId(q) => {
+            check_subscribable(q.aggregate.is_none() && q.sort_by.is_empty())?;
+            Snapshot::evaluate(
+                ValidQuery::execute(q.query.clone(), q.predicate.clone(), state)?,
+                &q.selector,
+            )
+        }
+        QueryBox::FindAccountsWithAsset(q) => {
+            check_subscribable(q.aggregate.is_none() && q.sort_by.is_empty())?;
+            Snapshot::evaluate(
+                ValidQuery::execute(q.query.clone(), q.predicate.clone(), state)?,
+                &q.selector,
+            )
+        }
+        QueryBox::FindPeers(q) => {
+            check_subscribable(q.aggregate.is_none() && q.sort_by.is_empty())?;
+            Snapshot::evaluate(
+                ValidQuery::execute(q.query.clone(), q.predicate.clone(), state)?,
+                &q.selector,
+            )
+        }
+        QueryBox::FindActiveTriggerIds(q) => {
+            check_subscribable(q.aggregate.is_none() && q.sort_by.is_empty())?;
+            Snapshot::evaluate(
+                ValidQuery::execute(q.query.clone(), q.predicate.clone(), state)?,
+                &q.selector,
+            )
+        }
+        QueryBox::FindTriggers(q) => {
+            check_subscribable(q.aggregate.is_none() && q.sort_by.is_empty())?;
+            Snapshot::evaluate(
+                ValidQuery::execute(q.query.clone(), q.predicate.clone(), state)?,
+                &q.selector,
+            )
+        }
+        QueryBox::FindTransactions(q) => {
+            check_subscribable(q.aggregate.is_none() && q.sort_by.is_empty())?;
+            Snapshot::evaluate(
+                ValidQuery::execute(q.query.clone(), q.predicate.clone(), state)?,
+                &q.selector,
+            )
+        }
+        QueryBox::FindBlocks(q) => {
+            check_subscribable(q.aggregate.is_none() && q.sort_by.is_empty())?;
+            consume_stored_blocks(
+                state,
+                ValidQuery::execute(q.query.clone(), q.predicate.clone(), state)?,
+                |blocks| Snapshot::evaluate(blocks, &q.selector),
+            )
+        }
+        QueryBox::FindBlockHeaders(q) => {
+            check_subscribable(q.aggregate.is_none() && q.sort_by.is_empty())?;
+            consume_stored_blocks(
+                state,
+                ValidQuery::execute(q.query.clone(), q.predicate.clone(), state)?,
+                |headers| Snapshot::evaluate(headers, &q.selector),
+            )
+        }
+    }
+}
+
+fn check_subscribable(plain: bool) -> Result<(), QueryExecutionFail> {
+    if plain {
+        Ok(())
+    } else {
+        // aggregated or sorted rows have no stable identity to be tracked by
+        Err(QueryExecutionFail::InvalidSubscriptionParameters)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use iroha_data_model::query::{parameters::QueryParams, QueryOutputBatchBox};
+    use iroha_primitives::json::Json;
+    use iroha_test_samples::{ALICE_ID, BOB_ID};
+    use nonzero_ext::nonzero;
+
+    use super::*;
+
+    fn asset(account: &AccountId, value: u32) -> Asset {
+        Asset::new(
+            AssetId::new("rose#wonderland".parse().unwrap(), account.clone()),
+            value,
+        )
+    }
+
+    fn assets(batch: &QueryOutputBatchBoxTuple) -> Vec<Asset> {
+        match batch.tuple.as_slice() {
+            [QueryOutputBatchBox::Asset(assets)] => assets.clone(),
+            other => panic!("Unexpected batch: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn delta_tells_changed_items_from_inserted_and_removed() {
+        let selector = SelectorTuple::<Asset>::default();
+        let Snapshot { empty, rows } = Snapshot::evaluate(
+            [asset(&ALICE_ID, 13), asset(&BOB_ID, 5)].into_iter(),
+            &selector,
+        )
+        .unwrap();
+        let query = QueryWithFilter::new(
+            FindAssets,
+            CompoundPredicate::PASS,
+            selector.clone(),
+            None,
+            Vec::new(),
+        );
+        let mut subscription = QuerySubscription {
+            authority: ALICE_ID.clone(),
+            query: QueryWithParams::new(query.into(), QueryParams::default()),
+            empty,
+            results: rows.into_iter().collect(),
+            height: 2,
+            changes: Vec::new(),
+            invalidated: false,
+        };
+
+        let Snapshot { rows, .. } = Snapshot::evaluate(
+            [asset(&ALICE_ID, 13), asset(&BOB_ID, 8)].into_iter(),
+            &selector,
+        )
+        .unwrap();
+        subscription.height = 3;
+        let delta = subscription.replace_results(rows).unwrap();
+        assert_eq!(delta.height, nonzero!(3_u64));
+        assert!(delta.inserted.is_empty() && delta.removed.is_empty());
+        assert_eq!(assets(&delta.updated), [asset(&BOB_ID, 8)]);
+        assert_eq!(assets(&delta.replaced), [asset(&BOB_ID, 5)]);
+
+        let Snapshot { rows, .. } =
+            Snapshot::evaluate([asset(&BOB_ID, 8)].into_iter(), &selector).unwrap();
+        subscription.height = 4;
+        let delta = subscription.replace_results(rows).unwrap();
+        assert!(delta.inserted.is_empty() && delta.updated.is_empty());
+        assert_eq!(assets(&delta.removed), [asset(&ALICE_ID, 13)]);
+
+        let Snapshot { rows, .. } =
+            Snapshot::evaluate([asset(&BOB_ID, 8)].into_iter(), &selector).unwrap();
+        assert!(subscription.replace_results(rows).is_none());
+    }
+
+    #[test]
+    fn only_changes_affecting_the_query_are_tracked() {
+        let query = QueryWithFilter::new(
+            FindAssets,
+            CompoundPredicate::PASS,
+            SelectorTuple::default(),
+            None,
+            Vec::new(),
+        );
+        let mut subscription = QuerySubscription {
+            authority: ALICE_ID.clone(),
+            query: QueryWithParams::new(query.into(), QueryParams::default()),
+            empty: QueryOutputBatchBoxTuple::new(vec![QueryOutputBatchBox::Asset(Vec::new())]),
+            results: BTreeMap::new(),
+            height: 2,
+            changes: Vec::new(),
+            invalidated: false,
+        };
+        let rose_of_bob = asset(&BOB_ID, 0).id().clone();
+
+        let events: [DataEvent; 4] = [
+            AccountEvent::Asset(AssetEvent::Frozen(rose_of_bob.clone())).into(),
+            AssetDefinitionEvent::MintabilityChanged(rose_of_bob.definition.clone()).into(),
+            AccountEvent::MetadataRemoved(MetadataChanged {
+                target: ALICE_ID.clone(),
+                key: "key".parse().unwrap(),
+                value: Json::new(1_u32),
+            })
+            .into(),
+            AccountEvent::Deleted(ALICE_ID.clone()).into(),
+        ];
+        for event in events {
+            subscription.observe(&EventBox::Data(event));
+        }
+
+        assert!(matches!(
+            subscription.changes.as_slice(),
+            [Change::Asset(asset), Change::AccountDeleted(account)]
+                if *asset == rose_of_bob && account == &*ALICE_ID
+        ));
+    }
+
+    #[test]
+    fn account_sequence_changes_are_tracked() {
+        let query = QueryWithFilter::new(
+            FindAccounts,
+            CompoundPredicate::PASS,
+            SelectorTuple::default(),
+            None,
+            Vec::new(),
+        );
+        let mut subscription = QuerySubscription {
+            authority: ALICE_ID.clone(),
+            query: QueryWithParams::new(query.into(), QueryParams::default()),
+            empty: QueryOutputBatchBoxTuple::new(vec![QueryOutputBatchBox::Account(Vec::new())]),
+            results: BTreeMap::new(),
+            height: 2,
+            changes: Vec::new(),
+            invalidated: false,
+        };
+
+        let event: DataEvent = AccountEvent::SequenceChanged(AccountSequenceChanged {
+            account: ALICE_ID.clone(),
+            sequence: 1,
+        })
+        .into();
+        subscription.observe(&EventBox::Data(event));
+
+        assert!(matches!(
+            subscription.changes.as_slice(),
+            [Change::Account(account)] if account == &*ALICE_ID
+        ));
+    }
+}
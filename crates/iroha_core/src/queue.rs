@@ -1,20 +1,30 @@
 //! Module with queue actor
-use core::time::Duration;
-use std::{num::NonZeroUsize, ops::Deref, sync::Arc};
+use core::{cmp::Reverse, time::Duration};
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap},
+    num::{NonZeroU64, NonZeroUsize},
+    ops::Deref,
+    sync::Arc,
+};
 
 use crossbeam_queue::ArrayQueue;
 use dashmap::{mapref::entry::Entry, DashMap};
 use eyre::Result;
 use indexmap::IndexSet;
-use iroha_config::parameters::actual::Queue as Config;
+use iroha_config::{parameters::actual::Queue as Config, queue::Ordering};
 use iroha_crypto::HashOf;
 use iroha_data_model::{
     account::AccountId,
     events::pipeline::{TransactionEvent, TransactionStatus},
+    name::Name,
+    permission::Permission,
     transaction::prelude::*,
 };
+use iroha_executor_data_model::permission::transaction::CanPrioritizeTransactions;
 use iroha_logger::{trace, warn};
 use iroha_primitives::time::TimeSource;
+use mv::storage::StorageReadOnly;
+use parking_lot::Mutex;
 use thiserror::Error;
 
 use crate::{prelude::*, state::StateReadOnlyWithTransactions, EventsSender};
@@ -39,6 +49,8 @@ pub struct Queue {
     txs: DashMap<HashOf<SignedTransaction>, AcceptedTransaction>,
     /// Amount of transactions per user in the queue
     txs_per_user: DashMap<AccountId, usize>,
+    /// Hashes of the transactions with a sequence number, used to replace them with newer ones
+    txs_by_sequence: DashMap<(AccountId, NonZeroU64), HashOf<SignedTransaction>>,
     /// The maximum number of transactions in the queue
     capacity: NonZeroUsize,
     /// The maximum number of transactions in the queue per user. Used to apply throttling
@@ -51,6 +63,13 @@ pub struct Queue {
     pub tx_time_to_live: Duration,
     /// Queue to gossip transactions
     tx_gossip: ArrayQueue<HashOf<SignedTransaction>>,
+    /// Order in which transactions are taken into blocks
+    ordering: Ordering,
+    /// Key of the transaction metadata holding its priority
+    priority_key: Name,
+    /// Transactions waiting in [`Self::tx_hashes`] indexed to be evicted by the higher ranked ones
+    /// when the queue is full, `None` if eviction is disabled
+    eviction: Option<Mutex<EvictionIndex>>,
 }
 
 /// Queue push error
@@ -67,6 +86,10 @@ pub enum Error {
     MaximumTransactionsPerUser,
     /// The transaction is already in the queue
     IsInQueue,
+    /// A newer transaction with the same sequence number is already in the queue
+    Superseded,
+    /// Sequence number is already used by a committed transaction
+    SequenceUsed,
 }
 
 /// Failure that can pop up when pushing transaction into the queue
@@ -106,19 +129,26 @@ impl Queue {
             capacity,
             capacity_per_user,
             transaction_time_to_live,
+            ordering,
+            priority_key,
+            evict_when_full,
         }: Config,
         events_sender: EventsSender,
     ) -> Self {
         Self {
             events_sender,
-            tx_hashes: ArrayQueue::new(capacity.get()),
+            tx_hashes: ArrayQueue::new(tx_hashes_capacity(capacity, evict_when_full)),
             txs: DashMap::new(),
             txs_per_user: DashMap::new(),
+            txs_by_sequence: DashMap::new(),
             capacity,
             capacity_per_user,
             time_source: TimeSource::new_system(),
             tx_time_to_live: transaction_time_to_live,
             tx_gossip: ArrayQueue::new(capacity.get()),
+            ordering,
+            priority_key,
+            eviction: evict_when_full.then(Mutex::default),
         }
     }
 
@@ -176,6 +206,12 @@ impl Queue {
             Err(Error::InBlockchain)
         } else if self.is_expired(tx) {
             Err(Error::Expired)
+        } else if tx
+            .as_ref()
+            .sequence()
+            .is_some_and(|sequence| sequence.get() <= committed_sequence(tx, state_view))
+        {
+            Err(Error::SequenceUsed)
         } else {
             Ok(())
         }
@@ -183,6 +219,11 @@ impl Queue {
 
     /// Push transaction into queue.
     ///
+    /// A pending transaction with the same authority and sequence number is replaced
+    /// if the new transaction is created later.
+    /// Only one of them can be committed, so the replaced transaction is rejected
+    /// even if it's still pending on other peers when the new one is committed.
+    ///
     /// # Errors
     /// See [`enum@Error`]
     pub fn push(&self, tx: AcceptedTransaction, state_view: StateView) -> Result<(), Failure> {
@@ -190,11 +231,18 @@ impl Queue {
         if let Err(err) = self.check_tx(&tx, &state_view) {
             return Err(Failure { tx: tx.into(), err });
         }
+        let priority = self.priority(&tx, &state_view);
         drop(state_view);
+        if let Err(err) = self.replace_pending(&tx) {
+            return Err(Failure { tx: tx.into(), err });
+        }
 
-        // Get `txs_len` before entry to avoid deadlock
+        // Get `txs_len` and evict before entry to avoid deadlock
         let txs_len = self.txs.len();
         let hash = tx.as_ref().hash();
+        let evicted = txs_len >= self.capacity.get()
+            && !self.txs.contains_key(&hash)
+            && self.evict_lower_ranked(&tx, priority);
         let entry = match self.txs.entry(hash) {
             Entry::Occupied(_) => {
                 return Err(Failure {
@@ -205,7 +253,7 @@ impl Queue {
             Entry::Vacant(entry) => entry,
         };
 
-        if txs_len >= self.capacity.get() {
+        if txs_len >= self.capacity.get() && !evicted {
             warn!(
                 max = self.capacity,
                 "Achieved maximum amount of transactions"
@@ -221,14 +269,21 @@ impl Queue {
         }
 
         // Insert entry first so that the `tx` popped from `queue` will always have a `(hash, tx)` record in `txs`.
-        entry.insert(tx);
+        let sequence_key = tx
+            .as_ref()
+            .sequence()
+            .map(|sequence| (tx.as_ref().authority().clone(), sequence));
+        self.index_for_eviction(entry.insert(tx).value(), priority);
+        if let Some(sequence_key) = sequence_key {
+            self.txs_by_sequence.insert(sequence_key, hash);
+        }
         self.tx_hashes.push(hash).map_err(|err_hash| {
             warn!("Queue is full");
             let (_, err_tx) = self
                 .txs
                 .remove(&err_hash)
                 .expect("Inserted just before match");
-            self.decrease_per_user_tx_count(err_tx.as_ref().authority());
+            self.forget(&err_tx);
             Failure {
                 tx: err_tx.into(),
                 err: Error::Full,
@@ -249,12 +304,57 @@ impl Queue {
         Ok(())
     }
 
+    /// Remove the pending transaction with the same authority and sequence number as `tx`.
+    ///
+    /// # Errors
+    /// Fails if the pending transaction is created later than `tx`
+    fn replace_pending(&self, tx: &AcceptedTransaction) -> Result<(), Error> {
+        let Some(sequence) = tx.as_ref().sequence() else {
+            return Ok(());
+        };
+        let hash = tx.as_ref().hash();
+        let Some(pending_hash) = self
+            .txs_by_sequence
+            .get(&(tx.as_ref().authority().clone(), sequence))
+            .map(|pending_hash| *pending_hash)
+            .filter(|pending_hash| *pending_hash != hash)
+        else {
+            return Ok(());
+        };
+        let Some(pending_creation_time) = self
+            .txs
+            .get(&pending_hash)
+            .map(|pending| pending.as_ref().creation_time())
+        else {
+            return Ok(());
+        };
+        if pending_creation_time >= tx.as_ref().creation_time() {
+            return Err(Error::Superseded);
+        }
+
+        if let Some((_, pending)) = self.txs.remove(&pending_hash) {
+            trace!(tx=%pending_hash, by=%hash, "Replacing pending transaction");
+            self.forget(&pending);
+            let _ = self.events_sender.send(
+                TransactionEvent {
+                    hash: pending_hash,
+                    block_height: None,
+                    status: TransactionStatus::Expired,
+                }
+                .into(),
+            );
+        }
+        Ok(())
+    }
+
     /// Pop single transaction from the queue. Removes all transactions that fail the `tx_check`.
+    ///
+    /// The transaction isn't guarded and has to be either taken into a block or put back with [`Self::requeue`].
     fn pop_from_queue(
-        self: &Arc<Self>,
+        &self,
         state_view: &StateView,
         expired_transactions: &mut Vec<AcceptedTransaction>,
-    ) -> Option<TransactionGuard> {
+    ) -> Option<AcceptedTransaction> {
         loop {
             let hash = self.tx_hashes.pop()?;
 
@@ -263,27 +363,32 @@ impl Queue {
                 // FIXME: Reachable under high load. Investigate, see if it's a problem.
                 // As practice shows this code is not `unreachable!()`.
                 // When transactions are submitted quickly it can be reached.
+                // Also reached for evicted transactions.
                 Entry::Vacant(_) => {
-                    warn!("Looks like we're experiencing a high load");
+                    if self.eviction.is_none() {
+                        warn!("Looks like we're experiencing a high load");
+                    }
                     continue;
                 }
             };
+            if let Some(eviction) = &self.eviction {
+                // The hash is left behind by the transaction evicted and then pushed again
+                if !eviction.lock().remove(&hash) {
+                    continue;
+                }
+            }
 
             let tx = entry.get();
             if let Err(e) = self.check_tx(tx, state_view) {
                 let (_, tx) = entry.remove_entry();
-                self.decrease_per_user_tx_count(tx.as_ref().authority());
-                if let Error::Expired = e {
+                self.forget(&tx);
+                if let Error::Expired | Error::SequenceUsed = e {
                     expired_transactions.push(tx);
                 }
                 continue;
             }
 
-            let guard = TransactionGuard {
-                tx: tx.clone(),
-                queue: Arc::clone(self),
-            };
-            return Some(guard);
+            return Some(tx.clone());
         }
     }
 
@@ -321,15 +426,61 @@ impl Queue {
 
         let mut expired_transactions = Vec::new();
 
-        let txs_from_queue =
-            core::iter::from_fn(|| self.pop_from_queue(state_view, &mut expired_transactions));
-
         let transactions_hashes: IndexSet<HashOf<SignedTransaction>> =
             transactions.iter().map(|tx| tx.as_ref().hash()).collect();
-        let txs = txs_from_queue
-            .filter(|tx| !transactions_hashes.contains(&tx.as_ref().hash()))
-            .take(max_txs_in_block.get() - transactions.len());
-        transactions.extend(txs);
+        let mut gate = SequenceGate::new(state_view, transactions);
+        if let Ordering::Fifo = self.ordering {
+            while transactions.len() < max_txs_in_block.get() {
+                let Some(tx) = self.pop_from_queue(state_view, &mut expired_transactions) else {
+                    break;
+                };
+                if transactions_hashes.contains(&tx.as_ref().hash()) {
+                    continue;
+                }
+                for tx in gate.admit(tx) {
+                    if transactions.len() < max_txs_in_block.get() {
+                        transactions.push(TransactionGuard {
+                            tx,
+                            queue: Arc::clone(self),
+                        });
+                    } else {
+                        self.requeue(&tx, state_view);
+                    }
+                }
+            }
+            for tx in gate.into_held() {
+                self.requeue(&tx, state_view);
+            }
+        } else {
+            // The whole queue has to be seen to pick transactions ranked the highest
+            let txs_from_queue =
+                core::iter::from_fn(|| self.pop_from_queue(state_view, &mut expired_transactions));
+            let txs = txs_from_queue
+                .filter(|tx| !transactions_hashes.contains(&tx.as_ref().hash()))
+                .collect::<Vec<_>>();
+            let order = self.rank(&txs, state_view);
+            let mut txs = txs.into_iter().map(Some).collect::<Vec<_>>();
+            let mut rest = Vec::new();
+            for idx in order {
+                let tx = txs[idx].take().expect("each index is ranked once");
+                for tx in gate.admit(tx) {
+                    if transactions.len() < max_txs_in_block.get() {
+                        transactions.push(TransactionGuard {
+                            tx,
+                            queue: Arc::clone(self),
+                        });
+                    } else {
+                        rest.push(tx);
+                    }
+                }
+            }
+            // The rest keeps the order of arrival
+            rest.extend(gate.into_held());
+            rest.sort_by_key(|tx| tx.as_ref().creation_time());
+            for tx in rest {
+                self.requeue(&tx, state_view);
+            }
+        }
 
         expired_transactions
             .into_iter()
@@ -343,6 +494,103 @@ impl Queue {
             });
     }
 
+    /// Indices of `txs` in the order they should be taken into a block according to [`Self::ordering`].
+    ///
+    /// `txs` are expected to be in the order of their arrival.
+    fn rank(&self, txs: &[AcceptedTransaction], state_view: &StateView) -> Vec<usize> {
+        let mut order = (0..txs.len()).collect::<Vec<_>>();
+        match self.ordering {
+            Ordering::Fifo => {}
+            Ordering::RoundRobin => {
+                // Number of transactions of the same authority which arrived earlier
+                let mut authority_txs = HashMap::<&AccountId, usize>::new();
+                let turns = txs
+                    .iter()
+                    .map(|tx| {
+                        let count = authority_txs.entry(tx.as_ref().authority()).or_default();
+                        *count += 1;
+                        *count
+                    })
+                    .collect::<Vec<_>>();
+                order.sort_by_key(|&idx| turns[idx]);
+            }
+            Ordering::Priority => {
+                let priorities = txs
+                    .iter()
+                    .map(|tx| self.priority(tx, state_view))
+                    .collect::<Vec<_>>();
+                order.sort_by_key(|&idx| Reverse(priorities[idx]));
+            }
+        }
+        order
+    }
+
+    /// Priority of the transaction taken from its metadata.
+    ///
+    /// It's `0` if the priority is missing or malformed
+    /// or if the authority isn't granted `CanPrioritizeTransactions`.
+    fn priority(&self, tx: &AcceptedTransaction, state_view: &StateView) -> u64 {
+        tx.as_ref()
+            .metadata()
+            .get(&self.priority_key)
+            .and_then(|priority| priority.try_into_any::<u64>().ok())
+            .filter(|_| can_prioritize(tx.as_ref().authority(), state_view))
+            .unwrap_or(0)
+    }
+
+    /// Remove the waiting transaction ranked lower than `tx` if there is one.
+    ///
+    /// Returns `true` if a transaction was evicted.
+    fn evict_lower_ranked(&self, tx: &AcceptedTransaction, priority: u64) -> bool {
+        let Some(eviction) = &self.eviction else {
+            return false;
+        };
+        // Hashes of the evicted transactions stay in `tx_hashes` until they are popped
+        if self.tx_hashes.is_full() {
+            return false;
+        }
+        let victim = eviction
+            .lock()
+            .evict(self.ordering, tx.as_ref().authority(), priority);
+        let Some((hash, victim)) = victim.and_then(|hash| self.txs.remove(&hash)) else {
+            return false;
+        };
+        trace!(tx=%hash, "Evicting transaction from the full queue");
+        self.forget(&victim);
+        let _ = self.events_sender.send(
+            TransactionEvent {
+                hash,
+                block_height: None,
+                status: TransactionStatus::Evicted,
+            }
+            .into(),
+        );
+        true
+    }
+
+    /// Make the transaction waiting in [`Self::tx_hashes`] a candidate for eviction.
+    fn index_for_eviction(&self, tx: &AcceptedTransaction, priority: u64) {
+        if let Some(eviction) = &self.eviction {
+            eviction.lock().insert(
+                tx.as_ref().hash(),
+                tx.as_ref().authority(),
+                priority,
+                tx.as_ref().creation_time(),
+            );
+        }
+    }
+
+    /// Put the transaction popped from [`Self::tx_hashes`] back without taking it into a block.
+    fn requeue(&self, tx: &AcceptedTransaction, state_view: &StateView) {
+        self.index_for_eviction(tx, self.priority(tx, state_view));
+        if let Err(hash) = self.tx_hashes.push(tx.as_ref().hash()) {
+            warn!(tx=%hash, "Queue is full, dropping transaction");
+            if let Some((_, tx)) = self.txs.remove(&hash) {
+                self.forget(&tx);
+            }
+        }
+    }
+
     /// Overview:
     /// 1. Transaction is added to queue using [`Queue::push`] method.
     /// 2. Transaction is moved to [`Sumeragi::transaction_cache`] using [`Queue::pop_from_queue`] method.
@@ -354,7 +602,7 @@ impl Queue {
     fn remove_transaction(&self, tx: &AcceptedTransaction) {
         let removed = self.txs.remove(&tx.as_ref().hash());
         if removed.is_some() {
-            self.decrease_per_user_tx_count(tx.as_ref().authority());
+            self.forget(tx);
         }
     }
 
@@ -381,6 +629,21 @@ impl Queue {
         Ok(())
     }
 
+    /// Update bookkeeping for the transaction removed from [`Self::txs`].
+    fn forget(&self, tx: &AcceptedTransaction) {
+        self.decrease_per_user_tx_count(tx.as_ref().authority());
+        let hash = tx.as_ref().hash();
+        if let Some(sequence) = tx.as_ref().sequence() {
+            self.txs_by_sequence.remove_if(
+                &(tx.as_ref().authority().clone(), sequence),
+                |_, pending_hash| *pending_hash == hash,
+            );
+        }
+        if let Some(eviction) = &self.eviction {
+            eviction.lock().remove(&hash);
+        }
+    }
+
     fn decrease_per_user_tx_count(&self, account_id: &AccountId) {
         let Entry::Occupied(mut occupied) = self.txs_per_user.entry(account_id.clone()) else {
             panic!("Call to decrease always should be paired with increase count. This is a bug.")
@@ -395,11 +658,230 @@ impl Queue {
     }
 }
 
+/// Capacity of [`Queue::tx_hashes`].
+///
+/// Hashes of the evicted transactions stay there until they are popped,
+/// so with eviction it has room for as many of them as there are transactions.
+fn tx_hashes_capacity(capacity: NonZeroUsize, evict_when_full: bool) -> usize {
+    if evict_when_full {
+        capacity.get().saturating_mul(2)
+    } else {
+        capacity.get()
+    }
+}
+
+/// Check if the `authority` is granted `CanPrioritizeTransactions` directly or through a role.
+fn can_prioritize(authority: &AccountId, state_view: &StateView) -> bool {
+    let permission: Permission = CanPrioritizeTransactions.into();
+    let world = state_view.world();
+    world.account_contains_inherent_permission(authority, &permission)
+        || world.account_roles_iter(authority).any(|role_id| {
+            world
+                .roles()
+                .get(role_id)
+                .is_some_and(|role| role.permissions().any(|granted| *granted == permission))
+        })
+}
+
+/// Transactions waiting in [`Queue::tx_hashes`] ordered the way they are evicted.
+#[derive(Debug, Default)]
+struct EvictionIndex {
+    /// From the lowest priority, the latest transaction first among equal ones
+    by_priority: BTreeSet<(u64, Reverse<Duration>, HashOf<SignedTransaction>)>,
+    /// Transactions of each authority in the order of creation
+    by_authority: HashMap<AccountId, BTreeSet<(Duration, HashOf<SignedTransaction>)>>,
+    /// Authorities by the number of their transactions
+    by_count: BTreeSet<(usize, AccountId)>,
+    /// Positions of the indexed transactions
+    keys: HashMap<HashOf<SignedTransaction>, EvictionKey>,
+}
+
+/// Position of the transaction in [`EvictionIndex`].
+#[derive(Debug)]
+struct EvictionKey {
+    authority: AccountId,
+    priority: u64,
+    creation_time: Duration,
+}
+
+impl EvictionIndex {
+    fn insert(
+        &mut self,
+        hash: HashOf<SignedTransaction>,
+        authority: &AccountId,
+        priority: u64,
+        creation_time: Duration,
+    ) {
+        if self.keys.contains_key(&hash) {
+            return;
+        }
+        self.by_priority
+            .insert((priority, Reverse(creation_time), hash));
+        let txs = self.by_authority.entry(authority.clone()).or_default();
+        self.by_count.remove(&(txs.len(), authority.clone()));
+        txs.insert((creation_time, hash));
+        self.by_count.insert((txs.len(), authority.clone()));
+        self.keys.insert(
+            hash,
+            EvictionKey {
+                authority: authority.clone(),
+                priority,
+                creation_time,
+            },
+        );
+    }
+
+    /// Returns `false` if the transaction isn't indexed.
+    fn remove(&mut self, hash: &HashOf<SignedTransaction>) -> bool {
+        let Some(key) = self.keys.remove(hash) else {
+            return false;
+        };
+        self.by_priority
+            .remove(&(key.priority, Reverse(key.creation_time), *hash));
+        if let Some(txs) = self.by_authority.get_mut(&key.authority) {
+            self.by_count.remove(&(txs.len(), key.authority.clone()));
+            txs.remove(&(key.creation_time, *hash));
+            if txs.is_empty() {
+                self.by_authority.remove(&key.authority);
+            } else {
+                self.by_count.insert((txs.len(), key.authority));
+            }
+        }
+        true
+    }
+
+    /// Remove and return the transaction ranked by `ordering` lower than
+    /// a new transaction of `authority` with `priority`.
+    fn evict(
+        &mut self,
+        ordering: Ordering,
+        authority: &AccountId,
+        priority: u64,
+    ) -> Option<HashOf<SignedTransaction>> {
+        let victim = match ordering {
+            Ordering::Fifo => None,
+            Ordering::RoundRobin => {
+                let own_txs = self.by_authority.get(authority).map_or(0, BTreeSet::len);
+                // Authority with the most transactions gives up the latest one
+                self.by_count
+                    .last()
+                    .filter(|(count, _)| *count > own_txs + 1)
+                    .and_then(|(_, busiest)| self.by_authority.get(busiest))
+                    .and_then(BTreeSet::last)
+                    .map(|(_, hash)| *hash)
+            }
+            Ordering::Priority => self
+                .by_priority
+                .first()
+                .filter(|(victim_priority, ..)| *victim_priority < priority)
+                .map(|(.., hash)| *hash),
+        }?;
+        self.remove(&victim);
+        Some(victim)
+    }
+}
+
+/// Sequence number of the latest committed transaction of the `tx` authority.
+fn committed_sequence(tx: &AcceptedTransaction, state_view: &StateView) -> u64 {
+    state_view
+        .world()
+        .account(tx.as_ref().authority())
+        .map_or(0, |account| account.sequence())
+}
+
+/// Holds back transactions whose sequence number doesn't directly follow
+/// the sequence number of the previous transaction of the same authority.
+struct SequenceGate<'view, 'state> {
+    state_view: &'view StateView<'state>,
+    /// Sequence number expected next from each authority
+    next: HashMap<AccountId, u64>,
+    /// Transactions waiting for the missing sequence numbers of their authority,
+    /// as well as the ones duplicating the sequence number of a taken transaction,
+    /// each with the number of transactions held before it
+    held: HashMap<AccountId, BTreeMap<u64, Vec<(usize, AcceptedTransaction)>>>,
+    /// Number of transactions held so far
+    arrivals: usize,
+}
+
+impl<'view, 'state> SequenceGate<'view, 'state> {
+    /// Create gate, taking into account sequence numbers of the already taken `transactions`.
+    fn new(state_view: &'view StateView<'state>, transactions: &[TransactionGuard]) -> Self {
+        let mut gate = Self {
+            state_view,
+            next: HashMap::new(),
+            held: HashMap::new(),
+            arrivals: 0,
+        };
+        for tx in transactions {
+            if let Some(sequence) = tx.as_ref().sequence() {
+                let next = gate.next(tx);
+                gate.next.insert(
+                    tx.as_ref().authority().clone(),
+                    next.max(sequence.get().saturating_add(1)),
+                );
+            }
+        }
+        gate
+    }
+
+    fn next(&self, tx: &AcceptedTransaction) -> u64 {
+        self.next.get(tx.as_ref().authority()).map_or_else(
+            || committed_sequence(tx, self.state_view).saturating_add(1),
+            |next| *next,
+        )
+    }
+
+    /// Pass `tx` through the gate.
+    ///
+    /// Returns transactions which can be taken into the block in the order they have to be taken.
+    /// Those are `tx` itself followed by the held transactions of the same authority it unblocks.
+    fn admit(&mut self, tx: AcceptedTransaction) -> Vec<AcceptedTransaction> {
+        let Some(sequence) = tx.as_ref().sequence() else {
+            return vec![tx];
+        };
+        let mut next = self.next(&tx);
+        let authority = tx.as_ref().authority().clone();
+        let held = self.held.entry(authority.clone()).or_default();
+        if sequence.get() != next {
+            held.entry(sequence.get())
+                .or_default()
+                .push((self.arrivals, tx));
+            self.arrivals += 1;
+            return Vec::new();
+        }
+
+        let mut ready = vec![tx];
+        next = next.saturating_add(1);
+        while let Some(mut txs) = held.remove(&next) {
+            ready.push(txs.remove(0).1);
+            if !txs.is_empty() {
+                // Duplicates wait in case the taken transaction is rejected
+                held.insert(next, txs);
+            }
+            next = next.saturating_add(1);
+        }
+        self.next.insert(authority, next);
+        ready
+    }
+
+    /// Transactions which are still held back in the order they arrived at the gate.
+    fn into_held(self) -> impl Iterator<Item = AcceptedTransaction> {
+        let mut held = self
+            .held
+            .into_values()
+            .flat_map(BTreeMap::into_values)
+            .flatten()
+            .collect::<Vec<_>>();
+        held.sort_unstable_by_key(|(arrival, _)| *arrival);
+        held.into_iter().map(|(_, tx)| tx)
+    }
+}
+
 #[cfg(test)]
 // this is `pub` to re-use internal utils
 #[allow(missing_docs)]
 pub mod tests {
-    use std::{sync::Arc, thread, time::Duration};
+    use std::{num::NonZeroU64, sync::Arc, thread, time::Duration};
 
     use iroha_data_model::{parameter::TransactionParameters, prelude::*};
     use iroha_test_samples::gen_account_in;
@@ -419,14 +901,18 @@ pub mod tests {
         pub fn test(cfg: Config, time_source: &TimeSource) -> Self {
             Self {
                 events_sender: tokio::sync::broadcast::Sender::new(1),
-                tx_hashes: ArrayQueue::new(cfg.capacity.get()),
+                tx_hashes: ArrayQueue::new(tx_hashes_capacity(cfg.capacity, cfg.evict_when_full)),
                 tx_gossip: ArrayQueue::new(cfg.capacity.get()),
                 txs: DashMap::new(),
                 txs_per_user: DashMap::new(),
+                txs_by_sequence: DashMap::new(),
                 capacity: cfg.capacity,
                 capacity_per_user: cfg.capacity_per_user,
                 time_source: time_source.clone(),
                 tx_time_to_live: cfg.transaction_time_to_live,
+                ordering: cfg.ordering,
+                priority_key: cfg.priority_key,
+                eviction: cfg.evict_when_full.then(Mutex::default),
             }
         }
     }
@@ -441,15 +927,56 @@ pub mod tests {
         key_pair: &KeyPair,
         time_source: &TimeSource,
     ) -> AcceptedTransaction {
+        accepted_tx_with(account_id, key_pair, time_source, Metadata::default())
+    }
+
+    fn accepted_tx_with_priority(
+        account_id: AccountId,
+        key_pair: &KeyPair,
+        time_source: &TimeSource,
+        priority: u64,
+    ) -> AcceptedTransaction {
+        let mut metadata = Metadata::default();
+        metadata.insert("priority".parse().unwrap(), priority);
+        accepted_tx_with(account_id, key_pair, time_source, metadata)
+    }
+
+    fn accepted_tx_with(
+        account_id: AccountId,
+        key_pair: &KeyPair,
+        time_source: &TimeSource,
+        metadata: Metadata,
+    ) -> AcceptedTransaction {
+        accept(
+            tx_builder(account_id, time_source).with_metadata(metadata),
+            key_pair,
+        )
+    }
+
+    fn accepted_tx_with_sequence(
+        account_id: AccountId,
+        key_pair: &KeyPair,
+        time_source: &TimeSource,
+        sequence: NonZeroU64,
+    ) -> AcceptedTransaction {
+        let mut tx_builder = tx_builder(account_id, time_source);
+        tx_builder.set_sequence(sequence);
+        accept(tx_builder, key_pair)
+    }
+
+    fn tx_builder(account_id: AccountId, time_source: &TimeSource) -> TransactionBuilder {
         let chain_id = ChainId::from("00000000-0000-0000-0000-000000000000");
         // Random name needed so all transactions will be different
         let domain_name = format!("dummy{}", rand::random::<u64>());
         let fail_isi = Unregister::domain(domain_name.parse().unwrap());
         let instructions = [fail_isi];
-        let tx =
-            TransactionBuilder::new_with_time_source(chain_id.clone(), account_id, time_source)
-                .with_instructions(instructions)
-                .sign(key_pair.private_key());
+        TransactionBuilder::new_with_time_source(chain_id, account_id, time_source)
+            .with_instructions(instructions)
+    }
+
+    fn accept(tx_builder: TransactionBuilder, key_pair: &KeyPair) -> AcceptedTransaction {
+        let chain_id = ChainId::from("00000000-0000-0000-0000-000000000000");
+        let tx = tx_builder.sign(key_pair.private_key());
         let tx_limits = TransactionParameters {
             max_instructions: nonzero!(4096_u64),
             smart_contract_size: nonzero!(1024_u64),
@@ -466,6 +993,18 @@ pub mod tests {
         World::with([domain], [account], [])
     }
 
+    /// [`world_with_test_domains`] where `account_id` is granted `CanPrioritizeTransactions`.
+    fn world_with_prioritizing_account(account_id: &AccountId) -> World {
+        let mut world = world_with_test_domains();
+        world.account_permissions = [(
+            account_id.clone(),
+            BTreeSet::from([CanPrioritizeTransactions.into()]),
+        )]
+        .into_iter()
+        .collect();
+        world
+    }
+
     fn config_factory() -> Config {
         Config {
             transaction_time_to_live: Duration::from_secs(100),
@@ -524,6 +1063,328 @@ pub mod tests {
         ));
     }
 
+    #[test]
+    async fn push_tx_overflow_evicts_lower_priority() {
+        let (account_id, key_pair) = gen_account_in("wonderland");
+        let kura: Arc<Kura> = Kura::blank_kura_for_testing();
+        let query_handle = LiveQueryStore::start_test();
+        let state = Arc::new(State::new(
+            world_with_prioritizing_account(&account_id),
+            kura,
+            query_handle,
+        ));
+
+        let (time_handle, time_source) = TimeSource::new_mock(Duration::default());
+
+        let queue = Arc::new(Queue::test(
+            Config {
+                capacity: nonzero!(2_usize),
+                ordering: Ordering::Priority,
+                evict_when_full: true,
+                ..config_factory()
+            },
+            &time_source,
+        ));
+
+        let low = accepted_tx_with_priority(account_id.clone(), &key_pair, &time_source, 1);
+        let low_hash = low.as_ref().hash();
+        queue.push(low, state.view()).expect("Failed to push tx");
+        time_handle.advance(Duration::from_millis(10));
+        let middle = accepted_tx_with_priority(account_id.clone(), &key_pair, &time_source, 2);
+        let middle_hash = middle.as_ref().hash();
+        queue.push(middle, state.view()).expect("Failed to push tx");
+        time_handle.advance(Duration::from_millis(10));
+
+        let high = accepted_tx_with_priority(account_id.clone(), &key_pair, &time_source, 3);
+        let high_hash = high.as_ref().hash();
+        queue
+            .push(high, state.view())
+            .expect("Transaction with the higher priority should evict another one");
+        assert_eq!(queue.tx_len(), 2);
+        assert!(!queue.txs.contains_key(&low_hash));
+
+        assert!(matches!(
+            queue.push(
+                accepted_tx_with_priority(account_id, &key_pair, &time_source, 0),
+                state.view()
+            ),
+            Err(Failure {
+                err: Error::Full,
+                ..
+            })
+        ));
+
+        // The hash of the evicted transaction left in the queue is skipped
+        let transactions = queue.collect_transactions_for_block(&state.view(), nonzero!(10_usize));
+        assert_eq!(
+            transactions
+                .iter()
+                .map(|tx| tx.as_ref().hash())
+                .collect::<Vec<_>>(),
+            vec![high_hash, middle_hash]
+        );
+    }
+
+    #[test]
+    async fn priority_is_ignored_without_permission() {
+        let kura = Kura::blank_kura_for_testing();
+        let query_handle = LiveQueryStore::start_test();
+        let state = State::new(world_with_test_domains(), kura, query_handle);
+        let (account_id, key_pair) = gen_account_in("wonderland");
+
+        let (time_handle, time_source) = TimeSource::new_mock(Duration::default());
+
+        let queue = Arc::new(Queue::test(
+            Config {
+                ordering: Ordering::Priority,
+                ..config_factory()
+            },
+            &time_source,
+        ));
+
+        let mut hashes = Vec::new();
+        for priority in [0, 5] {
+            let tx =
+                accepted_tx_with_priority(account_id.clone(), &key_pair, &time_source, priority);
+            hashes.push(tx.as_ref().hash());
+            queue.push(tx, state.view()).expect("Failed to push tx");
+            time_handle.advance(Duration::from_millis(10));
+        }
+
+        let transactions = queue.collect_transactions_for_block(&state.view(), nonzero!(1_usize));
+        assert_eq!(transactions.len(), 1);
+        assert_eq!(transactions[0].as_ref().hash(), hashes[0]);
+    }
+
+    #[test]
+    async fn push_tx_replaces_pending_tx_with_same_sequence() {
+        let kura: Arc<Kura> = Kura::blank_kura_for_testing();
+        let query_handle = LiveQueryStore::start_test();
+        let state = Arc::new(State::new(world_with_test_domains(), kura, query_handle));
+        let (account_id, key_pair) = gen_account_in("wonderland");
+        let sequence = nonzero!(1_u64);
+
+        let (time_handle, time_source) = TimeSource::new_mock(Duration::default());
+
+        let queue = Arc::new(Queue::test(config_factory(), &time_source));
+
+        let pending =
+            accepted_tx_with_sequence(account_id.clone(), &key_pair, &time_source, sequence);
+        queue
+            .push(pending.clone(), state.view())
+            .expect("Failed to push tx");
+        time_handle.advance(Duration::from_millis(10));
+
+        let replacement =
+            accepted_tx_with_sequence(account_id.clone(), &key_pair, &time_source, sequence);
+        queue
+            .push(replacement.clone(), state.view())
+            .expect("Newer transaction should replace the pending one");
+        assert_eq!(queue.tx_len(), 1);
+        assert!(!queue.txs.contains_key(&pending.as_ref().hash()));
+
+        // Older transaction can't replace the newer one, e.g. when it's gossiped late
+        assert!(matches!(
+            queue.push(pending, state.view()),
+            Err(Failure {
+                err: Error::Superseded,
+                ..
+            })
+        ));
+
+        let transactions = queue.collect_transactions_for_block(&state.view(), nonzero!(10_usize));
+        assert_eq!(transactions.len(), 1);
+        assert_eq!(transactions[0].as_ref().hash(), replacement.as_ref().hash());
+    }
+
+    #[test]
+    async fn push_tx_with_same_nonce_keeps_pending_tx() {
+        let kura: Arc<Kura> = Kura::blank_kura_for_testing();
+        let query_handle = LiveQueryStore::start_test();
+        let state = Arc::new(State::new(world_with_test_domains(), kura, query_handle));
+        let (account_id, key_pair) = gen_account_in("wonderland");
+
+        let (time_handle, time_source) = TimeSource::new_mock(Duration::default());
+
+        let queue = Queue::test(config_factory(), &time_source);
+
+        // Nonce only makes the hashes different, it doesn't opt in to replacement
+        for _ in 0..2 {
+            let mut tx_builder = tx_builder(account_id.clone(), &time_source);
+            tx_builder.set_nonce(nonzero!(7_u32));
+            queue
+                .push(accept(tx_builder, &key_pair), state.view())
+                .expect("Failed to push tx");
+            time_handle.advance(Duration::from_millis(10));
+        }
+        assert_eq!(queue.tx_len(), 2);
+    }
+
+    #[test]
+    async fn priority_ordering_takes_higher_priority_first() {
+        let (account_id, key_pair) = gen_account_in("wonderland");
+        let kura = Kura::blank_kura_for_testing();
+        let query_handle = LiveQueryStore::start_test();
+        let state = State::new(
+            world_with_prioritizing_account(&account_id),
+            kura,
+            query_handle,
+        );
+
+        let (time_handle, time_source) = TimeSource::new_mock(Duration::default());
+
+        let queue = Arc::new(Queue::test(
+            Config {
+                ordering: Ordering::Priority,
+                ..config_factory()
+            },
+            &time_source,
+        ));
+
+        let mut hashes = Vec::new();
+        for priority in [1, 0, 5] {
+            let tx =
+                accepted_tx_with_priority(account_id.clone(), &key_pair, &time_source, priority);
+            hashes.push(tx.as_ref().hash());
+            queue.push(tx, state.view()).expect("Failed to push tx");
+            time_handle.advance(Duration::from_millis(10));
+        }
+
+        let transactions = queue.collect_transactions_for_block(&state.view(), nonzero!(2_usize));
+        assert_eq!(
+            transactions
+                .iter()
+                .map(|tx| tx.as_ref().hash())
+                .collect::<Vec<_>>(),
+            vec![hashes[2], hashes[0]]
+        );
+        // The transaction which isn't taken stays in the queue
+        assert_eq!(queue.tx_hashes.len(), 1);
+    }
+
+    #[test]
+    async fn sequenced_transactions_wait_for_missing_sequence() {
+        let kura = Kura::blank_kura_for_testing();
+        let query_handle = LiveQueryStore::start_test();
+        let state = State::new(world_with_test_domains(), kura, query_handle);
+        let (account_id, key_pair) = gen_account_in("wonderland");
+
+        let (time_handle, time_source) = TimeSource::new_mock(Duration::default());
+
+        let queue = Arc::new(Queue::test(config_factory(), &time_source));
+
+        let mut txs = [3, 2, 1]
+            .into_iter()
+            .map(|sequence| {
+                time_handle.advance(Duration::from_millis(10));
+                accepted_tx_with_sequence(
+                    account_id.clone(),
+                    &key_pair,
+                    &time_source,
+                    NonZeroU64::new(sequence).unwrap(),
+                )
+            })
+            .collect::<Vec<_>>();
+        let first = txs.pop().unwrap();
+        let hashes = [&first, &txs[1], &txs[0]].map(|tx| tx.as_ref().hash());
+        for tx in txs {
+            queue.push(tx, state.view()).expect("Failed to push tx");
+        }
+
+        let transactions = queue.collect_transactions_for_block(&state.view(), nonzero!(10_usize));
+        assert!(transactions.is_empty());
+        // Transactions with a gap stay in the queue
+        assert_eq!(queue.tx_hashes.len(), 2);
+
+        queue.push(first, state.view()).expect("Failed to push tx");
+        let transactions = queue.collect_transactions_for_block(&state.view(), nonzero!(10_usize));
+        assert_eq!(
+            transactions
+                .iter()
+                .map(|tx| tx.as_ref().hash())
+                .collect::<Vec<_>>(),
+            hashes
+        );
+    }
+
+    #[test]
+    async fn held_transactions_keep_order_of_arrival() {
+        let kura = Kura::blank_kura_for_testing();
+        let query_handle = LiveQueryStore::start_test();
+        let state = State::new(world_with_test_domains(), kura, query_handle);
+        let alice = gen_account_in("wonderland");
+        let bob = gen_account_in("wonderland");
+
+        let (time_handle, time_source) = TimeSource::new_mock(Duration::default());
+
+        let queue = Arc::new(Queue::test(config_factory(), &time_source));
+
+        // All of them wait for the missing first sequence number of their authority
+        let mut hashes = Vec::new();
+        for ((account_id, key_pair), sequence) in [(&alice, 2), (&bob, 2), (&alice, 3), (&bob, 3)] {
+            let tx = accepted_tx_with_sequence(
+                account_id.clone(),
+                key_pair,
+                &time_source,
+                NonZeroU64::new(sequence).unwrap(),
+            );
+            hashes.push(tx.as_ref().hash());
+            queue.push(tx, state.view()).expect("Failed to push tx");
+            time_handle.advance(Duration::from_millis(10));
+        }
+
+        let transactions = queue.collect_transactions_for_block(&state.view(), nonzero!(10_usize));
+        assert!(transactions.is_empty());
+        assert_eq!(
+            core::iter::from_fn(|| queue.tx_hashes.pop()).collect::<Vec<_>>(),
+            hashes
+        );
+    }
+
+    #[test]
+    async fn round_robin_ordering_interleaves_authorities() {
+        let kura = Kura::blank_kura_for_testing();
+        let query_handle = LiveQueryStore::start_test();
+        let state = State::new(world_with_test_domains(), kura, query_handle);
+        let (alice_id, alice_keypair) = gen_account_in("wonderland");
+        let (bob_id, bob_keypair) = gen_account_in("wonderland");
+
+        let (time_handle, time_source) = TimeSource::new_mock(Duration::default());
+
+        let queue = Arc::new(Queue::test(
+            Config {
+                ordering: Ordering::RoundRobin,
+                ..config_factory()
+            },
+            &time_source,
+        ));
+
+        for _ in 0..3 {
+            queue
+                .push(
+                    accepted_tx_by(alice_id.clone(), &alice_keypair, &time_source),
+                    state.view(),
+                )
+                .expect("Failed to push tx");
+            time_handle.advance(Duration::from_millis(10));
+        }
+        queue
+            .push(
+                accepted_tx_by(bob_id.clone(), &bob_keypair, &time_source),
+                state.view(),
+            )
+            .expect("Failed to push tx");
+
+        let transactions = queue.collect_transactions_for_block(&state.view(), nonzero!(2_usize));
+        assert_eq!(
+            transactions
+                .iter()
+                .map(|tx| tx.as_ref().authority().clone())
+                .collect::<Vec<_>>(),
+            vec![alice_id, bob_id]
+        );
+    }
+
     #[test]
     async fn get_available_txs() {
         let max_txs_in_block = nonzero!(2_usize);
@@ -865,6 +1726,7 @@ pub mod tests {
                 transaction_time_to_live: Duration::from_secs(100),
                 capacity: 100.try_into().unwrap(),
                 capacity_per_user: 1.try_into().unwrap(),
+                ..Config::default()
             },
             &time_source,
         );
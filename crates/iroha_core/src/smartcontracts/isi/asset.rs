@@ -2,6 +2,7 @@
 //! instructions implementations.
 
 use iroha_data_model::{
+    asset::AssetValue,
     isi::error::{MathError, Mismatch, TypeError},
     prelude::*,
     query::error::FindError,
@@ -15,6 +16,8 @@ use super::prelude::*;
 /// - update metadata
 /// - transfer, etc.
 pub mod isi {
+    use core::num::NonZeroU32;
+
     use iroha_data_model::isi::error::MintabilityError;
 
     use super::*;
@@ -33,14 +36,20 @@ pub mod isi {
                 .asset_definition(&asset_id.definition)?;
             assert_numeric_spec(&self.object, &asset_definition)?;
 
-            assert_can_mint(&asset_definition, state_transaction)?;
-            let asset = state_transaction
+            let quantity = state_transaction
                 .world
-                .asset_or_insert(&asset_id, Numeric::ZERO)?;
-            let quantity = &mut asset.value;
-            *quantity = quantity
+                .assets
+                .get(&asset_id)
+                .map_or(Numeric::ZERO, |asset| asset.value)
                 .checked_add(self.object)
                 .ok_or(MathError::Overflow)?;
+            assert_within_balance_limit(&asset_definition, quantity)?;
+
+            assert_can_mint(&asset_definition, self.object, state_transaction)?;
+            let asset = state_transaction
+                .world
+                .asset_or_insert(&asset_id, Numeric::ZERO)?;
+            asset.value = quantity;
 
             #[allow(clippy::float_arithmetic)]
             {
@@ -82,10 +91,7 @@ pub mod isi {
                 .assets
                 .get_mut(&asset_id)
                 .ok_or_else(|| FindError::Asset(asset_id.clone().into()))?;
-            let quantity = &mut asset.value;
-            *quantity = quantity
-                .checked_sub(self.object)
-                .ok_or(MathError::NotEnoughQuantity)?;
+            withdraw(asset, &asset_id, self.object)?;
 
             if asset.value.is_zero() {
                 assert!(state_transaction
@@ -138,10 +144,7 @@ pub mod isi {
                     .assets
                     .get_mut(&source_id)
                     .ok_or_else(|| FindError::Asset(source_id.clone().into()))?;
-                let quantity = &mut asset.value;
-                *quantity = quantity
-                    .checked_sub(self.object)
-                    .ok_or(MathError::NotEnoughQuantity)?;
+                withdraw(asset, &source_id, self.object)?;
                 if asset.value.is_zero() {
                     assert!(state_transaction
                         .world
@@ -159,6 +162,7 @@ pub mod isi {
                 *quantity = quantity
                     .checked_add(self.object)
                     .ok_or(MathError::Overflow)?;
+                assert_within_balance_limit(&asset_definition, *quantity)?;
             }
 
             #[allow(clippy::float_arithmetic)]
@@ -182,6 +186,137 @@ pub mod isi {
         }
     }
 
+    impl Execute for Freeze<Asset> {
+        fn execute(
+            self,
+            _authority: &AccountId,
+            state_transaction: &mut StateTransaction<'_, '_>,
+        ) -> Result<(), Error> {
+            let asset_id = self.object;
+
+            let asset = state_transaction.world.asset_mut(&asset_id)?;
+            if asset.frozen {
+                return Ok(());
+            }
+            asset.frozen = true;
+
+            state_transaction
+                .world
+                .emit_events(Some(AssetEvent::Frozen(asset_id)));
+
+            Ok(())
+        }
+    }
+
+    impl Execute for Unfreeze<Asset> {
+        fn execute(
+            self,
+            _authority: &AccountId,
+            state_transaction: &mut StateTransaction<'_, '_>,
+        ) -> Result<(), Error> {
+            let asset_id = self.object;
+
+            let asset = state_transaction.world.asset_mut(&asset_id)?;
+            if !asset.frozen {
+                return Ok(());
+            }
+            asset.frozen = false;
+
+            state_transaction
+                .world
+                .emit_events(Some(AssetEvent::Unfrozen(asset_id)));
+
+            Ok(())
+        }
+    }
+
+    impl Execute for Hold<Numeric, Asset> {
+        fn execute(
+            self,
+            _authority: &AccountId,
+            state_transaction: &mut StateTransaction<'_, '_>,
+        ) -> Result<(), Error> {
+            let asset_id = self.destination;
+
+            let asset_definition = state_transaction
+                .world
+                .asset_definition(&asset_id.definition)?;
+            assert_numeric_spec(&self.object, &asset_definition)?;
+
+            let asset = state_transaction.world.asset_mut(&asset_id)?;
+            let held = asset
+                .held
+                .checked_add(self.object)
+                .ok_or(MathError::Overflow)?;
+            if held > asset.value {
+                return Err(MathError::NotEnoughQuantity.into());
+            }
+            asset.held = held;
+
+            state_transaction
+                .world
+                .emit_events(Some(AssetEvent::Held(AssetChanged {
+                    asset: asset_id,
+                    amount: self.object,
+                })));
+
+            Ok(())
+        }
+    }
+
+    impl Execute for Release<Numeric, Asset> {
+        fn execute(
+            self,
+            _authority: &AccountId,
+            state_transaction: &mut StateTransaction<'_, '_>,
+        ) -> Result<(), Error> {
+            let asset_id = self.destination;
+
+            let asset_definition = state_transaction
+                .world
+                .asset_definition(&asset_id.definition)?;
+            assert_numeric_spec(&self.object, &asset_definition)?;
+
+            let asset = state_transaction.world.asset_mut(&asset_id)?;
+            asset.held = asset
+                .held
+                .checked_sub(self.object)
+                .ok_or(MathError::NotEnoughQuantity)?;
+
+            state_transaction
+                .world
+                .emit_events(Some(AssetEvent::Released(AssetChanged {
+                    asset: asset_id,
+                    amount: self.object,
+                })));
+
+            Ok(())
+        }
+    }
+
+    /// Take `amount` out of the `asset`, leaving its held part untouched.
+    ///
+    /// # Errors
+    /// - The asset is frozen
+    /// - The asset doesn't have enough quantity which isn't on hold
+    fn withdraw(asset: &mut AssetValue, asset_id: &AssetId, amount: Numeric) -> Result<(), Error> {
+        if asset.frozen {
+            return Err(Error::Frozen(asset_id.clone()));
+        }
+        let available = asset
+            .value
+            .checked_sub(asset.held)
+            .ok_or(MathError::NotEnoughQuantity)?;
+        if amount > available {
+            return Err(MathError::NotEnoughQuantity.into());
+        }
+        asset.value = asset
+            .value
+            .checked_sub(amount)
+            .ok_or(MathError::NotEnoughQuantity)?;
+        Ok(())
+    }
+
     /// Assert that asset type is Numeric and that it satisfy asset definition spec
     pub(crate) fn assert_numeric_spec(
         object: &Numeric,
@@ -198,20 +333,51 @@ pub mod isi {
         Ok(asset_spec)
     }
 
-    /// Assert that this asset is `mintable`.
+    /// Assert that the given supply cap and balance limit satisfy the asset definition spec
+    pub(crate) fn assert_limits_spec(
+        max_supply: Option<Numeric>,
+        max_balance: Option<Numeric>,
+        asset_definition: &AssetDefinition,
+    ) -> Result<(), Error> {
+        for limit in [max_supply, max_balance].iter().flatten() {
+            assert_numeric_spec(limit, asset_definition)?;
+        }
+        Ok(())
+    }
+
+    /// Assert that this asset is `mintable` and that minting `amount` keeps it within its supply cap.
     fn assert_can_mint(
         asset_definition: &AssetDefinition,
+        amount: Numeric,
         state_transaction: &mut StateTransaction<'_, '_>,
     ) -> Result<(), Error> {
+        if let Some(max_supply) = asset_definition.max_supply {
+            let total_quantity = asset_definition
+                .total_quantity
+                .checked_add(amount)
+                .ok_or(MathError::Overflow)?;
+            if total_quantity > max_supply {
+                return Err(Error::Mintability(MintabilityError::SupplyCapExceeded));
+            }
+        }
+
         match asset_definition.mintable {
             Mintable::Infinitely => Ok(()),
             Mintable::Not => Err(Error::Mintability(MintabilityError::MintUnmintable)),
-            Mintable::Once => {
+            Mintable::Once | Mintable::Limited(_) => {
                 let asset_definition_id = asset_definition.id.clone();
                 let asset_definition = state_transaction
                     .world
                     .asset_definition_mut(&asset_definition_id)?;
-                forbid_minting(asset_definition)?;
+                if let Mintable::Limited(times) = asset_definition.mintable {
+                    if let Some(remaining) = NonZeroU32::new(times.get() - 1) {
+                        asset_definition.mintable = Mintable::Limited(remaining);
+                        return Ok(());
+                    }
+                    asset_definition.mintable = Mintable::Not;
+                } else {
+                    forbid_minting(asset_definition)?;
+                }
                 state_transaction.world.emit_events(Some(
                     AssetDefinitionEvent::MintabilityChanged(asset_definition_id),
                 ));
@@ -219,6 +385,19 @@ pub mod isi {
             }
         }
     }
+
+    /// Assert that an account holding `quantity` of the asset stays within its balance limit.
+    fn assert_within_balance_limit(
+        asset_definition: &AssetDefinition,
+        quantity: Numeric,
+    ) -> Result<(), Error> {
+        match asset_definition.max_balance {
+            Some(max_balance) if quantity > max_balance => {
+                Err(Error::Mintability(MintabilityError::BalanceLimitExceeded))
+            }
+            _ => Ok(()),
+        }
+    }
 }
 
 /// Asset-related query implementations.
@@ -1,4 +1,6 @@
 //! This module contains trait implementations related to block queries
+use core::cell::Cell;
+
 use eyre::Result;
 use iroha_data_model::{
     block::{BlockHeader, SignedBlock},
@@ -10,6 +12,53 @@ use nonzero_ext::nonzero;
 use super::*;
 use crate::{smartcontracts::ValidQuery, state::StateReadOnly};
 
+/// Blocks listed by a query, noting whether the listing went past the earliest stored block.
+pub struct StoredBlocks<'reached, I> {
+    blocks: I,
+    reached_end: &'reached Cell<bool>,
+}
+
+impl<I: Iterator> Iterator for StoredBlocks<'_, I> {
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let block = self.blocks.next();
+        if block.is_none() {
+            self.reached_end.set(true);
+        }
+        block
+    }
+}
+
+/// Pass the `blocks` listed by a query to `consume`.
+///
+/// Only the stored blocks are listed. If `consume` goes past the earliest of them
+/// while the blocks below it are pruned, the query fails instead of silently truncating the chain.
+///
+/// # Errors
+///
+/// - `consume` fails
+/// - `consume` reaches the pruned blocks
+pub fn consume_stored_blocks<I: Iterator, R>(
+    state_ro: &impl StateReadOnly,
+    blocks: I,
+    consume: impl FnOnce(StoredBlocks<'_, I>) -> Result<R, QueryExecutionFail>,
+) -> Result<R, QueryExecutionFail> {
+    let reached_end = Cell::new(false);
+    let output = consume(StoredBlocks {
+        blocks,
+        reached_end: &reached_end,
+    })?;
+
+    let pruned_blocks_count = state_ro.kura().pruned_blocks_count();
+    if reached_end.get() && pruned_blocks_count > 0 {
+        return Err(QueryExecutionFail::BlocksPruned(
+            pruned_blocks_count as u64 + 1,
+        ));
+    }
+    Ok(output)
+}
+
 impl ValidQuery for FindBlocks {
     #[metrics(+"find_blocks")]
     fn execute(
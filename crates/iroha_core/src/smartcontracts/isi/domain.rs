@@ -13,12 +13,13 @@ use super::super::isi::prelude::*;
 /// - transfer, etc.
 pub mod isi {
     use iroha_data_model::{
-        isi::error::{InstructionExecutionError, RepetitionError},
+        isi::error::{InstructionExecutionError, MintabilityError, RepetitionError},
         IntoKeyValue,
     };
     use iroha_logger::prelude::*;
 
     use super::*;
+    use crate::smartcontracts::asset::isi::assert_limits_spec;
 
     impl Execute for Register<Account> {
         #[metrics(+"register_account")]
@@ -125,6 +126,11 @@ pub mod isi {
             state_transaction: &mut StateTransaction<'_, '_>,
         ) -> Result<(), Error> {
             let asset_definition = self.object.build(authority);
+            assert_limits_spec(
+                asset_definition.max_supply,
+                asset_definition.max_balance,
+                &asset_definition,
+            )?;
 
             let asset_definition_id = asset_definition.id().clone();
             if state_transaction
@@ -279,6 +285,38 @@ pub mod isi {
         }
     }
 
+    impl Execute for SetAssetDefinitionLimits {
+        #[metrics(+"set_asset_definition_limits")]
+        fn execute(
+            self,
+            _authority: &AccountId,
+            state_transaction: &mut StateTransaction<'_, '_>,
+        ) -> Result<(), Error> {
+            let asset_definition_id = self.asset_definition;
+
+            let asset_definition = state_transaction
+                .world
+                .asset_definition_mut(&asset_definition_id)?;
+            assert_limits_spec(self.max_supply, self.max_balance, asset_definition)?;
+            if self
+                .max_supply
+                .is_some_and(|max_supply| asset_definition.total_quantity > max_supply)
+            {
+                return Err(Error::Mintability(MintabilityError::SupplyCapExceeded));
+            }
+            asset_definition.max_supply = self.max_supply;
+            asset_definition.max_balance = self.max_balance;
+
+            state_transaction
+                .world
+                .emit_events(Some(AssetDefinitionEvent::LimitsChanged(
+                    asset_definition_id,
+                )));
+
+            Ok(())
+        }
+    }
+
     impl Execute for SetKeyValue<Domain> {
         #[metrics(+"set_domain_key_value")]
         fn execute(
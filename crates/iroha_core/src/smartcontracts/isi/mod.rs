@@ -48,6 +48,11 @@ impl Execute for InstructionBox {
             Self::SetParameter(isi) => isi.execute(authority, state_transaction),
             Self::Upgrade(isi) => isi.execute(authority, state_transaction),
             Self::Log(isi) => isi.execute(authority, state_transaction),
+            Self::Freeze(isi) => isi.execute(authority, state_transaction),
+            Self::Unfreeze(isi) => isi.execute(authority, state_transaction),
+            Self::Hold(isi) => isi.execute(authority, state_transaction),
+            Self::Release(isi) => isi.execute(authority, state_transaction),
+            Self::SetAssetDefinitionLimits(isi) => isi.execute(authority, state_transaction),
             Self::Custom(_) => {
                 panic!("Custom instructions should be handled in custom executor");
             }
@@ -208,10 +213,16 @@ pub mod prelude {
 mod tests {
     use std::sync::Arc;
 
-    use iroha_crypto::KeyPair;
+    use iroha_crypto::{Algorithm, KeyPair};
+    use iroha_data_model::{
+        isi::error::{MathError, MintabilityError},
+        parameter::PeerWeight,
+        query::error::FindError,
+    };
     use iroha_test_samples::{
         gen_account_in, ALICE_ID, SAMPLE_GENESIS_ACCOUNT_ID, SAMPLE_GENESIS_ACCOUNT_KEYPAIR,
     };
+    use nonzero_ext::nonzero;
     use tokio::test;
 
     use super::*;
@@ -453,6 +464,271 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    async fn frozen_and_held_assets_cannot_be_withdrawn() -> Result<()> {
+        let kura = Kura::blank_kura_for_testing();
+        let state = state_with_test_domains(&kura)?;
+        let block_header = ValidBlock::new_dummy(&KeyPair::random().into_parts().1)
+            .as_ref()
+            .header();
+        let mut state_block = state.block(block_header);
+        let mut state_transaction = state_block.transaction();
+        let account_id = ALICE_ID.clone();
+        let asset_id = AssetId::new("rose#wonderland".parse()?, account_id.clone());
+        Mint::asset_numeric(10_u32, asset_id.clone())
+            .execute(&account_id, &mut state_transaction)?;
+
+        Freeze::asset(asset_id.clone()).execute(&account_id, &mut state_transaction)?;
+        assert!(matches!(
+            Transfer::asset_numeric(asset_id.clone(), 1_u32, SAMPLE_GENESIS_ACCOUNT_ID.clone())
+                .execute(&account_id, &mut state_transaction)
+                .expect_err("Error expected"),
+            Error::Frozen(_)
+        ));
+        Unfreeze::asset(asset_id.clone()).execute(&account_id, &mut state_transaction)?;
+
+        Hold::asset_numeric(7_u32, asset_id.clone())
+            .execute(&account_id, &mut state_transaction)?;
+        assert!(matches!(
+            Burn::asset_numeric(4_u32, asset_id.clone())
+                .execute(&account_id, &mut state_transaction)
+                .expect_err("Error expected"),
+            Error::Math(MathError::NotEnoughQuantity)
+        ));
+        Burn::asset_numeric(3_u32, asset_id.clone())
+            .execute(&account_id, &mut state_transaction)?;
+        Release::asset_numeric(7_u32, asset_id.clone())
+            .execute(&account_id, &mut state_transaction)?;
+        Burn::asset_numeric(7_u32, asset_id.clone())
+            .execute(&account_id, &mut state_transaction)?;
+
+        Ok(())
+    }
+
+    #[test]
+    async fn mint_limits_are_enforced() -> Result<()> {
+        let kura = Kura::blank_kura_for_testing();
+        let state = state_with_test_domains(&kura)?;
+        let block_header = ValidBlock::new_dummy(&KeyPair::random().into_parts().1)
+            .as_ref()
+            .header();
+        let mut state_block = state.block(block_header);
+        let mut state_transaction = state_block.transaction();
+        let account_id = ALICE_ID.clone();
+        let asset_definition_id = "tulip#wonderland".parse::<AssetDefinitionId>()?;
+        Register::asset_definition(
+            AssetDefinition::numeric(asset_definition_id.clone())
+                .mintable_limited(nonzero!(3_u32))
+                .with_max_supply(10_u32)
+                .with_max_balance(6_u32),
+        )
+        .execute(&account_id, &mut state_transaction)?;
+        let alice_asset = AssetId::new(asset_definition_id.clone(), account_id.clone());
+        let genesis_asset = AssetId::new(
+            asset_definition_id.clone(),
+            SAMPLE_GENESIS_ACCOUNT_ID.clone(),
+        );
+
+        assert!(matches!(
+            Mint::asset_numeric(7_u32, alice_asset.clone())
+                .execute(&account_id, &mut state_transaction)
+                .expect_err("Error expected"),
+            Error::Mintability(MintabilityError::BalanceLimitExceeded)
+        ));
+        Mint::asset_numeric(6_u32, alice_asset.clone())
+            .execute(&account_id, &mut state_transaction)?;
+        Mint::asset_numeric(4_u32, genesis_asset.clone())
+            .execute(&account_id, &mut state_transaction)?;
+        assert!(matches!(
+            Mint::asset_numeric(1_u32, genesis_asset.clone())
+                .execute(&account_id, &mut state_transaction)
+                .expect_err("Error expected"),
+            Error::Mintability(MintabilityError::SupplyCapExceeded)
+        ));
+        Burn::asset_numeric(1_u32, alice_asset.clone())
+            .execute(&account_id, &mut state_transaction)?;
+        assert_eq!(
+            state_transaction
+                .world
+                .asset_definition(&asset_definition_id)?
+                .mintable,
+            Mintable::Limited(nonzero!(1_u32))
+        );
+        Mint::asset_numeric(1_u32, genesis_asset.clone())
+            .execute(&account_id, &mut state_transaction)?;
+        assert_eq!(
+            state_transaction
+                .world
+                .asset_definition(&asset_definition_id)?
+                .mintable,
+            Mintable::Not
+        );
+        Burn::asset_numeric(1_u32, alice_asset.clone())
+            .execute(&account_id, &mut state_transaction)?;
+        assert!(matches!(
+            Mint::asset_numeric(1_u32, alice_asset)
+                .execute(&account_id, &mut state_transaction)
+                .expect_err("Error expected"),
+            Error::Mintability(MintabilityError::MintUnmintable)
+        ));
+        assert!(matches!(
+            Transfer::asset_numeric(genesis_asset, 3_u32, account_id.clone())
+                .execute(&account_id, &mut state_transaction)
+                .expect_err("Error expected"),
+            Error::Mintability(MintabilityError::BalanceLimitExceeded)
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    async fn asset_definition_limits_can_be_updated() -> Result<()> {
+        let kura = Kura::blank_kura_for_testing();
+        let state = state_with_test_domains(&kura)?;
+        let block_header = ValidBlock::new_dummy(&KeyPair::random().into_parts().1)
+            .as_ref()
+            .header();
+        let mut state_block = state.block(block_header);
+        let mut state_transaction = state_block.transaction();
+        let account_id = ALICE_ID.clone();
+        assert!(matches!(
+            Register::asset_definition(
+                AssetDefinition::new("lily#wonderland".parse()?, NumericSpec::integer())
+                    .with_max_balance(Numeric::new(15, 1)),
+            )
+            .execute(&account_id, &mut state_transaction)
+            .expect_err("Error expected"),
+            Error::Evaluate(_)
+        ));
+        let asset_definition_id = "tulip#wonderland".parse::<AssetDefinitionId>()?;
+        Register::asset_definition(
+            AssetDefinition::new(asset_definition_id.clone(), NumericSpec::integer())
+                .with_max_supply(10_u32),
+        )
+        .execute(&account_id, &mut state_transaction)?;
+        let alice_asset = AssetId::new(asset_definition_id.clone(), account_id.clone());
+        let genesis_asset = AssetId::new(
+            asset_definition_id.clone(),
+            SAMPLE_GENESIS_ACCOUNT_ID.clone(),
+        );
+        Mint::asset_numeric(6_u32, alice_asset.clone())
+            .execute(&account_id, &mut state_transaction)?;
+
+        assert!(matches!(
+            SetAssetDefinitionLimits::new(asset_definition_id.clone())
+                .with_max_supply(5_u32)
+                .execute(&account_id, &mut state_transaction)
+                .expect_err("Error expected"),
+            Error::Mintability(MintabilityError::SupplyCapExceeded)
+        ));
+        assert!(matches!(
+            SetAssetDefinitionLimits::new(asset_definition_id.clone())
+                .with_max_supply(Numeric::new(85, 1))
+                .execute(&account_id, &mut state_transaction)
+                .expect_err("Error expected"),
+            Error::Evaluate(_)
+        ));
+        SetAssetDefinitionLimits::new(asset_definition_id.clone())
+            .with_max_supply(8_u32)
+            .with_max_balance(7_u32)
+            .execute(&account_id, &mut state_transaction)?;
+        assert!(matches!(
+            Mint::asset_numeric(2_u32, alice_asset.clone())
+                .execute(&account_id, &mut state_transaction)
+                .expect_err("Error expected"),
+            Error::Mintability(MintabilityError::BalanceLimitExceeded)
+        ));
+        Mint::asset_numeric(1_u32, genesis_asset.clone())
+            .execute(&account_id, &mut state_transaction)?;
+        assert!(matches!(
+            Mint::asset_numeric(2_u32, genesis_asset)
+                .execute(&account_id, &mut state_transaction)
+                .expect_err("Error expected"),
+            Error::Mintability(MintabilityError::SupplyCapExceeded)
+        ));
+
+        SetAssetDefinitionLimits::new(asset_definition_id)
+            .execute(&account_id, &mut state_transaction)?;
+        Mint::asset_numeric(100_u32, alice_asset).execute(&account_id, &mut state_transaction)?;
+
+        Ok(())
+    }
+
+    #[test]
+    async fn peer_weights_are_validated() -> Result<()> {
+        let kura = Kura::blank_kura_for_testing();
+        let state = state_with_test_domains(&kura)?;
+        let block_header = ValidBlock::new_dummy(&KeyPair::random().into_parts().1)
+            .as_ref()
+            .header();
+        let mut state_block = state.block(block_header);
+        let mut state_transaction = state_block.transaction();
+        let authority = SAMPLE_GENESIS_ACCOUNT_ID.clone();
+        let peers = core::iter::repeat_with(|| PeerId::new(KeyPair::random().into_parts().0))
+            .take(7)
+            .collect::<Vec<_>>();
+        let set_weight = |peer: &PeerId, weight| {
+            SetParameter::new(Parameter::PeerWeight(PeerWeight::new(peer.clone(), weight)))
+        };
+
+        assert!(matches!(
+            set_weight(&peers[0], nonzero!(2_u64))
+                .execute(&authority, &mut state_transaction)
+                .expect_err("Error expected"),
+            Error::Find(FindError::Peer(_))
+        ));
+        for peer in &peers {
+            Register::peer(peer.clone()).execute(&authority, &mut state_transaction)?;
+        }
+        // 2 out of 8 is less than a third of the total weight
+        set_weight(&peers[0], nonzero!(2_u64)).execute(&authority, &mut state_transaction)?;
+        // 3 out of 9 is a third of the total weight
+        assert!(matches!(
+            set_weight(&peers[0], nonzero!(3_u64))
+                .execute(&authority, &mut state_transaction)
+                .expect_err("Error expected"),
+            Error::InvariantViolation(_)
+        ));
+        assert_eq!(
+            state_transaction
+                .world
+                .parameters
+                .peer_weights
+                .get(&peers[0]),
+            Some(&nonzero!(2_u64))
+        );
+
+        Unregister::peer(peers[0].clone()).execute(&authority, &mut state_transaction)?;
+        assert!(state_transaction.world.parameters.peer_weights.is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    async fn peers_without_bls_keys_cant_join_bls_peers() -> Result<()> {
+        let kura = Kura::blank_kura_for_testing();
+        let state = state_with_test_domains(&kura)?;
+        let block_header = ValidBlock::new_dummy(&KeyPair::random().into_parts().1)
+            .as_ref()
+            .header();
+        let mut state_block = state.block(block_header);
+        let mut state_transaction = state_block.transaction();
+        let authority = SAMPLE_GENESIS_ACCOUNT_ID.clone();
+        let peer =
+            |algorithm| PeerId::new(KeyPair::random_with_algorithm(algorithm).into_parts().0);
+
+        Register::peer(peer(Algorithm::BlsNormal)).execute(&authority, &mut state_transaction)?;
+        Register::peer(peer(Algorithm::BlsSmall)).execute(&authority, &mut state_transaction)?;
+        assert!(matches!(
+            Register::peer(peer(Algorithm::Ed25519))
+                .execute(&authority, &mut state_transaction)
+                .expect_err("Error expected"),
+            Error::InvariantViolation(_)
+        ));
+
+        Ok(())
+    }
+
     #[test]
     async fn transaction_signed_by_genesis_account_should_be_rejected() -> Result<()> {
         let chain_id = ChainId::from("00000000-0000-0000-0000-000000000000");
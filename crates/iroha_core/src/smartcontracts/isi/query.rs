@@ -1,23 +1,27 @@
 //! Query functionality. The common error type is also defined here,
 //! alongside functions for converting them into HTTP responses.
-use std::cmp::Ordering;
+use std::num::NonZeroU64;
 
 use eyre::Result;
 use iroha_data_model::{
     prelude::*,
     query::{
-        dsl::{EvaluateSelector, HasProjection, SelectorMarker},
+        dsl::{Aggregate, EvaluateSelector, HasProjection, SelectorMarker, SortKey},
         error::QueryExecutionFail as Error,
-        parameters::QueryParams,
-        CommittedTransaction, QueryBox, QueryOutputBatchBox, QueryRequest,
-        QueryRequestWithAuthority, QueryResponse, SingularQueryBox, SingularQueryOutputBox,
+        parameters::{Pagination, QueryContinuation, QueryParams, Sorting},
+        CommittedTransaction, QueryBox, QueryOutputBatchBox, QueryOutputBatchBoxTuple,
+        QueryRequest, QueryRequestWithAuthority, QueryResponse, QueryWithParams, SingularQueryBox,
+        SingularQueryOutputBox,
     },
 };
 
 use crate::{
     prelude::ValidSingularQuery,
-    query::{cursor::ErasedQueryIterator, pagination::Paginate as _, store::LiveQueryStoreHandle},
-    smartcontracts::{wasm, ValidQuery},
+    query::{
+        aggregate::AggregatedRows, cursor::ErasedQueryIterator, pagination::Paginate as _,
+        store::LiveQueryStoreHandle, subscription::QuerySubscription,
+    },
+    smartcontracts::{isi::block::consume_stored_blocks, wasm, ValidQuery},
     state::{StateReadOnly, WorldReadOnly},
 };
 
@@ -27,48 +31,83 @@ pub trait SortableQueryOutput {
     ///
     /// If the type doesn't have metadata or metadata key doesn't exist - return None
     fn get_metadata_sorting_key(&self, key: &Name) -> Option<Json>;
+
+    /// Get the id the outputs are ordered by when no sorting is requested, as a single-element batch
+    ///
+    /// Allows to resume the results after an output. If the outputs aren't ordered by their ids - return None
+    fn continuation_id(&self) -> Option<QueryOutputBatchBox> {
+        None
+    }
 }
 
 impl SortableQueryOutput for Account {
     fn get_metadata_sorting_key(&self, key: &Name) -> Option<Json> {
         self.metadata.get(key).cloned()
     }
+
+    fn continuation_id(&self) -> Option<QueryOutputBatchBox> {
+        Some(vec![self.id().clone()].into())
+    }
 }
 
 impl SortableQueryOutput for Domain {
     fn get_metadata_sorting_key(&self, key: &Name) -> Option<Json> {
         self.metadata.get(key).cloned()
     }
+
+    fn continuation_id(&self) -> Option<QueryOutputBatchBox> {
+        Some(vec![self.id().clone()].into())
+    }
 }
 
 impl SortableQueryOutput for AssetDefinition {
     fn get_metadata_sorting_key(&self, key: &Name) -> Option<Json> {
         self.metadata.get(key).cloned()
     }
+
+    fn continuation_id(&self) -> Option<QueryOutputBatchBox> {
+        Some(vec![self.id().clone()].into())
+    }
 }
 
 impl SortableQueryOutput for Asset {
     fn get_metadata_sorting_key(&self, _key: &Name) -> Option<Json> {
         None
     }
+
+    fn continuation_id(&self) -> Option<QueryOutputBatchBox> {
+        Some(vec![self.id().clone()].into())
+    }
 }
 
 impl SortableQueryOutput for Nft {
     fn get_metadata_sorting_key(&self, key: &Name) -> Option<Json> {
         self.content.get(key).cloned()
     }
+
+    fn continuation_id(&self) -> Option<QueryOutputBatchBox> {
+        Some(vec![self.id().clone()].into())
+    }
 }
 
 impl SortableQueryOutput for Role {
     fn get_metadata_sorting_key(&self, _key: &Name) -> Option<Json> {
         None
     }
+
+    fn continuation_id(&self) -> Option<QueryOutputBatchBox> {
+        Some(vec![self.id().clone()].into())
+    }
 }
 
 impl SortableQueryOutput for RoleId {
     fn get_metadata_sorting_key(&self, _key: &Name) -> Option<Json> {
         None
     }
+
+    fn continuation_id(&self) -> Option<QueryOutputBatchBox> {
+        Some(vec![self.clone()].into())
+    }
 }
 
 impl SortableQueryOutput for CommittedTransaction {
@@ -113,18 +152,32 @@ impl SortableQueryOutput for iroha_data_model::block::BlockHeader {
     }
 }
 
-/// Applies sorting and pagination to the query output and wraps it into a type-erasing batching iterator.
+/// Applies aggregation, sorting and pagination to the query output and wraps it into a type-erasing batching iterator.
+///
+/// The results are sorted by the selected `sort_by` keys first and by the metadata key from the parameters next.
+/// When an aggregation is requested, the selector and the metadata key are ignored, groups are returned in the sorted order of their first results
+/// and the pagination is applied to the aggregated rows.
+///
+/// When a continuation is provided, only the results ordered after it are returned, the ties being ordered by ids.
+/// The offset is then already behind and the results returned before the continuation are deducted from the limit.
+/// The continuations of the returned results are pinned to the `at_height` parameter,
+/// which has to be set to the height of the state the results are read from.
 ///
 /// # Errors
 ///
-/// Returns an error if the fetch size is too big
+/// Returns an error if the fetch size is too big, the sorting or the aggregation fails,
+/// or the continuation wasn't taken from the results of the same query
 pub fn apply_query_postprocessing<I>(
     iter: I,
     selector: SelectorTuple<I::Item>,
+    aggregate: Option<Aggregate<I::Item>>,
+    sort_by: Vec<SortKey<I::Item>>,
     &QueryParams {
         pagination,
         ref sorting,
         fetch_size,
+        at_height,
+        ref continuation,
     }: &QueryParams,
 ) -> Result<ErasedQueryIterator, Error>
 where
@@ -141,45 +194,99 @@ where
         return Err(Error::FetchSizeTooBig);
     }
 
-    // sort & paginate, erase the iterator with QueryBatchedErasedIterator
-    let output = if let Some(key) = &sorting.sort_by_metadata_key {
-        // if sorting was requested, we need to retrieve all the results first
-        let mut pairs: Vec<(Option<Json>, I::Item)> = iter
-            .map(|value| {
-                let key = value.get_metadata_sorting_key(key);
-                (key, value)
-            })
-            .collect();
-        pairs.sort_by(
-            |(left_key, _), (right_key, _)| match (left_key, right_key) {
-                (Some(l), Some(r)) => l.cmp(r),
-                (Some(_), None) => Ordering::Less,
-                (None, Some(_)) => Ordering::Greater,
-                (None, None) => Ordering::Equal,
-            },
-        );
+    if let Some(aggregate) = aggregate {
+        if continuation.is_some() {
+            // aggregated rows have no ids to resume after
+            return Err(Error::CursorMismatch);
+        }
+        let AggregatedRows { empty, rows } = if sort_by.is_empty() {
+            crate::query::aggregate::aggregate(iter, &aggregate)?
+        } else {
+            let sorted = crate::query::sorting::sort(iter, &sort_by, &Sorting::default())?;
+            crate::query::aggregate::aggregate(sorted.into_iter(), &aggregate)?
+        };
 
-        ErasedQueryIterator::new(
-            pairs.into_iter().map(|(_, val)| val).paginate(pagination),
-            selector,
+        return Ok(ErasedQueryIterator::from_rows(
+            rows.into_iter().paginate(pagination),
+            empty,
             fetch_size,
-        )
-    } else {
-        // FP: this collect is very deliberate
-        #[allow(clippy::needless_collect)]
-        let output = iter
-            .paginate(pagination)
-            // it should theoretically be possible to not collect the results into a vec and build the response lazily
-            // but:
-            // - the iterator is bound to the 'state lifetime and this lifetime should somehow be erased
-            // - for small queries this might not be efficient
-            // TODO: investigate this
-            .collect::<Vec<_>>();
+        ));
+    }
 
-        ErasedQueryIterator::new(output.into_iter(), selector, fetch_size)
+    // sort & paginate, erase the iterator with QueryBatchedErasedIterator
+    // FP: this collect is very deliberate
+    #[allow(clippy::needless_collect)]
+    let output: Vec<_> = if sort_by.is_empty() && sorting.sort_by_metadata_key.is_none() {
+        // it should theoretically be possible to not collect the results into a vec and build the response lazily
+        // but:
+        // - the iterator is bound to the 'state lifetime and this lifetime should somehow be erased
+        // - for small queries this might not be efficient
+        // TODO: investigate this
+        match continuation {
+            None => iter.paginate(pagination).collect(),
+            Some(continuation) => resume_after(iter, continuation, &sort_by, sorting, pagination)?,
+        }
+    } else {
+        // if sorting was requested, we need to retrieve all the results first
+        let sorted = crate::query::sorting::sort(iter, &sort_by, sorting)?;
+
+        match continuation {
+            None => sorted.into_iter().paginate(pagination).collect(),
+            Some(continuation) => resume_after(
+                sorted.into_iter(),
+                continuation,
+                &sort_by,
+                sorting,
+                pagination,
+            )?,
+        }
     };
 
-    Ok(output)
+    let position = continuation
+        .as_ref()
+        .map_or(0, |continuation| continuation.position.get());
+    let height = at_height.map_or(0, NonZeroU64::get);
+    let sorting = sorting.clone();
+
+    Ok(ErasedQueryIterator::resumable(
+        output.into_iter(),
+        selector,
+        fetch_size,
+        position,
+        move |item, position| {
+            crate::query::sorting::continuation_after(item, height, position, &sort_by, &sorting)
+        },
+    ))
+}
+
+/// Takes the results ordered after the `continuation`, up to what remains of the pagination limit.
+fn resume_after<I>(
+    iter: I,
+    continuation: &QueryContinuation,
+    sort_by: &[SortKey<I::Item>],
+    sorting: &Sorting,
+    pagination: Pagination,
+) -> Result<Vec<I::Item>, Error>
+where
+    I: Iterator<Item: SortableQueryOutput>,
+    I::Item: HasProjection<SelectorMarker, AtomType = ()> + 'static,
+    <I::Item as HasProjection<SelectorMarker>>::Projection: EvaluateSelector<I::Item>,
+{
+    let remaining = pagination.limit.map_or(usize::MAX, |limit| {
+        limit
+            .get()
+            .saturating_sub(continuation.position.get())
+            .try_into()
+            .expect("u64 should fit into usize")
+    });
+
+    iter.map(|item| {
+        crate::query::sorting::is_after(&item, continuation, sort_by, sorting)
+            .map(|is_after| is_after.then_some(item))
+    })
+    .filter_map(Result::transpose)
+    .take(remaining)
+    .collect()
 }
 
 /// Query Request statefully validated on the Iroha node side.
@@ -229,7 +336,6 @@ impl ValidQueryRequest {
     /// # Errors
     ///
     /// Returns an error if the query execution fails.
-    #[allow(clippy::too_many_lines)] // not much we can do, we _need_ to list all the box types here
     pub fn execute(
         self,
         live_query_store: &LiveQueryStoreHandle,
@@ -237,111 +343,228 @@ impl ValidQueryRequest {
         authority: &AccountId,
     ) -> Result<QueryResponse, Error> {
         match self.0 {
-            QueryRequest::Singular(singular_query) => {
-                let output = match singular_query {
-                    SingularQueryBox::FindExecutorDataModel(q) => {
-                        SingularQueryOutputBox::from(q.execute(state)?)
-                    }
-                    SingularQueryBox::FindParameters(q) => {
-                        SingularQueryOutputBox::from(q.execute(state)?)
-                    }
-                };
-
-                Ok(QueryResponse::Singular(output))
+            QueryRequest::Singular(singular_query) => Self::execute_singular(singular_query, state),
+            QueryRequest::Start(iter_query) => Ok(QueryResponse::Iterable(
+                live_query_store
+                    .handle_iter_start(Self::start_iterable(iter_query, state)?, authority)?,
+            )),
+            QueryRequest::Continue(cursor) => Ok(QueryResponse::Iterable(
+                live_query_store.handle_iter_continue(cursor)?,
+            )),
+        }
+    }
+
+    /// Execute a validated query request without keeping the rest of the iterable query output
+    /// in the live query store.
+    ///
+    /// The returned cursor can't be continued, but carries a continuation
+    /// to start the query again from, if the results have ids to order by.
+    ///
+    /// # Errors
+    ///
+    /// - The request continues a cursor, as there are no live queries to continue
+    /// - The query execution fails
+    pub fn execute_detached(self, state: &impl StateReadOnly) -> Result<QueryResponse, Error> {
+        match self.0 {
+            QueryRequest::Singular(singular_query) => Self::execute_singular(singular_query, state),
+            QueryRequest::Start(iter_query) => Ok(QueryResponse::Iterable(
+                LiveQueryStoreHandle::handle_iter_start_detached(Self::start_iterable(
+                    iter_query, state,
+                )?)?,
+            )),
+            QueryRequest::Continue(_) => Err(Error::NotFound),
+        }
+    }
+
+    fn execute_singular(
+        singular_query: SingularQueryBox,
+        state: &impl StateReadOnly,
+    ) -> Result<QueryResponse, Error> {
+        let output = match singular_query {
+            SingularQueryBox::FindExecutorDataModel(q) => {
+                SingularQueryOutputBox::from(q.execute(state)?)
             }
-            QueryRequest::Start(iter_query) => {
-                let output = match iter_query.query {
-                    // dispatch on a concrete query type, erasing the type with `QueryBatchedErasedIterator` in the end
-                    QueryBox::FindDomains(q) => apply_query_postprocessing(
-                        ValidQuery::execute(q.query, q.predicate, state)?,
-                        q.selector,
-                        &iter_query.params,
-                    )?,
-                    QueryBox::FindAccounts(q) => apply_query_postprocessing(
-                        ValidQuery::execute(q.query, q.predicate, state)?,
-                        q.selector,
-                        &iter_query.params,
-                    )?,
-                    QueryBox::FindAssets(q) => apply_query_postprocessing(
-                        ValidQuery::execute(q.query, q.predicate, state)?,
-                        q.selector,
-                        &iter_query.params,
-                    )?,
-                    QueryBox::FindAssetsDefinitions(q) => apply_query_postprocessing(
-                        ValidQuery::execute(q.query, q.predicate, state)?,
-                        q.selector,
-                        &iter_query.params,
-                    )?,
-                    QueryBox::FindNfts(q) => apply_query_postprocessing(
-                        ValidQuery::execute(q.query, q.predicate, state)?,
-                        q.selector,
-                        &iter_query.params,
-                    )?,
-                    QueryBox::FindRoles(q) => apply_query_postprocessing(
-                        ValidQuery::execute(q.query, q.predicate, state)?,
-                        q.selector,
-                        &iter_query.params,
-                    )?,
-                    QueryBox::FindRoleIds(q) => apply_query_postprocessing(
-                        ValidQuery::execute(q.query, q.predicate, state)?,
-                        q.selector,
-                        &iter_query.params,
-                    )?,
-                    QueryBox::FindPermissionsByAccountId(q) => apply_query_postprocessing(
-                        ValidQuery::execute(q.query, q.predicate, state)?,
-                        q.selector,
-                        &iter_query.params,
-                    )?,
-                    QueryBox::FindRolesByAccountId(q) => apply_query_postprocessing(
-                        ValidQuery::execute(q.query, q.predicate, state)?,
-                        q.selector,
-                        &iter_query.params,
-                    )?,
-                    QueryBox::FindAccountsWithAsset(q) => apply_query_postprocessing(
-                        ValidQuery::execute(q.query, q.predicate, state)?,
-                        q.selector,
-                        &iter_query.params,
-                    )?,
-                    QueryBox::FindPeers(q) => apply_query_postprocessing(
-                        ValidQuery::execute(q.query, q.predicate, state)?,
-                        q.selector,
-                        &iter_query.params,
-                    )?,
-                    QueryBox::FindActiveTriggerIds(q) => apply_query_postprocessing(
-                        ValidQuery::execute(q.query, q.predicate, state)?,
-                        q.selector,
-                        &iter_query.params,
-                    )?,
-                    QueryBox::FindTriggers(q) => apply_query_postprocessing(
-                        ValidQuery::execute(q.query, q.predicate, state)?,
-                        q.selector,
-                        &iter_query.params,
-                    )?,
-                    QueryBox::FindTransactions(q) => apply_query_postprocessing(
-                        ValidQuery::execute(q.query, q.predicate, state)?,
-                        q.selector,
-                        &iter_query.params,
-                    )?,
-                    QueryBox::FindBlocks(q) => apply_query_postprocessing(
-                        ValidQuery::execute(q.query, q.predicate, state)?,
+            SingularQueryBox::FindParameters(q) => SingularQueryOutputBox::from(q.execute(state)?),
+        };
+
+        Ok(QueryResponse::Singular(output))
+    }
+
+    #[allow(clippy::too_many_lines)] // not much we can do, we _need_ to list all the box types here
+    fn start_iterable(
+        mut iter_query: QueryWithParams,
+        state: &impl StateReadOnly,
+    ) -> Result<ErasedQueryIterator, Error> {
+        let params = &mut iter_query.params;
+        let pinned_height = params
+            .continuation
+            .as_ref()
+            .map(|continuation| continuation.height);
+        if let (Some(at_height), Some(pinned_height)) = (params.at_height, pinned_height) {
+            if at_height.get() != pinned_height {
+                return Err(Error::CursorMismatch);
+            }
+        }
+        // State at the requested height has to be provided by the caller
+        let height = state.height() as u64;
+        if pinned_height
+            .or(params.at_height.map(NonZeroU64::get))
+            .is_some_and(|requested| requested != height)
+        {
+            return Err(Error::HeightUnavailable);
+        }
+        // Continuations of the results are pinned to the height they are read at
+        params.at_height = NonZeroU64::new(height);
+
+        let output = match iter_query.query {
+            // dispatch on a concrete query type, erasing the type with `QueryBatchedErasedIterator` in the end
+            QueryBox::FindDomains(q) => apply_query_postprocessing(
+                ValidQuery::execute(q.query, q.predicate, state)?,
+                q.selector,
+                q.aggregate,
+                q.sort_by,
+                &iter_query.params,
+            )?,
+            QueryBox::FindAccounts(q) => apply_query_postprocessing(
+                ValidQuery::execute(q.query, q.predicate, state)?,
+                q.selector,
+                q.aggregate,
+                q.sort_by,
+                &iter_query.params,
+            )?,
+            QueryBox::FindAssets(q) => apply_query_postprocessing(
+                ValidQuery::execute(q.query, q.predicate, state)?,
+                q.selector,
+                q.aggregate,
+                q.sort_by,
+                &iter_query.params,
+            )?,
+            QueryBox::FindAssetsDefinitions(q) => apply_query_postprocessing(
+                ValidQuery::execute(q.query, q.predicate, state)?,
+                q.selector,
+                q.aggregate,
+                q.sort_by,
+                &iter_query.params,
+            )?,
+            QueryBox::FindNfts(q) => apply_query_postprocessing(
+                ValidQuery::execute(q.query, q.predicate, state)?,
+                q.selector,
+                q.aggregate,
+                q.sort_by,
+                &iter_query.params,
+            )?,
+            QueryBox::FindRoles(q) => apply_query_postprocessing(
+                ValidQuery::execute(q.query, q.predicate, state)?,
+                q.selector,
+                q.aggregate,
+                q.sort_by,
+                &iter_query.params,
+            )?,
+            QueryBox::FindRoleIds(q) => apply_query_postprocessing(
+                ValidQuery::execute(q.query, q.predicate, state)?,
+                q.selector,
+                q.aggregate,
+                q.sort_by,
+                &iter_query.params,
+            )?,
+            QueryBox::FindPermissionsByAccountId(q) => apply_query_postprocessing(
+                ValidQuery::execute(q.query, q.predicate, state)?,
+                q.selector,
+                q.aggregate,
+                q.sort_by,
+                &iter_query.params,
+            )?,
+            QueryBox::FindRolesByAccountId(q) => apply_query_postprocessing(
+                ValidQuery::execute(q.query, q.predicate, state)?,
+                q.selector,
+                q.aggregate,
+                q.sort_by,
+                &iter_query.params,
+            )?,
+            QueryBox::FindAccountsWithAsset(q) => apply_query_postprocessing(
+                ValidQuery::execute(q.query, q.predicate, state)?,
+                q.selector,
+                q.aggregate,
+                q.sort_by,
+                &iter_query.params,
+            )?,
+            QueryBox::FindPeers(q) => apply_query_postprocessing(
+                ValidQuery::execute(q.query, q.predicate, state)?,
+                q.selector,
+                q.aggregate,
+                q.sort_by,
+                &iter_query.params,
+            )?,
+            QueryBox::FindActiveTriggerIds(q) => apply_query_postprocessing(
+                ValidQuery::execute(q.query, q.predicate, state)?,
+                q.selector,
+                q.aggregate,
+                q.sort_by,
+                &iter_query.params,
+            )?,
+            QueryBox::FindTriggers(q) => apply_query_postprocessing(
+                ValidQuery::execute(q.query, q.predicate, state)?,
+                q.selector,
+                q.aggregate,
+                q.sort_by,
+                &iter_query.params,
+            )?,
+            QueryBox::FindTransactions(q) => apply_query_postprocessing(
+                ValidQuery::execute(q.query, q.predicate, state)?,
+                q.selector,
+                q.aggregate,
+                q.sort_by,
+                &iter_query.params,
+            )?,
+            QueryBox::FindBlocks(q) => consume_stored_blocks(
+                state,
+                ValidQuery::execute(q.query, q.predicate, state)?,
+                |blocks| {
+                    apply_query_postprocessing(
+                        blocks,
                         q.selector,
+                        q.aggregate,
+                        q.sort_by,
                         &iter_query.params,
-                    )?,
-                    QueryBox::FindBlockHeaders(q) => apply_query_postprocessing(
-                        ValidQuery::execute(q.query, q.predicate, state)?,
+                    )
+                },
+            )?,
+            QueryBox::FindBlockHeaders(q) => consume_stored_blocks(
+                state,
+                ValidQuery::execute(q.query, q.predicate, state)?,
+                |headers| {
+                    apply_query_postprocessing(
+                        headers,
                         q.selector,
+                        q.aggregate,
+                        q.sort_by,
                         &iter_query.params,
-                    )?,
-                };
+                    )
+                },
+            )?,
+        };
 
-                Ok(QueryResponse::Iterable(
-                    live_query_store.handle_iter_start(output, authority)?,
-                ))
-            }
-            QueryRequest::Continue(cursor) => Ok(QueryResponse::Iterable(
-                live_query_store.handle_iter_continue(cursor)?,
-            )),
-        }
+        Ok(output)
+    }
+
+    /// Start a subscription to the results of a validated iterable query request.
+    ///
+    /// Returns the subscription along with the initial results split into batches.
+    ///
+    /// # Errors
+    ///
+    /// - The request is not an iterable query start or its parameters are not applicable to subscriptions
+    /// - The query execution fails
+    pub fn subscribe(
+        self,
+        state: &impl StateReadOnly,
+        authority: AccountId,
+    ) -> Result<(QuerySubscription, Vec<QueryOutputBatchBoxTuple>), Error> {
+        let QueryRequest::Start(iter_query) = self.0 else {
+            return Err(Error::InvalidSubscriptionParameters);
+        };
+
+        QuerySubscription::start(authority, iter_query, state)
     }
 }
 
@@ -24,6 +24,7 @@ use mv::storage::{
     Block as StorageBlock, Storage, StorageReadOnly, Transaction as StorageTransaction,
     View as StorageView,
 };
+use parity_scale_codec::{Decode, DecodeAll, Encode};
 use serde::{
     de::{DeserializeSeed, MapAccess, Visitor},
     Deserialize, Serialize,
@@ -45,6 +46,8 @@ use crate::{
 pub enum Error {
     /// Failed to preload wasm trigger
     Preload(#[from] wasm::error::Error),
+    /// Failed to decode snapshot of triggers
+    Decode(#[from] parity_scale_codec::Error),
 }
 
 /// Result type for [`Set`] operations.
@@ -83,6 +86,17 @@ pub struct Set {
     contracts: WasmSmartContractMap,
 }
 
+/// Contents of [`Set`] stored in state snapshots.
+/// Contracts are stored without compiled modules, they are compiled again on load.
+type SetSnapshot = (
+    Vec<(TriggerId, LoadedAction<DataEventFilter>)>,
+    Vec<(TriggerId, LoadedAction<PipelineEventFilterBox>)>,
+    Vec<(TriggerId, LoadedAction<TimeEventFilter>)>,
+    Vec<(TriggerId, LoadedAction<ExecuteTriggerEventFilter>)>,
+    Vec<(TriggerId, TriggeringEventType)>,
+    Vec<(HashOf<WasmSmartContract>, WasmSmartContract, NonZeroU64)>,
+);
+
 /// Trigger set for block's aggregated changes
 pub struct SetBlock<'set> {
     /// Triggers using [`DataEventFilter`]
@@ -526,6 +540,65 @@ impl Set {
             contracts: self.contracts.view(),
         }
     }
+
+    /// Decode [`Set`] encoded by [`SetView::encode_snapshot`], compiling its contracts.
+    ///
+    /// # Errors
+    /// - Decoding fails
+    /// - Failed to compile a contract
+    pub(crate) fn decode_snapshot(engine: &wasmtime::Engine, bytes: &[u8]) -> Result<Self> {
+        let (data_triggers, pipeline_triggers, time_triggers, by_call_triggers, ids, contracts) =
+            SetSnapshot::decode_all(&mut &*bytes)?;
+        let contracts = contracts
+            .into_iter()
+            .map(|(hash, original_contract, count)| {
+                let compiled_contract = wasm::load_module(engine, &original_contract)?;
+                Ok((
+                    hash,
+                    WasmSmartContractEntry {
+                        original_contract,
+                        compiled_contract,
+                        count,
+                    },
+                ))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(Self {
+            data_triggers: Storage::from_iter(data_triggers),
+            pipeline_triggers: Storage::from_iter(pipeline_triggers),
+            time_triggers: Storage::from_iter(time_triggers),
+            by_call_triggers: Storage::from_iter(by_call_triggers),
+            ids: Storage::from_iter(ids),
+            contracts: Storage::from_iter(contracts),
+        })
+    }
+}
+
+impl SetView<'_> {
+    /// Encode triggers to be stored in a state snapshot.
+    pub(crate) fn encode_snapshot(&self) -> Vec<u8> {
+        fn entries<'view, K, V>(
+            storage: &'view impl StorageReadOnly<K, V>,
+        ) -> Vec<(&'view K, &'view V)> {
+            storage.iter().collect()
+        }
+
+        let contracts = self
+            .contracts
+            .iter()
+            .map(|(hash, entry)| (hash, &entry.original_contract, entry.count))
+            .collect::<Vec<_>>();
+        (
+            entries(&self.data_triggers),
+            entries(&self.pipeline_triggers),
+            entries(&self.time_triggers),
+            entries(&self.by_call_triggers),
+            entries(&self.ids),
+            contracts,
+        )
+            .encode()
+    }
 }
 
 impl<'set> SetBlock<'set> {
@@ -921,7 +994,7 @@ impl<'block, 'set> SetTransaction<'block, 'set> {
 /// Same as [`Executable`], but instead of
 /// [`Wasm`](iroha_data_model::transaction::Executable::Wasm) contains hash of the WASM blob
 /// Which can be used to obtain compiled by `wasmtime` module
-#[derive(Clone, Serialize, Deserialize)]
+#[derive(Clone, Serialize, Deserialize, Encode, Decode)]
 pub enum ExecutableRef {
     /// Loaded WASM
     Wasm(HashOf<WasmSmartContract>),
@@ -8,6 +8,7 @@ use iroha_data_model::{
     metadata::Metadata,
     prelude::*,
 };
+use parity_scale_codec::{Decode, Encode};
 use serde::{Deserialize, Serialize};
 
 use crate::smartcontracts::triggers::set::ExecutableRef;
@@ -110,7 +111,7 @@ impl_try_from_box! {
 
 /// Same as [`iroha_data_model::trigger::action::Action`] but with
 /// a reference to a pre-loaded executable.
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, Encode, Decode)]
 pub struct LoadedAction<F> {
     /// Reference to the pre-loaded executable.
     pub(super) executable: ExecutableRef,
@@ -2,6 +2,7 @@
 
 use eyre::Result;
 use iroha_data_model::{
+    block::SignedBlock,
     prelude::*,
     query::{dsl::CompoundPredicate, error::QueryExecutionFail, CommittedTransaction},
 };
@@ -22,48 +23,47 @@ impl ValidQuery for FindTransactions {
             .all_blocks(nonzero!(1_usize))
             // Iterate over blocks in descending order (most recent first).
             .rev()
-            .flat_map(|block| {
-                let block_hash = block.hash();
-
-                // Iterate over transactions in descending order (most recent first).
-                let entrypoint_hashes = block.entrypoint_hashes().rev();
-                let entrypoint_proofs = block.entrypoint_proofs().rev();
-                let entrypoints = block.entrypoints_cloned().rev();
-                let result_hashes = block.result_hashes().rev();
-                let result_proofs = block.result_proofs().rev();
-                let results = block.results().cloned().rev();
-
-                entrypoint_hashes
-                    .zip(entrypoint_proofs)
-                    .zip(entrypoints)
-                    .zip(result_hashes)
-                    .zip(result_proofs)
-                    .zip(results)
-                    .map(
-                        |(
-                            (
-                                (((entrypoint_hash, entrypoint_proof), entrypoint), result_hash),
-                                result_proof,
-                            ),
-                            result,
-                        )| {
-                            CommittedTransaction {
-                                block_hash,
-                                entrypoint_hash,
-                                entrypoint_proof,
-                                entrypoint,
-                                result_hash,
-                                result_proof,
-                                result,
-                            }
-                        },
-                    )
-                    .collect::<Vec<_>>()
-            })
+            .flat_map(|block| committed_transactions(&block))
             .filter(move |tx| filter.applies(tx)))
     }
 }
 
+/// Transactions of the committed `block` in descending order (most recent first).
+pub(crate) fn committed_transactions(block: &SignedBlock) -> Vec<CommittedTransaction> {
+    let block_hash = block.hash();
+
+    let entrypoint_hashes = block.entrypoint_hashes().rev();
+    let entrypoint_proofs = block.entrypoint_proofs().rev();
+    let entrypoints = block.entrypoints_cloned().rev();
+    let result_hashes = block.result_hashes().rev();
+    let result_proofs = block.result_proofs().rev();
+    let results = block.results().cloned().rev();
+
+    entrypoint_hashes
+        .zip(entrypoint_proofs)
+        .zip(entrypoints)
+        .zip(result_hashes)
+        .zip(result_proofs)
+        .zip(results)
+        .map(
+            |(
+                ((((entrypoint_hash, entrypoint_proof), entrypoint), result_hash), result_proof),
+                result,
+            )| {
+                CommittedTransaction {
+                    block_hash,
+                    entrypoint_hash,
+                    entrypoint_proof,
+                    entrypoint,
+                    result_hash,
+                    result_proof,
+                    result,
+                }
+            },
+        )
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use iroha_data_model::prelude::{TransactionEntrypoint, TransactionResult};
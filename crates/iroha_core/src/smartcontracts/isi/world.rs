@@ -9,13 +9,15 @@ use crate::prelude::*;
 pub mod isi {
     use eyre::Result;
     use iroha_data_model::{
+        block::LeaderSeed,
         isi::error::{InstructionExecutionError, InvalidParameterError, RepetitionError},
-        parameter::{CustomParameter, Parameter},
+        parameter::{CustomParameter, Parameter, PeerWeight, PeerWeights},
         prelude::*,
         query::error::FindError,
         Level,
     };
     use iroha_primitives::{json::Json, unique_vec::PushResult};
+    use nonzero_ext::nonzero;
 
     use super::*;
 
@@ -29,6 +31,17 @@ pub mod isi {
             let peer_id = self.object;
 
             let world = &mut state_transaction.world;
+            let has_bls_key =
+                |peer: &PeerId| LeaderSeed::is_revealed_with(peer.public_key().algorithm());
+            // Otherwise the leaders following this peer would be predictable
+            if !has_bls_key(&peer_id)
+                && !world.peers.is_empty()
+                && world.peers.iter().all(has_bls_key)
+            {
+                return Err(InstructionExecutionError::InvariantViolation(format!(
+                    "Peer {peer_id} must have a BLS key like all the other peers to select leaders at random"
+                )));
+            }
             if let PushResult::Duplicate(duplicate) = world.peers.push(peer_id.clone()) {
                 return Err(RepetitionError {
                     instruction: InstructionType::Register,
@@ -57,6 +70,7 @@ pub mod isi {
             };
 
             world.peers.remove(index);
+            world.parameters.peer_weights.remove(&peer_id);
 
             world.emit_events(Some(PeerEvent::Removed(peer_id)));
 
@@ -313,6 +327,24 @@ pub mod isi {
         }
     }
 
+    /// Check that no peer holds a third of the total voting weight or more, so that
+    /// a single faulty peer is always tolerated and can never reach the commit quorum alone.
+    /// Peers with the default weight are exempt, as the quorum of a few equal peers
+    /// requires them all anyway.
+    fn check_peer_weights(peers: &[PeerId], weights: &PeerWeights) -> Result<(), Error> {
+        let weight = |peer: &PeerId| weights.get(peer).map_or(1, |weight| weight.get());
+        let total_weight = peers.iter().map(weight).fold(0_u64, u64::saturating_add);
+
+        match peers.iter().max_by_key(|&peer| weight(peer)) {
+            Some(peer) if weight(peer) > 1 && weight(peer).saturating_mul(3) >= total_weight => {
+                Err(InstructionExecutionError::InvariantViolation(format!(
+                    "Peer {peer} can't hold a third of the total voting weight or more"
+                )))
+            }
+            _ => Ok(()),
+        }
+    }
+
     impl Execute for SetParameter {
         #[metrics(+"set_parameter")]
         fn execute(
@@ -365,6 +397,31 @@ pub mod isi {
                                     new_value: Parameter::Custom(next),
                                 })));
                         }
+                        Parameter::PeerWeight(next) => {
+                            let world = &mut state_transaction.world;
+                            if !world.peers.contains(&next.peer) {
+                                return Err(FindError::Peer(next.peer).into());
+                            }
+                            let mut weights = world.parameters.peer_weights.clone();
+                            weights.insert(next.peer.clone(), next.weight);
+                            check_peer_weights(&world.peers, &weights)?;
+
+                            let mut prev_weights =
+                                core::mem::replace(&mut world.parameters.peer_weights, weights);
+                            let prev = prev_weights
+                                .remove(&next.peer)
+                                .unwrap_or(nonzero!(1_u64));
+
+                            state_transaction
+                                .world
+                                .emit_events(Some(ConfigurationEvent::Changed(ParameterChanged {
+                                    old_value: Parameter::PeerWeight(PeerWeight::new(
+                                        next.peer.clone(),
+                                        prev,
+                                    )),
+                                    new_value: Parameter::PeerWeight(next),
+                                })));
+                        }
                     }
                 };
             }
@@ -1,5 +1,10 @@
 //! This module contains [`State`] snapshot actor service.
+//!
+//! Snapshots are stored in a binary format described in [`format`].
+//! A full base snapshot is written to [`SNAPSHOT_FILE_NAME`], and in between
+//! base snapshots only the changed parts of the state are written as a delta.
 use std::{
+    collections::{BTreeMap, HashSet},
     io::Read,
     num::NonZeroUsize,
     path::{Path, PathBuf},
@@ -8,24 +13,42 @@ use std::{
 };
 
 use iroha_config::{parameters::actual::Snapshot as Config, snapshot::Mode};
-use iroha_crypto::HashOf;
-use iroha_data_model::block::BlockHeader;
+use iroha_crypto::{Hash, HashOf};
+use iroha_data_model::{
+    block::BlockHeader, parameter::PeerWeights, peer::PeerId, transaction::SignedTransaction,
+};
 use iroha_futures::supervisor::{Child, OnShutdown, ShutdownSignal};
 use iroha_logger::prelude::*;
-use serde::{de::DeserializeSeed, Serialize};
+use mv::{cell::Cell, storage::StorageReadOnly};
+use parity_scale_codec::{Decode, DecodeAll, Encode};
+use serde::de::DeserializeSeed;
 
+use self::format::{Entry, FormatError, Header, Section, SectionData, Snapshot};
 #[cfg(feature = "telemetry")]
 use crate::telemetry::StateTelemetry;
 use crate::{
+    executor::Executor,
     kura::{BlockCount, Kura},
     query::store::LiveQueryStoreHandle,
-    state::{deserialize::KuraSeed, State, StateReadOnly},
+    smartcontracts::{triggers::set::Set as TriggerSet, wasm},
+    state::{
+        deserialize::{KuraSeed, WasmSeed},
+        storage_transactions::{TransactionsStorage, TransactionsView},
+        State, StateReadOnly, World, WorldReadOnly, WorldView,
+    },
 };
 
-/// Name of the [`State`] snapshot file.
+pub mod format;
+pub mod history;
+
+/// Name of the base [`State`] snapshot file.
 const SNAPSHOT_FILE_NAME: &str = "snapshot.data";
 /// Name of the temporary [`State`] snapshot file.
 const SNAPSHOT_TMP_FILE_NAME: &str = "snapshot.tmp";
+/// Name of the delta snapshot file, applied on top of the base snapshot.
+const SNAPSHOT_DELTA_FILE_NAME: &str = "snapshot.delta";
+/// Name of the temporary delta snapshot file.
+const SNAPSHOT_DELTA_TMP_FILE_NAME: &str = "snapshot.delta.tmp";
 
 // /// Errors produced by [`SnapshotMaker`] actor.
 // pub type Result<T, E = Error> = core::result::Result<T, E>;
@@ -37,10 +60,29 @@ pub struct SnapshotMaker {
     create_every: Duration,
     /// Path to the directory where snapshots are stored
     store_dir: PathBuf,
+    /// Number of delta snapshots made on top of a base before a new base is made
+    deltas_per_base: u32,
+    /// Base snapshot which delta snapshots are made against
+    base: Option<BaseSnapshot>,
+    /// Number of base snapshots kept in the history
+    history_size: u32,
     /// Hash of the latest block stored in the state
     latest_block_hash: Option<HashOf<BlockHeader>>,
 }
 
+/// Summary of the base snapshot written to disk.
+#[derive(Debug, Clone)]
+pub struct BaseSnapshot {
+    /// Height of the state in the base snapshot
+    height: u64,
+    /// Digest of the base snapshot
+    digest: Hash,
+    /// Digests of the base snapshot sections by their names
+    sections: BTreeMap<String, SectionDigests>,
+    /// Number of deltas written on top of this base
+    deltas_written: u32,
+}
+
 impl SnapshotMaker {
     /// Start the actor.
     pub fn start(self, shutdown_signal: ShutdownSignal) -> Child {
@@ -84,14 +126,37 @@ impl SnapshotMaker {
 
         if latest_block_hash != self.latest_block_hash {
             let state = self.state.clone();
-            let handle = tokio::task::spawn_blocking(move || -> Result<(), TryWriteError> {
-                // TODO: enhance error by attaching `store_dir` parameter origin
-                try_write_snapshot(&state, store_dir)
-            });
+            let deltas_per_base = self.deltas_per_base;
+            let history_size = self.history_size;
+            // In case of failure the next snapshot is going to be a full one
+            let base = self.base.take();
+            let handle =
+                tokio::task::spawn_blocking(move || -> Result<BaseSnapshot, TryWriteError> {
+                    // TODO: enhance error by attaching `store_dir` parameter origin
+                    match base {
+                        Some(mut base) if base.deltas_written < deltas_per_base => {
+                            try_write_delta_snapshot(&state, store_dir, &base)?;
+                            base.deltas_written += 1;
+                            Ok(base)
+                        }
+                        _ => {
+                            let base = try_write_snapshot(&state, &store_dir)?;
+                            history::record(&store_dir, base.height, history_size)
+                                .map_err(|err| TryWriteError::IO(err, store_dir.clone()))?;
+                            prune_blocks(&state, &store_dir, base.height);
+                            Ok(base)
+                        }
+                    }
+                });
 
             match handle.await {
-                Ok(Ok(())) => {
-                    iroha_logger::info!(at_height, "Successfully created a snapshot of state");
+                Ok(Ok(base)) => {
+                    iroha_logger::info!(
+                        at_height,
+                        delta = base.deltas_written > 0,
+                        "Successfully created a snapshot of state"
+                    );
+                    self.base = Some(base);
                     self.latest_block_hash = latest_block_hash;
                 }
                 Ok(Err(error)) => {
@@ -114,6 +179,10 @@ impl SnapshotMaker {
                 state,
                 create_every: config.create_every_ms.get(),
                 store_dir: config.store_dir.resolve_relative_path(),
+                deltas_per_base: config.deltas_per_base,
+                history_size: config.history_size,
+                // First snapshot after start is always a full one
+                base: None,
                 latest_block_hash,
             })
         } else {
@@ -124,6 +193,10 @@ impl SnapshotMaker {
 
 /// Try to deserialize [`State`] from a snapshot file.
 ///
+/// Snapshots in the legacy JSON format are read as well,
+/// they are replaced with binary ones by the next written snapshot
+/// or by [`migrate_legacy_snapshot`].
+///
 /// # Errors
 /// - IO errors
 /// - Deserialization errors
@@ -134,28 +207,38 @@ pub fn try_read_snapshot(
     BlockCount(block_count): BlockCount,
     #[cfg(feature = "telemetry")] telemetry: StateTelemetry,
 ) -> Result<State, TryReadError> {
-    let mut bytes = Vec::new();
-    let path = store_dir.as_ref().join(SNAPSHOT_FILE_NAME);
-    let mut file = match std::fs::OpenOptions::new().read(true).open(&path) {
-        Ok(file) => file,
-        Err(err) => {
-            return if err.kind() == std::io::ErrorKind::NotFound {
-                Err(TryReadError::NotFound)
-            } else {
-                Err(TryReadError::IO(err, path.clone()))
-            }
-        }
-    };
-    file.read_to_end(&mut bytes)
-        .map_err(|err| TryReadError::IO(err, path.clone()))?;
-    let mut deserializer = serde_json::Deserializer::from_slice(&bytes);
+    let store_dir = store_dir.as_ref();
+    let path = store_dir.join(SNAPSHOT_FILE_NAME);
+    let bytes = read_file(&path)
+        .map_err(|err| TryReadError::IO(err, path))?
+        .ok_or(TryReadError::NotFound)?;
     let seed = KuraSeed {
         kura: Arc::clone(kura),
         query_handle: live_query_store_lazy(),
         #[cfg(feature = "telemetry")]
         telemetry,
     };
-    let state = seed.deserialize(&mut deserializer)?;
+    let state = if Snapshot::is_binary(&bytes) {
+        let base = Snapshot::from_bytes(&bytes)?;
+        let delta = read_delta_snapshot(store_dir, &base);
+        decode_state(resolve_sections(&base, delta.as_ref())?, seed)?
+    } else {
+        iroha_logger::warn!("Reading snapshot stored in the legacy JSON format");
+        let mut deserializer = serde_json::Deserializer::from_slice(&bytes);
+        seed.deserialize(&mut deserializer)?
+    };
+    check_against_kura(state, kura, block_count)
+}
+
+/// Check that the blocks applied to the `state` are the ones stored in [`Kura`].
+///
+/// If only the latest block differs, it is considered a soft-fork
+/// and changes made by this block are discarded.
+fn check_against_kura(
+    state: State,
+    kura: &Kura,
+    block_count: usize,
+) -> Result<State, TryReadError> {
     let state_view = state.view();
     let snapshot_height = state_view.height();
     if snapshot_height > block_count {
@@ -165,22 +248,26 @@ pub fn try_read_snapshot(
         });
     }
     for height in 1..=snapshot_height {
-        let kura_block = kura
-            .get_block(NonZeroUsize::new(height).expect("iterating from 1"))
+        let height_nonzero = NonZeroUsize::new(height).expect("iterating from 1");
+        let kura_block_hash = kura
+            .get_block_hash(height_nonzero)
             .expect("Kura has height at least as large as state height");
         let snapshot_block_hash = state_view.block_hashes[height - 1];
-        if kura_block.hash() != snapshot_block_hash {
+        if kura_block_hash != snapshot_block_hash {
             // If last block hash is different it might mean that snapshot was crated for soft-fork block so just drop changes made by this block
             if height == snapshot_height {
                 iroha_logger::warn!(
                     "Snapshot has incorrect latest block hash, discarding changes made by this block"
                 );
+                let kura_block = kura
+                    .get_block(height_nonzero)
+                    .expect("Block at snapshot height is never pruned");
                 state.block_and_revert(kura_block.header()).commit();
             } else {
                 return Err(TryReadError::MismatchedHash {
                     height,
                     snapshot_block_hash,
-                    kura_block_hash: kura_block.hash(),
+                    kura_block_hash,
                 });
             }
         }
@@ -188,25 +275,545 @@ pub fn try_read_snapshot(
     Ok(state)
 }
 
-/// Serialize and write snapshot to file,
-/// overwriting any previously stored data.
+/// Let [`Kura`] prune blocks covered by the base snapshot at `height`.
+///
+/// Blocks are pruned only once the snapshot is read back intact.
+fn prune_blocks(state: &State, store_dir: &Path, height: u64) {
+    let state_view = state.view();
+    let kura = state_view.kura();
+    if !kura.prunes_blocks() {
+        return;
+    }
+    match verify_snapshot(store_dir) {
+        Ok(()) => kura.prune_blocks(
+            usize::try_from(height).expect("INTERNAL BUG: block height exceeds usize::MAX"),
+        ),
+        Err(error) => {
+            warn!(%error, "Written snapshot can't be read back, keeping old blocks");
+        }
+    }
+}
+
+/// Check that every section of the base snapshot can be read and matches its digest.
+fn verify_snapshot(store_dir: &Path) -> Result<(), TryReadError> {
+    let path = store_dir.join(SNAPSHOT_FILE_NAME);
+    let bytes = read_file(&path)
+        .map_err(|err| TryReadError::IO(err, path))?
+        .ok_or(TryReadError::NotFound)?;
+    for section in Snapshot::from_bytes(&bytes)?.sections {
+        section.data()?;
+    }
+    Ok(())
+}
+
+/// Rewrite snapshot stored in the legacy JSON format into the binary format.
+///
+/// Returns `false` if the snapshot is already in the binary format.
 ///
 /// # Errors
 /// - IO errors
+/// - Legacy snapshot is not a valid JSON snapshot
 /// - Serialization errors
-fn try_write_snapshot(state: &State, store_dir: impl AsRef<Path>) -> Result<(), TryWriteError> {
-    std::fs::create_dir_all(store_dir.as_ref())
-        .map_err(|err| TryWriteError::IO(err, store_dir.as_ref().to_path_buf()))?;
-    let path_to_file = store_dir.as_ref().join(SNAPSHOT_FILE_NAME);
-    let path_to_tmp_file = store_dir.as_ref().join(SNAPSHOT_TMP_FILE_NAME);
-    let file = std::fs::OpenOptions::new()
-        .create(true)
-        .write(true)
-        .truncate(true)
-        .open(&path_to_tmp_file)
+pub fn migrate_legacy_snapshot(store_dir: impl AsRef<Path>) -> Result<bool, MigrateError> {
+    let store_dir = store_dir.as_ref();
+    let path = store_dir.join(SNAPSHOT_FILE_NAME);
+    let bytes = read_file(&path)
+        .map_err(|err| MigrateError::IO(err, path))?
+        .ok_or(MigrateError::NotFound)?;
+    if Snapshot::is_binary(&bytes) {
+        return Ok(false);
+    }
+
+    let serde_json::Value::Object(mut root) = serde_json::from_slice(&bytes)? else {
+        return Err(MigrateError::Malformed("state is not a JSON object"));
+    };
+    let mut field = |name: &'static str| root.remove(name).ok_or(MigrateError::MissingField(name));
+    let engine = wasm::create_engine();
+    let world = WasmSeed::new(&engine)
+        .cast::<World>()
+        .deserialize(field("world")?)?;
+    let block_hashes: Vec<HashOf<BlockHeader>> = serde_json::from_value(field("block_hashes")?)?;
+    let transactions: TransactionsStorage = serde_json::from_value(field("transactions")?)?;
+    let commit_topology: Vec<PeerId> = serde_json::from_value(field("commit_topology")?)?;
+    let prev_commit_topology: Vec<PeerId> = serde_json::from_value(field("prev_commit_topology")?)?;
+    // NOTE: Legacy snapshots predate peer weights, so all peers had the default weight
+    let weights = PeerWeights::new();
+
+    let mut writer = SectionWriter::new(None);
+    writer
+        .state(
+            &world.view(),
+            &block_hashes,
+            &transactions.view(),
+            (&commit_topology, &weights),
+            (&prev_commit_topology, &weights),
+        )
+        .map_err(|err| TryWriteError::IO(err, store_dir.to_path_buf()))?;
+    write_base_snapshot(
+        store_dir,
+        block_hashes.len() as u64,
+        block_hashes.last().copied(),
+        writer,
+    )?;
+    Ok(true)
+}
+
+/// Read the whole file, returning [`None`] if it doesn't exist.
+fn read_file(path: &Path) -> std::io::Result<Option<Vec<u8>>> {
+    let mut file = match std::fs::OpenOptions::new().read(true).open(path) {
+        Ok(file) => file,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(err) => return Err(err),
+    };
+    let mut bytes = Vec::new();
+    file.read_to_end(&mut bytes)?;
+    Ok(Some(bytes))
+}
+
+/// Read delta snapshot made on top of the `base` snapshot.
+///
+/// Delta is only an optimization, so any problem with it is logged
+/// and the state is restored from the base snapshot alone.
+fn read_delta_snapshot(store_dir: &Path, base: &Snapshot) -> Option<Snapshot> {
+    let bytes = match read_file(&store_dir.join(SNAPSHOT_DELTA_FILE_NAME)) {
+        Ok(bytes) => bytes?,
+        Err(error) => {
+            iroha_logger::warn!(%error, "Failed to read delta snapshot, ignoring it");
+            return None;
+        }
+    };
+    let delta = match Snapshot::from_bytes(&bytes) {
+        Ok(delta) => delta,
+        Err(error) => {
+            iroha_logger::warn!(%error, "Delta snapshot is corrupted, ignoring it");
+            return None;
+        }
+    };
+    if delta.header.base != Some(base.digest()) {
+        iroha_logger::warn!("Delta snapshot was made against another base snapshot, ignoring it");
+        return None;
+    }
+    Some(delta)
+}
+
+/// Decoded sections of the `base` snapshot with the `delta` applied on top.
+fn resolve_sections(base: &Snapshot, delta: Option<&Snapshot>) -> Result<Sections, FormatError> {
+    let Some(delta) = delta else {
+        return base
+            .sections
+            .iter()
+            .map(|section| {
+                let data = section
+                    .data()?
+                    .ok_or_else(|| FormatError::MissingSection(section.name.clone()))?;
+                Ok((section.name.clone(), data))
+            })
+            .collect::<Result<_, _>>()
+            .map(Sections);
+    };
+
+    delta
+        .sections
+        .iter()
+        .map(|section| {
+            let base_data = || {
+                base.sections
+                    .iter()
+                    .find(|base_section| base_section.name == section.name)
+                    .map(Section::data)
+                    .transpose()
+                    .map(Option::flatten)
+            };
+            let data = match section.data()? {
+                Some(data @ SectionData::Changes { .. }) => data
+                    .rebase(base_data()?)
+                    .map_err(|_| FormatError::UnexpectedSection(section.name.clone()))?,
+                Some(data) => data,
+                None => base
+                    .sections
+                    .iter()
+                    .find(|base_section| {
+                        base_section.name == section.name && base_section.digest == section.digest
+                    })
+                    .map(Section::data)
+                    .transpose()?
+                    .flatten()
+                    .ok_or_else(|| FormatError::MissingSection(section.name.clone()))?,
+            };
+            Ok((section.name.clone(), data))
+        })
+        .collect::<Result<_, _>>()
+        .map(Sections)
+}
+
+/// Decoded sections of a snapshot by their names.
+struct Sections(BTreeMap<String, SectionData>);
+
+impl Sections {
+    fn take(&mut self, name: &str) -> Result<SectionData, FormatError> {
+        self.0
+            .remove(name)
+            .ok_or_else(|| FormatError::MissingSection(name.to_owned()))
+    }
+
+    /// Take the encoded value stored in the section.
+    fn raw_value(&mut self, name: &str) -> Result<Vec<u8>, FormatError> {
+        match self.take(name)? {
+            SectionData::Value(raw) => Ok(raw),
+            _ => Err(FormatError::UnexpectedSection(name.to_owned())),
+        }
+    }
+
+    /// Take and decode the value stored in the section.
+    fn value<T: Decode>(&mut self, name: &str) -> Result<T, FormatError> {
+        Ok(T::decode_all(&mut self.raw_value(name)?.as_slice())?)
+    }
+
+    /// Take and decode entries of the keyed storage stored in the section.
+    fn entries<K: Decode, V: Decode, C: FromIterator<(K, V)>>(
+        &mut self,
+        name: &str,
+    ) -> Result<C, FormatError> {
+        let SectionData::Entries(entries) = self.take(name)? else {
+            return Err(FormatError::UnexpectedSection(name.to_owned()));
+        };
+        entries
+            .into_iter()
+            .map(|Entry { key, value }| {
+                Ok((
+                    K::decode_all(&mut key.as_slice())?,
+                    V::decode_all(&mut value.as_slice())?,
+                ))
+            })
+            .collect()
+    }
+}
+
+/// Assemble [`State`] from the decoded sections.
+fn decode_state(mut sections: Sections, seed: KuraSeed) -> Result<State, TryReadError> {
+    let engine = wasm::create_engine();
+    let world = World {
+        parameters: Cell::new(sections.value("world.parameters")?),
+        peers: Cell::new(sections.value("world.peers")?),
+        domains: sections.entries("world.domains")?,
+        accounts: sections.entries("world.accounts")?,
+        asset_definitions: sections.entries("world.asset_definitions")?,
+        assets: sections.entries("world.assets")?,
+        nfts: sections.entries("world.nfts")?,
+        roles: sections.entries("world.roles")?,
+        account_permissions: sections.entries("world.account_permissions")?,
+        account_roles: sections.entries("world.account_roles")?,
+        triggers: TriggerSet::decode_snapshot(&engine, &sections.raw_value("world.triggers")?)?,
+        executor: Cell::new(Executor::from_raw_executor(
+            &engine,
+            sections.value("world.executor")?,
+        )?),
+        executor_data_model: Cell::new(sections.value("world.executor_data_model")?),
+        // Events are only buffered while a block is being applied
+        external_event_buf: Cell::new(Vec::new()),
+    };
+
+    let block_hashes = sections
+        .entries::<u64, HashOf<BlockHeader>, BTreeMap<_, _>>("block_hashes")?
+        .into_iter()
+        .enumerate()
+        .map(|(expected, (height, hash))| {
+            (height == expected as u64)
+                .then_some(hash)
+                .ok_or_else(|| FormatError::UnexpectedSection("block_hashes".to_owned()))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    let transactions = sections
+        .entries::<(HashOf<SignedTransaction>, u64), (), Vec<_>>("transactions")?
+        .into_iter()
+        .map(|((hash, height), ())| {
+            usize::try_from(height)
+                .ok()
+                .and_then(NonZeroUsize::new)
+                .map(|height| (hash, height))
+                .ok_or_else(|| FormatError::UnexpectedSection("transactions".to_owned()))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    let transactions = TransactionsStorage::from_entries(transactions, block_hashes.len());
+
+    Ok(seed.into_state(
+        world,
+        block_hashes,
+        transactions,
+        sections.value("commit_topology")?,
+        sections.value("prev_commit_topology")?,
+        sections.value("commit_weights")?,
+        sections.value("prev_commit_weights")?,
+        engine,
+    ))
+}
+
+/// Digests of a base snapshot section used to find what changed since the base.
+#[derive(Debug, Clone)]
+enum SectionDigests {
+    /// Digest of the whole section
+    Value(Hash),
+    /// Digests of the values of a keyed storage by their encoded keys
+    Entries(BTreeMap<Vec<u8>, Hash>),
+}
+
+/// Builder of snapshot sections.
+///
+/// Given the digests of the base snapshot, only the changes made since the base are kept.
+struct SectionWriter<'base> {
+    base: Option<&'base BTreeMap<String, SectionDigests>>,
+    sections: Vec<Section>,
+    digests: BTreeMap<String, SectionDigests>,
+}
+
+impl<'base> SectionWriter<'base> {
+    fn new(base: Option<&'base BTreeMap<String, SectionDigests>>) -> Self {
+        Self {
+            base,
+            sections: Vec::new(),
+            digests: BTreeMap::new(),
+        }
+    }
+
+    /// Write all sections of the state seen through the given views.
+    fn state(
+        &mut self,
+        world: &WorldView,
+        block_hashes: &[HashOf<BlockHeader>],
+        transactions: &TransactionsView,
+        (commit_topology, commit_weights): (&[PeerId], &PeerWeights),
+        (prev_commit_topology, prev_commit_weights): (&[PeerId], &PeerWeights),
+    ) -> std::io::Result<()> {
+        self.value("world.parameters", world.parameters())?;
+        self.value("world.peers", world.peers())?;
+        self.entries("world.domains", world.domains().iter())?;
+        self.entries("world.accounts", world.accounts().iter())?;
+        self.entries("world.asset_definitions", world.asset_definitions().iter())?;
+        self.entries("world.assets", world.assets().iter())?;
+        self.entries("world.nfts", world.nfts().iter())?;
+        self.entries("world.roles", world.roles().iter())?;
+        self.entries(
+            "world.account_permissions",
+            world.account_permissions().iter(),
+        )?;
+        self.entries("world.account_roles", world.account_roles().iter())?;
+        self.raw_value("world.triggers", world.triggers.encode_snapshot())?;
+        self.value("world.executor", &world.executor().raw_executor())?;
+        self.value("world.executor_data_model", world.executor_data_model())?;
+        self.entries(
+            "block_hashes",
+            block_hashes
+                .iter()
+                .enumerate()
+                .map(|(height, hash)| (height as u64, hash)),
+        )?;
+        self.entries(
+            "transactions",
+            transactions
+                .iter()
+                .map(|(hash, height)| ((hash, height.get() as u64), ())),
+        )?;
+        self.value("commit_topology", &commit_topology)?;
+        self.value("prev_commit_topology", &prev_commit_topology)?;
+        self.value("commit_weights", commit_weights)?;
+        self.value("prev_commit_weights", prev_commit_weights)
+    }
+
+    /// Write section holding a single `value`.
+    fn value(&mut self, name: &str, value: &impl Encode) -> std::io::Result<()> {
+        self.raw_value(name, value.encode())
+    }
+
+    /// Write section holding a single encoded value.
+    fn raw_value(&mut self, name: &str, raw: Vec<u8>) -> std::io::Result<()> {
+        let data = SectionData::Value(raw).encode();
+        let digest = Hash::new(&data);
+        let section = match self.base.and_then(|base| base.get(name)) {
+            Some(SectionDigests::Value(base_digest)) if *base_digest == digest => {
+                Section::in_base(name, digest)
+            }
+            _ => Section::inline(name, &data)?,
+        };
+        self.sections.push(section);
+        self.digests
+            .insert(name.to_owned(), SectionDigests::Value(digest));
+        Ok(())
+    }
+
+    /// Write section holding entries of a keyed storage.
+    ///
+    /// Against a base only upserted and removed entries are written.
+    fn entries<K: Encode, V: Encode>(
+        &mut self,
+        name: &str,
+        entries: impl Iterator<Item = (K, V)>,
+    ) -> std::io::Result<()> {
+        let entries = entries.map(|(key, value)| Entry {
+            key: key.encode(),
+            value: value.encode(),
+        });
+
+        let data = if let Some(SectionDigests::Entries(base)) =
+            self.base.and_then(|base| base.get(name))
+        {
+            let mut present = HashSet::new();
+            let upserted = entries
+                .filter(|entry| match base.get_key_value(&entry.key) {
+                    Some((key, digest)) => {
+                        present.insert(key.as_slice());
+                        *digest != Hash::new(&entry.value)
+                    }
+                    None => true,
+                })
+                .collect();
+            let removed = base
+                .keys()
+                .filter(|key| !present.contains(key.as_slice()))
+                .cloned()
+                .collect();
+            SectionData::Changes { upserted, removed }
+        } else {
+            let entries = entries.collect::<Vec<_>>();
+            self.digests.insert(
+                name.to_owned(),
+                SectionDigests::Entries(
+                    entries
+                        .iter()
+                        .map(|entry| (entry.key.clone(), Hash::new(&entry.value)))
+                        .collect(),
+                ),
+            );
+            SectionData::Entries(entries)
+        };
+        self.sections.push(Section::inline(name, &data.encode())?);
+        Ok(())
+    }
+}
+
+/// Serialize and write full snapshot to file,
+/// overwriting any previously stored data.
+///
+/// # Errors
+/// - IO errors
+fn try_write_snapshot(
+    state: &State,
+    store_dir: impl AsRef<Path>,
+) -> Result<BaseSnapshot, TryWriteError> {
+    let store_dir = store_dir.as_ref();
+    let state_view = state.view();
+    let mut writer = SectionWriter::new(None);
+    writer
+        .state(
+            &state_view.world,
+            &state_view.block_hashes,
+            &state_view.transactions,
+            (&state_view.commit_topology, &state_view.commit_weights),
+            (
+                &state_view.prev_commit_topology,
+                &state_view.prev_commit_weights,
+            ),
+        )
+        .map_err(|err| TryWriteError::IO(err, store_dir.to_path_buf()))?;
+    write_base_snapshot(
+        store_dir,
+        state_view.height() as u64,
+        state_view.latest_block_hash(),
+        writer,
+    )
+}
+
+/// Serialize and write delta snapshot containing changes made since the `base`.
+///
+/// # Errors
+/// - IO errors
+fn try_write_delta_snapshot(
+    state: &State,
+    store_dir: impl AsRef<Path>,
+    base: &BaseSnapshot,
+) -> Result<(), TryWriteError> {
+    let store_dir = store_dir.as_ref();
+    let state_view = state.view();
+    let mut writer = SectionWriter::new(Some(&base.sections));
+    writer
+        .state(
+            &state_view.world,
+            &state_view.block_hashes,
+            &state_view.transactions,
+            (&state_view.commit_topology, &state_view.commit_weights),
+            (
+                &state_view.prev_commit_topology,
+                &state_view.prev_commit_weights,
+            ),
+        )
+        .map_err(|err| TryWriteError::IO(err, store_dir.to_path_buf()))?;
+    let snapshot = Snapshot {
+        header: Header {
+            height: state_view.height() as u64,
+            latest_block_hash: state_view.latest_block_hash(),
+            base: Some(base.digest),
+        },
+        sections: writer.sections,
+    };
+
+    write_file(
+        store_dir,
+        SNAPSHOT_DELTA_FILE_NAME,
+        SNAPSHOT_DELTA_TMP_FILE_NAME,
+        &snapshot.to_bytes(),
+    )
+}
+
+/// Write base snapshot made of the `writer` sections and remove the outdated delta snapshot.
+fn write_base_snapshot(
+    store_dir: &Path,
+    height: u64,
+    latest_block_hash: Option<HashOf<BlockHeader>>,
+    writer: SectionWriter,
+) -> Result<BaseSnapshot, TryWriteError> {
+    let snapshot = Snapshot {
+        header: Header {
+            height,
+            latest_block_hash,
+            base: None,
+        },
+        sections: writer.sections,
+    };
+
+    write_file(
+        store_dir,
+        SNAPSHOT_FILE_NAME,
+        SNAPSHOT_TMP_FILE_NAME,
+        &snapshot.to_bytes(),
+    )?;
+    // Delta made against the previous base is ignored on read anyway
+    let path_to_delta = store_dir.join(SNAPSHOT_DELTA_FILE_NAME);
+    match std::fs::remove_file(&path_to_delta) {
+        Err(err) if err.kind() != std::io::ErrorKind::NotFound => {
+            return Err(TryWriteError::IO(err, path_to_delta));
+        }
+        _ => {}
+    }
+
+    Ok(BaseSnapshot {
+        height,
+        digest: snapshot.digest(),
+        sections: writer.digests,
+        deltas_written: 0,
+    })
+}
+/// Write `bytes` to a temporary file and atomically move it into place.
+fn write_file(
+    store_dir: &Path,
+    file_name: &str,
+    tmp_file_name: &str,
+    bytes: &[u8],
+) -> Result<(), TryWriteError> {
+    std::fs::create_dir_all(store_dir)
+        .map_err(|err| TryWriteError::IO(err, store_dir.to_path_buf()))?;
+    let path_to_file = store_dir.join(file_name);
+    let path_to_tmp_file = store_dir.join(tmp_file_name);
+    std::fs::write(&path_to_tmp_file, bytes)
         .map_err(|err| TryWriteError::IO(err, path_to_tmp_file.clone()))?;
-    let mut serializer = serde_json::Serializer::new(file);
-    state.serialize(&mut serializer)?;
     std::fs::rename(path_to_tmp_file, &path_to_file)
         .map_err(|err| TryWriteError::IO(err, path_to_file.clone()))?;
     Ok(())
@@ -221,6 +828,14 @@ pub enum TryReadError {
     IO(#[source] std::io::Error, PathBuf),
     /// Error (de)serializing state snapshot
     Serialization(#[from] serde_json::Error),
+    /// Snapshot file is malformed
+    Format(#[from] FormatError),
+    /// Failed to load triggers from the snapshot
+    Triggers(#[from] crate::smartcontracts::triggers::set::Error),
+    /// Failed to load executor from the snapshot
+    Executor(#[from] wasm::error::Error),
+    /// State at height {0} can't be restored from the snapshot history
+    HeightUnavailable(usize),
     /// Snapshot is in a non-consistent state. Snapshot has greater height (`snapshot_height`) than kura block store (`kura_height`)
     MismatchedHeight {
         /// The amount of block hashes stored by snapshot
@@ -241,11 +856,26 @@ pub enum TryReadError {
 
 /// Error variants for snapshot writing
 #[derive(thiserror::Error, Debug, displaydoc::Display)]
-enum TryWriteError {
+pub enum TryWriteError {
+    /// Failed reading/writing {1:?} from disk
+    IO(#[source] std::io::Error, PathBuf),
+}
+
+/// Error variants for legacy snapshot migration
+#[derive(thiserror::Error, Debug, displaydoc::Display)]
+pub enum MigrateError {
+    /// The snapshot was not found
+    NotFound,
     /// Failed reading/writing {1:?} from disk
     IO(#[source] std::io::Error, PathBuf),
-    /// Error (de)serializing World State View snapshot
+    /// Error deserializing legacy state snapshot
     Serialization(#[from] serde_json::Error),
+    /// Legacy state snapshot is malformed: {0}
+    Malformed(&'static str),
+    /// Legacy state snapshot is missing `{0}`
+    MissingField(&'static str),
+    /// Failed to write migrated snapshot
+    Write(#[from] TryWriteError),
 }
 
 #[cfg(test)]
@@ -253,8 +883,8 @@ mod tests {
     use std::{fs::File, io::Write};
 
     use iroha_crypto::KeyPair;
-    use iroha_data_model::peer::PeerId;
     use nonzero_ext::nonzero;
+    use serde::Serialize;
     use tempfile::tempdir;
     use tokio::test;
 
@@ -345,6 +975,126 @@ mod tests {
         assert_eq!(format!("{error}"), "Error (de)serializing state snapshot");
     }
 
+    #[test]
+    async fn can_read_legacy_snapshot_after_migration() {
+        let tmp_root = tempdir().unwrap();
+        let store_dir = tmp_root.path().join("snapshot");
+        std::fs::create_dir(&store_dir).unwrap();
+        let state = state_factory();
+        {
+            let file = File::create(store_dir.join(SNAPSHOT_FILE_NAME)).unwrap();
+            state
+                .serialize(&mut serde_json::Serializer::new(file))
+                .unwrap();
+        }
+
+        assert!(migrate_legacy_snapshot(&store_dir).unwrap());
+        assert!(!migrate_legacy_snapshot(&store_dir).unwrap());
+        let _wsv = try_read_snapshot(
+            &store_dir,
+            &Kura::blank_kura_for_testing(),
+            LiveQueryStore::start_test,
+            BlockCount(state.view().height()),
+            #[cfg(feature = "telemetry")]
+            StateTelemetry::default(),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    async fn can_read_delta_snapshot() {
+        let tmp_root = tempdir().unwrap();
+        let store_dir = tmp_root.path().join("snapshot");
+        let kura = Kura::blank_kura_for_testing();
+        let state = state_factory();
+
+        let base = try_write_snapshot(&state, &store_dir).unwrap();
+
+        let peer_key_pair = KeyPair::random();
+        let peer_id = PeerId::new(peer_key_pair.public_key().clone());
+        let topology = Topology::new(vec![peer_id]);
+        let valid_block =
+            ValidBlock::new_dummy_and_modify_header(peer_key_pair.private_key(), |header| {
+                header.height = nonzero!(1u64);
+            });
+        let committed_block = valid_block
+            .clone()
+            .commit(&topology)
+            .unpack(|_| {})
+            .unwrap();
+
+        {
+            let mut state_block = state.block(committed_block.as_ref().header());
+            let _events =
+                state_block.apply_without_execution(&committed_block, topology.as_ref().to_owned());
+            state_block.commit();
+        }
+        kura.store_block(committed_block);
+
+        try_write_delta_snapshot(&state, &store_dir, &base).unwrap();
+
+        let state = try_read_snapshot(
+            &store_dir,
+            &kura,
+            LiveQueryStore::start_test,
+            BlockCount(state.view().height()),
+            #[cfg(feature = "telemetry")]
+            StateTelemetry::default(),
+        )
+        .unwrap();
+
+        assert_eq!(state.view().height(), 1);
+    }
+
+    #[test]
+    async fn delta_snapshot_keeps_only_changed_entries() {
+        let tmp_root = tempdir().unwrap();
+        let store_dir = tmp_root.path().join("snapshot");
+        let state = state_factory();
+
+        let base = try_write_snapshot(&state, &store_dir).unwrap();
+
+        let peer_key_pair = KeyPair::random();
+        let topology = Topology::new(vec![PeerId::new(peer_key_pair.public_key().clone())]);
+        let committed_block =
+            ValidBlock::new_dummy_and_modify_header(peer_key_pair.private_key(), |header| {
+                header.height = nonzero!(1u64);
+            })
+            .commit(&topology)
+            .unpack(|_| {})
+            .unwrap();
+        {
+            let mut state_block = state.block(committed_block.as_ref().header());
+            let _events =
+                state_block.apply_without_execution(&committed_block, topology.as_ref().to_owned());
+            state_block.commit();
+        }
+
+        try_write_delta_snapshot(&state, &store_dir, &base).unwrap();
+
+        let bytes = std::fs::read(store_dir.join(SNAPSHOT_DELTA_FILE_NAME)).unwrap();
+        let delta = Snapshot::from_bytes(&bytes).unwrap();
+        let section = |name: &str| {
+            delta
+                .sections
+                .iter()
+                .find(|section| section.name == name)
+                .unwrap()
+                .data()
+                .unwrap()
+        };
+        assert!(section("world.parameters").is_none());
+        let Some(SectionData::Changes { upserted, removed }) = section("world.domains") else {
+            panic!("domains should be stored as changes")
+        };
+        assert!(upserted.is_empty() && removed.is_empty());
+        let Some(SectionData::Changes { upserted, removed }) = section("block_hashes") else {
+            panic!("block hashes should be stored as changes")
+        };
+        assert_eq!(upserted.len(), 1);
+        assert!(removed.is_empty());
+    }
+
     #[test]
     async fn can_read_multiple_blocks() {
         let tmp_root = tempdir().unwrap();
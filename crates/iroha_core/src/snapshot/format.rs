@@ -0,0 +1,345 @@
+//! On-disk layout of binary [`State`](crate::state::State) snapshots.
+//!
+//! A snapshot file starts with [`MAGIC`], the format version byte and a CRC32
+//! checksum of the remaining bytes, which hold a SCALE-encoded [`Snapshot`].
+//! The state itself is split into sections, one per top-level storage.
+//! Each section holds a SCALE-encoded [`SectionData`], compressed with zstd
+//! and protected by its own checksum. Keyed storages are stored entry by entry.
+//!
+//! A delta snapshot keeps only the changes made since its base snapshot:
+//! unchanged sections are marked as [`SectionBody::InBase`] and keyed storages
+//! store only upserted and removed entries as [`SectionData::Changes`].
+
+use std::collections::BTreeMap;
+
+use iroha_crypto::{Hash, HashOf};
+use iroha_data_model::block::BlockHeader;
+use parity_scale_codec::{Decode, DecodeAll, Encode};
+
+/// Magic bytes at the start of every binary snapshot file.
+pub const MAGIC: [u8; 4] = *b"IRSN";
+/// Current version of the binary snapshot format.
+pub const VERSION: u8 = 1;
+/// Length of the magic bytes, version and checksum preceding the encoded [`Snapshot`].
+const PREFIX_LEN: usize = MAGIC.len() + 1 + 4;
+/// Compression level used for snapshot sections.
+const COMPRESSION_LEVEL: i32 = 3;
+
+/// Binary snapshot of the [`State`](crate::state::State), either a base or a delta.
+#[derive(Debug, Clone, Encode, Decode)]
+pub struct Snapshot {
+    /// Information about the snapshot
+    pub header: Header,
+    /// Serialized parts of the state
+    pub sections: Vec<Section>,
+}
+
+/// Header of the [`Snapshot`].
+#[derive(Debug, Clone, Encode, Decode)]
+pub struct Header {
+    /// Height of the state at which the snapshot was made
+    pub height: u64,
+    /// Hash of the latest block applied to the state
+    pub latest_block_hash: Option<HashOf<BlockHeader>>,
+    /// Digest of the base snapshot if this snapshot is a delta
+    pub base: Option<Hash>,
+}
+
+/// Named part of the serialized state.
+#[derive(Debug, Clone, Encode, Decode)]
+pub struct Section {
+    /// Name of the section, e.g. `world.accounts`
+    pub name: String,
+    /// Hash of the uncompressed and encoded [`SectionData`]
+    pub digest: Hash,
+    /// Contents of the section
+    pub body: SectionBody,
+}
+
+/// Contents of the [`Section`].
+#[derive(Debug, Clone, Encode, Decode)]
+pub enum SectionBody {
+    /// Section is stored in this snapshot
+    Inline {
+        /// CRC32 of `data`
+        checksum: u32,
+        /// zstd-compressed [`SectionData`]
+        data: Vec<u8>,
+    },
+    /// Section hasn't changed and has to be taken from the base snapshot
+    InBase,
+}
+
+/// Uncompressed contents of the [`Section`].
+#[derive(Debug, Clone, PartialEq, Eq, Encode, Decode)]
+pub enum SectionData {
+    /// Single SCALE-encoded value
+    Value(Vec<u8>),
+    /// All entries of a keyed storage
+    Entries(Vec<Entry>),
+    /// Entries of a keyed storage changed since the base snapshot
+    Changes {
+        /// Entries inserted or updated since the base snapshot
+        upserted: Vec<Entry>,
+        /// Encoded keys of the entries removed since the base snapshot
+        removed: Vec<Vec<u8>>,
+    },
+}
+
+/// Entry of a keyed storage.
+#[derive(Debug, Clone, PartialEq, Eq, Encode, Decode)]
+pub struct Entry {
+    /// SCALE-encoded key
+    pub key: Vec<u8>,
+    /// SCALE-encoded value
+    pub value: Vec<u8>,
+}
+
+impl Section {
+    /// Compress encoded [`SectionData`] and store it inline.
+    ///
+    /// # Errors
+    /// Fails if compression fails
+    pub fn inline(name: impl Into<String>, raw: &[u8]) -> std::io::Result<Self> {
+        let data = zstd::encode_all(raw, COMPRESSION_LEVEL)?;
+        Ok(Self {
+            name: name.into(),
+            digest: Hash::new(raw),
+            body: SectionBody::Inline {
+                checksum: crc32fast::hash(&data),
+                data,
+            },
+        })
+    }
+
+    /// Reference section with the given `digest` from the base snapshot.
+    pub fn in_base(name: impl Into<String>, digest: Hash) -> Self {
+        Self {
+            name: name.into(),
+            digest,
+            body: SectionBody::InBase,
+        }
+    }
+
+    /// Verify and decompress section contents.
+    ///
+    /// Returns [`None`] if the section is stored in the base snapshot.
+    ///
+    /// # Errors
+    /// - Checksum or digest doesn't match
+    /// - Decompression fails
+    pub fn decompress(&self) -> Result<Option<Vec<u8>>, FormatError> {
+        let SectionBody::Inline { checksum, data } = &self.body else {
+            return Ok(None);
+        };
+        if crc32fast::hash(data) != *checksum {
+            return Err(FormatError::Checksum(self.name.clone()));
+        }
+        let raw = zstd::decode_all(data.as_slice())
+            .map_err(|err| FormatError::Decompress(self.name.clone(), err))?;
+        if Hash::new(&raw) != self.digest {
+            return Err(FormatError::Checksum(self.name.clone()));
+        }
+        Ok(Some(raw))
+    }
+
+    /// Verify, decompress and decode section contents.
+    ///
+    /// Returns [`None`] if the section is stored in the base snapshot.
+    ///
+    /// # Errors
+    /// - Checksum or digest doesn't match
+    /// - Decompression or decoding fails
+    pub fn data(&self) -> Result<Option<SectionData>, FormatError> {
+        self.decompress()?
+            .map(|raw| SectionData::decode_all(&mut raw.as_slice()))
+            .transpose()
+            .map_err(Into::into)
+    }
+}
+
+impl SectionData {
+    /// Apply [`Changes`](Self::Changes) on top of the `base` section entries.
+    ///
+    /// Other kinds of contents replace the `base` as is.
+    ///
+    /// # Errors
+    /// `base` isn't made of entries while `self` holds changes
+    pub fn rebase(self, base: Option<Self>) -> Result<Self, Self> {
+        let Self::Changes { upserted, removed } = self else {
+            return Ok(self);
+        };
+        let Some(Self::Entries(base)) = base else {
+            return Err(Self::Changes { upserted, removed });
+        };
+
+        let mut entries = base
+            .into_iter()
+            .map(|entry| (entry.key, entry.value))
+            .collect::<BTreeMap<_, _>>();
+        for key in &removed {
+            entries.remove(key);
+        }
+        entries.extend(upserted.into_iter().map(|entry| (entry.key, entry.value)));
+
+        Ok(Self::Entries(
+            entries
+                .into_iter()
+                .map(|(key, value)| Entry { key, value })
+                .collect(),
+        ))
+    }
+}
+
+impl Snapshot {
+    /// Check whether `bytes` look like a binary snapshot.
+    pub fn is_binary(bytes: &[u8]) -> bool {
+        bytes.starts_with(&MAGIC)
+    }
+
+    /// Digest identifying this snapshot, referenced by deltas built on top of it.
+    pub fn digest(&self) -> Hash {
+        let digests = self
+            .sections
+            .iter()
+            .map(|section| (&section.name, section.digest))
+            .collect::<Vec<_>>();
+        Hash::new((&self.header, digests).encode())
+    }
+
+    /// Encode snapshot into bytes prefixed with magic, version and checksum.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let body = self.encode();
+        let mut bytes = Vec::with_capacity(PREFIX_LEN + body.len());
+        bytes.extend_from_slice(&MAGIC);
+        bytes.push(VERSION);
+        bytes.extend_from_slice(&crc32fast::hash(&body).to_le_bytes());
+        bytes.extend_from_slice(&body);
+        bytes
+    }
+
+    /// Decode snapshot from bytes produced by [`Self::to_bytes`].
+    ///
+    /// # Errors
+    /// - Unknown magic bytes or format version
+    /// - Checksum mismatch
+    /// - Decoding errors
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, FormatError> {
+        if bytes.len() < PREFIX_LEN || !Self::is_binary(bytes) {
+            return Err(FormatError::Truncated);
+        }
+        let (prefix, body) = bytes.split_at(PREFIX_LEN);
+        let version = prefix[MAGIC.len()];
+        if version != VERSION {
+            return Err(FormatError::UnsupportedVersion(version));
+        }
+        let checksum = u32::from_le_bytes(
+            prefix[MAGIC.len() + 1..]
+                .try_into()
+                .expect("prefix has exactly 4 bytes of checksum"),
+        );
+        if crc32fast::hash(body) != checksum {
+            return Err(FormatError::Checksum("snapshot".to_owned()));
+        }
+        Ok(Self::decode_all(&mut &*body)?)
+    }
+}
+
+/// Error variants for binary snapshot decoding
+#[derive(thiserror::Error, Debug, displaydoc::Display)]
+pub enum FormatError {
+    /// Snapshot file is truncated or isn't a binary snapshot
+    Truncated,
+    /// Unsupported snapshot format version: {0}
+    UnsupportedVersion(u8),
+    /// Checksum mismatch in `{0}`
+    Checksum(String),
+    /// Section `{0}` is missing from the base snapshot
+    MissingSection(String),
+    /// Section `{0}` has unexpected contents
+    UnexpectedSection(String),
+    /// Failed to decompress section `{0}`
+    Decompress(String, #[source] std::io::Error),
+    /// Failed to decode snapshot
+    Decode(#[from] parity_scale_codec::Error),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn snapshot() -> Snapshot {
+        Snapshot {
+            header: Header {
+                height: 3,
+                latest_block_hash: None,
+                base: None,
+            },
+            sections: vec![Section::inline("block_hashes", b"some bytes").unwrap()],
+        }
+    }
+
+    #[test]
+    fn roundtrip() {
+        let snapshot = snapshot();
+        let decoded = Snapshot::from_bytes(&snapshot.to_bytes()).unwrap();
+
+        assert_eq!(decoded.digest(), snapshot.digest());
+        assert_eq!(
+            decoded.sections[0].decompress().unwrap().unwrap(),
+            b"some bytes"
+        );
+    }
+
+    #[test]
+    fn changes_are_applied_to_base_entries() {
+        let entry = |key: u8, value: u8| Entry {
+            key: vec![key],
+            value: vec![value],
+        };
+        let base = SectionData::Entries(vec![entry(1, 1), entry(2, 2), entry(3, 3)]);
+        let changes = SectionData::Changes {
+            upserted: vec![entry(2, 20), entry(4, 4)],
+            removed: vec![vec![1]],
+        };
+
+        assert_eq!(
+            changes.rebase(Some(base)).unwrap(),
+            SectionData::Entries(vec![entry(2, 20), entry(3, 3), entry(4, 4)])
+        );
+    }
+
+    #[test]
+    fn changes_without_base_entries_are_rejected() {
+        let changes = SectionData::Changes {
+            upserted: Vec::new(),
+            removed: Vec::new(),
+        };
+
+        assert!(changes
+            .rebase(Some(SectionData::Value(Vec::new())))
+            .is_err());
+    }
+
+    #[test]
+    fn corrupted_bytes_are_detected() {
+        let mut bytes = snapshot().to_bytes();
+        *bytes.last_mut().unwrap() ^= 0xFF;
+
+        assert!(matches!(
+            Snapshot::from_bytes(&bytes),
+            Err(FormatError::Checksum(_))
+        ));
+    }
+
+    #[test]
+    fn unknown_version_is_rejected() {
+        let mut bytes = snapshot().to_bytes();
+        bytes[MAGIC.len()] = VERSION + 1;
+
+        assert!(matches!(
+            Snapshot::from_bytes(&bytes),
+            Err(FormatError::UnsupportedVersion(_))
+        ));
+    }
+}
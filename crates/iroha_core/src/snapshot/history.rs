@@ -0,0 +1,363 @@
+//! History of [`State`] snapshots used to answer queries about past states.
+//!
+//! Every base snapshot made by [`SnapshotMaker`](super::SnapshotMaker) is also kept
+//! in the history directory under its height. The state at an arbitrary height is
+//! rebuilt from the nearest snapshot below it by replaying blocks stored in [`Kura`].
+
+use std::{
+    num::NonZeroUsize,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use indexmap::IndexMap;
+use iroha_config::{parameters::actual::Snapshot as Config, snapshot::Mode};
+use iroha_data_model::block::SignedBlock;
+use parking_lot::Mutex;
+
+use super::{
+    check_against_kura, decode_state, read_file, resolve_sections, Snapshot, TryReadError,
+    SNAPSHOT_FILE_NAME,
+};
+#[cfg(feature = "telemetry")]
+use crate::telemetry::StateTelemetry;
+use crate::{
+    block::ValidBlock,
+    kura::Kura,
+    query::store::LiveQueryStoreHandle,
+    state::{deserialize::KuraSeed, State, StateReadOnly, StateView, WorldReadOnly},
+    sumeragi::network_topology::Topology,
+};
+
+/// Name of the directory inside of the snapshot store where historical snapshots are kept.
+const HISTORY_DIR_NAME: &str = "history";
+/// Extension of the historical snapshot files.
+const HISTORY_FILE_EXTENSION: &str = "data";
+/// Maximum number of blocks replayed on top of a snapshot to answer a single query.
+pub const MAX_REPLAYED_BLOCKS: usize = 1024;
+
+/// Add the latest base snapshot at `height` to the history,
+/// removing the oldest snapshots so that at most `history_size` remain.
+///
+/// # Errors
+/// IO errors
+pub(super) fn record(store_dir: &Path, height: u64, history_size: u32) -> std::io::Result<()> {
+    if history_size == 0 {
+        return Ok(());
+    }
+    let history_dir = store_dir.join(HISTORY_DIR_NAME);
+    std::fs::create_dir_all(&history_dir)?;
+
+    let path = history_dir.join(format!("{height}.{HISTORY_FILE_EXTENSION}"));
+    match std::fs::remove_file(&path) {
+        Err(err) if err.kind() != std::io::ErrorKind::NotFound => return Err(err),
+        _ => {}
+    }
+    // Base snapshot is always replaced by renaming,
+    // so the link keeps pointing to the contents written at `height`
+    if std::fs::hard_link(store_dir.join(SNAPSHOT_FILE_NAME), &path).is_err() {
+        std::fs::copy(store_dir.join(SNAPSHOT_FILE_NAME), &path)?;
+    }
+
+    let heights = list(&history_dir)?;
+    let keep = usize::try_from(history_size).expect("history size fits into usize");
+    for height in heights.iter().rev().skip(keep) {
+        std::fs::remove_file(history_dir.join(format!("{height}.{HISTORY_FILE_EXTENSION}")))?;
+    }
+    Ok(())
+}
+
+/// Heights of the snapshots kept in the history, in ascending order.
+fn list(history_dir: &Path) -> std::io::Result<Vec<u64>> {
+    let mut heights = Vec::new();
+    for entry in std::fs::read_dir(history_dir)? {
+        let path = entry?.path();
+        if path.extension().and_then(|ext| ext.to_str()) != Some(HISTORY_FILE_EXTENSION) {
+            continue;
+        }
+        if let Some(height) = path
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .and_then(|stem| stem.parse().ok())
+        {
+            heights.push(height);
+        }
+    }
+    heights.sort_unstable();
+    Ok(heights)
+}
+
+/// Access to the states at past heights.
+pub struct StateHistory {
+    /// Path to the directory where historical snapshots are stored
+    history_dir: PathBuf,
+    kura: Arc<Kura>,
+    query_handle: LiveQueryStoreHandle,
+    #[cfg(feature = "telemetry")]
+    telemetry: StateTelemetry,
+    /// Recently rebuilt states, locked only to look up or store a state
+    cache: Mutex<Cache<State>>,
+}
+
+impl StateHistory {
+    /// Create from [`Config`].
+    ///
+    /// Returns [`None`] if the configuration doesn't keep the snapshot history.
+    pub fn from_config(
+        config: &Config,
+        kura: Arc<Kura>,
+        query_handle: LiveQueryStoreHandle,
+        #[cfg(feature = "telemetry")] telemetry: StateTelemetry,
+    ) -> Option<Self> {
+        if matches!(config.mode, Mode::Disabled) || config.history_size == 0 {
+            return None;
+        }
+
+        Some(Self {
+            history_dir: config
+                .store_dir
+                .resolve_relative_path()
+                .join(HISTORY_DIR_NAME),
+            kura,
+            query_handle,
+            #[cfg(feature = "telemetry")]
+            telemetry,
+            cache: Mutex::new(Cache::new(
+                usize::try_from(config.history_cache_size).expect("cache size fits into usize"),
+            )),
+        })
+    }
+
+    /// Run `f` against the state as it was right after the block at `height` was committed.
+    ///
+    /// The state is rebuilt from the nearest cached state or historical snapshot
+    /// below `height`, replaying at most [`MAX_REPLAYED_BLOCKS`] blocks.
+    ///
+    /// # Errors
+    /// - There is no snapshot at or below `height` in the history
+    /// - The nearest snapshot is more than [`MAX_REPLAYED_BLOCKS`] blocks below `height`
+    /// - Blocks up to `height` are not stored in [`Kura`]
+    /// - Errors reading the historical snapshot
+    pub fn with_state_at<R>(
+        &self,
+        height: NonZeroUsize,
+        f: impl FnOnce(&StateView<'_>) -> R,
+    ) -> Result<R, TryReadError> {
+        let height = height.get();
+        let block_count = self.kura.blocks_count();
+        if height > block_count {
+            return Err(TryReadError::HeightUnavailable(height));
+        }
+
+        let state = self.state_at(height, block_count)?;
+        // Cached states are never modified, so the lock isn't needed to run the query
+        Ok(f(&state.view()))
+    }
+
+    fn state_at(&self, height: usize, block_count: usize) -> Result<Arc<State>, TryReadError> {
+        let nearest = {
+            let mut cache = self.cache.lock();
+            if let Some(state) = cache.get(height) {
+                return Ok(state);
+            }
+            cache.take_below(height, MAX_REPLAYED_BLOCKS)
+        };
+
+        // Rolling a cached state forward is cheaper than reading a snapshot,
+        // but only a state which isn't used by other queries can be modified
+        let state = match nearest.map(Arc::try_unwrap) {
+            Some(Ok(state)) => state,
+            Some(Err(shared)) => {
+                self.cache.lock().insert(shared.view().height(), shared);
+                self.read_snapshot_below(height, block_count)?
+            }
+            None => self.read_snapshot_below(height, block_count)?,
+        };
+        replay_blocks(&state, &self.kura, height)?;
+
+        let state = Arc::new(state);
+        self.cache.lock().insert(height, Arc::clone(&state));
+        Ok(state)
+    }
+
+    /// Read the nearest historical snapshot at or below `height`.
+    fn read_snapshot_below(
+        &self,
+        height: usize,
+        block_count: usize,
+    ) -> Result<State, TryReadError> {
+        let snapshot_height = list(&self.history_dir)
+            .map_err(|err| TryReadError::IO(err, self.history_dir.clone()))?
+            .into_iter()
+            .filter_map(|snapshot_height| usize::try_from(snapshot_height).ok())
+            .filter(|&snapshot_height| snapshot_height <= height)
+            .last()
+            .ok_or(TryReadError::HeightUnavailable(height))?;
+        if height - snapshot_height > MAX_REPLAYED_BLOCKS {
+            return Err(TryReadError::HeightUnavailable(height));
+        }
+
+        self.read_snapshot(snapshot_height, block_count)
+    }
+
+    fn read_snapshot(&self, height: usize, block_count: usize) -> Result<State, TryReadError> {
+        let path = self
+            .history_dir
+            .join(format!("{height}.{HISTORY_FILE_EXTENSION}"));
+        let bytes = read_file(&path)
+            .map_err(|err| TryReadError::IO(err, path))?
+            .ok_or(TryReadError::HeightUnavailable(height))?;
+        let snapshot = Snapshot::from_bytes(&bytes)?;
+        let seed = KuraSeed {
+            kura: Arc::clone(&self.kura),
+            query_handle: self.query_handle.clone(),
+            #[cfg(feature = "telemetry")]
+            telemetry: self.telemetry.clone(),
+        };
+        let state = decode_state(resolve_sections(&snapshot, None)?, seed)?;
+        check_against_kura(state, &self.kura, block_count)
+    }
+}
+
+/// States by their heights, evicting the least recently used ones.
+struct Cache<T> {
+    /// Cached states, least recently used first
+    entries: IndexMap<usize, Arc<T>>,
+    capacity: usize,
+}
+
+impl<T> Cache<T> {
+    fn new(capacity: usize) -> Self {
+        Self {
+            entries: IndexMap::with_capacity(capacity),
+            capacity,
+        }
+    }
+
+    /// Get the state at exactly `height`.
+    fn get(&mut self, height: usize) -> Option<Arc<T>> {
+        let state = self.entries.shift_remove(&height)?;
+        self.entries.insert(height, Arc::clone(&state));
+        Some(state)
+    }
+
+    /// Remove the nearest state below `height` but no more than `max_distance` blocks below.
+    fn take_below(&mut self, height: usize, max_distance: usize) -> Option<Arc<T>> {
+        let nearest = self
+            .entries
+            .keys()
+            .copied()
+            .filter(|&cached| cached < height && height - cached <= max_distance)
+            .max()?;
+        self.entries.shift_remove(&nearest)
+    }
+
+    fn insert(&mut self, height: usize, state: Arc<T>) {
+        self.entries.shift_remove(&height);
+        self.entries.insert(height, state);
+        while self.entries.len() > self.capacity {
+            self.entries.shift_remove_index(0);
+        }
+    }
+}
+
+/// Apply blocks stored in [`Kura`] to the `state` until it reaches `height`.
+///
+/// Blocks are assumed to be valid since they are already committed.
+fn replay_blocks(state: &State, kura: &Kura, height: usize) -> Result<(), TryReadError> {
+    let (from_height, commit_topology) = {
+        let state_view = state.view();
+        (state_view.height(), state_view.commit_topology().to_vec())
+    };
+    let mut topology = Topology::new(commit_topology);
+
+    for block_height in from_height + 1..=height {
+        let block = NonZeroUsize::new(block_height)
+            .and_then(|block_height| kura.get_block(block_height))
+            .ok_or(TryReadError::HeightUnavailable(height))?;
+        let mut state_block = state.block(block.header());
+        topology.nth_rotation(block.header().view_change_index as usize);
+
+        let block = ValidBlock::validate_unchecked(SignedBlock::clone(&block), &mut state_block)
+            .unpack(|_| {})
+            .commit_unchecked()
+            .unpack(|_| {});
+        topology.block_committed(
+            block.as_ref().header().leader_seed(),
+            state_block.world.peers().clone(),
+        );
+        let _events = state_block.apply_without_execution(&block, topology.as_ref().to_owned());
+        state_block.commit();
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::tempdir;
+
+    use super::*;
+
+    #[test]
+    fn history_keeps_latest_snapshots() {
+        let store_dir = tempdir().unwrap();
+        std::fs::write(store_dir.path().join(SNAPSHOT_FILE_NAME), b"snapshot").unwrap();
+
+        for height in [3, 1, 7, 5] {
+            record(store_dir.path(), height, 2).unwrap();
+        }
+
+        assert_eq!(
+            list(&store_dir.path().join(HISTORY_DIR_NAME)).unwrap(),
+            vec![5, 7]
+        );
+    }
+
+    #[test]
+    fn cache_evicts_least_recently_used_states() {
+        let mut cache = Cache::new(2);
+        cache.insert(1, Arc::new("one"));
+        cache.insert(2, Arc::new("two"));
+        assert_eq!(cache.get(1).as_deref(), Some(&"one"));
+
+        cache.insert(3, Arc::new("three"));
+
+        assert!(cache.get(2).is_none());
+        assert_eq!(cache.get(1).as_deref(), Some(&"one"));
+        assert_eq!(cache.get(3).as_deref(), Some(&"three"));
+    }
+
+    #[test]
+    fn empty_cache_keeps_no_states() {
+        let mut cache = Cache::new(0);
+        cache.insert(1, Arc::new("one"));
+
+        assert!(cache.get(1).is_none());
+        assert!(cache.take_below(2, 10).is_none());
+    }
+
+    #[test]
+    fn cache_takes_nearest_state_below_height() {
+        let mut cache = Cache::new(4);
+        for height in [2, 5, 9] {
+            cache.insert(height, Arc::new(height));
+        }
+
+        assert!(cache.take_below(20, 10).is_none());
+        assert_eq!(cache.take_below(8, 10).as_deref(), Some(&5));
+        assert_eq!(cache.take_below(8, 10).as_deref(), Some(&2));
+        assert!(cache.take_below(8, 10).is_none());
+        assert_eq!(cache.get(9).as_deref(), Some(&9));
+    }
+
+    #[test]
+    fn disabled_history_is_not_recorded() {
+        let store_dir = tempdir().unwrap();
+        std::fs::write(store_dir.path().join(SNAPSHOT_FILE_NAME), b"snapshot").unwrap();
+
+        record(store_dir.path(), 1, 0).unwrap();
+
+        assert!(!store_dir.path().join(HISTORY_DIR_NAME).exists());
+    }
+}
@@ -18,7 +18,7 @@ use iroha_data_model::{
     executor::ExecutorDataModel,
     isi::error::{InstructionExecutionError as Error, MathError},
     nft::{NftEntry, NftValue},
-    parameter::Parameters,
+    parameter::{Parameters, PeerWeights},
     permission::Permissions,
     prelude::*,
     query::error::{FindError, QueryExecutionFail},
@@ -99,7 +99,7 @@ pub struct World {
     pub(crate) executor_data_model: Cell<ExecutorDataModel>,
     /// Placeholder buffer of events pending publication to external subscribers.
     /// Included for formal correctness, although used only below the block level.
-    external_event_buf: Cell<Vec<EventBox>>,
+    pub(crate) external_event_buf: Cell<Vec<EventBox>>,
 }
 
 /// Struct for block's aggregated changes
@@ -214,6 +214,10 @@ pub struct State {
     pub commit_topology: Cell<Vec<PeerId>>,
     /// Topology used to commit previous block
     pub prev_commit_topology: Cell<Vec<PeerId>>,
+    /// Voting weights of the peers in `commit_topology`
+    pub commit_weights: Cell<PeerWeights>,
+    /// Voting weights of the peers in `prev_commit_topology`
+    pub prev_commit_weights: Cell<PeerWeights>,
     /// Engine for WASM [`Runtime`](wasm::Runtime) to execute triggers.
     #[serde(skip)]
     pub engine: wasmtime::Engine,
@@ -246,6 +250,10 @@ pub struct StateBlock<'state> {
     pub commit_topology: CellBlock<'state, Vec<PeerId>>,
     /// Topology used to commit previous block
     pub prev_commit_topology: CellBlock<'state, Vec<PeerId>>,
+    /// Voting weights of the peers in `commit_topology`
+    pub commit_weights: CellBlock<'state, PeerWeights>,
+    /// Voting weights of the peers in `prev_commit_topology`
+    pub prev_commit_weights: CellBlock<'state, PeerWeights>,
     /// Engine for WASM [`Runtime`](wasm::Runtime) to execute triggers.
     pub engine: &'state wasmtime::Engine,
 
@@ -272,6 +280,10 @@ pub struct StateTransaction<'block, 'state> {
     pub commit_topology: CellTransaction<'block, 'state, Vec<PeerId>>,
     /// Topology used to commit previous block
     pub prev_commit_topology: CellTransaction<'block, 'state, Vec<PeerId>>,
+    /// Voting weights of the peers in `commit_topology`
+    pub commit_weights: CellTransaction<'block, 'state, PeerWeights>,
+    /// Voting weights of the peers in `prev_commit_topology`
+    pub prev_commit_weights: CellTransaction<'block, 'state, PeerWeights>,
     /// Engine for WASM [`Runtime`](wasm::Runtime) to execute triggers.
     pub engine: &'state wasmtime::Engine,
 
@@ -298,6 +310,10 @@ pub struct StateView<'state> {
     pub commit_topology: CellView<'state, Vec<PeerId>>,
     /// Topology used to commit previous block
     pub prev_commit_topology: CellView<'state, Vec<PeerId>>,
+    /// Voting weights of the peers in `commit_topology`
+    pub commit_weights: CellView<'state, PeerWeights>,
+    /// Voting weights of the peers in `prev_commit_topology`
+    pub prev_commit_weights: CellView<'state, PeerWeights>,
     /// Engine for WASM [`Runtime`](wasm::Runtime) to execute triggers.
     pub engine: &'state wasmtime::Engine,
 
@@ -1087,7 +1103,7 @@ impl WorldTransaction<'_, '_> {
 impl State {
     #[must_use]
     #[inline]
-    fn new_inner(
+    pub(crate) fn new_inner(
         world: World,
         kura: Arc<Kura>,
         query_handle: LiveQueryStoreHandle,
@@ -1098,6 +1114,8 @@ impl State {
             transactions: TransactionsStorage::new(),
             commit_topology: Cell::new(Vec::new()),
             prev_commit_topology: Cell::new(Vec::new()),
+            commit_weights: Cell::new(PeerWeights::new()),
+            prev_commit_weights: Cell::new(PeerWeights::new()),
             block_hashes: Cell::new(Vec::new()),
             engine: wasm::create_engine(),
             kura,
@@ -1162,6 +1180,8 @@ impl State {
             transactions: self.transactions.block(),
             commit_topology: self.commit_topology.block(),
             prev_commit_topology: self.prev_commit_topology.block(),
+            commit_weights: self.commit_weights.block(),
+            prev_commit_weights: self.prev_commit_weights.block(),
             engine: &self.engine,
             kura: &self.kura,
             query_handle: &self.query_handle,
@@ -1180,6 +1200,8 @@ impl State {
             transactions: self.transactions.block_and_revert(),
             commit_topology: self.commit_topology.block_and_revert(),
             prev_commit_topology: self.prev_commit_topology.block_and_revert(),
+            commit_weights: self.commit_weights.block_and_revert(),
+            prev_commit_weights: self.prev_commit_weights.block_and_revert(),
             engine: &self.engine,
             kura: &self.kura,
             query_handle: &self.query_handle,
@@ -1199,6 +1221,8 @@ impl State {
             transactions: self.transactions.view(),
             commit_topology: self.commit_topology.view(),
             prev_commit_topology: self.prev_commit_topology.view(),
+            commit_weights: self.commit_weights.view(),
+            prev_commit_weights: self.prev_commit_weights.view(),
             engine: &self.engine,
             kura: &self.kura,
             query_handle: &self.query_handle,
@@ -1253,11 +1277,14 @@ pub trait StateReadOnly {
     }
 
     /// Load all blocks in the block chain from disc
+    ///
+    /// Blocks pruned from the storage are skipped.
     fn all_blocks(
         &self,
         start: NonZeroUsize,
     ) -> impl DoubleEndedIterator<Item = Arc<SignedBlock>> + '_ {
-        (start.get()..=self.height()).map(|height| {
+        let start = start.get().max(self.kura().pruned_blocks_count() + 1);
+        (start..=self.height()).map(|height| {
             NonZeroUsize::new(height)
                 .and_then(|height| self.kura().get_block(height))
                 .expect("INTERNAL BUG: Failed to load block")
@@ -1274,7 +1301,8 @@ pub trait StateReadOnly {
     /// committed, or [`None`] if it wasn't.
     #[inline]
     fn genesis_timestamp(&self) -> Option<Duration> {
-        if self.block_hashes().is_empty() {
+        // Genesis block isn't available on peers which prune blocks
+        if self.block_hashes().is_empty() || self.kura().pruned_blocks_count() > 0 {
             None
         } else {
             let opt = self
@@ -1363,6 +1391,8 @@ impl<'state> StateBlock<'state> {
             block_hashes: self.block_hashes.transaction(),
             commit_topology: self.commit_topology.transaction(),
             prev_commit_topology: self.prev_commit_topology.transaction(),
+            commit_weights: self.commit_weights.transaction(),
+            prev_commit_weights: self.prev_commit_weights.transaction(),
             engine: self.engine,
             kura: self.kura,
             query_handle: self.query_handle,
@@ -1381,10 +1411,14 @@ impl<'state> StateBlock<'state> {
             transactions,
             commit_topology: committed_topology,
             prev_commit_topology: prev_committed_topology,
+            commit_weights: committed_weights,
+            prev_commit_weights: prev_committed_weights,
             view_lock,
             ..
         } = self;
         let _view_lock = view_lock.write();
+        prev_committed_weights.commit();
+        committed_weights.commit();
         prev_committed_topology.commit();
         committed_topology.commit();
         transactions.commit();
@@ -1421,6 +1455,8 @@ impl<'state> StateBlock<'state> {
 
         *self.prev_commit_topology = core::mem::take(&mut self.commit_topology);
         *self.commit_topology = topology;
+        *self.prev_commit_weights = core::mem::take(&mut self.commit_weights);
+        *self.commit_weights = self.world.parameters().peer_weights().clone();
 
         self.world.external_event_buf.push(
             BlockEvent {
@@ -1593,8 +1629,12 @@ impl StateTransaction<'_, '_> {
             block_hashes,
             commit_topology: committed_topology,
             prev_commit_topology: prev_committed_topology,
+            commit_weights: committed_weights,
+            prev_commit_weights: prev_committed_weights,
             ..
         } = self;
+        prev_committed_weights.apply();
+        committed_weights.apply();
         prev_committed_topology.apply();
         committed_topology.apply();
         block_hashes.apply();
@@ -2099,6 +2139,15 @@ pub(crate) mod deserialize {
         _marker: PhantomData<T>,
     }
 
+    impl<'e> WasmSeed<'e, ()> {
+        pub fn new(engine: &'e wasmtime::Engine) -> Self {
+            Self {
+                engine,
+                _marker: PhantomData,
+            }
+        }
+    }
+
     impl<'e, T> WasmSeed<'e, T> {
         pub fn cast<U>(&self) -> WasmSeed<'e, U> {
             WasmSeed {
@@ -2306,6 +2355,37 @@ pub(crate) mod deserialize {
         pub telemetry: StateTelemetry,
     }
 
+    impl KuraSeed {
+        /// Assemble [`State`] from the parts restored from a snapshot.
+        pub(crate) fn into_state(
+            self,
+            world: World,
+            block_hashes: Vec<HashOf<BlockHeader>>,
+            transactions: TransactionsStorage,
+            commit_topology: Vec<PeerId>,
+            prev_commit_topology: Vec<PeerId>,
+            commit_weights: PeerWeights,
+            prev_commit_weights: PeerWeights,
+            engine: wasmtime::Engine,
+        ) -> State {
+            State {
+                world,
+                block_hashes: Cell::new(block_hashes),
+                transactions,
+                commit_topology: Cell::new(commit_topology),
+                prev_commit_topology: Cell::new(prev_commit_topology),
+                commit_weights: Cell::new(commit_weights),
+                prev_commit_weights: Cell::new(prev_commit_weights),
+                engine,
+                kura: self.kura,
+                query_handle: self.query_handle,
+                #[cfg(feature = "telemetry")]
+                telemetry: self.telemetry,
+                view_lock: parking_lot::RwLock::new(()),
+            }
+        }
+    }
+
     impl<'de> DeserializeSeed<'de> for KuraSeed {
         type Value = State;
 
@@ -2333,6 +2413,8 @@ pub(crate) mod deserialize {
                     let mut transactions = None;
                     let mut commit_topology = None;
                     let mut prev_commit_topology = None;
+                    let mut commit_weights = None;
+                    let mut prev_commit_weights = None;
 
                     let engine = wasm::create_engine();
 
@@ -2358,6 +2440,12 @@ pub(crate) mod deserialize {
                             "prev_commit_topology" => {
                                 prev_commit_topology = Some(map.next_value()?);
                             }
+                            "commit_weights" => {
+                                commit_weights = Some(map.next_value()?);
+                            }
+                            "prev_commit_weights" => {
+                                prev_commit_weights = Some(map.next_value()?);
+                            }
                             _ => { /* Skip unknown fields */ }
                         }
                     }
@@ -2373,6 +2461,11 @@ pub(crate) mod deserialize {
                         prev_commit_topology: prev_commit_topology.ok_or_else(|| {
                             serde::de::Error::missing_field("prev_commit_topology")
                         })?,
+                        commit_weights: commit_weights
+                            .ok_or_else(|| serde::de::Error::missing_field("commit_weights"))?,
+                        prev_commit_weights: prev_commit_weights.ok_or_else(|| {
+                            serde::de::Error::missing_field("prev_commit_weights")
+                        })?,
                         kura: self.loader.kura,
                         query_handle: self.loader.query_handle,
                         #[cfg(feature = "telemetry")]
@@ -2391,6 +2484,8 @@ pub(crate) mod deserialize {
                     "transactions",
                     "commit_topology",
                     "prev_commit_topology",
+                    "commit_weights",
+                    "prev_commit_weights",
                 ],
                 StateVisitor { loader: self },
             )
@@ -2402,6 +2497,7 @@ pub(crate) mod deserialize {
 mod tests {
     use core::num::NonZeroU64;
 
+    use iroha_data_model::parameter::PeerWeight;
     use iroha_test_samples::gen_account_in;
 
     use super::*;
@@ -2484,6 +2580,32 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn weights_of_previous_commit_topology_are_kept() {
+        let kura = Kura::blank_kura_for_testing();
+        let query_handle = LiveQueryStore::start_test();
+        let state = State::new(World::default(), kura, query_handle);
+        let peer = PeerId::new(iroha_crypto::KeyPair::random().into_parts().0);
+
+        for (height, weight) in [(1, nonzero!(2_u64)), (2, nonzero!(3_u64))] {
+            let block = new_dummy_block_with_payload(|header| {
+                header.height = NonZeroU64::new(height).unwrap();
+            });
+
+            let mut state_block = state.block(block.as_ref().header());
+            state_block
+                .world
+                .parameters
+                .set_parameter(Parameter::PeerWeight(PeerWeight::new(peer.clone(), weight)));
+            let _events = state_block.apply(&block, Vec::new());
+            state_block.commit();
+        }
+
+        let view = state.view();
+        assert_eq!(view.commit_weights.get(&peer), Some(&nonzero!(3_u64)));
+        assert_eq!(view.prev_commit_weights.get(&peer), Some(&nonzero!(2_u64)));
+    }
+
     #[test]
     fn role_account_range() {
         let (account_id, _account_keypair) = gen_account_in("wonderland");
@@ -52,6 +52,32 @@ impl TransactionsStorage {
         }
     }
 
+    /// Restore storage from the transactions of all blocks up to `height`,
+    /// as produced by [`TransactionsView::iter`].
+    pub fn from_entries(entries: impl IntoIterator<Item = (Key, Value)>, height: usize) -> Self {
+        let storage = Self::new();
+        let Some(height) = NonZeroUsize::new(height) else {
+            return storage;
+        };
+        let mut transactions = HashSet::new();
+        for (tx, tx_height) in entries {
+            match tx_height.cmp(&height) {
+                std::cmp::Ordering::Less => {
+                    storage.blocks.insert(tx, tx_height);
+                }
+                std::cmp::Ordering::Equal => {
+                    transactions.insert(tx);
+                }
+                std::cmp::Ordering::Greater => {}
+            }
+        }
+        storage.latest_block.store(Some(Arc::new(BlockInfo {
+            transactions,
+            height,
+        })));
+        storage
+    }
+
     /// Create persistent view of storage at certain point in time
     pub fn view(&self) -> TransactionsView {
         TransactionsView {
@@ -104,6 +130,24 @@ mod view {
         pub(super) blocks: &'storage DashMap<Key, Value>,
     }
 
+    impl TransactionsView<'_> {
+        /// Iterate over transactions of all blocks in this view with heights of their blocks.
+        pub fn iter(&self) -> impl Iterator<Item = (Key, Value)> + '_ {
+            let block = self.latest_block.as_deref();
+            let latest = block
+                .into_iter()
+                .flat_map(|block| block.transactions.iter().map(|&tx| (tx, block.height)));
+            // Entries added after `Self` was created are filtered out, see `get`
+            let previous = block.into_iter().flat_map(move |block| {
+                self.blocks
+                    .iter()
+                    .map(|entry| (*entry.key(), *entry.value()))
+                    .filter(move |&(_, height)| height < block.height)
+            });
+            latest.chain(previous)
+        }
+    }
+
     impl TransactionsReadOnly for TransactionsView<'_> {
         fn get<Q>(&self, key: &Q) -> Option<Value>
         where
@@ -399,6 +443,31 @@ mod tests {
         assert_eq!(view2.get(&k0), Some(v1));
     }
 
+    #[test]
+    fn restore_from_entries() {
+        let [k0, k1, k2, k3] = get_keys();
+        let [v1, v2, v3] = get_values();
+
+        let storage = TransactionsStorage::new();
+        for (keys, value) in [(&[k0, k1][..], v1), (&[k2], v2), (&[k3], v3)] {
+            let mut block = storage.block();
+            insert_keys(&mut block, keys, value);
+            block.commit()
+        }
+        let view = storage.view();
+
+        let restored = TransactionsStorage::from_entries(view.iter(), v3.get());
+        let restored_view = restored.view();
+        for key in [k0, k1, k2, k3] {
+            assert_eq!(restored_view.get(&key), view.get(&key));
+        }
+
+        // Latest block is kept separately, so it can still be reverted
+        restored.block_and_revert().commit();
+        assert_eq!(restored.view().get(&k3), None);
+        assert_eq!(restored.view().get(&k2), Some(v2));
+    }
+
     #[test]
     fn serialization() {
         fn assert_views_equal(view1: &TransactionsView, view2: &TransactionsView, keys: &[Key]) {
@@ -2,7 +2,9 @@
 use std::{collections::BTreeSet, ops::Deref, sync::mpsc};
 
 use iroha_crypto::{HashOf, KeyPair};
-use iroha_data_model::{block::*, events::pipeline::PipelineEventBox, peer::PeerId};
+use iroha_data_model::{
+    block::*, events::pipeline::PipelineEventBox, parameter::PeerWeights, peer::PeerId,
+};
 use iroha_futures::supervisor::ShutdownSignal;
 use iroha_p2p::UpdateTopology;
 use tracing::{span, Level};
@@ -40,6 +42,8 @@ pub struct Sumeragi {
     /// Only used in testing. Causes the genesis peer to withhold blocks when it
     /// is the proxy tail.
     pub debug_force_soft_fork: bool,
+    /// Follow the chain through block sync only, without ever taking part in consensus.
+    pub read_replica: bool,
     /// The current network topology.
     pub topology: Topology,
     /// In order to *be fast*, we must minimize communication with
@@ -71,6 +75,9 @@ impl Debug for Sumeragi {
 
 impl Sumeragi {
     fn role(&self) -> Role {
+        if self.read_replica {
+            return Role::Undefined;
+        }
         self.topology.role(&self.peer.id)
     }
 
@@ -281,6 +288,8 @@ impl Sumeragi {
 
                     // NOTE: By this time genesis block is executed and list of trusted peers is updated
                     self.topology = Topology::new(state_block.world.peers.clone());
+                    self.topology
+                        .set_weights(state_block.world.parameters().peer_weights().clone());
                     self.commit_block(block, state_block);
                     return Ok(());
                 }
@@ -336,6 +345,8 @@ impl Sumeragi {
 
         // NOTE: By this time genesis block is executed and list of trusted peers is updated
         self.topology = Topology::new(state_block.world.peers.clone());
+        self.topology
+            .set_weights(state_block.world.parameters().peer_weights().clone());
 
         let genesis = genesis
             .commit(&self.topology)
@@ -354,13 +365,19 @@ impl Sumeragi {
 
     fn update_state<Strategy: ApplyBlockStrategy>(
         &mut self,
-        block: CommittedBlock,
+        mut block: CommittedBlock,
         mut state_block: StateBlock<'_>,
     ) {
         let prev_role = self.role();
 
+        // NOTE: Aggregate with the topology the block was committed with, before it is rotated
+        block.aggregate_signatures(&self.topology);
+        self.topology.block_committed(
+            block.as_ref().header().leader_seed(),
+            state_block.world.peers().clone(),
+        );
         self.topology
-            .block_committed(state_block.world.peers().clone());
+            .set_weights(state_block.world.parameters().peer_weights().clone());
 
         let state_events =
             state_block.apply_without_execution(&block, self.topology.as_ref().to_owned());
@@ -827,8 +844,10 @@ impl Sumeragi {
     ) -> Option<VotingBlock<'state>> {
         assert_eq!(self.role(), Role::ProxyTail);
 
-        let votes_count = voting_block.block.as_ref().signatures().len();
-        if votes_count + 1 >= self.topology.min_votes_for_commit() {
+        let votes_weight = self
+            .topology
+            .votes_weight(voting_block.block.as_ref().signatures());
+        if votes_weight + self.topology.weight(&self.peer.id) >= self.topology.quorum_weight() {
             voting_block.block.sign(&self.key_pair, &self.topology);
 
             let committed_block = voting_block
@@ -1172,7 +1191,8 @@ pub(crate) fn run(
         let block_expected = tx_cache_non_empty || !prev_block_is_empty;
 
         let view_change_in_progress = view_change_index > 0;
-        if (block_expected || view_change_in_progress)
+        if !sumeragi.read_replica
+            && (block_expected || view_change_in_progress)
             && last_view_change_time.elapsed() > view_change_time
         {
             if block_expected {
@@ -1368,11 +1388,14 @@ fn handle_categorized_block_sync<'state, F: Fn(PipelineEventBox)>(
 
     let topology = {
         let view = state.view();
-        let mut topology = Topology::new(if soft_fork {
-            view.prev_commit_topology.clone()
+        // NOTE: The top block is replaced by a block voted for with the weights before it
+        let (peers, weights): (&[PeerId], &PeerWeights) = if soft_fork {
+            (&view.prev_commit_topology, &view.prev_commit_weights)
         } else {
-            view.commit_topology.clone()
-        });
+            (&view.commit_topology, &view.commit_weights)
+        };
+        let mut topology = Topology::new(peers.to_vec());
+        topology.set_weights(weights.clone());
         topology.nth_rotation(block.header().view_change_index as usize);
         topology
     };
@@ -37,7 +37,7 @@ impl ControlFlowMessage {
 }
 
 /// `BlockCreated` message structure.
-#[derive(Debug, Clone, Decode, Encode)]
+#[derive(Debug, Clone, Encode)]
 pub struct BlockCreated {
     /// The corresponding block.
     pub block: SignedBlock,
@@ -124,6 +124,30 @@ mod candidate {
 
     use super::*;
 
+    #[derive(Decode)]
+    struct BlockCreatedCandidate {
+        /// The corresponding block.
+        pub block: SignedBlock,
+    }
+
+    impl BlockCreatedCandidate {
+        fn validate(self) -> Result<BlockCreated, &'static str> {
+            if self.block.commit_certificate().is_some() {
+                return Err("Created block has a commit certificate");
+            }
+
+            Ok(BlockCreated { block: self.block })
+        }
+    }
+
+    impl Decode for BlockCreated {
+        fn decode<I: Input>(input: &mut I) -> Result<Self, parity_scale_codec::Error> {
+            BlockCreatedCandidate::decode(input)?
+                .validate()
+                .map_err(Into::into)
+        }
+    }
+
     #[derive(Decode)]
     struct BlockCommittedCandidate {
         /// Hash of the block being signed.
@@ -31,8 +31,12 @@ use self::{message::*, view_change::ProofChain};
 #[cfg(feature = "telemetry")]
 use crate::telemetry::Telemetry;
 use crate::{
-    kura::Kura, peers_gossiper::PeersGossiperHandle, prelude::*, queue::Queue, EventsSender,
-    IrohaNetwork, NetworkMessage,
+    kura::Kura,
+    peers_gossiper::PeersGossiperHandle,
+    prelude::*,
+    query::store::{LiveQueryStore, LiveQueryStoreHandle},
+    queue::Queue,
+    EventsSender, IrohaNetwork, NetworkMessage,
 };
 
 /// Handle to `Sumeragi` actor
@@ -121,7 +125,11 @@ impl SumeragiHandle {
             *topology = Topology::new(state_block.world.peers.clone());
         }
 
-        topology.block_committed(state_block.world.peers().clone());
+        topology.block_committed(
+            block.as_ref().header().leader_seed(),
+            state_block.world.peers().clone(),
+        );
+        topology.set_weights(state_block.world.parameters().peer_weights().clone());
 
         state_block
             .apply_without_execution(&block, topology.as_ref().to_owned())
@@ -140,9 +148,11 @@ impl SumeragiStartArgs {
     #[allow(clippy::too_many_lines)]
     pub fn start(self, shutdown_signal: ShutdownSignal) -> (SumeragiHandle, Child) {
         let Self {
-            config: SumeragiConfig {
-                debug_force_soft_fork,
-            },
+            config:
+                SumeragiConfig {
+                    read_replica,
+                    debug_force_soft_fork,
+                },
             common_config,
             events_sender,
             state,
@@ -173,6 +183,15 @@ impl SumeragiStartArgs {
             });
 
             topology = match state_view.height() {
+                // Read replica is never part of the topology, it only knows the peers it follows
+                0 if read_replica => Topology::new(
+                    common_config
+                        .trusted_peers
+                        .value()
+                        .others
+                        .iter()
+                        .map(|peer| peer.id().clone()),
+                ),
                 0 => Topology::new(
                     common_config
                         .trusted_peers
@@ -182,6 +201,7 @@ impl SumeragiStartArgs {
                 ),
                 _height => Topology::new(state_view.commit_topology.clone()),
             };
+            topology.set_weights(state_view.world.parameters().peer_weights().clone());
         }
 
         let genesis_account = AccountId::new(
@@ -218,6 +238,7 @@ impl SumeragiStartArgs {
             control_message_receiver,
             message_receiver,
             debug_force_soft_fork,
+            read_replica,
             topology,
             transaction_cache: Vec::new(),
             #[cfg(feature = "telemetry")]
@@ -302,6 +323,73 @@ pub struct SumeragiStartArgs {
     pub telemetry: Telemetry,
 }
 
+/// Follows the commit topology by replaying committed blocks on top of a blank state.
+///
+/// Meant for tools inspecting the block store. Peers registered or unregistered
+/// by smart contracts and triggers are followed as well.
+pub struct TopologyReplay {
+    state: State,
+    topology: Option<Topology>,
+}
+
+impl Default for TopologyReplay {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TopologyReplay {
+    /// Construct [`Self`] with a blank state, the first replayed block must be the genesis block.
+    pub fn new() -> Self {
+        let query_handle = LiveQueryStoreHandle::new(Arc::new(LiveQueryStore::from_config(
+            <_>::default(),
+            ShutdownSignal::new(),
+        )));
+        let state = State::new_inner(
+            World::new(),
+            Kura::blank_kura_for_testing(),
+            query_handle,
+            #[cfg(feature = "telemetry")]
+            <_>::default(),
+        );
+        Self {
+            state,
+            topology: None,
+        }
+    }
+
+    /// Apply `block` to the state.
+    ///
+    /// Returns the topology which had to commit `block`, `None` for the genesis block
+    /// or if no peers were registered by the preceding blocks.
+    /// Blocks are assumed to be valid, transactions are executed without checking signatures.
+    pub fn replay(&mut self, block: &SignedBlock) -> Option<Vec<PeerId>> {
+        let commit_topology = self.topology.as_mut().map(|topology| {
+            topology.nth_rotation(block.header().view_change_index as usize);
+            topology.as_ref().to_vec()
+        });
+
+        let mut state_block = self.state.block(block.header());
+        let block = ValidBlock::validate_unchecked(block.clone(), &mut state_block)
+            .unpack(|_| {})
+            .commit_unchecked()
+            .unpack(|_| {});
+        let peers = state_block.world.peers().clone();
+        if self.topology.is_none() && !peers.is_empty() {
+            self.topology = Some(Topology::new(peers.clone()));
+        }
+        let topology = self.topology.as_mut().map_or_else(Vec::new, |topology| {
+            topology.block_committed(block.as_ref().header().leader_seed(), peers);
+            topology.set_weights(state_block.world.parameters().peer_weights().clone());
+            topology.as_ref().to_owned()
+        });
+        let _events = state_block.apply_without_execution(&block, topology);
+        state_block.commit();
+
+        commit_topology
+    }
+}
+
 /// Optional genesis paired with genesis public key for verification
 #[allow(missing_docs)]
 pub struct GenesisWithPubKey {
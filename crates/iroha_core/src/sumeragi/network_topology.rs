@@ -4,8 +4,12 @@ use derive_more::Display;
 use indexmap::IndexSet;
 #[cfg(test)]
 use iroha_crypto::KeyPair;
-use iroha_crypto::PublicKey;
-use iroha_data_model::{block::BlockSignature, prelude::PeerId};
+use iroha_crypto::{Algorithm, Hash, HashOf, PublicKey};
+use iroha_data_model::{
+    block::{BlockSignature, LeaderSeed},
+    parameter::PeerWeights,
+    prelude::PeerId,
+};
 
 /// The ordering of the peers which defines their roles in the current round of consensus.
 ///
@@ -18,12 +22,17 @@ use iroha_data_model::{block::BlockSignature, prelude::PeerId};
 /// G             |   f   |                        |<-|
 ///
 /// Above is an illustration of how the various operations work for a f = 2 topology.
+///
+/// When peers have unequal voting weights, set A is the shortest prefix of peers
+/// whose votes together reach the commit quorum weight.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Topology(
     /// Ordered set of peers
     Vec<PeerId>,
     /// Current view change index. Reset to 0 after every block commit
     usize,
+    /// Voting weights of the peers. Peers not in the map have a weight of one
+    PeerWeights,
 );
 
 /// Topology with at least one peer
@@ -63,7 +72,12 @@ impl Topology {
             "Topology must contain at least one peer"
         );
 
-        Topology(topology.into_iter().collect(), 0)
+        Topology(topology.into_iter().collect(), 0, PeerWeights::new())
+    }
+
+    /// Set voting weights of the peers, replacing the previous ones.
+    pub fn set_weights(&mut self, weights: PeerWeights) {
+        self.2 = weights;
     }
 
     pub(crate) fn position(&self, peer: &PublicKey) -> Option<usize> {
@@ -84,22 +98,78 @@ impl Topology {
         (self.0.len() > 1).then_some(ConsensusTopology { topology: self })
     }
 
-    /// How many faulty peers can this topology tolerate.
+    /// How many faulty peers can this topology tolerate, not taking weights into account.
     pub fn max_faults(&self) -> usize {
         (self.0.len().saturating_sub(1)) / 3
     }
 
-    /// The required amount of votes to commit a block with this topology.
-    pub fn min_votes_for_commit(&self) -> usize {
-        let len = self.0.len();
+    /// Voting weight of the `peer`.
+    pub fn weight(&self, peer: &PeerId) -> u64 {
+        self.2.get(peer).map_or(1, |weight| weight.get())
+    }
 
-        if len > 3 {
-            self.max_faults() * 2 + 1
+    /// Sum of the voting weights of all peers in the topology.
+    pub fn total_weight(&self) -> u64 {
+        self.0
+            .iter()
+            .map(|peer| self.weight(peer))
+            .fold(0, u64::saturating_add)
+    }
+
+    /// How much voting weight of faulty peers can this topology tolerate.
+    pub fn max_faulty_weight(&self) -> u64 {
+        self.total_weight().saturating_sub(1) / 3
+    }
+
+    /// The required weight of votes to commit a block with this topology.
+    ///
+    /// It is strictly more than two thirds of the total weight,
+    /// so that any two quorums share more weight than the faulty peers can have.
+    pub fn quorum_weight(&self) -> u64 {
+        if self.0.len() > 3 {
+            self.total_weight() - self.max_faulty_weight()
         } else {
-            len
+            self.total_weight()
         }
     }
 
+    /// Voting weight behind `signatures`, counting every signatory once.
+    pub fn votes_weight<'a>(
+        &self,
+        signatures: impl IntoIterator<Item = &'a BlockSignature>,
+    ) -> u64 {
+        self.signatories_weight(signatures.into_iter().map(|signature| signature.index))
+    }
+
+    /// Voting weight behind the peers at `signatories` indices, counting every signatory once.
+    pub fn signatories_weight(&self, signatories: impl IntoIterator<Item = u64>) -> u64 {
+        signatories
+            .into_iter()
+            .filter_map(|index| usize::try_from(index).ok())
+            .collect::<IndexSet<_>>()
+            .into_iter()
+            .filter_map(|index| self.0.get(index))
+            .map(|peer| self.weight(peer))
+            .fold(0, u64::saturating_add)
+    }
+
+    /// The amount of peers in set A, i.e. the peers whose votes are required to commit a block.
+    pub fn min_votes_for_commit(&self) -> usize {
+        let quorum_weight = self.quorum_weight();
+        let mut weight = 0_u64;
+        let set_a_len = self
+            .0
+            .iter()
+            .position(|peer| {
+                weight = weight.saturating_add(self.weight(peer));
+                weight >= quorum_weight
+            })
+            .map_or(self.0.len(), |index| index + 1);
+
+        // NOTE: Leader and proxy tail must be different peers whenever consensus is required
+        set_a_len.max(self.0.len().min(2))
+    }
+
     /// Index of leader
     #[allow(clippy::unused_self)] // In order to be consistent with `proxy_tail_index` method
     pub const fn leader_index(&self) -> usize {
@@ -122,6 +192,19 @@ impl Topology {
         &self.0[self.proxy_tail_index()]
     }
 
+    /// Algorithm to aggregate block signatures with, if all peers use the same BLS algorithm.
+    pub fn aggregation_algorithm(&self) -> Option<Algorithm> {
+        let algorithm = self.0.first()?.public_key().algorithm();
+        let is_bls = matches!(algorithm, Algorithm::BlsNormal | Algorithm::BlsSmall);
+
+        (is_bls
+            && self
+                .0
+                .iter()
+                .all(|peer| peer.public_key().algorithm() == algorithm))
+        .then_some(algorithm)
+    }
+
     /// Filter signatures by roles in the topology.
     pub fn filter_signatures_by_roles<'a, I: IntoIterator<Item = &'a BlockSignature>>(
         &self,
@@ -197,14 +280,52 @@ impl Topology {
     }
 
     /// Re-arrange the set of peers after each successful block commit.
-    fn rotate_set_a(&mut self) {
-        let rotate_at = self.min_votes_for_commit();
-        self.0[..rotate_at].rotate_left(1);
-    }
-
-    /// Rotate topology after a block has been committed
-    pub fn block_committed(&mut self, new_peers: impl IntoIterator<Item = PeerId>) {
-        self.rotate_set_a();
+    ///
+    /// Set A is rotated by an offset derived from the `seed` revealed by the leader of
+    /// the committed block, so the next leader is unknown until the block is proposed,
+    /// yet nobody can choose it. Without a seed set A is rotated by one. The offset doesn't
+    /// depend on the weights, as if all peers had equal weights.
+    fn rotate_set_a(&mut self, seed: Option<HashOf<LeaderSeed>>) {
+        let len = self.0.len();
+        let rotate_at = if len > 3 {
+            self.max_faults() * 2 + 1
+        } else {
+            len
+        };
+        if rotate_at < 2 {
+            return;
+        }
+        // Offset is in `1..rotate_at` so that the leader of the committed block always changes
+        let offset = seed.map_or(1, |seed| {
+            let seed: &[u8; Hash::LENGTH] = seed.as_ref();
+            let mut offset = [0; 8];
+            offset.copy_from_slice(&seed[..8]);
+            u64::from_le_bytes(offset) % (rotate_at as u64 - 1) + 1
+        });
+        let offset = usize::try_from(offset).expect("INTERNAL BUG: offset is below set A length");
+        self.0[..rotate_at].rotate_left(offset);
+    }
+
+    /// Whether the leaders are selected at random, see [`Self::block_committed`].
+    pub fn randomizes_leaders(&self) -> bool {
+        !self.0.is_empty()
+            && self
+                .0
+                .iter()
+                .all(|peer| LeaderSeed::is_revealed_with(peer.public_key().algorithm()))
+    }
+
+    /// Rotate topology after a block has been committed.
+    ///
+    /// `seed` is the leader seed recorded in the committed block's header. It is only used
+    /// if all peers have BLS keys, since leaders with other keys don't reveal a seed
+    /// and the rotation after their blocks would be predictable.
+    pub fn block_committed(
+        &mut self,
+        seed: Option<HashOf<LeaderSeed>>,
+        new_peers: impl IntoIterator<Item = PeerId>,
+    ) {
+        self.rotate_set_a(seed.filter(|_| self.randomizes_leaders()));
         self.update_peer_list(new_peers);
         self.1 = 0;
     }
@@ -287,6 +408,7 @@ pub fn test_topology_with_keys<'a>(keys: impl IntoIterator<Item = &'a KeyPair>)
 mod tests {
     use iroha_crypto::KeyPair;
     use iroha_primitives::unique_vec;
+    use nonzero_ext::nonzero;
 
     use super::*;
     use crate::block::ValidBlock;
@@ -305,17 +427,86 @@ mod tests {
             .collect()
     }
 
+    fn test_seed(offset: u8) -> Option<HashOf<LeaderSeed>> {
+        let mut seed = [0; Hash::LENGTH];
+        seed[0] = offset;
+        Some(HashOf::from_untyped_unchecked(Hash::prehashed(seed)))
+    }
+
     #[test]
     fn rotate_set_a() {
         let mut topology = test_topology(7);
         let initial_topology = topology.clone();
-        topology.rotate_set_a();
+        topology.rotate_set_a(test_seed(0));
+        assert_eq!(
+            extract_order(&topology, &initial_topology),
+            vec![1, 2, 3, 4, 0, 5, 6]
+        )
+    }
+
+    #[test]
+    fn rotate_set_a_by_seed() {
+        let mut topology = test_topology(7);
+        let initial_topology = topology.clone();
+        topology.rotate_set_a(test_seed(2));
+        assert_eq!(
+            extract_order(&topology, &initial_topology),
+            vec![3, 4, 0, 1, 2, 5, 6]
+        );
+
+        let mut other_topology = initial_topology.clone();
+        other_topology.rotate_set_a(test_seed(2));
+        assert_eq!(topology, other_topology);
+    }
+
+    #[test]
+    fn rotate_set_a_without_seed() {
+        let mut topology = test_topology(7);
+        let initial_topology = topology.clone();
+        topology.rotate_set_a(None);
         assert_eq!(
             extract_order(&topology, &initial_topology),
             vec![1, 2, 3, 4, 0, 5, 6]
         )
     }
 
+    #[test]
+    fn rotate_set_a_always_changes_leader() {
+        let initial_topology = test_topology(7);
+        for offset in 0..=u8::MAX {
+            let mut topology = initial_topology.clone();
+            topology.rotate_set_a(test_seed(offset));
+            assert_ne!(topology.0[0], initial_topology.0[0]);
+        }
+    }
+
+    #[test]
+    fn leaders_are_randomized_only_with_bls_keys() {
+        let bls_keys =
+            core::iter::repeat_with(|| KeyPair::random_with_algorithm(Algorithm::BlsNormal))
+                .take(7)
+                .collect::<Vec<_>>();
+        let mut topology = test_topology_with_keys(&bls_keys);
+        assert!(topology.randomizes_leaders());
+        let initial_topology = topology.clone();
+        let peers = topology.0.clone();
+        topology.block_committed(test_seed(2), peers);
+        assert_eq!(
+            extract_order(&topology, &initial_topology),
+            vec![3, 4, 0, 1, 2, 5, 6]
+        );
+
+        let mut topology = test_topology(7);
+        assert!(!topology.randomizes_leaders());
+        let initial_topology = topology.clone();
+        let peers = topology.0.clone();
+        topology.block_committed(test_seed(2), peers);
+        assert_eq!(
+            extract_order(&topology, &initial_topology),
+            vec![1, 2, 3, 4, 0, 5, 6]
+        );
+    }
+
     #[test]
     fn update_peer_list() {
         let mut topology = test_topology(7);
@@ -329,6 +520,92 @@ mod tests {
         assert_eq!(topology.0, vec![peer0, peer2, peer5, peer7])
     }
 
+    #[test]
+    fn equal_weights_keep_peer_count_quorum() {
+        let mut topology = test_topology(7);
+        let weights = topology.iter().map(|peer| (peer.clone(), nonzero!(1_u64)));
+        topology.set_weights(weights.collect());
+
+        assert_eq!(topology.quorum_weight(), 5);
+        assert_eq!(topology.min_votes_for_commit(), 5);
+        assert_eq!(topology.max_faulty_weight(), topology.max_faults() as u64);
+    }
+
+    #[test]
+    fn weighted_set_a() {
+        let mut topology = test_topology(4);
+        let heavy_peer = topology.0[0].clone();
+        topology.set_weights([(heavy_peer, nonzero!(4_u64))].into_iter().collect());
+
+        assert_eq!(topology.total_weight(), 7);
+        assert_eq!(topology.max_faulty_weight(), 2);
+        assert_eq!(topology.quorum_weight(), 5);
+        // Leader and the next peer already reach the quorum
+        assert_eq!(topology.min_votes_for_commit(), 2);
+        assert_eq!(topology.proxy_tail_index(), 1);
+        assert_eq!(topology.role(&topology.0[2].clone()), Role::ObservingPeer);
+    }
+
+    #[test]
+    fn weighted_set_a_has_distinct_leader_and_proxy_tail() {
+        let mut topology = test_topology(4);
+        let heavy_peer = topology.0[0].clone();
+        topology.set_weights([(heavy_peer, nonzero!(10_u64))].into_iter().collect());
+
+        assert_eq!(topology.min_votes_for_commit(), 2);
+        assert_ne!(topology.leader(), topology.proxy_tail());
+    }
+
+    #[test]
+    fn votes_weight_counts_signatory_once() {
+        let key_pairs = core::iter::repeat_with(KeyPair::random)
+            .take(4)
+            .collect::<Vec<_>>();
+        let mut topology = test_topology_with_keys(&key_pairs);
+        let heavy_peer = topology.0[1].clone();
+        topology.set_weights([(heavy_peer, nonzero!(3_u64))].into_iter().collect());
+
+        let dummy_block = ValidBlock::new_dummy(key_pairs[0].private_key());
+        let dummy_signature = &dummy_block.as_ref().signatures().next().unwrap().signature;
+        let signatures = [0, 1, 1]
+            .map(|i| BlockSignature::new(i, dummy_signature.clone()))
+            .to_vec();
+
+        assert_eq!(topology.votes_weight(&signatures), 4);
+    }
+
+    #[test]
+    fn aggregation_algorithm_requires_same_bls_keys() {
+        let bls_keys =
+            core::iter::repeat_with(|| KeyPair::random_with_algorithm(Algorithm::BlsNormal))
+                .take(4)
+                .collect::<Vec<_>>();
+        let topology = test_topology_with_keys(&bls_keys);
+        assert_eq!(topology.aggregation_algorithm(), Some(Algorithm::BlsNormal));
+
+        let mixed_keys = bls_keys
+            .into_iter()
+            .chain(Some(KeyPair::random_with_algorithm(Algorithm::BlsSmall)))
+            .collect::<Vec<_>>();
+        let topology = test_topology_with_keys(&mixed_keys);
+        assert_eq!(topology.aggregation_algorithm(), None);
+
+        assert_eq!(test_topology(4).aggregation_algorithm(), None);
+    }
+
+    #[test]
+    fn rotate_set_a_ignores_weights() {
+        let mut topology = test_topology(7);
+        let heavy_peer = topology.0[0].clone();
+        topology.set_weights([(heavy_peer, nonzero!(10_u64))].into_iter().collect());
+        let initial_topology = topology.clone();
+        topology.rotate_set_a(test_seed(0));
+        assert_eq!(
+            extract_order(&topology, &initial_topology),
+            vec![1, 2, 3, 4, 0, 5, 6]
+        )
+    }
+
     #[test]
     fn filter_by_role() {
         let key_pairs = core::iter::repeat_with(KeyPair::random)
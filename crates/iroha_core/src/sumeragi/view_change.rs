@@ -111,14 +111,15 @@ impl SignedViewChangeProof {
 
     /// Verify if the proof is valid, given the peers in `topology`.
     fn verify(&self, topology: &Topology) -> bool {
-        let valid_count = self
+        let valid_weight = self
             .signatures
             .iter()
-            .filter(|signature| topology.position(&signature.public_key).is_some())
-            .count();
+            .filter_map(|signature| topology.position(&signature.public_key))
+            .map(|index| topology.weight(&topology.as_ref()[index]))
+            .fold(0, u64::saturating_add);
 
         // NOTE: See Whitepaper for the information on this limit.
-        valid_count > topology.max_faults()
+        valid_weight > topology.max_faulty_weight()
     }
 }
 
@@ -59,6 +59,14 @@ impl Telemetry {
         self.metrics.dropped_messages.inc();
     }
 
+    /// Increase rejected requests metric for the `route`, labeled by the kind of the rate limit `key`
+    pub fn inc_rejected_requests(&self, route: &str, key: &str) {
+        self.metrics
+            .rejected_requests
+            .with_label_values(&[route, key])
+            .inc();
+    }
+
     /// Set view changes metrics
     pub fn set_view_changes(&self, value: u64) {
         self.metrics.view_changes.set(value);
@@ -136,7 +144,8 @@ impl Actor {
 
         let state_view = self.state.view();
 
-        let start_index = self.last_sync_block;
+        // Pruned blocks can't be loaded, so they are not accounted
+        let start_index = self.last_sync_block.max(self.kura.pruned_blocks_count());
         {
             let mut inc_txs_accepted = 0;
             let mut inc_txs_rejected = 0;
@@ -380,6 +389,7 @@ mod tests {
                     capacity: nonzero!(10usize),
                     capacity_per_user: nonzero!(10usize),
                     transaction_time_to_live: Duration::from_secs(100),
+                    ..iroha_config::parameters::actual::Queue::default()
                 },
                 &time_source,
             ));
@@ -7,7 +7,10 @@
 //! This is also where the actual execution of instructions, as well
 //! as various forms of validation are performed.
 
-use std::time::{Duration, SystemTime};
+use std::{
+    num::NonZeroU64,
+    time::{Duration, SystemTime},
+};
 
 use eyre::Result;
 pub use iroha_data_model::prelude::*;
@@ -272,10 +275,40 @@ impl StateBlock<'_> {
     ) -> TransactionResultInner {
         let authority = tx.as_ref().authority().clone();
 
-        if state_transaction.world.accounts.get(&authority).is_none() {
+        let Some(account) = state_transaction.world.accounts.get(&authority) else {
             return Err(TransactionRejectionReason::AccountDoesNotExist(
                 FindError::Account(authority.clone()),
             ));
+        };
+
+        if let Some(sequence) = tx.as_ref().sequence() {
+            let expected = account
+                .sequence
+                .checked_add(1)
+                .and_then(NonZeroU64::new)
+                .expect("INTERNAL BUG: account sequence number overflow");
+            if sequence != expected {
+                return Err(TransactionRejectionReason::InvalidSequence(
+                    SequenceMismatch {
+                        expected,
+                        actual: sequence,
+                    },
+                ));
+            }
+            // Discarded together with the rest of the changes if the transaction is rejected
+            state_transaction
+                .world
+                .account_mut(&authority)
+                .expect("account existence is checked above")
+                .sequence = sequence.get();
+            state_transaction
+                .world
+                .emit_events(Some(AccountEvent::SequenceChanged(
+                    AccountSequenceChanged {
+                        account: authority.clone(),
+                        sequence: sequence.get(),
+                    },
+                )));
         }
 
         debug!(tx=%tx.as_ref().hash(), "Validating transaction");
@@ -2,15 +2,17 @@
 use alloc::{borrow::ToOwned as _, string::ToString as _, vec, vec::Vec};
 use core::marker::PhantomData;
 
+use ark_ec::Group as _;
 #[cfg(feature = "rand")]
 use rand_chacha::rand_core::OsRng;
-use sha2::Sha256;
+use sha2::{Digest as _, Sha256};
 // TODO: Better to use `SecretKey`, not `SecretKeyVT`, but it requires to implement
 // interior mutability
 use w3f_bls::{EngineBLS as _, PublicKey, SecretKeyVT as SecretKey, SerializableToBytes as _};
 use zeroize::Zeroize as _;
 
 pub(super) const MESSAGE_CONTEXT: &[u8; 20] = b"for signing messages";
+const AGGREGATE_CONTEXT: &[u8; 26] = b"for aggregating signatures";
 
 use crate::{Algorithm, Error, KeyGenOption, ParseError};
 
@@ -70,6 +72,77 @@ impl<C: BlsConfiguration + ?Sized> BlsImpl<C> {
         Ok(())
     }
 
+    /// Aggregate signatures of the same message made by the paired `pks` into a single signature.
+    ///
+    /// Every signature is weighted by the coefficient of its public key,
+    /// so that a rogue public key derived from the others can't forge the aggregate.
+    pub fn aggregate(signatures: &[(&PublicKey<C::Engine>, &[u8])]) -> Result<Vec<u8>, Error> {
+        let pks = signatures.iter().map(|(pk, _)| *pk).collect::<Vec<_>>();
+        let aggregate = signatures
+            .iter()
+            .zip(Self::coefficients(&pks))
+            .map(|((_, signature), coefficient)| {
+                w3f_bls::Signature::<C::Engine>::from_bytes(signature)
+                    .map(|signature| signature.0.mul_bigint(coefficient))
+                    .map_err(|_| ParseError("Failed to parse signature.".to_owned()))
+            })
+            .try_fold(None, |aggregate, signature| {
+                signature.map(|signature| {
+                    Some(aggregate.map_or(signature, |aggregate| aggregate + signature))
+                })
+            })?
+            .ok_or_else(|| Error::Signing("No signatures to aggregate".to_owned()))?;
+
+        Ok(w3f_bls::Signature::<C::Engine>(aggregate).to_bytes())
+    }
+
+    /// Verify the aggregate of the signatures of the message made by `pks`.
+    pub fn verify_aggregate(
+        message: &[u8],
+        signature: &[u8],
+        pks: &[&PublicKey<C::Engine>],
+    ) -> Result<(), Error> {
+        let aggregate = pks
+            .iter()
+            .zip(Self::coefficients(pks))
+            .map(|(pk, coefficient)| pk.0.mul_bigint(coefficient))
+            .reduce(|aggregate, pk| aggregate + pk)
+            .ok_or(Error::BadSignature)?;
+
+        Self::verify(message, signature, &PublicKey(aggregate))
+    }
+
+    /// Coefficients `H(pk_i, {pk})` of the public keys in an aggregate.
+    ///
+    /// Each coefficient depends on the whole set of public keys, so a public key
+    /// can't be chosen to cancel out the others (see <https://eprint.iacr.org/2018/483>).
+    fn coefficients(pks: &[&PublicKey<C::Engine>]) -> Vec<[u64; 2]> {
+        let pks = pks.iter().map(|pk| pk.to_bytes()).collect::<Vec<_>>();
+        let mut set = pks.clone();
+        set.sort_unstable();
+        let set = set
+            .iter()
+            .fold(Sha256::new(), |hasher, pk| hasher.chain_update(pk))
+            .finalize();
+
+        pks.iter()
+            .map(|pk| {
+                let hash = Sha256::new()
+                    .chain_update(AGGREGATE_CONTEXT)
+                    .chain_update(pk)
+                    .chain_update(set)
+                    .finalize();
+                // 128-bit coefficients are enough for the security of the aggregate
+                let limb = |i: usize| {
+                    let mut limb = [0; 8];
+                    limb.copy_from_slice(&hash[i * 8..(i + 1) * 8]);
+                    u64::from_le_bytes(limb)
+                };
+                [limb(0), limb(1)]
+            })
+            .collect()
+    }
+
     pub fn parse_public_key(payload: &[u8]) -> Result<PublicKey<C::Engine>, ParseError> {
         PublicKey::from_bytes(payload).map_err(|err| ParseError(err.to_string()))
     }
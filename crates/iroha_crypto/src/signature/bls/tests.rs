@@ -1,4 +1,4 @@
-use w3f_bls::SerializableToBytes as _;
+use w3f_bls::{PublicKey, SerializableToBytes as _};
 
 use super::{
     implementation::{BlsConfiguration, BlsImpl},
@@ -48,6 +48,39 @@ fn test_signature_verification_different_keys<C: BlsConfiguration>() {
         .expect_err("Signature verification for wrong public key should fail");
 }
 
+#[allow(clippy::similar_names)]
+fn test_aggregate_signature_verification<C: BlsConfiguration>() {
+    let (pk_1, sk_1) = BlsImpl::<C>::keypair(KeyGenOption::Random);
+    let (pk_2, sk_2) = BlsImpl::<C>::keypair(KeyGenOption::Random);
+
+    let signature_1 = BlsImpl::<C>::sign(MESSAGE_1, &sk_1);
+    let signature_2 = BlsImpl::<C>::sign(MESSAGE_1, &sk_2);
+    let aggregate = BlsImpl::<C>::aggregate(&[
+        (&pk_1, signature_1.as_slice()),
+        (&pk_2, signature_2.as_slice()),
+    ])
+    .expect("Signatures should be aggregated");
+
+    BlsImpl::<C>::verify_aggregate(MESSAGE_1, &aggregate, &[&pk_1, &pk_2])
+        .expect("Aggregate signature verification should succeed");
+    BlsImpl::<C>::verify_aggregate(MESSAGE_1, &aggregate, &[&pk_1])
+        .expect_err("Aggregate signature verification with missing public key should fail");
+    BlsImpl::<C>::verify_aggregate(MESSAGE_2, &aggregate, &[&pk_1, &pk_2])
+        .expect_err("Aggregate signature verification for wrong message should fail");
+}
+
+#[allow(clippy::similar_names)]
+fn test_aggregate_signature_rogue_key<C: BlsConfiguration>() {
+    let (pk_honest, _sk_honest) = BlsImpl::<C>::keypair(KeyGenOption::Random);
+    let (pk_evil, sk_evil) = BlsImpl::<C>::keypair(KeyGenOption::Random);
+    // Without coefficients `pk_honest + pk_rogue` would be `pk_evil`
+    let pk_rogue = PublicKey::<C::Engine>(pk_evil.0 - pk_honest.0);
+
+    let forged = BlsImpl::<C>::sign(MESSAGE_1, &sk_evil);
+    BlsImpl::<C>::verify_aggregate(MESSAGE_1, &forged, &[&pk_honest, &pk_rogue])
+        .expect_err("Aggregate signature forged with a rogue key should fail");
+}
+
 mod normal {
     use super::*;
 
@@ -70,6 +103,16 @@ mod normal {
     fn signature_verification_different_keys() {
         test_signature_verification_different_keys::<NormalConfiguration>();
     }
+
+    #[test]
+    fn aggregate_signature_verification() {
+        test_aggregate_signature_verification::<NormalConfiguration>();
+    }
+
+    #[test]
+    fn aggregate_signature_rogue_key() {
+        test_aggregate_signature_rogue_key::<NormalConfiguration>();
+    }
 }
 
 mod small {
@@ -94,4 +137,14 @@ mod small {
     fn signature_verification_different_keys() {
         test_signature_verification_different_keys::<SmallConfiguration>();
     }
+
+    #[test]
+    fn aggregate_signature_verification() {
+        test_aggregate_signature_verification::<SmallConfiguration>();
+    }
+
+    #[test]
+    fn aggregate_signature_rogue_key() {
+        test_aggregate_signature_rogue_key::<SmallConfiguration>();
+    }
 }
@@ -113,6 +113,86 @@ impl Signature {
 
         Ok(())
     }
+
+    /// Aggregate signatures of the same payload made by the paired public keys
+    /// into a single signature.
+    ///
+    /// Only signatures made with BLS keys of the same algorithm can be aggregated.
+    /// The aggregate is protected against rogue key attacks, so the signatories
+    /// don't have to prove possession of their private keys.
+    ///
+    /// # Errors
+    /// - `signatures` is empty or contains a signature not made with the algorithm of its key
+    /// - algorithms of the public keys differ or don't support aggregation
+    pub fn aggregate<'a>(
+        signatures: impl IntoIterator<Item = (&'a PublicKey, &'a Signature)>,
+    ) -> Result<Self, Error> {
+        let signatures = signatures
+            .into_iter()
+            .map(|(public_key, signature)| ((&public_key.0).into(), signature.payload()))
+            .collect::<Vec<(PublicKeyFull, _)>>();
+
+        let mut normal = Vec::new();
+        let mut small = Vec::new();
+        for (public_key, signature) in &signatures {
+            match public_key {
+                PublicKeyFull::BlsNormal(pk) => normal.push((pk, *signature)),
+                PublicKeyFull::BlsSmall(pk) => small.push((pk, *signature)),
+                PublicKeyFull::Ed25519(_) | PublicKeyFull::Secp256k1(_) => {
+                    return Err(Error::Signing(
+                        "Only BLS signatures can be aggregated".into(),
+                    ))
+                }
+            }
+        }
+
+        let signature = match (normal.is_empty(), small.is_empty()) {
+            (false, true) => bls::BlsNormal::aggregate(&normal),
+            (true, false) => bls::BlsSmall::aggregate(&small),
+            (true, true) => Err(Error::Signing("No signatures to aggregate".into())),
+            (false, false) => Err(Error::Signing(
+                "Signatures of different algorithms can't be aggregated".into(),
+            )),
+        }?;
+
+        Ok(Self {
+            payload: ConstVec::new(signature),
+        })
+    }
+
+    /// Verify `payload` using the signature aggregated from the signatures of all `public_keys`.
+    ///
+    /// # Errors
+    /// Fails if the message doesn't pass verification,
+    /// or if `public_keys` is empty or their algorithms differ or don't support aggregation
+    pub fn verify_aggregate<'a>(
+        &self,
+        public_keys: impl IntoIterator<Item = &'a PublicKey>,
+        payload: &[u8],
+    ) -> Result<(), Error> {
+        let public_keys = public_keys
+            .into_iter()
+            .map(|public_key| (&public_key.0).into())
+            .collect::<Vec<PublicKeyFull>>();
+
+        let mut normal = Vec::new();
+        let mut small = Vec::new();
+        for public_key in &public_keys {
+            match public_key {
+                PublicKeyFull::BlsNormal(pk) => normal.push(pk),
+                PublicKeyFull::BlsSmall(pk) => small.push(pk),
+                PublicKeyFull::Ed25519(_) | PublicKeyFull::Secp256k1(_) => {
+                    return Err(Error::BadSignature)
+                }
+            }
+        }
+
+        match (normal.is_empty(), small.is_empty()) {
+            (false, true) => bls::BlsNormal::verify_aggregate(payload, &self.payload, &normal),
+            (true, false) => bls::BlsSmall::verify_aggregate(payload, &self.payload, &small),
+            _ => Err(Error::BadSignature),
+        }
+    }
 }
 
 // TODO: Enable in ffi_import
@@ -220,6 +300,37 @@ impl<T> SignatureOf<T> {
     pub fn verify_hash(&self, public_key: &PublicKey, hash: HashOf<T>) -> Result<(), Error> {
         self.0.verify(public_key, hash.as_ref())
     }
+
+    /// Aggregate signatures of the same hash made by the paired public keys
+    /// into a single signature.
+    ///
+    /// # Errors
+    /// See [`Signature::aggregate`]
+    pub fn aggregate<'a>(
+        signatures: impl IntoIterator<Item = (&'a PublicKey, &'a SignatureOf<T>)>,
+    ) -> Result<Self, Error>
+    where
+        T: 'a,
+    {
+        Signature::aggregate(
+            signatures
+                .into_iter()
+                .map(|(public_key, signature)| (public_key, &signature.0)),
+        )
+        .map(|signature| Self(signature, PhantomData))
+    }
+
+    /// Verify aggregate signature of all `public_keys` for this hash
+    ///
+    /// # Errors
+    /// See [`Signature::verify_aggregate`]
+    pub fn verify_aggregate_hash<'a>(
+        &self,
+        public_keys: impl IntoIterator<Item = &'a PublicKey>,
+        hash: HashOf<T>,
+    ) -> Result<(), Error> {
+        self.0.verify_aggregate(public_keys, hash.as_ref())
+    }
 }
 
 impl<T: parity_scale_codec::Encode> SignatureOf<T> {
@@ -286,6 +397,37 @@ mod tests {
         signature.verify(key_pair.public_key(), message).unwrap();
     }
 
+    #[test]
+    #[cfg(feature = "rand")]
+    fn aggregate_signature_bls_normal() {
+        let key_pairs =
+            core::iter::repeat_with(|| KeyPair::random_with_algorithm(Algorithm::BlsNormal))
+                .take(3)
+                .collect::<Vec<_>>();
+        let message = b"Test message to sign.";
+        let signatures = key_pairs
+            .iter()
+            .map(|key_pair| Signature::new(key_pair.private_key(), message))
+            .collect::<Vec<_>>();
+        let aggregate =
+            Signature::aggregate(key_pairs.iter().map(KeyPair::public_key).zip(&signatures))
+                .unwrap();
+        aggregate
+            .verify_aggregate(key_pairs.iter().map(KeyPair::public_key), message)
+            .unwrap();
+        aggregate
+            .verify_aggregate(key_pairs.iter().skip(1).map(KeyPair::public_key), message)
+            .unwrap_err();
+    }
+
+    #[test]
+    #[cfg(feature = "rand")]
+    fn aggregate_signature_ed25519_fails() {
+        let key_pair = KeyPair::random_with_algorithm(Algorithm::Ed25519);
+        let signature = Signature::new(key_pair.private_key(), b"Test message to sign.");
+        Signature::aggregate([(key_pair.public_key(), &signature)]).unwrap_err();
+    }
+
     #[test]
     fn signature_serialized_representation() {
         let input = json!("3A7991AF1ABB77F3FD27CC148404A6AE4439D095A63591B77C788D53F708A02A1509A611AD6D97B01D871E58ED00C8FD7C3917B6CA61A8C2833A19E000AAC2E4");
@@ -75,6 +75,9 @@ mod model {
         pub id: AccountId,
         /// Metadata of this account as a key-value store.
         pub metadata: Metadata,
+        /// Sequence number of the latest committed transaction of this account that had one.
+        #[serde(default)]
+        pub sequence: u64,
     }
 
     /// Builder which should be submitted in a transaction to create a new [`Account`]
@@ -100,15 +103,20 @@ pub struct AccountEntry<'world> {
     pub id: &'world AccountId,
     /// Metadata of this account as a key-value store.
     pub metadata: &'world Metadata,
+    /// Sequence number of the latest committed transaction of this account that had one.
+    pub sequence: u64,
 }
 
 /// [`Account`] without `id`.
 /// Needed only for [`World::accounts`] map to reduce memory usage.
 /// In other places use [`Account`] directly.
-#[derive(Clone, Deserialize, Serialize)]
+#[derive(Clone, Decode, Encode, Deserialize, Serialize)]
 pub struct AccountValue {
     /// Metadata of this account as a key-value store.
     pub metadata: Metadata,
+    /// Sequence number of the latest committed transaction of this account that had one.
+    #[serde(default)]
+    pub sequence: u64,
 }
 
 impl AccountId {
@@ -159,6 +167,7 @@ impl NewAccount {
         Account {
             id: self.id,
             metadata: self.metadata,
+            sequence: 0,
         }
     }
 }
@@ -187,6 +196,7 @@ impl Registrable for NewAccount {
         Account {
             id: self.id,
             metadata: self.metadata,
+            sequence: 0,
         }
     }
 }
@@ -224,6 +234,7 @@ impl<'world> AccountEntry<'world> {
         Self {
             id,
             metadata: &value.metadata,
+            sequence: value.sequence,
         }
     }
 
@@ -237,11 +248,17 @@ impl<'world> AccountEntry<'world> {
         self.metadata
     }
 
+    /// Getter for `sequence`
+    pub fn sequence(&self) -> u64 {
+        self.sequence
+    }
+
     /// Converts to `Account`
     pub fn to_owned(&self) -> Account {
         Account {
             id: self.id.clone(),
             metadata: self.metadata.clone(),
+            sequence: self.sequence,
         }
     }
 }
@@ -252,6 +269,7 @@ impl IntoKeyValue for Account {
     fn into_key_value(self) -> (Self::Key, Self::Value) {
         let value = AccountValue {
             metadata: self.metadata,
+            sequence: self.sequence,
         };
         (self.id, value)
     }
@@ -2,7 +2,7 @@
 //! instructions implementations.
 #[cfg(not(feature = "std"))]
 use alloc::{collections::btree_map, format, string::String, vec::Vec};
-use core::{fmt, str::FromStr};
+use core::{fmt, num::NonZeroU32, str::FromStr};
 #[cfg(feature = "std")]
 use std::collections::btree_map;
 
@@ -128,6 +128,14 @@ mod model {
         /// The total amount of this asset in existence (sum of all asset values).
         #[getset(get_copy = "pub")]
         pub total_quantity: Numeric,
+        /// Maximum value of [`Self::total_quantity`], enforced when minting.
+        #[getset(get_copy = "pub")]
+        #[serde(default)]
+        pub max_supply: Option<Numeric>,
+        /// Maximum quantity of this asset a single account can hold.
+        #[getset(get_copy = "pub")]
+        #[serde(default)]
+        pub max_balance: Option<Numeric>,
     }
 
     /// Asset represents some sort of commodity or value.
@@ -152,6 +160,13 @@ mod model {
         /// Asset's Quantity.
         #[getset(get = "pub")]
         pub value: Numeric,
+        /// Is the asset frozen, so that it can't be transferred or burned.
+        #[serde(default)]
+        pub frozen: bool,
+        /// Part of the quantity on hold, which can't be transferred or burned.
+        #[getset(get = "pub")]
+        #[serde(default)]
+        pub held: Numeric,
     }
 
     /// Builder which can be submitted in a transaction to create a new [`AssetDefinition`]
@@ -172,11 +187,17 @@ mod model {
         pub logo: Option<IpfsPath>,
         /// Metadata associated with the asset definition builder.
         pub metadata: Metadata,
+        /// The maximum supply associated with the asset definition builder.
+        #[serde(default)]
+        pub max_supply: Option<Numeric>,
+        /// The per-account balance limit associated with the asset definition builder.
+        #[serde(default)]
+        pub max_balance: Option<Numeric>,
     }
 
     /// An assets mintability scheme. `Infinitely` means elastic
-    /// supply. `Once` is what you want to use. Don't use `Not` explicitly
-    /// outside of smartcontracts.
+    /// supply. `Once` is what you want to use. `Limited` allows a fixed
+    /// number of mints. Don't use `Not` explicitly outside of smartcontracts.
     #[derive(
         Debug,
         Display,
@@ -204,7 +225,10 @@ mod model {
         /// Non-mintable asset (token), with a fixed supply. Can be burned, but not minted.
         #[display(fmt = "-")]
         Not,
-        // TODO: Support more variants using bit-compacted tag, and `u32` mintability tokens.
+        /// Asset (token) which can be burned, and minted the given number of **more** times.
+        /// Becomes [`Mintable::Not`] after the last mint.
+        #[display(fmt = "~{_0}")]
+        Limited(NonZeroU32),
     }
 }
 
@@ -215,15 +239,25 @@ pub struct AssetEntry<'world> {
     pub id: &'world AssetId,
     /// Asset's Quantity.
     pub value: &'world Numeric,
+    /// Is the asset frozen, so that it can't be transferred or burned.
+    pub frozen: bool,
+    /// Part of the quantity on hold, which can't be transferred or burned.
+    pub held: &'world Numeric,
 }
 
 /// [`Asset`] without `id` field.
 /// Needed only for [`World::assets`] map to reduce memory usage.
 /// In other places use [`Asset`] directly.
-#[derive(Copy, Clone, Deserialize, Serialize)]
+#[derive(Copy, Clone, Decode, Encode, Deserialize, Serialize)]
 pub struct AssetValue {
     /// Asset's Quantity.
     pub value: Numeric,
+    /// Is the asset frozen, so that it can't be transferred or burned.
+    #[serde(default)]
+    pub frozen: bool,
+    /// Part of the quantity on hold, which can't be transferred or burned.
+    #[serde(default)]
+    pub held: Numeric,
 }
 
 impl AssetDefinition {
@@ -258,6 +292,8 @@ impl Asset {
         Self {
             id,
             value: value.into(),
+            frozen: false,
+            held: Numeric::ZERO,
         }
     }
 }
@@ -271,6 +307,8 @@ impl NewAssetDefinition {
             mintable: Mintable::Infinitely,
             logo: None,
             metadata: Metadata::default(),
+            max_supply: None,
+            max_balance: None,
         }
     }
 
@@ -282,6 +320,28 @@ impl NewAssetDefinition {
         self
     }
 
+    /// Set mintability to [`Mintable::Limited`], allowing `times` mints
+    #[inline]
+    #[must_use]
+    pub fn mintable_limited(mut self, times: NonZeroU32) -> Self {
+        self.mintable = Mintable::Limited(times);
+        self
+    }
+
+    /// Cap the total supply of the asset, replacing previously defined value
+    #[must_use]
+    pub fn with_max_supply(mut self, max_supply: impl Into<Numeric>) -> Self {
+        self.max_supply = Some(max_supply.into());
+        self
+    }
+
+    /// Limit the quantity of the asset a single account can hold, replacing previously defined value
+    #[must_use]
+    pub fn with_max_balance(mut self, max_balance: impl Into<Numeric>) -> Self {
+        self.max_balance = Some(max_balance.into());
+        self
+    }
+
     /// Add [`logo`](IpfsPath) to the asset definition replacing previously defined value
     #[must_use]
     pub fn with_logo(mut self, logo: IpfsPath) -> Self {
@@ -398,6 +458,8 @@ impl Registrable for NewAssetDefinition {
             metadata: self.metadata,
             owned_by: authority.clone(),
             total_quantity: Numeric::ZERO,
+            max_supply: self.max_supply,
+            max_balance: self.max_balance,
         }
     }
 }
@@ -408,6 +470,8 @@ impl<'world> AssetEntry<'world> {
         Self {
             id,
             value: &value.value,
+            frozen: value.frozen,
+            held: &value.held,
         }
     }
 
@@ -421,11 +485,23 @@ impl<'world> AssetEntry<'world> {
         self.value
     }
 
+    /// Getter for `frozen`
+    pub fn frozen(&self) -> bool {
+        self.frozen
+    }
+
+    /// Getter for `held`
+    pub fn held(&self) -> &Numeric {
+        self.held
+    }
+
     /// Converts to `Asset`
     pub fn to_owned(&self) -> Asset {
         Asset {
             id: self.id.clone(),
             value: *self.value,
+            frozen: self.frozen,
+            held: *self.held,
         }
     }
 }
@@ -434,7 +510,11 @@ impl IntoKeyValue for Asset {
     type Key = AssetId;
     type Value = AssetValue;
     fn into_key_value(self) -> (Self::Key, Self::Value) {
-        let value = AssetValue { value: self.value };
+        let value = AssetValue {
+            value: self.value,
+            frozen: self.frozen,
+            held: self.held,
+        };
         (self.id, value)
     }
 }
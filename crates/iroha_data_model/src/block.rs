@@ -9,7 +9,9 @@ use core::{fmt::Display, num::NonZeroU64, time::Duration};
 use std::collections::BTreeSet;
 
 use derive_more::{Constructor, Display};
-use iroha_crypto::{HashOf, MerkleProof, MerkleTree, SignatureOf};
+use iroha_crypto::{
+    Algorithm, Hash, HashOf, MerkleProof, MerkleTree, PrivateKey, PublicKey, Signature, SignatureOf,
+};
 use iroha_data_model_derive::model;
 use iroha_macro::FromVariant;
 use iroha_schema::IntoSchema;
@@ -67,6 +69,11 @@ mod model {
         /// Value of view change index. Used to resolve soft forks.
         #[getset(skip)]
         pub view_change_index: u32,
+        /// Hash of the [`LeaderSeed`] revealed by this block's leader,
+        /// which seeds the selection of the next block's leader.
+        /// None for the genesis block and for leaders without a BLS key.
+        #[getset(get_copy = "pub")]
+        pub leader_seed: Option<HashOf<LeaderSeed>>,
     }
 
     /// Core contents of a block.
@@ -91,8 +98,33 @@ mod model {
         pub header: BlockHeader,
         /// External transactions as source of the state, forming the first half of the transaction entrypoints.
         pub transactions: Vec<SignedTransaction>,
+        /// Seed revealed by the leader, committed to by [`BlockHeader::leader_seed`].
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub leader_seed: Option<LeaderSeed>,
     }
 
+    /// Verifiable random value revealed by the leader of a block,
+    /// which seeds the selection of the next block's leader.
+    ///
+    /// It is the leader's BLS signature of the previous block hash. BLS signatures are unique,
+    /// so the leader can't choose the seed and nobody else can compute it before it is revealed.
+    #[derive(
+        Debug,
+        Clone,
+        PartialEq,
+        Eq,
+        PartialOrd,
+        Ord,
+        Decode,
+        Encode,
+        Deserialize,
+        Serialize,
+        IntoSchema,
+    )]
+    #[serde(transparent)]
+    #[repr(transparent)]
+    pub struct LeaderSeed(pub(super) Signature);
+
     /// The validator index and its corresponding signature on the block header.
     #[derive(
         Debug,
@@ -115,6 +147,32 @@ mod model {
         pub signature: SignatureOf<BlockHeader>,
     }
 
+    /// Signatures of the validators on the block header aggregated into a single signature.
+    ///
+    /// Replaces individual [`BlockSignature`]s of a committed block when all validators use the same BLS algorithm.
+    #[derive(
+        Debug,
+        Clone,
+        PartialEq,
+        Eq,
+        PartialOrd,
+        Ord,
+        Getters,
+        Decode,
+        Encode,
+        Deserialize,
+        Serialize,
+        IntoSchema,
+    )]
+    pub struct BlockCommitCertificate {
+        /// Bitmap of the validator indices in the network topology: bit `i % 8` of byte `i / 8` is set if validator `i` signed.
+        #[getset(skip)]
+        pub(super) signatories: Vec<u8>,
+        /// Aggregate of the validator signatures on the block header.
+        #[getset(get = "pub")]
+        pub(super) signature: SignatureOf<BlockHeader>,
+    }
+
     /// Block collecting signatures from validators.
     #[version_with_scale(version = 1, versioned_alias = "SignedBlock")]
     #[derive(
@@ -136,6 +194,9 @@ mod model {
     pub struct SignedBlockV1 {
         /// Signatures of validators who approved this block.
         pub(super) signatures: BTreeSet<BlockSignature>,
+        /// Signatures of validators aggregated into a single one. Set instead of `signatures` once the block is committed.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub(super) commit_certificate: Option<BlockCommitCertificate>,
         /// Block payload to be signed.
         pub(super) payload: BlockPayload,
         /// Secondary block state resulting from execution.
@@ -211,6 +272,7 @@ impl BlockHeader {
             merkle_root: Option<HashOf<MerkleTree<TransactionEntrypoint>>>,
             creation_time_ms: u64,
             view_change_index: u32,
+            leader_seed: Option<HashOf<LeaderSeed>>,
         }
 
         impl From<&BlockHeader> for BlockHeaderForConsensus {
@@ -222,6 +284,7 @@ impl BlockHeader {
                     result_merkle_root: _,
                     creation_time_ms,
                     view_change_index,
+                    leader_seed,
                 } = *value;
 
                 Self {
@@ -230,11 +293,85 @@ impl BlockHeader {
                     merkle_root,
                     creation_time_ms,
                     view_change_index,
+                    leader_seed,
                 }
             }
         }
 
-        HashOf::from_untyped_unchecked(HashOf::new(&BlockHeaderForConsensus::from(self)).into())
+        // The header is identified by the hash of its consensus fields
+        HashOf::from_untyped_unchecked(Hash::new(BlockHeaderForConsensus::from(self).encode()))
+    }
+}
+
+impl LeaderSeed {
+    /// Sets leader seeds apart from other signatures made with the leader key.
+    const DOMAIN: &'static [u8] = b"iroha:leader_seed:";
+
+    fn payload(prev_block_hash: HashOf<BlockHeader>) -> Vec<u8> {
+        let prev_block_hash: &[u8] = prev_block_hash.as_ref();
+        [Self::DOMAIN, prev_block_hash].concat()
+    }
+
+    /// Whether leaders with keys of the given `algorithm` reveal a seed.
+    ///
+    /// Only BLS signatures are unique, other algorithms would let the leader choose the seed.
+    pub const fn is_revealed_with(algorithm: Algorithm) -> bool {
+        matches!(algorithm, Algorithm::BlsNormal | Algorithm::BlsSmall)
+    }
+
+    /// Reveal the seed of the leader of the block following the one with `prev_block_hash`.
+    ///
+    /// None if the leader's key isn't a BLS key.
+    pub fn new(private_key: &PrivateKey, prev_block_hash: HashOf<BlockHeader>) -> Option<Self> {
+        Self::is_revealed_with(private_key.algorithm())
+            .then(|| Self(Signature::new(private_key, &Self::payload(prev_block_hash))))
+    }
+
+    /// Verify that the seed was revealed by the leader with `public_key`
+    /// for the block following the one with `prev_block_hash`.
+    ///
+    /// # Errors
+    /// Fails if the seed isn't a signature of the leader
+    pub fn verify(
+        &self,
+        public_key: &PublicKey,
+        prev_block_hash: HashOf<BlockHeader>,
+    ) -> Result<(), iroha_crypto::Error> {
+        self.0.verify(public_key, &Self::payload(prev_block_hash))
+    }
+}
+
+impl BlockCommitCertificate {
+    /// Construct [`Self`] from the topology indices of the validators and their aggregate signature.
+    pub fn new(
+        signatories: impl IntoIterator<Item = u64>,
+        signature: SignatureOf<BlockHeader>,
+    ) -> Self {
+        let mut bitmap = Vec::new();
+        for index in signatories {
+            let byte = usize::try_from(index / 8).expect("Validator index should fit into usize");
+            if bitmap.len() <= byte {
+                bitmap.resize(byte + 1, 0);
+            }
+            bitmap[byte] |= 1 << (index % 8);
+        }
+
+        Self {
+            signatories: bitmap,
+            signature,
+        }
+    }
+
+    /// Topology indices of the validators whose signatures were aggregated, in ascending order.
+    pub fn signatories(&self) -> impl Iterator<Item = u64> + '_ {
+        self.signatories
+            .iter()
+            .enumerate()
+            .flat_map(|(byte_idx, byte)| {
+                (0..8_u64)
+                    .filter(move |bit| byte & (1 << bit) != 0)
+                    .map(move |bit| byte_idx as u64 * 8 + bit)
+            })
     }
 }
 
@@ -259,12 +396,15 @@ impl SignedBlock {
         signature: BlockSignature,
         header: BlockHeader,
         transactions: Vec<SignedTransaction>,
+        leader_seed: Option<LeaderSeed>,
     ) -> SignedBlock {
         SignedBlockV1 {
             signatures: [signature].into_iter().collect(),
+            commit_certificate: None,
             payload: BlockPayload {
                 header,
                 transactions,
+                leader_seed,
             },
             result: BlockResult::default(),
         }
@@ -318,7 +458,42 @@ impl SignedBlock {
         block.header()
     }
 
+    /// Seed revealed by the leader of this block, if the leader has a BLS key.
+    #[inline]
+    pub fn leader_seed(&self) -> Option<&LeaderSeed> {
+        let SignedBlock::V1(block) = self;
+        block.payload.leader_seed.as_ref()
+    }
+
+    /// Aggregated signatures of the validators, if the block signatures were aggregated.
+    #[inline]
+    pub fn commit_certificate(&self) -> Option<&BlockCommitCertificate> {
+        let SignedBlock::V1(block) = self;
+        block.commit_certificate.as_ref()
+    }
+
+    /// Topology indices of the validators who signed this block,
+    /// either individually or through the commit certificate.
+    ///
+    /// If the block carries a commit certificate, only its signatories are returned.
+    pub fn signatories(&self) -> impl Iterator<Item = u64> + '_ {
+        let SignedBlock::V1(block) = self;
+        let signatures = if block.commit_certificate.is_some() {
+            [].iter()
+        } else {
+            block.signatures.iter()
+        };
+
+        signatures.map(|signature| signature.index).chain(
+            block
+                .commit_certificate
+                .iter()
+                .flat_map(BlockCommitCertificate::signatories),
+        )
+    }
+
     /// Signatures of peers which approved this block.
+    /// Empty if the signatures were aggregated into the [`BlockCommitCertificate`].
     #[inline]
     pub fn signatures(
         &self,
@@ -530,6 +705,48 @@ impl SignedBlock {
         Ok(core::mem::replace(&mut block.signatures, signatures))
     }
 
+    /// Aggregate the individual signatures of the block into a commit certificate.
+    ///
+    /// `public_keys` are the keys of the peers in the topology the block was committed with.
+    /// Does nothing if the block signatures are already aggregated.
+    ///
+    /// # Errors
+    ///
+    /// if a signatory isn't in `public_keys` or the signatures can't be aggregated
+    #[cfg(feature = "transparent_api")]
+    pub fn aggregate_signatures(
+        &mut self,
+        public_keys: &[&PublicKey],
+    ) -> Result<(), iroha_crypto::Error> {
+        let SignedBlock::V1(block) = self;
+        if block.signatures.is_empty() && block.commit_certificate.is_some() {
+            return Ok(());
+        }
+
+        let signatures = block
+            .signatures
+            .iter()
+            .map(|signature| {
+                let public_key = usize::try_from(signature.index)
+                    .ok()
+                    .and_then(|index| public_keys.get(index))
+                    .ok_or_else(|| {
+                        iroha_crypto::Error::Signing(format!(
+                            "{}: Unknown signatory",
+                            signature.index
+                        ))
+                    })?;
+                Ok((*public_key, &signature.signature))
+            })
+            .collect::<Result<Vec<_>, iroha_crypto::Error>>()?;
+        let signature = SignatureOf::aggregate(signatures)?;
+        let signatories = block.signatures.iter().map(|signature| signature.index);
+        block.commit_certificate = Some(BlockCommitCertificate::new(signatories, signature));
+        block.signatures.clear();
+
+        Ok(())
+    }
+
     /// Creates genesis block signed with genesis private key (and not signed by any peer)
     #[cfg(feature = "std")]
     pub fn genesis(
@@ -552,16 +769,19 @@ impl SignedBlock {
             result_merkle_root: None,
             creation_time_ms,
             view_change_index: 0,
+            leader_seed: None,
         };
 
         let signature = BlockSignature::new(0, SignatureOf::from_hash(private_key, header.hash()));
         let payload = BlockPayload {
             header,
             transactions,
+            leader_seed: None,
         };
 
         SignedBlockV1 {
             signatures: [signature].into_iter().collect(),
+            commit_certificate: None,
             payload,
             result: BlockResult::default(),
         }
@@ -778,6 +998,7 @@ mod tests {
             result_merkle_root: None,
             creation_time_ms: 123_456_789_000,
             view_change_index: 123,
+            leader_seed: None,
         };
         let hash0 = header.hash();
         header.result_merkle_root = Some(HashOf::from_untyped_unchecked(iroha_crypto::Hash::new(
@@ -786,4 +1007,19 @@ mod tests {
         let hash1 = header.hash();
         assert_eq!(hash0, hash1);
     }
+
+    #[test]
+    fn commit_certificate_signatories_roundtrip() {
+        let key_pair = iroha_crypto::KeyPair::random();
+        let hash = HashOf::from_untyped_unchecked(iroha_crypto::Hash::new(b"block_header"));
+        let signature = SignatureOf::from_hash(key_pair.private_key(), hash);
+
+        let signatories = [0, 3, 7, 8, 21];
+        let certificate = BlockCommitCertificate::new(signatories, signature);
+        assert_eq!(
+            certificate.signatories,
+            vec![0b1000_1001, 0b0000_0001, 0b0010_0000]
+        );
+        assert_eq!(certificate.signatories().collect::<Vec<_>>(), signatories);
+    }
 }
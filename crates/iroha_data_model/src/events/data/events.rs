@@ -114,6 +114,14 @@ mod asset {
             Added(AssetChanged),
             #[has_origin(asset_changed => &asset_changed.asset)]
             Removed(AssetChanged),
+            #[has_origin(asset_id => asset_id)]
+            Frozen(AssetId),
+            #[has_origin(asset_id => asset_id)]
+            Unfrozen(AssetId),
+            #[has_origin(asset_changed => &asset_changed.asset)]
+            Held(AssetChanged),
+            #[has_origin(asset_changed => &asset_changed.asset)]
+            Released(AssetChanged),
         }
     }
 
@@ -132,6 +140,7 @@ mod asset {
             TotalQuantityChanged(AssetDefinitionTotalQuantityChanged),
             #[has_origin(ownership_changed => &ownership_changed.asset_definition)]
             OwnerChanged(AssetDefinitionOwnerChanged),
+            LimitsChanged(AssetDefinitionId),
         }
     }
 
@@ -139,7 +148,7 @@ mod asset {
     mod model {
         use super::*;
 
-        /// Depending on the wrapping event, [`Self`] represents the added or removed asset quantity.
+        /// Depending on the wrapping event, [`Self`] represents the added, removed, held or released asset quantity.
         #[derive(
             Debug,
             Clone,
@@ -361,6 +370,8 @@ mod account {
             MetadataInserted(AccountMetadataChanged),
             #[has_origin(metadata_changed => &metadata_changed.target)]
             MetadataRemoved(AccountMetadataChanged),
+            #[has_origin(sequence_changed => &sequence_changed.account)]
+            SequenceChanged(AccountSequenceChanged),
         }
     }
 
@@ -413,6 +424,28 @@ mod account {
             pub account: AccountId,
             pub role: RoleId,
         }
+
+        /// Event indicating that the sequence number of the [`Account`] is advanced by a transaction
+        #[derive(
+            Debug,
+            Clone,
+            PartialEq,
+            Eq,
+            PartialOrd,
+            Ord,
+            Getters,
+            Decode,
+            Encode,
+            Deserialize,
+            Serialize,
+            IntoSchema,
+        )]
+        #[getset(get = "pub")]
+        #[ffi_type]
+        pub struct AccountSequenceChanged {
+            pub account: AccountId,
+            pub sequence: u64,
+        }
     }
 
     impl AccountPermissionChanged {
@@ -716,7 +749,10 @@ impl<Id> MetadataChanged<Id> {
 
 pub mod prelude {
     pub use super::{
-        account::{AccountEvent, AccountEventSet, AccountPermissionChanged, AccountRoleChanged},
+        account::{
+            AccountEvent, AccountEventSet, AccountPermissionChanged, AccountRoleChanged,
+            AccountSequenceChanged,
+        },
         asset::{
             AssetChanged, AssetDefinitionEvent, AssetDefinitionEventSet,
             AssetDefinitionOwnerChanged, AssetDefinitionTotalQuantityChanged, AssetEvent,
@@ -845,4 +845,37 @@ mod tests {
         assert!(!asset_filter.matches(&account_created));
         assert!(asset_filter.matches(&asset_created));
     }
+
+    #[test]
+    #[cfg(feature = "transparent_api")]
+    fn frozen_asset_scope() {
+        let domain_id: DomainId = "wonderland".parse().unwrap();
+        let account_id = AccountId::new(domain_id.clone(), KeyPair::random().into_parts().0);
+        let asset_id: AssetId = format!("rose##{account_id}").parse().unwrap();
+        let other_asset_id: AssetId = format!("tulip##{account_id}").parse().unwrap();
+
+        let frozen =
+            DomainEvent::Account(AccountEvent::Asset(AssetEvent::Frozen(asset_id.clone()))).into();
+        let unfrozen =
+            DomainEvent::Account(AccountEvent::Asset(AssetEvent::Unfrozen(asset_id.clone())))
+                .into();
+
+        let domain_filter = DataEventFilter::Domain(DomainEventFilter::new().for_domain(domain_id));
+        let account_filter =
+            DataEventFilter::Account(AccountEventFilter::new().for_account(account_id));
+        let asset_filter = DataEventFilter::Asset(AssetEventFilter::new().for_asset(asset_id));
+        let other_asset_filter =
+            DataEventFilter::Asset(AssetEventFilter::new().for_asset(other_asset_id));
+        let frozen_filter =
+            DataEventFilter::Asset(AssetEventFilter::new().for_events(AssetEventSet::Frozen));
+
+        for event in [&frozen, &unfrozen] {
+            assert!(domain_filter.matches(event));
+            assert!(account_filter.matches(event));
+            assert!(asset_filter.matches(event));
+            assert!(!other_asset_filter.matches(event));
+        }
+        assert!(frozen_filter.matches(&frozen));
+        assert!(!frozen_filter.matches(&unfrozen));
+    }
 }
@@ -139,6 +139,8 @@ mod model {
         Approved,
         /// Transaction was stored in the block as invalid
         Rejected(Box<crate::transaction::error::TransactionRejectionReason>),
+        /// Transaction was evicted from the full queue by a higher ranked one
+        Evicted,
     }
 
     #[derive(
@@ -359,6 +361,7 @@ mod tests {
                 result_merkle_root: None,
                 creation_time_ms: 0,
                 view_change_index: 0,
+                leader_seed: None,
             }
         }
     }
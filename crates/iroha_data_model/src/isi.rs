@@ -117,6 +117,16 @@ mod model {
         Upgrade(Upgrade),
         #[debug(fmt = "{_0:?}")]
         Log(Log),
+        #[debug(fmt = "{_0:?}")]
+        Freeze(Freeze<Asset>),
+        #[debug(fmt = "{_0:?}")]
+        Unfreeze(Unfreeze<Asset>),
+        #[debug(fmt = "{_0:?}")]
+        Hold(Hold<Numeric, Asset>),
+        #[debug(fmt = "{_0:?}")]
+        Release(Release<Numeric, Asset>),
+        #[debug(fmt = "{_0:?}")]
+        SetAssetDefinitionLimits(SetAssetDefinitionLimits),
 
         #[debug(fmt = "{_0:?}")]
         Custom(CustomInstruction),
@@ -164,6 +174,11 @@ impl_instruction! {
     Mint<u32, Trigger>,
     Burn<Numeric, Asset>,
     Burn<u32, Trigger>,
+    Freeze<Asset>,
+    Unfreeze<Asset>,
+    Hold<Numeric, Asset>,
+    Release<Numeric, Asset>,
+    SetAssetDefinitionLimits,
     Transfer<Account, DomainId, Account>,
     Transfer<Account, AssetDefinitionId, Account>,
     Transfer<Asset, Numeric, Account>,
@@ -698,6 +713,161 @@ mod transparent {
     => BurnBoxRef<'a> => InstructionBoxRef<'a>[Burn]
     }
 
+    isi! {
+        /// Generic instruction for freezing an object, which forbids transferring and burning it.
+        pub struct Freeze<O: Identifiable> {
+            /// [`Identifiable::Id`] of the object which should be frozen.
+            pub object: O::Id,
+        }
+    }
+
+    impl Freeze<Asset> {
+        /// Constructs a new [`Freeze`] for an [`Asset`].
+        pub fn asset(asset_id: AssetId) -> Self {
+            Self { object: asset_id }
+        }
+    }
+
+    impl_display! {
+        Freeze<O>
+        where
+            O: Identifiable,
+            O::Id: Display,
+        =>
+        "FREEZE `{}`",
+        object,
+    }
+
+    isi! {
+        /// Generic instruction for unfreezing an object frozen with [`Freeze`].
+        pub struct Unfreeze<O: Identifiable> {
+            /// [`Identifiable::Id`] of the object which should be unfrozen.
+            pub object: O::Id,
+        }
+    }
+
+    impl Unfreeze<Asset> {
+        /// Constructs a new [`Unfreeze`] for an [`Asset`].
+        pub fn asset(asset_id: AssetId) -> Self {
+            Self { object: asset_id }
+        }
+    }
+
+    impl_display! {
+        Unfreeze<O>
+        where
+            O: Identifiable,
+            O::Id: Display,
+        =>
+        "UNFREEZE `{}`",
+        object,
+    }
+
+    isi! {
+        /// Generic instruction for placing a hold on a part of the identifiable destination.
+        /// The held part can't be transferred or burned until it's released.
+        pub struct Hold<O, D: Identifiable> {
+            /// Object which should be held.
+            pub object: O,
+            /// Destination object [`Identifiable::Id`].
+            pub destination: D::Id,
+        }
+    }
+
+    impl Hold<Numeric, Asset> {
+        /// Constructs a new [`Hold`] for an [`Asset`] of [`Numeric`] type.
+        pub fn asset_numeric(object: impl Into<Numeric>, asset_id: AssetId) -> Self {
+            Self {
+                object: object.into(),
+                destination: asset_id,
+            }
+        }
+    }
+
+    impl_display! {
+        Hold<O, D>
+        where
+            O: Display,
+            D: Identifiable,
+            D::Id: Display,
+        =>
+        "HOLD `{}` OF `{}`",
+        object,
+        destination,
+    }
+
+    isi! {
+        /// Generic instruction for releasing a part of the hold placed with [`Hold`].
+        pub struct Release<O, D: Identifiable> {
+            /// Object which should be released.
+            pub object: O,
+            /// Destination object [`Identifiable::Id`].
+            pub destination: D::Id,
+        }
+    }
+
+    impl Release<Numeric, Asset> {
+        /// Constructs a new [`Release`] for an [`Asset`] of [`Numeric`] type.
+        pub fn asset_numeric(object: impl Into<Numeric>, asset_id: AssetId) -> Self {
+            Self {
+                object: object.into(),
+                destination: asset_id,
+            }
+        }
+    }
+
+    impl_display! {
+        Release<O, D>
+        where
+            O: Display,
+            D: Identifiable,
+            D::Id: Display,
+        =>
+        "RELEASE `{}` OF `{}`",
+        object,
+        destination,
+    }
+
+    isi! {
+        /// Instruction to replace the supply cap and the balance limit of an asset definition.
+        /// Balances already above the new limit are kept, but can't grow.
+        #[derive(Display)]
+        #[display(fmt = "SET LIMITS OF `{asset_definition}`")]
+        pub struct SetAssetDefinitionLimits {
+            /// Asset definition whose limits should be replaced.
+            pub asset_definition: AssetDefinitionId,
+            /// New maximum total supply, or `None` to lift the cap.
+            pub max_supply: Option<Numeric>,
+            /// New maximum balance of a single account, or `None` to lift the limit.
+            pub max_balance: Option<Numeric>,
+        }
+    }
+
+    impl SetAssetDefinitionLimits {
+        /// Constructs a new [`SetAssetDefinitionLimits`] lifting both limits.
+        pub fn new(asset_definition: AssetDefinitionId) -> Self {
+            Self {
+                asset_definition,
+                max_supply: None,
+                max_balance: None,
+            }
+        }
+
+        /// Cap the total supply of the asset
+        #[must_use]
+        pub fn with_max_supply(mut self, max_supply: impl Into<Numeric>) -> Self {
+            self.max_supply = Some(max_supply.into());
+            self
+        }
+
+        /// Limit the quantity of the asset a single account can hold
+        #[must_use]
+        pub fn with_max_balance(mut self, max_balance: impl Into<Numeric>) -> Self {
+            self.max_balance = Some(max_balance.into());
+            self
+        }
+    }
+
     isi! {
         /// Generic instruction for a transfer of an object from the identifiable source to the identifiable destination.
         pub struct Transfer<S: Identifiable, O, D: Identifiable> {
@@ -1204,6 +1374,7 @@ pub mod error {
     pub use self::model::*;
     use super::InstructionType;
     use crate::{
+        asset::AssetId,
         prelude::NumericSpec,
         query::error::{FindError, QueryExecutionFail},
         IdBox,
@@ -1265,6 +1436,12 @@ pub mod error {
                 #[skip_try_from]
                 String,
             ),
+            /// Asset `{0}` is frozen
+            Frozen(
+                #[skip_from]
+                #[skip_try_from]
+                AssetId,
+            ),
         }
 
         /// Evaluation error. This error indicates instruction is not a valid Iroha DSL
@@ -1406,6 +1583,10 @@ pub mod error {
             MintUnmintable,
             /// This asset was set as infinitely mintable. You cannot forbid its minting
             ForbidMintOnMintable,
+            /// Minting would exceed the maximum supply of this asset
+            SupplyCapExceeded,
+            /// The account would exceed the maximum balance allowed for this asset
+            BalanceLimitExceeded,
         }
 
         /// Invalid instruction parameter error
@@ -1485,9 +1666,10 @@ pub mod error {
 /// The prelude re-exports most commonly used traits, structs and macros from this crate.
 pub mod prelude {
     pub use super::{
-        Burn, BurnBox, CustomInstruction, ExecuteTrigger, Grant, GrantBox, Instruction,
-        InstructionBox, Log, Mint, MintBox, Register, RegisterBox, RemoveKeyValue,
-        RemoveKeyValueBox, Revoke, RevokeBox, SetKeyValue, SetKeyValueBox, SetParameter, Transfer,
-        TransferBox, Unregister, UnregisterBox, Upgrade,
+        Burn, BurnBox, CustomInstruction, ExecuteTrigger, Freeze, Grant, GrantBox, Hold,
+        Instruction, InstructionBox, Log, Mint, MintBox, Register, RegisterBox, Release,
+        RemoveKeyValue, RemoveKeyValueBox, Revoke, RevokeBox, SetAssetDefinitionLimits,
+        SetKeyValue, SetKeyValueBox, SetParameter, Transfer, TransferBox, Unfreeze, Unregister,
+        UnregisterBox, Upgrade,
     };
 }
@@ -103,6 +103,12 @@ mod seal {
         Burn<Numeric, Asset>,
         Burn<u32, Trigger>,
 
+        Freeze<Asset>,
+        Unfreeze<Asset>,
+        Hold<Numeric, Asset>,
+        Release<Numeric, Asset>,
+        SetAssetDefinitionLimits,
+
         Transfer<Account, DomainId, Account>,
         Transfer<Account, AssetDefinitionId, Account>,
         Transfer<Asset, Numeric, Account>,
@@ -5,6 +5,7 @@ use alloc::{format, string::String, vec::Vec};
 use core::str::FromStr;
 
 use iroha_data_model_derive::model;
+use parity_scale_codec::{Decode, Encode};
 use serde::{Deserialize, Serialize};
 
 pub use self::model::*;
@@ -117,7 +118,7 @@ pub struct NftEntry<'world> {
 /// [`Nft`] without `id` field.
 /// Needed only for [`World::nfts`] map to reduce memory usage.
 /// In other places use [`Nft`] directly.
-#[derive(Clone, Deserialize, Serialize)]
+#[derive(Clone, Decode, Encode, Deserialize, Serialize)]
 pub struct NftValue {
     /// Content of the [`Nft`], as a key-value store.
     pub content: Metadata,
@@ -9,11 +9,14 @@ use iroha_data_model_derive::model;
 use iroha_primitives::json::Json;
 
 pub use self::model::*;
-use crate::{name::Name, Identifiable};
+use crate::{name::Name, peer::PeerId, Identifiable};
 
 /// Collection of [`CustomParameter`]s
 pub(crate) type CustomParameters = btree_map::BTreeMap<CustomParameterId, CustomParameter>;
 
+/// Voting weights of the peers, see [`PeerWeight`]
+pub type PeerWeights = btree_map::BTreeMap<PeerId, NonZeroU64>;
+
 #[model]
 mod model {
     #[cfg(not(feature = "std"))]
@@ -238,6 +241,33 @@ mod model {
         pub payload: Json,
     }
 
+    /// Voting weight of a peer in consensus.
+    ///
+    /// Peers without an explicit weight have a weight of one,
+    /// so that all peers count equally unless weights are set.
+    #[derive(
+        Debug,
+        Display,
+        Clone,
+        PartialEq,
+        Eq,
+        PartialOrd,
+        Ord,
+        Constructor,
+        Decode,
+        Encode,
+        Deserialize,
+        Serialize,
+        IntoSchema,
+    )]
+    #[display(fmt = "{peer}={weight}")]
+    pub struct PeerWeight {
+        /// Peer the weight is assigned to
+        pub peer: PeerId,
+        /// Weight of the peer votes
+        pub weight: NonZeroU64,
+    }
+
     /// Set of all current blockchain parameter values
     #[derive(
         Debug,
@@ -281,6 +311,11 @@ mod model {
         #[serde(default)]
         #[serde(skip_serializing_if = "BTreeMap::is_empty")]
         pub custom: CustomParameters,
+        /// Voting weights of the peers in consensus
+        #[getset(get = "pub")]
+        #[serde(default)]
+        #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+        pub peer_weights: PeerWeights,
     }
 
     /// Single blockchain parameter.
@@ -308,6 +343,7 @@ mod model {
         SmartContract(SmartContractParameter),
         Executor(SmartContractParameter),
         Custom(CustomParameter),
+        PeerWeight(PeerWeight),
     }
 }
 
@@ -319,6 +355,7 @@ impl core::fmt::Display for Parameter {
             Self::Transaction(v) => core::fmt::Display::fmt(&v, f),
             Self::SmartContract(v) | Self::Executor(v) => core::fmt::Display::fmt(&v, f),
             Self::Custom(v) => write!(f, "{}({})", v.id, v.payload),
+            Self::PeerWeight(v) => core::fmt::Display::fmt(&v, f),
         }
     }
 }
@@ -474,6 +511,9 @@ impl Parameters {
                     .map(Parameter::SmartContract),
             )
             .chain(self.custom.values().cloned().map(Parameter::Custom))
+            .chain(self.peer_weights.iter().map(|(peer, weight)| {
+                Parameter::PeerWeight(PeerWeight::new(peer.clone(), *weight))
+            }))
     }
 
     /// Set `parameter` value to corresponding parameter in `self`
@@ -489,6 +529,9 @@ impl Parameters {
                     Parameter::Custom(next) => {
                         self.custom.insert(next.id.clone(), next);
                     }
+                    Parameter::PeerWeight(next) => {
+                        self.peer_weights.insert(next.peer, next.weight);
+                    }
                 }
             };
         }
@@ -24,6 +24,7 @@ impl<T> ExactSizeIterator for TypedBatchIterUntupled<T> {
     }
 }
 
+/// An error of downcasting a type-erased batch of query results to the expected type.
 #[derive(Debug, Copy, Clone, displaydoc::Display)]
 #[cfg_attr(feature = "std", derive(thiserror::Error))]
 pub enum TypedBatchDowncastError {
@@ -35,8 +36,15 @@ pub enum TypedBatchDowncastError {
     WrongType(usize),
 }
 
+/// A type of query results that can be extracted from a type-erased batch.
 pub trait HasTypedBatchIter {
+    /// An iterator over the results of a batch.
     type TypedBatchIter: Iterator<Item = Self> + ExactSizeIterator;
+    /// Downcast a type-erased batch to an iterator over the results of this type.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the batch holds results of a different type.
     fn downcast(
         erased_batch: QueryOutputBatchBoxTuple,
     ) -> Result<Self::TypedBatchIter, TypedBatchDowncastError>;
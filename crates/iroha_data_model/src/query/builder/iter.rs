@@ -1,7 +1,7 @@
 use crate::query::{
     builder::{
         batch_downcast::{HasTypedBatchIter, TypedBatchDowncastError},
-        QueryExecutor,
+        AsyncQueryExecutor, QueryExecutor,
     },
     QueryOutputBatchBoxTuple,
 };
@@ -87,3 +87,72 @@ where
             .expect("should be within the range of usize")
     }
 }
+
+/// An asynchronous iterator over results of an iterable query, fetching the next batch once the current one is consumed.
+#[derive(Debug)]
+pub struct AsyncQueryIterator<E: AsyncQueryExecutor, T: HasTypedBatchIter> {
+    current_batch_iter: T::TypedBatchIter,
+    remaining_items: u64,
+    continue_cursor: Option<E::Cursor>,
+}
+
+impl<E, T> AsyncQueryIterator<E, T>
+where
+    E: AsyncQueryExecutor,
+    T: HasTypedBatchIter,
+{
+    /// Create a new asynchronous iterator over iterable query results.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the type of the batch does not match the expected type `T`.
+    pub fn new(
+        first_batch: QueryOutputBatchBoxTuple,
+        remaining_items: u64,
+        continue_cursor: Option<E::Cursor>,
+    ) -> Result<Self, TypedBatchDowncastError> {
+        let batch_iter = T::downcast(first_batch)?;
+
+        Ok(Self {
+            current_batch_iter: batch_iter,
+            remaining_items,
+            continue_cursor,
+        })
+    }
+
+    /// Get the next result, fetching the next batch from iroha if the current one is exhausted.
+    /// Returns `None` once all the results are consumed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if fetching the next batch fails.
+    pub async fn try_next(&mut self) -> Result<Option<T>, E::Error> {
+        loop {
+            if let Some(item) = self.current_batch_iter.next() {
+                return Ok(Some(item));
+            }
+
+            // no cursor means the query result is exhausted or an error occurred on one of the previous iterations
+            let Some(cursor) = self.continue_cursor.take() else {
+                return Ok(None);
+            };
+
+            let (batch, remaining_items, cursor) = E::continue_query(cursor).await?;
+            self.continue_cursor = cursor;
+
+            // we've already downcast the first batch to the expected type, so if iroha returns a different type here, it surely is a bug
+            self.current_batch_iter =
+                T::downcast(batch).expect("BUG: iroha returned unexpected type in iterable query");
+            self.remaining_items = remaining_items;
+        }
+    }
+
+    /// The number of results left, including the ones not fetched yet.
+    pub fn remaining(&self) -> usize {
+        self.remaining_items
+            .try_into()
+            .ok()
+            .and_then(|r: usize| r.checked_add(self.current_batch_iter.len()))
+            .expect("should be within the range of usize")
+    }
+}
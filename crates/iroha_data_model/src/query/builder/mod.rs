@@ -5,20 +5,22 @@ mod iter;
 
 #[cfg(not(feature = "std"))]
 use alloc::vec::Vec;
-use core::marker::PhantomData;
+use core::{future::Future, marker::PhantomData, num::NonZeroU64};
 
+pub use batch_downcast::{HasTypedBatchIter, TypedBatchDowncastError};
 use derive_where::derive_where;
-pub use iter::QueryIterator;
+use iroha_primitives::numeric::Numeric;
+pub use iter::{AsyncQueryIterator, QueryIterator};
 use parity_scale_codec::{Decode, Encode};
 use serde::{Deserialize, Serialize};
 
 use crate::query::{
-    builder::batch_downcast::HasTypedBatchIter,
     dsl::{
-        BaseProjector, CompoundPredicate, HasPrototype, IntoSelectorTuple, PredicateMarker,
-        SelectorMarker, SelectorTuple,
+        Aggregate, AggregateFunction, BaseProjector, CompoundPredicate, HasProjection,
+        HasPrototype, IntoSelector, IntoSelectorTuple, PredicateMarker, SelectorMarker,
+        SelectorTuple, SortKey,
     },
-    parameters::{FetchSize, Pagination, QueryParams, Sorting},
+    parameters::{FetchSize, Pagination, QueryContinuation, QueryParams, SortOrder, Sorting},
     Query, QueryBox, QueryOutputBatchBoxTuple, QueryWithFilter, QueryWithParams, SingularQueryBox,
     SingularQueryOutputBox,
 };
@@ -64,6 +66,51 @@ pub trait QueryExecutor {
     ) -> Result<(QueryOutputBatchBoxTuple, u64, Option<Self::Cursor>), Self::Error>;
 }
 
+/// An asynchronous counterpart of [`QueryExecutor`], for backends that don't block on the network.
+pub trait AsyncQueryExecutor {
+    /// A type of cursor used in iterable queries.
+    ///
+    /// The cursor type is an opaque type that allows to continue execution of an iterable query with [`Self::continue_query`]
+    type Cursor;
+    /// An error that can occur during query execution.
+    type Error;
+
+    /// Executes a singular query and returns its result.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the query execution fails.
+    fn execute_singular_query(
+        &self,
+        query: SingularQueryBox,
+    ) -> impl Future<Output = Result<SingularQueryOutputBox, Self::Error>> + Send;
+
+    /// Starts an iterable query and returns the first batch of results, the remaining number of results and a cursor to continue the query.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the query execution fails.
+    #[expect(clippy::type_complexity)]
+    fn start_query(
+        &self,
+        query: QueryWithParams,
+    ) -> impl Future<
+        Output = Result<(QueryOutputBatchBoxTuple, u64, Option<Self::Cursor>), Self::Error>,
+    > + Send;
+
+    /// Continues an iterable query from the given cursor and returns the next batch of results, the remaining number of results and a cursor to continue the query.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the query execution fails.
+    #[expect(clippy::type_complexity)]
+    fn continue_query(
+        cursor: Self::Cursor,
+    ) -> impl Future<
+        Output = Result<(QueryOutputBatchBoxTuple, u64, Option<Self::Cursor>), Self::Error>,
+    > + Send;
+}
+
 /// An error that can occur when constraining the number of results of an iterable query to one.
 #[derive(
     Debug,
@@ -97,8 +144,14 @@ impl<E> From<E> for SingleQueryError<E> {
     }
 }
 
+type SelectorPrototype<T> =
+    <T as HasPrototype>::Prototype<SelectorMarker, BaseProjector<SelectorMarker, T>>;
+
 /// Struct that simplifies construction of an iterable query.
-#[derive_where(Clone; Q, CompoundPredicate<Q::Item>, SelectorTuple<Q::Item>)]
+#[derive_where(
+    Clone;
+    Q, CompoundPredicate<Q::Item>, SelectorTuple<Q::Item>, Aggregate<Q::Item>, SortKey<Q::Item>
+)]
 pub struct QueryBuilder<'e, E, Q, T>
 where
     Q: Query,
@@ -107,10 +160,14 @@ where
     query: Q,
     filter: CompoundPredicate<Q::Item>,
     selector: SelectorTuple<Q::Item>,
+    aggregate: Option<Aggregate<Q::Item>>,
+    sort_by: Vec<SortKey<Q::Item>>,
     pagination: Pagination,
     sorting: Sorting,
     fetch_size: FetchSize,
-    // NOTE: T is a phantom type used to denote the selected tuple in `selector`
+    at_height: Option<NonZeroU64>,
+    continuation: Option<QueryContinuation>,
+    // NOTE: T is a phantom type used to denote the selected tuple in `selector` (or the output of `aggregate`)
     phantom: PhantomData<T>,
 }
 
@@ -125,9 +182,13 @@ where
             query,
             filter: CompoundPredicate::PASS,
             selector: SelectorTuple::default(),
+            aggregate: None,
+            sort_by: Vec::new(),
             pagination: Pagination::default(),
             sorting: Sorting::default(),
             fetch_size: FetchSize::default(),
+            at_height: None,
+            continuation: None,
             phantom: PhantomData,
         }
     }
@@ -172,6 +233,8 @@ where
     /// Return only the fields of the results specified by the given closure.
     ///
     /// You can select multiple fields by returning a tuple from the closure.
+    ///
+    /// Discards the aggregation requested earlier, if any.
     #[must_use]
     pub fn select_with<B, O>(self, f: B) -> QueryBuilder<'a, E, Q, O::SelectedTuple>
     where
@@ -195,13 +258,116 @@ where
             query: self.query,
             filter: self.filter,
             selector: new_selector,
+            aggregate: None,
+            sort_by: self.sort_by,
             pagination: self.pagination,
             sorting: self.sorting,
             fetch_size: self.fetch_size,
+            at_height: self.at_height,
+            continuation: self.continuation,
             phantom: PhantomData,
         }
     }
 
+    fn with_aggregate<U>(self, aggregate: Option<Aggregate<Q::Item>>) -> QueryBuilder<'a, E, Q, U> {
+        QueryBuilder {
+            query_executor: self.query_executor,
+            query: self.query,
+            filter: self.filter,
+            selector: SelectorTuple::default(),
+            aggregate,
+            sort_by: self.sort_by,
+            pagination: self.pagination,
+            sorting: self.sorting,
+            fetch_size: self.fetch_size,
+            at_height: self.at_height,
+            continuation: self.continuation,
+            phantom: PhantomData,
+        }
+    }
+
+    /// Return the number of results instead of the results themselves.
+    #[must_use]
+    pub fn count(self) -> QueryBuilder<'a, E, Q, Numeric> {
+        self.with_aggregate(Some(Aggregate::count()))
+    }
+
+    /// Return the sum of the numeric values selected by the given closure.
+    #[must_use]
+    pub fn sum_with<B, O>(self, f: B) -> QueryBuilder<'a, E, Q, Numeric>
+    where
+        Q::Item: HasPrototype,
+        B: FnOnce(SelectorPrototype<Q::Item>) -> O,
+        SelectorPrototype<Q::Item>: Default,
+        O: IntoSelector<SelectingType = Q::Item, SelectedType = Numeric>,
+    {
+        self.with_aggregate(Some(Aggregate::sum_with(f)))
+    }
+
+    /// Return the smallest of the numeric values selected by the given closure.
+    ///
+    /// Returns no results if nothing matched the filter.
+    #[must_use]
+    pub fn min_with<B, O>(self, f: B) -> QueryBuilder<'a, E, Q, Numeric>
+    where
+        Q::Item: HasPrototype,
+        B: FnOnce(SelectorPrototype<Q::Item>) -> O,
+        SelectorPrototype<Q::Item>: Default,
+        O: IntoSelector<SelectingType = Q::Item, SelectedType = Numeric>,
+    {
+        self.with_aggregate(Some(Aggregate::min_with(f)))
+    }
+
+    /// Return the largest of the numeric values selected by the given closure.
+    ///
+    /// Returns no results if nothing matched the filter.
+    #[must_use]
+    pub fn max_with<B, O>(self, f: B) -> QueryBuilder<'a, E, Q, Numeric>
+    where
+        Q::Item: HasPrototype,
+        B: FnOnce(SelectorPrototype<Q::Item>) -> O,
+        SelectorPrototype<Q::Item>: Default,
+        O: IntoSelector<SelectingType = Q::Item, SelectedType = Numeric>,
+    {
+        self.with_aggregate(Some(Aggregate::max_with(f)))
+    }
+
+    /// Group the results by the value selected by the given closure.
+    ///
+    /// One of the aggregating methods of [`GroupedQueryBuilder`] has to be called to complete the query.
+    #[must_use]
+    pub fn group_by_with<B, O>(self, f: B) -> GroupedQueryBuilder<'a, E, Q, O::SelectedType>
+    where
+        Q::Item: HasPrototype,
+        B: FnOnce(SelectorPrototype<Q::Item>) -> O,
+        SelectorPrototype<Q::Item>: Default,
+        O: IntoSelector<SelectingType = Q::Item>,
+    {
+        let key = f(Default::default()).into_selector();
+
+        GroupedQueryBuilder {
+            builder: self.with_aggregate(None),
+            key,
+        }
+    }
+
+    /// Sort the results by the value selected by the given closure.
+    ///
+    /// If called multiple times, the results are sorted by the first key, then by the second one, and so on.
+    /// These keys take precedence over the metadata key set with [`Self::with_sorting`].
+    /// When combined with an aggregation, groups are returned in the sorted order of their first results.
+    #[must_use]
+    pub fn sort_by_with<B, O>(mut self, order: SortOrder, f: B) -> Self
+    where
+        Q::Item: HasPrototype,
+        B: FnOnce(SelectorPrototype<Q::Item>) -> O,
+        SelectorPrototype<Q::Item>: Default,
+        O: IntoSelector<SelectingType = Q::Item>,
+    {
+        self.sort_by.push(SortKey::build(order, f));
+        self
+    }
+
     /// Sort the results according to the specified sorting.
     #[must_use]
     pub fn with_sorting(self, sorting: Sorting) -> Self {
@@ -221,35 +387,78 @@ where
     pub fn with_fetch_size(self, fetch_size: FetchSize) -> Self {
         Self { fetch_size, ..self }
     }
+
+    /// Execute the query against the state as it was at the given block height.
+    ///
+    /// The peer has to keep a history of state snapshots to answer such queries.
+    #[must_use]
+    pub fn at_height(self, height: NonZeroU64) -> Self {
+        Self {
+            at_height: Some(height),
+            ..self
+        }
+    }
+
+    /// Only return the results ordered after the continuation of a cursor.
+    ///
+    /// Allows to resume an iteration on any peer, see
+    /// [`ForwardCursor::continuation`](crate::query::parameters::ForwardCursor::continuation).
+    #[must_use]
+    pub fn continue_after(self, continuation: QueryContinuation) -> Self {
+        Self {
+            continuation: Some(continuation),
+            ..self
+        }
+    }
 }
 
 impl<E, Q, T> QueryBuilder<'_, E, Q, T>
 where
     Q: Query,
-    E: QueryExecutor,
     QueryBox: From<QueryWithFilter<Q>>,
-    T: HasTypedBatchIter,
 {
-    /// Execute the query, returning an iterator over its results.
-    ///
-    /// # Errors
-    ///
-    /// Returns an error if the query execution fails.
-    pub fn execute(self) -> Result<QueryIterator<E, T>, E::Error> {
-        let with_filter = QueryWithFilter::new(self.query, self.filter, self.selector);
+    /// Build the type-erased query along with its parameters without executing it,
+    /// e.g. to subscribe to its results.
+    pub fn into_query_with_params(self) -> QueryWithParams {
+        let with_filter = QueryWithFilter::new(
+            self.query,
+            self.filter,
+            self.selector,
+            self.aggregate,
+            self.sort_by,
+        );
         let boxed: QueryBox = with_filter.into();
 
-        let query = QueryWithParams {
+        QueryWithParams {
             query: boxed,
             params: QueryParams {
                 pagination: self.pagination,
                 sorting: self.sorting,
                 fetch_size: self.fetch_size,
+                at_height: self.at_height,
+                continuation: self.continuation,
             },
-        };
+        }
+    }
+}
 
-        let (first_batch, remaining_items, continue_cursor) =
-            self.query_executor.start_query(query)?;
+impl<E, Q, T> QueryBuilder<'_, E, Q, T>
+where
+    Q: Query,
+    E: QueryExecutor,
+    QueryBox: From<QueryWithFilter<Q>>,
+    T: HasTypedBatchIter,
+{
+    /// Execute the query, returning an iterator over its results.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the query execution fails.
+    pub fn execute(self) -> Result<QueryIterator<E, T>, E::Error> {
+        let query_executor = self.query_executor;
+        let query = self.into_query_with_params();
+
+        let (first_batch, remaining_items, continue_cursor) = query_executor.start_query(query)?;
 
         let iterator = QueryIterator::<E, T>::new(first_batch, remaining_items, continue_cursor)
             .expect(
@@ -260,6 +469,121 @@ where
     }
 }
 
+impl<E, Q, T> QueryBuilder<'_, E, Q, T>
+where
+    Q: Query,
+    E: AsyncQueryExecutor,
+    QueryBox: From<QueryWithFilter<Q>>,
+    T: HasTypedBatchIter,
+{
+    /// Execute the query asynchronously, returning an iterator fetching its results as they are consumed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the query execution fails.
+    pub async fn execute_async(self) -> Result<AsyncQueryIterator<E, T>, E::Error> {
+        let query_executor = self.query_executor;
+        let query = self.into_query_with_params();
+
+        let (first_batch, remaining_items, continue_cursor) =
+            query_executor.start_query(query).await?;
+
+        let iterator =
+            AsyncQueryIterator::<E, T>::new(first_batch, remaining_items, continue_cursor).expect(
+                "INTERNAL BUG: iroha returned unexpected type in iterable query. Is there a schema mismatch?",
+            );
+
+        Ok(iterator)
+    }
+
+    /// Execute the query asynchronously, returning all the results collected into a vector.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the query execution fails.
+    pub async fn execute_all_async(self) -> Result<Vec<T>, E::Error> {
+        let mut iter = self.execute_async().await?;
+        let mut results = Vec::new();
+        while let Some(item) = iter.try_next().await? {
+            results.push(item);
+        }
+        Ok(results)
+    }
+}
+
+/// A query builder that groups the results by a key of type `K`, created by [`QueryBuilder::group_by_with`].
+///
+/// The aggregating methods return a query builder yielding a `(K, Numeric)` pair per group.
+pub struct GroupedQueryBuilder<'e, E, Q, K>
+where
+    Q: Query,
+{
+    builder: QueryBuilder<'e, E, Q, K>,
+    key: <Q::Item as HasProjection<SelectorMarker>>::Projection,
+}
+
+impl<'a, E, Q, K> GroupedQueryBuilder<'a, E, Q, K>
+where
+    Q: Query,
+{
+    fn aggregate(
+        self,
+        function: AggregateFunction<Q::Item>,
+    ) -> QueryBuilder<'a, E, Q, (K, Numeric)> {
+        let mut aggregate = Aggregate::new(function);
+        aggregate.group_by.push(self.key);
+        self.builder.with_aggregate(Some(aggregate))
+    }
+
+    /// Return the number of results in each group.
+    #[must_use]
+    pub fn count(self) -> QueryBuilder<'a, E, Q, (K, Numeric)> {
+        self.aggregate(AggregateFunction::Count)
+    }
+
+    /// Return the sum of the numeric values selected by the given closure for each group.
+    #[must_use]
+    pub fn sum_with<B, O>(self, f: B) -> QueryBuilder<'a, E, Q, (K, Numeric)>
+    where
+        Q::Item: HasPrototype,
+        B: FnOnce(SelectorPrototype<Q::Item>) -> O,
+        SelectorPrototype<Q::Item>: Default,
+        O: IntoSelector<SelectingType = Q::Item, SelectedType = Numeric>,
+    {
+        self.aggregate(AggregateFunction::Sum(
+            f(Default::default()).into_selector(),
+        ))
+    }
+
+    /// Return the smallest of the numeric values selected by the given closure for each group.
+    #[must_use]
+    pub fn min_with<B, O>(self, f: B) -> QueryBuilder<'a, E, Q, (K, Numeric)>
+    where
+        Q::Item: HasPrototype,
+        B: FnOnce(SelectorPrototype<Q::Item>) -> O,
+        SelectorPrototype<Q::Item>: Default,
+        O: IntoSelector<SelectingType = Q::Item, SelectedType = Numeric>,
+    {
+        self.aggregate(AggregateFunction::Min(
+            f(Default::default()).into_selector(),
+        ))
+    }
+
+    /// Return the largest of the numeric values selected by the given closure for each group.
+    #[must_use]
+    pub fn max_with<B, O>(self, f: B) -> QueryBuilder<'a, E, Q, (K, Numeric)>
+    where
+        Q::Item: HasPrototype,
+        B: FnOnce(SelectorPrototype<Q::Item>) -> O,
+        SelectorPrototype<Q::Item>: Default,
+        O: IntoSelector<SelectingType = Q::Item, SelectedType = Numeric>,
+    {
+        self.aggregate(AggregateFunction::Max(
+            f(Default::default()).into_selector(),
+        ))
+    }
+}
+
 /// An extension trait for query builders that provides convenience methods to execute queries.
 pub trait QueryBuilderExt<E, Q, T>
 where
@@ -0,0 +1,113 @@
+#[cfg(not(feature = "std"))]
+use alloc::{format, string::String, vec::Vec};
+
+use derive_where::derive_where;
+use iroha_macro::serde_where;
+use iroha_primitives::numeric::Numeric;
+use iroha_schema::IntoSchema;
+use parity_scale_codec::{Decode, Encode};
+use serde::{Deserialize, Serialize};
+
+use crate::query::dsl::{BaseProjector, HasProjection, HasPrototype, IntoSelector, SelectorMarker};
+
+/// A function folding the results of an iterable query (or of a group of them) into a single [`Numeric`].
+#[derive_where(Debug, Eq, PartialEq, Clone; T::Projection)]
+#[serde_where(T::Projection)]
+#[derive(Decode, Encode, Deserialize, Serialize, IntoSchema)]
+pub enum AggregateFunction<T: HasProjection<SelectorMarker, AtomType = ()>> {
+    /// The number of results
+    Count,
+    /// The sum of the selected numeric values
+    Sum(T::Projection),
+    /// The smallest of the selected numeric values
+    Min(T::Projection),
+    /// The largest of the selected numeric values
+    Max(T::Projection),
+}
+
+/// An aggregation to compute on the server instead of returning the results of an iterable query.
+///
+/// The output of an aggregated query is a tuple of the values selected by `group_by` (one per group), followed by the aggregated [`Numeric`].
+/// Without grouping, a single value is returned for [`AggregateFunction::Count`] and [`AggregateFunction::Sum`], while [`AggregateFunction::Min`] and [`AggregateFunction::Max`] return nothing if no results matched the predicate.
+#[derive_where(Debug, Eq, PartialEq, Clone; T::Projection)]
+#[serde_where(T::Projection)]
+#[derive(Decode, Encode, Deserialize, Serialize, IntoSchema)]
+pub struct Aggregate<T: HasProjection<SelectorMarker, AtomType = ()>> {
+    /// The function used to aggregate the values of each group
+    pub function: AggregateFunction<T>,
+    /// Selectors of the grouping key. If empty, all the results are aggregated together.
+    #[serde(default)]
+    pub group_by: Vec<T::Projection>,
+}
+
+type SelectorPrototype<T> =
+    <T as HasPrototype>::Prototype<SelectorMarker, BaseProjector<SelectorMarker, T>>;
+
+impl<T: HasProjection<SelectorMarker, AtomType = ()>> Aggregate<T> {
+    /// Aggregate the results with the given function, without grouping them.
+    pub fn new(function: AggregateFunction<T>) -> Self {
+        Self {
+            function,
+            group_by: Vec::new(),
+        }
+    }
+
+    /// Count the results.
+    pub fn count() -> Self {
+        Self::new(AggregateFunction::Count)
+    }
+
+    /// Sum the numeric values selected by the given closure.
+    pub fn sum_with<F, O>(f: F) -> Self
+    where
+        T: HasPrototype,
+        F: FnOnce(SelectorPrototype<T>) -> O,
+        SelectorPrototype<T>: Default,
+        O: IntoSelector<SelectingType = T, SelectedType = Numeric>,
+    {
+        Self::new(AggregateFunction::Sum(
+            f(Default::default()).into_selector(),
+        ))
+    }
+
+    /// Find the smallest of the numeric values selected by the given closure.
+    pub fn min_with<F, O>(f: F) -> Self
+    where
+        T: HasPrototype,
+        F: FnOnce(SelectorPrototype<T>) -> O,
+        SelectorPrototype<T>: Default,
+        O: IntoSelector<SelectingType = T, SelectedType = Numeric>,
+    {
+        Self::new(AggregateFunction::Min(
+            f(Default::default()).into_selector(),
+        ))
+    }
+
+    /// Find the largest of the numeric values selected by the given closure.
+    pub fn max_with<F, O>(f: F) -> Self
+    where
+        T: HasPrototype,
+        F: FnOnce(SelectorPrototype<T>) -> O,
+        SelectorPrototype<T>: Default,
+        O: IntoSelector<SelectingType = T, SelectedType = Numeric>,
+    {
+        Self::new(AggregateFunction::Max(
+            f(Default::default()).into_selector(),
+        ))
+    }
+
+    /// Aggregate the results separately for each value selected by the given closure.
+    ///
+    /// Calling this method multiple times groups the results by a compound key.
+    #[must_use]
+    pub fn group_by_with<F, O>(mut self, f: F) -> Self
+    where
+        T: HasPrototype,
+        F: FnOnce(SelectorPrototype<T>) -> O,
+        SelectorPrototype<T>: Default,
+        O: IntoSelector<SelectingType = T>,
+    {
+        self.group_by.push(f(Default::default()).into_selector());
+        self
+    }
+}
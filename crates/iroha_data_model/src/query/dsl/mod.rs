@@ -69,23 +69,30 @@
 //! This is the type that is actually sent when a query is requested.
 //!
 //! A selector also selects just a single field. To allow selecting multiple fields, [`SelectorTuple`] is used in queries.
+//!
+//! Selectors are also used to describe an [`Aggregate`] computed over the query results, both for the aggregated values and for the grouping keys,
+//! and to describe a [`SortKey`] to order the results by.
 
 #[cfg(not(feature = "std"))]
 use alloc::{format, string::String, vec::Vec};
 use core::marker::PhantomData;
 
+mod aggregate;
 mod compound_predicate;
 pub mod predicates;
 mod selector_traits;
 mod selector_tuple;
+mod sort_key;
 pub mod type_descriptions;
 
 use iroha_schema::IntoSchema;
 
 pub use self::{
+    aggregate::{Aggregate, AggregateFunction},
     compound_predicate::CompoundPredicate,
     selector_traits::{IntoSelector, IntoSelectorTuple},
     selector_tuple::SelectorTuple,
+    sort_key::SortKey,
 };
 use crate::query::{error::QueryExecutionFail, QueryOutputBatchBox};
 
@@ -206,6 +213,7 @@ where
 /// The prelude re-exports most commonly used traits, structs and macros from this crate.
 pub mod prelude {
     pub use super::{
-        predicates::prelude::*, type_descriptions::prelude::*, CompoundPredicate, SelectorTuple,
+        predicates::prelude::*, type_descriptions::prelude::*, Aggregate, AggregateFunction,
+        CompoundPredicate, SelectorTuple, SortKey,
     };
 }
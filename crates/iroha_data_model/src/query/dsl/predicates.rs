@@ -2,9 +2,11 @@
 
 #[cfg(not(feature = "std"))]
 use alloc::{format, string::String, vec::Vec};
+use core::ops::RangeInclusive;
 
 use iroha_crypto::{HashOf, PublicKey};
 use iroha_primitives::{json::Json, numeric::Numeric};
+use serde_json::Value;
 
 use crate::{
     account::{Account, AccountId},
@@ -255,6 +257,216 @@ where
     }
 }
 
+/// A condition on a value found under a [JSON pointer](https://www.rfc-editor.org/rfc/rfc6901).
+///
+/// Evaluates to `false` if there is no value under the pointer.
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    Eq,
+    parity_scale_codec::Decode,
+    parity_scale_codec::Encode,
+    serde::Deserialize,
+    serde::Serialize,
+    iroha_schema::IntoSchema,
+)]
+pub struct JsonPointerPredicate {
+    /// Pointer to the value, e.g. `/kyc/tier`. An empty pointer refers to the whole input.
+    pub pointer: String,
+    /// Condition the value has to satisfy.
+    pub condition: JsonValuePredicate,
+}
+
+/// A condition on a JSON value.
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    Eq,
+    parity_scale_codec::Decode,
+    parity_scale_codec::Encode,
+    serde::Deserialize,
+    serde::Serialize,
+    iroha_schema::IntoSchema,
+)]
+pub enum JsonValuePredicate {
+    /// Checks that the value exists.
+    Exists,
+    /// Checks if the value is equal to the expected value.
+    Equals(Json),
+    /// Checks if the value is a number, or a string holding a [`Numeric`], satisfying the predicate.
+    Numeric(NumericPredicateAtom),
+    /// Checks if the value is a string satisfying the predicate.
+    String(StringPredicateAtom),
+    /// Checks if the value is an array containing the expected element.
+    Contains(Json),
+}
+
+impl JsonPointerPredicate {
+    /// Parses the input once and evaluates the condition on the value under the pointer.
+    fn applies_to_json(&self, input: &Json) -> bool {
+        input.try_into_any::<Value>().is_ok_and(|input| {
+            input
+                .pointer(&self.pointer)
+                .is_some_and(|value| self.condition.applies_to_value(value))
+        })
+    }
+}
+
+impl JsonValuePredicate {
+    // Expected values are compared in their serialized form, like in `JsonPredicateAtom::Equals`,
+    // so that they aren't parsed on every evaluation
+    fn applies_to_value(&self, input: &Value) -> bool {
+        match self {
+            JsonValuePredicate::Exists => true,
+            JsonValuePredicate::Equals(expected) => Json::from(input) == *expected,
+            JsonValuePredicate::Numeric(predicate) => {
+                let number = match input {
+                    Value::Number(number) => number.to_string().parse::<Numeric>().ok(),
+                    Value::String(string) => string.parse::<Numeric>().ok(),
+                    _ => None,
+                };
+                number.is_some_and(|number| super::EvaluatePredicate::applies(predicate, &number))
+            }
+            JsonValuePredicate::String(predicate) => input
+                .as_str()
+                .is_some_and(|string| predicate.applies_to_str(string)),
+            JsonValuePredicate::Contains(expected) => input.as_array().is_some_and(|elements| {
+                elements
+                    .iter()
+                    .any(|element| Json::from(element) == *expected)
+            }),
+        }
+    }
+}
+
+impl<Projector> JsonPrototype<PredicateMarker, Projector>
+where
+    Projector: ObjectProjector<PredicateMarker, InputType = Json>,
+{
+    /// Accessor for a value under a [JSON pointer](https://www.rfc-editor.org/rfc/rfc6901) in the input, e.g. `/kyc/tier`.
+    ///
+    /// Predicates on a nonexistent value evaluate to `false`.
+    pub fn at(self, pointer: impl Into<String>) -> JsonPointerPrototype<Projector> {
+        JsonPointerPrototype {
+            pointer: pointer.into(),
+            prototype: self,
+        }
+    }
+
+    /// Checks that the input exists, e.g. that the metadata contains the accessed key.
+    pub fn exists(self) -> CompoundPredicate<Projector::OutputType> {
+        self.at("").exists()
+    }
+
+    /// Checks if the input is an array containing the expected element.
+    pub fn contains(self, expected: impl Into<Json>) -> CompoundPredicate<Projector::OutputType> {
+        self.at("").contains(expected)
+    }
+}
+
+/// A prototype for a value under a JSON pointer, see [`JsonPrototype::at`].
+pub struct JsonPointerPrototype<Projector> {
+    pointer: String,
+    prototype: JsonPrototype<PredicateMarker, Projector>,
+}
+
+impl<Projector> JsonPointerPrototype<Projector>
+where
+    Projector: ObjectProjector<PredicateMarker, InputType = Json>,
+{
+    fn condition(self, condition: JsonValuePredicate) -> CompoundPredicate<Projector::OutputType> {
+        self.prototype.pointer(JsonPointerPredicate {
+            pointer: self.pointer,
+            condition,
+        })
+    }
+
+    /// Checks that the value exists.
+    pub fn exists(self) -> CompoundPredicate<Projector::OutputType> {
+        self.condition(JsonValuePredicate::Exists)
+    }
+
+    /// Checks if the value is equal to the expected value.
+    pub fn eq(self, expected: impl Into<Json>) -> CompoundPredicate<Projector::OutputType> {
+        self.condition(JsonValuePredicate::Equals(expected.into()))
+    }
+
+    /// Checks if the value is a number greater than the expected value.
+    pub fn gt(self, expected: Numeric) -> CompoundPredicate<Projector::OutputType> {
+        self.condition(JsonValuePredicate::Numeric(
+            NumericPredicateAtom::GreaterThan(expected),
+        ))
+    }
+
+    /// Checks if the value is a number greater than or equal to the expected value.
+    pub fn ge(self, expected: Numeric) -> CompoundPredicate<Projector::OutputType> {
+        self.condition(JsonValuePredicate::Numeric(
+            NumericPredicateAtom::GreaterOrEqual(expected),
+        ))
+    }
+
+    /// Checks if the value is a number less than the expected value.
+    pub fn lt(self, expected: Numeric) -> CompoundPredicate<Projector::OutputType> {
+        self.condition(JsonValuePredicate::Numeric(NumericPredicateAtom::LessThan(
+            expected,
+        )))
+    }
+
+    /// Checks if the value is a number less than or equal to the expected value.
+    pub fn le(self, expected: Numeric) -> CompoundPredicate<Projector::OutputType> {
+        self.condition(JsonValuePredicate::Numeric(
+            NumericPredicateAtom::LessOrEqual(expected),
+        ))
+    }
+
+    /// Checks if the value is a number within the expected range, bounds included.
+    pub fn between(
+        self,
+        expected: RangeInclusive<Numeric>,
+    ) -> CompoundPredicate<Projector::OutputType> {
+        self.condition(JsonValuePredicate::Numeric(NumericPredicateAtom::Between(
+            expected,
+        )))
+    }
+
+    /// Checks if the value is a string containing an expected substring, like [`str::contains()`].
+    pub fn contains_str(
+        self,
+        expected: impl Into<String>,
+    ) -> CompoundPredicate<Projector::OutputType> {
+        self.condition(JsonValuePredicate::String(StringPredicateAtom::Contains(
+            expected.into(),
+        )))
+    }
+
+    /// Checks if the value is a string starting with an expected substring, like [`str::starts_with()`].
+    pub fn starts_with(
+        self,
+        expected: impl Into<String>,
+    ) -> CompoundPredicate<Projector::OutputType> {
+        self.condition(JsonValuePredicate::String(StringPredicateAtom::StartsWith(
+            expected.into(),
+        )))
+    }
+
+    /// Checks if the value is a string ending with an expected substring, like [`str::ends_with()`].
+    pub fn ends_with(
+        self,
+        expected: impl Into<String>,
+    ) -> CompoundPredicate<Projector::OutputType> {
+        self.condition(JsonValuePredicate::String(StringPredicateAtom::EndsWith(
+            expected.into(),
+        )))
+    }
+
+    /// Checks if the value is an array containing the expected element.
+    pub fn contains(self, expected: impl Into<Json>) -> CompoundPredicate<Projector::OutputType> {
+        self.condition(JsonValuePredicate::Contains(expected.into()))
+    }
+}
+
 impl_predicate_atom! {
     MetadataPredicateAtom(_input: Metadata) [MetadataPrototype] {
         // TODO: populate
@@ -266,9 +478,22 @@ impl_predicate_atom! {
     JsonPredicateAtom(input: Json) [JsonPrototype] {
         /// Checks if the input is equal to the expected value.
         Equals(expected: Json) [eq] => input == expected,
+        /// Checks a value found in the input under a JSON pointer.
+        Pointer(predicate: JsonPointerPredicate) [pointer] => predicate.applies_to_json(input),
     }
-    NumericPredicateAtom(_input: Numeric) [NumericPrototype] {
-        // TODO: populate
+    NumericPredicateAtom(input: Numeric) [NumericPrototype] {
+        /// Checks if the input is equal to the expected value.
+        Equals(expected: Numeric) [eq] => input == expected,
+        /// Checks if the input is greater than the expected value.
+        GreaterThan(expected: Numeric) [gt] => input > expected,
+        /// Checks if the input is greater than or equal to the expected value.
+        GreaterOrEqual(expected: Numeric) [ge] => input >= expected,
+        /// Checks if the input is less than the expected value.
+        LessThan(expected: Numeric) [lt] => input < expected,
+        /// Checks if the input is less than or equal to the expected value.
+        LessOrEqual(expected: Numeric) [le] => input <= expected,
+        /// Checks if the input lies within the expected range, bounds included.
+        Between(expected: RangeInclusive<Numeric>) [between] => expected.contains(input),
     }
 
     // account
@@ -366,11 +591,93 @@ pub mod prelude {
         AssetDefinitionIdPredicateAtom, AssetDefinitionPredicateAtom, AssetIdPredicateAtom,
         AssetPredicateAtom, BlockHeaderHashPredicateAtom, BlockHeaderPredicateAtom,
         CommittedTransactionPredicateAtom, DomainIdPredicateAtom, DomainPredicateAtom,
-        JsonPredicateAtom, MetadataPredicateAtom, NftIdPredicateAtom, NftPredicateAtom,
-        NumericPredicateAtom, ParameterPredicateAtom, PeerIdPredicateAtom, PermissionPredicateAtom,
-        PublicKeyPredicateAtom, RoleIdPredicateAtom, RolePredicateAtom, SignedBlockPredicateAtom,
-        StringPredicateAtom, TransactionEntrypointHashPredicateAtom,
-        TransactionEntrypointPredicateAtom, TransactionResultHashPredicateAtom,
-        TransactionResultPredicateAtom, TriggerIdPredicateAtom, TriggerPredicateAtom,
+        JsonPointerPredicate, JsonPredicateAtom, JsonValuePredicate, MetadataPredicateAtom,
+        NftIdPredicateAtom, NftPredicateAtom, NumericPredicateAtom, ParameterPredicateAtom,
+        PeerIdPredicateAtom, PermissionPredicateAtom, PublicKeyPredicateAtom, RoleIdPredicateAtom,
+        RolePredicateAtom, SignedBlockPredicateAtom, StringPredicateAtom,
+        TransactionEntrypointHashPredicateAtom, TransactionEntrypointPredicateAtom,
+        TransactionResultHashPredicateAtom, TransactionResultPredicateAtom, TriggerIdPredicateAtom,
+        TriggerPredicateAtom,
     };
 }
+
+#[cfg(test)]
+mod tests {
+    use iroha_primitives::numeric::numeric;
+
+    use super::*;
+    use crate::query::dsl::EvaluatePredicate;
+
+    fn kyc() -> Json {
+        Json::new(serde_json::json!({
+            "tier": 2,
+            "limit": "100.5",
+            "country": "Wonderland",
+            "tags": ["verified", "premium"],
+        }))
+    }
+
+    fn applies(pointer: &str, condition: JsonValuePredicate) -> bool {
+        JsonPredicateAtom::Pointer(JsonPointerPredicate {
+            pointer: pointer.to_owned(),
+            condition,
+        })
+        .applies(&kyc())
+    }
+
+    #[test]
+    fn json_pointer_predicates() {
+        use JsonValuePredicate as Cond;
+
+        assert!(applies("/tier", Cond::Exists));
+        assert!(!applies("/missing", Cond::Exists));
+        assert!(applies("/tier", Cond::Equals(2_u32.into())));
+        assert!(applies(
+            "/tier",
+            Cond::Numeric(NumericPredicateAtom::GreaterOrEqual(numeric!(2)))
+        ));
+        assert!(!applies(
+            "/tier",
+            Cond::Numeric(NumericPredicateAtom::GreaterThan(numeric!(2)))
+        ));
+        assert!(applies(
+            "/limit",
+            Cond::Numeric(NumericPredicateAtom::Between(numeric!(100)..=numeric!(101)))
+        ));
+        assert!(!applies(
+            "/country",
+            Cond::Numeric(NumericPredicateAtom::LessThan(numeric!(0)))
+        ));
+        assert!(applies(
+            "/country",
+            Cond::String(StringPredicateAtom::StartsWith("Wonder".to_owned()))
+        ));
+        assert!(applies("/tags", Cond::Contains("premium".into())));
+        assert!(!applies("/tags", Cond::Contains("banned".into())));
+        assert!(!applies("", Cond::Contains("premium".into())));
+        assert!(applies("", Cond::Equals(kyc())));
+    }
+
+    #[test]
+    fn metadata_predicates() {
+        let mut metadata = Metadata::default();
+        metadata.insert("kyc".parse().unwrap(), kyc());
+
+        let has_kyc = CompoundPredicate::<Metadata>::build(|metadata| {
+            metadata.key("kyc".parse().unwrap()).exists()
+        });
+        let has_tags = CompoundPredicate::<Metadata>::build(|metadata| {
+            metadata.key("tags".parse().unwrap()).exists()
+        });
+        let high_tier = CompoundPredicate::<Metadata>::build(|metadata| {
+            metadata
+                .key("kyc".parse().unwrap())
+                .at("/tier")
+                .ge(numeric!(2))
+        });
+
+        assert!(has_kyc.applies(&metadata));
+        assert!(!has_tags.applies(&metadata));
+        assert!(high_tier.applies(&metadata));
+    }
+}
@@ -0,0 +1,47 @@
+#[cfg(not(feature = "std"))]
+use alloc::{format, string::String};
+
+use derive_where::derive_where;
+use iroha_macro::serde_where;
+use iroha_schema::IntoSchema;
+use parity_scale_codec::{Decode, Encode};
+use serde::{Deserialize, Serialize};
+
+use crate::query::{
+    dsl::{BaseProjector, HasProjection, HasPrototype, IntoSelector, SelectorMarker},
+    parameters::SortOrder,
+};
+
+/// A key to sort the results of an iterable query by.
+///
+/// The selected value has to be orderable, which is the case for all values except trigger actions.
+#[derive_where(Debug, Eq, PartialEq, Clone; T::Projection)]
+#[serde_where(T::Projection)]
+#[derive(Decode, Encode, Deserialize, Serialize, IntoSchema)]
+pub struct SortKey<T: HasProjection<SelectorMarker, AtomType = ()>> {
+    /// Selector of the value to compare
+    pub selector: T::Projection,
+    /// Direction of sorting
+    #[serde(default)]
+    pub order: SortOrder,
+}
+
+impl<T: HasProjection<SelectorMarker, AtomType = ()>> SortKey<T> {
+    /// Create a new sort key from a selector.
+    pub fn new(selector: T::Projection, order: SortOrder) -> Self {
+        Self { selector, order }
+    }
+
+    /// Build a sort key using a prototype.
+    pub fn build<F, O>(order: SortOrder, f: F) -> Self
+    where
+        T: HasPrototype,
+        F: FnOnce(
+            <T as HasPrototype>::Prototype<SelectorMarker, BaseProjector<SelectorMarker, T>>,
+        ) -> O,
+        <T as HasPrototype>::Prototype<SelectorMarker, BaseProjector<SelectorMarker, T>>: Default,
+        O: IntoSelector<SelectingType = T>,
+    {
+        Self::new(f(Default::default()).into_selector(), order)
+    }
+}
@@ -254,9 +254,10 @@ type_descriptions! {
     }
 
     // asset
-    AssetDefinition[AssetDefinitionProjection, AssetDefinitionPrototype]: AssetDefinitionId, DomainId, Name, Metadata, Json {
+    AssetDefinition[AssetDefinitionProjection, AssetDefinitionPrototype]: AssetDefinitionId, DomainId, Name, Metadata, Json, Numeric {
         id(Id, AssetDefinitionIdProjector): AssetDefinitionId,
         metadata(Metadata, AssetDefinitionMetadataProjector): Metadata,
+        total_quantity(TotalQuantity, AssetDefinitionTotalQuantityProjector): Numeric,
     }
     AssetDefinitionId[AssetDefinitionIdProjection, AssetDefinitionIdPrototype]: DomainId, Name {
         domain(Domain, AssetDefinitionIdDomainProjector): DomainId,
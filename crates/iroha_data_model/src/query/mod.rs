@@ -55,6 +55,7 @@ use crate::{
 pub mod builder;
 pub mod dsl;
 pub mod parameters;
+pub mod subscription;
 
 /// A query that either returns a single value or errors out
 // NOTE: we are planning to remove this class of queries (https://github.com/hyperledger-iroha/iroha/issues/4933)
@@ -87,9 +88,12 @@ mod model {
     };
 
     /// An iterable query bundled with a filter
-    #[serde_where(Q, CompoundPredicate<Q::Item>, SelectorTuple<Q::Item>)]
+    #[serde_where(
+        Q, CompoundPredicate<Q::Item>, SelectorTuple<Q::Item>, Aggregate<Q::Item>, SortKey<Q::Item>
+    )]
     #[derive_where(
-        Debug, Clone, PartialEq, Eq; Q, CompoundPredicate<Q::Item>, SelectorTuple<Q::Item>
+        Debug, Clone, PartialEq, Eq;
+        Q, CompoundPredicate<Q::Item>, SelectorTuple<Q::Item>, Aggregate<Q::Item>, SortKey<Q::Item>
     )]
     #[derive(Decode, Encode, Constructor, IntoSchema, Deserialize, Serialize)]
     pub struct QueryWithFilter<Q>
@@ -101,6 +105,12 @@ mod model {
         pub predicate: CompoundPredicate<Q::Item>,
         #[serde(default)]
         pub selector: SelectorTuple<Q::Item>,
+        /// If set, the results are aggregated on the server and the `selector` is ignored
+        #[serde(default)]
+        pub aggregate: Option<Aggregate<Q::Item>>,
+        /// Keys to sort the results by, compared in order. Applied before the sorting by metadata key from [`QueryParams`]
+        #[serde(default)]
+        pub sort_by: Vec<SortKey<Q::Item>>,
     }
 
     fn predicate_default<T>() -> CompoundPredicate<T>
@@ -1073,14 +1083,18 @@ pub mod block {
 
     queries! {
         /// [`FindBlocks`] Iroha Query lists all blocks sorted by
-        /// height in descending order
+        /// height in descending order.
+        /// Fails with [`BlocksPruned`](super::error::QueryExecutionFail::BlocksPruned)
+        /// if the results would include blocks pruned on the peer.
         #[derive(Copy, Display)]
         #[display(fmt = "Find all blocks")]
         #[ffi_type]
         pub struct FindBlocks;
 
         /// [`FindBlockHeaders`] Iroha Query lists all block headers
-        /// sorted by height in descending order
+        /// sorted by height in descending order.
+        /// Fails with [`BlocksPruned`](super::error::QueryExecutionFail::BlocksPruned)
+        /// if the results would include blocks pruned on the peer.
         #[derive(Copy, Display)]
         #[display(fmt = "Find all block headers")]
         #[ffi_type]
@@ -1150,6 +1164,22 @@ pub mod error {
             InvalidSingularParameters,
             /// Reached the limit of parallel queries. Either wait for previous queries to complete, or increase the limit in the config.
             CapacityLimit,
+            /// State at the requested height is not available on this peer
+            HeightUnavailable,
+            /// Aggregated values must be numeric
+            NonNumericAggregate,
+            /// Aggregated value overflowed
+            AggregateOverflow,
+            /// Selected values can't be used for sorting
+            UnorderedSortKey,
+            /// Some of the specified parameters (pagination/sorting/aggregation/height) are not applicable to query subscriptions
+            InvalidSubscriptionParameters,
+            /// Blocks below height {0} are pruned on this peer, query a peer keeping the whole chain
+            BlocksPruned(
+                #[skip_from]
+                #[skip_try_from]
+                u64,
+            ),
         }
 
         /// Type assertion error
@@ -1208,7 +1238,7 @@ pub mod prelude {
         account::prelude::*, asset::prelude::*, block::prelude::*, builder::prelude::*,
         domain::prelude::*, dsl::prelude::*, executor::prelude::*, nft::prelude::*,
         parameters::prelude::*, peer::prelude::*, permission::prelude::*, role::prelude::*,
-        transaction::prelude::*, trigger::prelude::*, CommittedTransaction, QueryBox, QueryRequest,
-        SingularQueryBox,
+        subscription::prelude::*, transaction::prelude::*, trigger::prelude::*,
+        CommittedTransaction, QueryBox, QueryRequest, SingularQueryBox,
     };
 }
@@ -7,12 +7,16 @@ use core::num::NonZeroU64;
 use derive_more::{Constructor, Display};
 use getset::Getters;
 use iroha_data_model_derive::model;
+use iroha_primitives::json::Json;
 use iroha_schema::IntoSchema;
 use iroha_version::{Decode, Encode};
 use nonzero_ext::nonzero;
 use serde::{Deserialize, Serialize};
 
-use crate::name::Name;
+use crate::{
+    name::Name,
+    query::{QueryOutputBatchBox, QueryOutputBatchBoxTuple},
+};
 
 /// Default value for `fetch_size` parameter in queries.
 pub const DEFAULT_FETCH_SIZE: NonZeroU64 = nonzero!(100_u64);
@@ -39,6 +43,37 @@ mod model {
         pub query: QueryId,
         /// Pointer to the next element in the result set
         pub cursor: NonZeroU64,
+        /// Position of the cursor in the ordered results.
+        ///
+        /// Allows to resume the iteration on any peer, even if it doesn't have the query in its live store
+        /// (e.g. after a restart or an eviction), by starting the query again with [`QueryParams::continuation`].
+        /// Once new blocks are committed, the peer has to rebuild the state at the pinned height from its snapshot history.
+        /// Only provided for the queries whose results have ids to order by.
+        #[serde(default)]
+        pub continuation: Option<QueryContinuation>,
+    }
+
+    /// Last result returned by a query, identified by its sorting keys and id,
+    /// pinned to the block height the results were read at.
+    ///
+    /// A query started with a continuation is answered against the state at that height
+    /// and only returns the results ordered after it, so the blocks committed in the meantime
+    /// don't change the results yet to be returned.
+    #[derive(
+        Debug, Clone, PartialEq, Eq, Getters, Encode, Decode, Serialize, Deserialize, IntoSchema,
+    )]
+    #[getset(get = "pub")]
+    pub struct QueryContinuation {
+        /// Height of the block the results were read at
+        pub height: u64,
+        /// Number of results returned so far, deducted from the pagination limit
+        pub position: NonZeroU64,
+        /// Values selected by the sort keys of the query
+        pub sort_keys: QueryOutputBatchBoxTuple,
+        /// Value of the metadata key the results are sorted by
+        pub metadata: Option<Json>,
+        /// Id of the result
+        pub id: QueryOutputBatchBox,
     }
 
     /// Structure for pagination requests
@@ -86,6 +121,31 @@ mod model {
     pub struct Sorting {
         /// Sort query result using [`Name`] of the key in metadata.
         pub sort_by_metadata_key: Option<Name>,
+        /// Direction of sorting by the metadata key.
+        #[serde(default)]
+        pub order: SortOrder,
+    }
+
+    /// Direction of sorting
+    #[derive(
+        Debug,
+        Clone,
+        Copy,
+        Default,
+        PartialEq,
+        Eq,
+        Decode,
+        Encode,
+        Deserialize,
+        Serialize,
+        IntoSchema,
+    )]
+    pub enum SortOrder {
+        /// From the smallest to the largest value
+        #[default]
+        Ascending,
+        /// From the largest to the smallest value
+        Descending,
     }
 
     /// Structure for query fetch size parameter encoding/decoding
@@ -128,6 +188,15 @@ mod model {
         pub pagination: Pagination,
         pub sorting: Sorting,
         pub fetch_size: FetchSize,
+        /// Execute the query against the state at the given block height.
+        ///
+        /// If not specified then the latest state is used.
+        pub at_height: Option<NonZeroU64>,
+        /// Only return the results ordered after the continuation taken from a [`ForwardCursor`].
+        ///
+        /// The query is executed against the state at the height of the continuation,
+        /// which has to match `at_height` if both are specified.
+        pub continuation: Option<QueryContinuation>,
     }
 }
 
@@ -136,11 +205,32 @@ impl Sorting {
     pub fn by_metadata_key(key: Name) -> Self {
         Self {
             sort_by_metadata_key: Some(key),
+            order: SortOrder::Ascending,
+        }
+    }
+
+    /// Sorts in descending order instead.
+    #[must_use]
+    pub fn descending(self) -> Self {
+        Self {
+            order: SortOrder::Descending,
+            ..self
+        }
+    }
+}
+
+impl SortOrder {
+    /// Applies the direction to the ordering of two values compared in ascending order.
+    #[must_use]
+    pub fn apply(self, ordering: core::cmp::Ordering) -> core::cmp::Ordering {
+        match self {
+            Self::Ascending => ordering,
+            Self::Descending => ordering.reverse(),
         }
     }
 }
 
 pub mod prelude {
     //! Prelude: re-export most commonly used traits, structs and macros from this module.
-    pub use super::{FetchSize, Pagination, Sorting};
+    pub use super::{FetchSize, Pagination, SortOrder, Sorting};
 }
@@ -0,0 +1,63 @@
+//! Structures related to subscriptions to the results of iterable queries
+
+use core::num::NonZeroU64;
+
+use derive_more::Constructor;
+use iroha_data_model_derive::model;
+use iroha_schema::IntoSchema;
+use parity_scale_codec::{Decode, Encode};
+use serde::{Deserialize, Serialize};
+
+pub use self::model::*;
+use super::{QueryOutputBatchBoxTuple, SignedQuery};
+use crate::ValidationFail;
+
+#[model]
+mod model {
+    use super::*;
+
+    /// Message sent by the stream consumer.
+    /// Request sent by the client to subscribe to the results of an iterable query.
+    ///
+    /// The signed request must start a query. Its predicate and selector define the tracked results,
+    /// while its fetch size limits the batches of the initial results.
+    /// Pagination, sorting and aggregation are not applicable to subscriptions.
+    #[derive(Debug, Clone, Constructor, Decode, Encode, Deserialize, Serialize, IntoSchema)]
+    #[repr(transparent)]
+    pub struct QuerySubscriptionRequest(pub SignedQuery);
+
+    /// Changes of the subscribed query results made by committed blocks
+    ///
+    /// Each batch tuple has the same layout as the selected tuple of the query.
+    #[derive(Debug, Clone, PartialEq, Eq, Decode, Encode, Deserialize, Serialize, IntoSchema)]
+    pub struct QueryResultsDelta {
+        /// Height of the latest block the results are up to date with
+        pub height: NonZeroU64,
+        /// Items that started matching the query
+        pub inserted: QueryOutputBatchBoxTuple,
+        /// New values of the items that changed
+        pub updated: QueryOutputBatchBoxTuple,
+        /// Previous values of the items in `updated`, in the same order
+        pub replaced: QueryOutputBatchBoxTuple,
+        /// Last values of the items that stopped matching the query
+        pub removed: QueryOutputBatchBoxTuple,
+    }
+
+    /// Message sent by the stream producer.
+    #[derive(Debug, Clone, PartialEq, Eq, Decode, Encode, Deserialize, Serialize, IntoSchema)]
+    pub enum QuerySubscriptionMessage {
+        /// A batch of the results at the moment of subscription
+        Initial(QueryOutputBatchBoxTuple),
+        /// All the initial results were sent, they are up to date with the block at this height
+        Synced(NonZeroU64),
+        /// Changes of the results since the previous message
+        Delta(QueryResultsDelta),
+        /// The subscription was rejected or can no longer be served. No further messages follow.
+        Failed(ValidationFail),
+    }
+}
+
+/// The prelude re-exports most commonly used traits, structs and macros from this module.
+pub mod prelude {
+    pub use super::{QueryResultsDelta, QuerySubscriptionMessage, QuerySubscriptionRequest};
+}
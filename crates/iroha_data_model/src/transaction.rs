@@ -115,6 +115,10 @@ mod model {
         pub time_to_live_ms: Option<NonZeroU64>,
         /// Random value to make different hashes for transactions which occur repeatedly and simultaneously.
         pub nonce: Option<NonZeroU32>,
+        /// Position of the transaction among the transactions of its authority.
+        ///
+        /// Must be greater by one than the sequence number of the latest committed transaction of the authority.
+        pub sequence: Option<NonZeroU64>,
         /// Store for additional information.
         pub metadata: Metadata,
     }
@@ -395,6 +399,13 @@ impl SignedTransaction {
         tx.payload.nonce
     }
 
+    /// Transaction sequence number
+    #[inline]
+    pub fn sequence(&self) -> Option<NonZeroU64> {
+        let SignedTransaction::V1(tx) = self;
+        tx.payload.sequence
+    }
+
     /// Transaction chain id
     #[inline]
     pub fn chain(&self) -> &ChainId {
@@ -483,6 +494,7 @@ impl TransactionBuilder {
                 authority,
                 creation_time_ms,
                 nonce: None,
+                sequence: None,
                 time_to_live_ms: None,
                 instructions: Vec::<InstructionBox>::new().into(),
                 metadata: Metadata::default(),
@@ -554,6 +566,12 @@ impl TransactionBuilder {
         self
     }
 
+    /// Set sequence number for this transaction
+    pub fn set_sequence(&mut self, sequence: NonZeroU64) -> &mut Self {
+        self.payload.sequence = Some(sequence);
+        self
+    }
+
     /// Set time-to-live for this transaction
     pub fn set_ttl(&mut self, time_to_live: Duration) -> &mut Self {
         let ttl: u64 = time_to_live
@@ -760,6 +778,32 @@ pub mod error {
             pub reason: String,
         }
 
+        /// Transaction was rejected because its sequence number doesn't follow
+        /// the sequence number of the latest committed transaction of its authority.
+        #[derive(
+            Debug,
+            Display,
+            Clone,
+            Copy,
+            PartialEq,
+            Eq,
+            PartialOrd,
+            Ord,
+            Decode,
+            Encode,
+            Deserialize,
+            Serialize,
+            IntoSchema,
+        )]
+        #[display(fmt = "Expected sequence number {expected}, got {actual}")]
+        #[ffi_type]
+        pub struct SequenceMismatch {
+            /// Sequence number following the latest committed one
+            pub expected: NonZeroU64,
+            /// Sequence number of the transaction
+            pub actual: NonZeroU64,
+        }
+
         /// Possible reasons for trigger-specific execution failure.
         #[derive(
             Debug,
@@ -827,6 +871,8 @@ pub mod error {
             WasmExecution(#[cfg_attr(feature = "std", source)] WasmExecutionFail),
             /// Execution of a time trigger or an invoked data trigger failed.
             TriggerExecution(#[cfg_attr(feature = "std", source)] TriggerExecutionFail),
+            /// Transaction sequence number is out of order
+            InvalidSequence(#[cfg_attr(feature = "std", source)] SequenceMismatch),
         }
     }
 
@@ -847,6 +893,11 @@ pub mod error {
                 SetParameter(_) => "set parameter",
                 Upgrade(_) => "upgrade",
                 Log(_) => "log",
+                Freeze(_) => "freeze",
+                Unfreeze(_) => "unfreeze",
+                Hold(_) => "hold",
+                Release(_) => "release",
+                SetAssetDefinitionLimits(_) => "set asset definition limits",
                 Custom(_) => "custom",
             };
             write!(
@@ -869,12 +920,15 @@ pub mod error {
     #[cfg(feature = "std")]
     impl std::error::Error for TriggerExecutionFail {}
 
+    #[cfg(feature = "std")]
+    impl std::error::Error for SequenceMismatch {}
+
     pub mod prelude {
         //! The prelude re-exports most commonly used traits, structs and macros from this module.
 
         pub use super::{
-            InstructionExecutionFail, TransactionRejectionReason, TriggerExecutionFail,
-            WasmExecutionFail,
+            InstructionExecutionFail, SequenceMismatch, TransactionRejectionReason,
+            TriggerExecutionFail, WasmExecutionFail,
         };
     }
 }
@@ -49,6 +49,11 @@ pub trait Visit {
         visit_execute_trigger(&ExecuteTrigger),
         visit_set_parameter(&SetParameter),
         visit_log(&Log),
+        visit_freeze_asset(&Freeze<Asset>),
+        visit_unfreeze_asset(&Unfreeze<Asset>),
+        visit_hold_asset_numeric(&Hold<Numeric, Asset>),
+        visit_release_asset_numeric(&Release<Numeric, Asset>),
+        visit_set_asset_definition_limits(&SetAssetDefinitionLimits),
         visit_custom_instruction(&CustomInstruction),
 
         // Visit SingularQueryBox
@@ -208,6 +213,15 @@ pub fn visit_instruction<V: Visit + ?Sized>(visitor: &mut V, isi: &InstructionBo
             visitor.visit_execute_trigger(variant_value)
         }
         InstructionBox::Log(variant_value) => visitor.visit_log(variant_value),
+        InstructionBox::Freeze(variant_value) => visitor.visit_freeze_asset(variant_value),
+        InstructionBox::Unfreeze(variant_value) => visitor.visit_unfreeze_asset(variant_value),
+        InstructionBox::Hold(variant_value) => visitor.visit_hold_asset_numeric(variant_value),
+        InstructionBox::Release(variant_value) => {
+            visitor.visit_release_asset_numeric(variant_value)
+        }
+        InstructionBox::SetAssetDefinitionLimits(variant_value) => {
+            visitor.visit_set_asset_definition_limits(variant_value)
+        }
         InstructionBox::Burn(variant_value) => visitor.visit_burn(variant_value),
         InstructionBox::Grant(variant_value) => visitor.visit_grant(variant_value),
         InstructionBox::Mint(variant_value) => visitor.visit_mint(variant_value),
@@ -328,6 +342,10 @@ leaf_visitors! {
     visit_mint_asset_numeric(&Mint<Numeric, Asset>),
     visit_burn_asset_numeric(&Burn<Numeric, Asset>),
     visit_transfer_asset_numeric(&Transfer<Asset, Numeric, Account>),
+    visit_freeze_asset(&Freeze<Asset>),
+    visit_unfreeze_asset(&Unfreeze<Asset>),
+    visit_hold_asset_numeric(&Hold<Numeric, Asset>),
+    visit_release_asset_numeric(&Release<Numeric, Asset>),
     visit_transfer_nft(&Transfer<Account, NftId, Account>),
     visit_set_nft_key_value(&SetKeyValue<Nft>),
     visit_remove_nft_key_value(&RemoveKeyValue<Nft>),
@@ -338,6 +356,7 @@ leaf_visitors! {
     visit_transfer_asset_definition(&Transfer<Account, AssetDefinitionId, Account>),
     visit_set_asset_definition_key_value(&SetKeyValue<AssetDefinition>),
     visit_remove_asset_definition_key_value(&RemoveKeyValue<AssetDefinition>),
+    visit_set_asset_definition_limits(&SetAssetDefinitionLimits),
     visit_register_domain(&Register<Domain>),
     visit_unregister_domain(&Unregister<Domain>),
     visit_transfer_domain(&Transfer<Account, DomainId, Account>),
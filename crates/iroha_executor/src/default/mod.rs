@@ -7,11 +7,15 @@ pub use account::{
     visit_register_account, visit_remove_account_key_value, visit_set_account_key_value,
     visit_unregister_account,
 };
-pub use asset::{visit_burn_asset_numeric, visit_mint_asset_numeric, visit_transfer_asset_numeric};
+pub use asset::{
+    visit_burn_asset_numeric, visit_freeze_asset, visit_hold_asset_numeric,
+    visit_mint_asset_numeric, visit_release_asset_numeric, visit_transfer_asset_numeric,
+    visit_unfreeze_asset,
+};
 pub use asset_definition::{
     visit_register_asset_definition, visit_remove_asset_definition_key_value,
-    visit_set_asset_definition_key_value, visit_transfer_asset_definition,
-    visit_unregister_asset_definition,
+    visit_set_asset_definition_key_value, visit_set_asset_definition_limits,
+    visit_transfer_asset_definition, visit_unregister_asset_definition,
 };
 pub use domain::{
     visit_register_domain, visit_remove_domain_key_value, visit_set_domain_key_value,
@@ -118,6 +122,21 @@ pub fn visit_instruction<V: Execute + Visit + ?Sized>(executor: &mut V, isi: &In
         InstructionBox::Upgrade(isi) => {
             executor.visit_upgrade(isi);
         }
+        InstructionBox::Freeze(isi) => {
+            executor.visit_freeze_asset(isi);
+        }
+        InstructionBox::Unfreeze(isi) => {
+            executor.visit_unfreeze_asset(isi);
+        }
+        InstructionBox::Hold(isi) => {
+            executor.visit_hold_asset_numeric(isi);
+        }
+        InstructionBox::Release(isi) => {
+            executor.visit_release_asset_numeric(isi);
+        }
+        InstructionBox::SetAssetDefinitionLimits(isi) => {
+            executor.visit_set_asset_definition_limits(isi);
+        }
         InstructionBox::Custom(isi) => {
             executor.visit_custom_instruction(isi);
         }
@@ -329,6 +348,13 @@ pub mod domain {
                 permission.asset.definition().domain() == domain_id
                     || permission.asset.account().domain() == domain_id
             }
+            AnyPermission::CanFreezeAssetWithDefinition(permission) => {
+                permission.asset_definition.domain() == domain_id
+            }
+            AnyPermission::CanFreezeAsset(permission) => {
+                permission.asset.definition().domain() == domain_id
+                    || permission.asset.account().domain() == domain_id
+            }
             AnyPermission::CanRegisterNft(permission) => &permission.domain == domain_id,
             AnyPermission::CanUnregisterNft(permission) => permission.nft.domain() == domain_id,
             AnyPermission::CanTransferNft(permission) => permission.nft.domain() == domain_id,
@@ -347,6 +373,7 @@ pub mod domain {
             | AnyPermission::CanModifyTrigger(_)
             | AnyPermission::CanModifyTriggerMetadata(_)
             | AnyPermission::CanManagePeers(_)
+            | AnyPermission::CanPrioritizeTransactions(_)
             | AnyPermission::CanRegisterDomain(_)
             | AnyPermission::CanSetParameters(_)
             | AnyPermission::CanManageRoles(_)
@@ -495,12 +522,14 @@ pub mod account {
             AnyPermission::CanMintAsset(permission) => permission.asset.account() == account_id,
             AnyPermission::CanBurnAsset(permission) => permission.asset.account() == account_id,
             AnyPermission::CanTransferAsset(permission) => permission.asset.account() == account_id,
+            AnyPermission::CanFreezeAsset(permission) => permission.asset.account() == account_id,
             AnyPermission::CanRegisterTrigger(permission) => permission.authority == *account_id,
             AnyPermission::CanUnregisterTrigger(_)
             | AnyPermission::CanExecuteTrigger(_)
             | AnyPermission::CanModifyTrigger(_)
             | AnyPermission::CanModifyTriggerMetadata(_)
             | AnyPermission::CanManagePeers(_)
+            | AnyPermission::CanPrioritizeTransactions(_)
             | AnyPermission::CanRegisterDomain(_)
             | AnyPermission::CanUnregisterDomain(_)
             | AnyPermission::CanModifyDomainMetadata(_)
@@ -699,6 +728,29 @@ pub mod asset_definition {
         );
     }
 
+    pub fn visit_set_asset_definition_limits<V: Execute + Visit + ?Sized>(
+        executor: &mut V,
+        isi: &SetAssetDefinitionLimits,
+    ) {
+        if executor.context().curr_block.is_genesis() {
+            execute!(executor, isi);
+        }
+        match is_asset_definition_owner(
+            isi.asset_definition(),
+            &executor.context().authority,
+            executor.host(),
+        ) {
+            Err(err) => deny!(executor, err),
+            Ok(true) => execute!(executor, isi),
+            Ok(false) => {}
+        }
+
+        deny!(
+            executor,
+            "Can't set limits of the asset definition created by another account"
+        );
+    }
+
     fn is_permission_asset_definition_associated(
         permission: &Permission,
         asset_definition_id: &AssetDefinitionId,
@@ -731,6 +783,12 @@ pub mod asset_definition {
             AnyPermission::CanTransferAsset(permission) => {
                 permission.asset.definition() == asset_definition_id
             }
+            AnyPermission::CanFreezeAssetWithDefinition(permission) => {
+                &permission.asset_definition == asset_definition_id
+            }
+            AnyPermission::CanFreezeAsset(permission) => {
+                permission.asset.definition() == asset_definition_id
+            }
             AnyPermission::CanUnregisterAccount(_)
             | AnyPermission::CanModifyAccountMetadata(_)
             | AnyPermission::CanRegisterTrigger(_)
@@ -739,6 +797,7 @@ pub mod asset_definition {
             | AnyPermission::CanModifyTrigger(_)
             | AnyPermission::CanModifyTriggerMetadata(_)
             | AnyPermission::CanManagePeers(_)
+            | AnyPermission::CanPrioritizeTransactions(_)
             | AnyPermission::CanRegisterDomain(_)
             | AnyPermission::CanUnregisterDomain(_)
             | AnyPermission::CanModifyDomainMetadata(_)
@@ -757,8 +816,8 @@ pub mod asset_definition {
 
 pub mod asset {
     use iroha_executor_data_model::permission::asset::{
-        CanBurnAsset, CanBurnAssetWithDefinition, CanMintAsset, CanMintAssetWithDefinition,
-        CanTransferAsset, CanTransferAssetWithDefinition,
+        CanBurnAsset, CanBurnAssetWithDefinition, CanFreezeAsset, CanFreezeAssetWithDefinition,
+        CanMintAsset, CanMintAssetWithDefinition, CanTransferAsset, CanTransferAssetWithDefinition,
     };
     use iroha_smart_contract::data_model::isi::BuiltInInstruction;
     use iroha_smart_contract_utils::Encode;
@@ -902,6 +961,83 @@ pub mod asset {
 
         deny!(executor, "Can't transfer assets of another account");
     }
+
+    /// Freeze, unfreeze, hold and release are issuer controls.
+    /// The asset owner may freeze or hold their own asset, e.g. for escrow,
+    /// but can't unfreeze or release it, otherwise they could lift the issuer restrictions.
+    fn execute_restrict_asset<V, I>(
+        executor: &mut V,
+        asset_id: &AssetId,
+        isi: &I,
+        permitted_to_owner: bool,
+    ) where
+        V: Execute + Visit + ?Sized,
+        I: BuiltInInstruction + Encode,
+    {
+        if executor.context().curr_block.is_genesis() {
+            execute!(executor, isi);
+        }
+        if permitted_to_owner {
+            match is_asset_owner(asset_id, &executor.context().authority, executor.host()) {
+                Err(err) => deny!(executor, err),
+                Ok(true) => execute!(executor, isi),
+                Ok(false) => {}
+            }
+        }
+        match is_asset_definition_owner(
+            asset_id.definition(),
+            &executor.context().authority,
+            executor.host(),
+        ) {
+            Err(err) => deny!(executor, err),
+            Ok(true) => execute!(executor, isi),
+            Ok(false) => {}
+        }
+        let can_freeze_assets_with_definition_token = CanFreezeAssetWithDefinition {
+            asset_definition: asset_id.definition().clone(),
+        };
+        if can_freeze_assets_with_definition_token
+            .is_owned_by(&executor.context().authority, executor.host())
+        {
+            execute!(executor, isi);
+        }
+        let can_freeze_user_asset_token = CanFreezeAsset {
+            asset: asset_id.clone(),
+        };
+        if can_freeze_user_asset_token.is_owned_by(&executor.context().authority, executor.host()) {
+            execute!(executor, isi);
+        }
+
+        deny!(
+            executor,
+            "Can't restrict assets with definitions registered by other accounts"
+        );
+    }
+
+    pub fn visit_freeze_asset<V: Execute + Visit + ?Sized>(executor: &mut V, isi: &Freeze<Asset>) {
+        execute_restrict_asset(executor, isi.object(), isi, true);
+    }
+
+    pub fn visit_unfreeze_asset<V: Execute + Visit + ?Sized>(
+        executor: &mut V,
+        isi: &Unfreeze<Asset>,
+    ) {
+        execute_restrict_asset(executor, isi.object(), isi, false);
+    }
+
+    pub fn visit_hold_asset_numeric<V: Execute + Visit + ?Sized>(
+        executor: &mut V,
+        isi: &Hold<Numeric, Asset>,
+    ) {
+        execute_restrict_asset(executor, isi.destination(), isi, true);
+    }
+
+    pub fn visit_release_asset_numeric<V: Execute + Visit + ?Sized>(
+        executor: &mut V,
+        isi: &Release<Numeric, Asset>,
+    ) {
+        execute_restrict_asset(executor, isi.destination(), isi, false);
+    }
 }
 
 pub mod nft {
@@ -1508,6 +1644,7 @@ pub mod trigger {
             }
             AnyPermission::CanRegisterTrigger(_)
             | AnyPermission::CanManagePeers(_)
+            | AnyPermission::CanPrioritizeTransactions(_)
             | AnyPermission::CanRegisterDomain(_)
             | AnyPermission::CanUnregisterDomain(_)
             | AnyPermission::CanModifyDomainMetadata(_)
@@ -1523,6 +1660,8 @@ pub mod trigger {
             | AnyPermission::CanMintAsset(_)
             | AnyPermission::CanBurnAsset(_)
             | AnyPermission::CanTransferAsset(_)
+            | AnyPermission::CanFreezeAssetWithDefinition(_)
+            | AnyPermission::CanFreezeAsset(_)
             | AnyPermission::CanSetParameters(_)
             | AnyPermission::CanManageRoles(_)
             | AnyPermission::CanRegisterNft(_)
@@ -108,6 +108,8 @@ declare_permissions! {
     iroha_executor_data_model::permission::asset::{CanMintAsset},
     iroha_executor_data_model::permission::asset::{CanBurnAsset},
     iroha_executor_data_model::permission::asset::{CanTransferAsset},
+    iroha_executor_data_model::permission::asset::{CanFreezeAssetWithDefinition},
+    iroha_executor_data_model::permission::asset::{CanFreezeAsset},
 
     iroha_executor_data_model::permission::nft::{CanRegisterNft},
     iroha_executor_data_model::permission::nft::{CanUnregisterNft},
@@ -123,6 +125,8 @@ declare_permissions! {
     iroha_executor_data_model::permission::trigger::{CanExecuteTrigger},
     iroha_executor_data_model::permission::trigger::{CanModifyTriggerMetadata},
 
+    iroha_executor_data_model::permission::transaction::{CanPrioritizeTransactions},
+
     iroha_executor_data_model::permission::executor::{CanUpgradeExecutor},
 }
 
@@ -225,6 +229,26 @@ mod peer {
     }
 }
 
+mod transaction {
+    use iroha_executor_data_model::permission::transaction::CanPrioritizeTransactions;
+
+    use super::*;
+
+    impl ValidateGrantRevoke for CanPrioritizeTransactions {
+        fn validate_grant(&self, authority: &AccountId, context: &Context, host: &Iroha) -> Result {
+            OnlyGenesis::from(self).validate(authority, host, context)
+        }
+        fn validate_revoke(
+            &self,
+            authority: &AccountId,
+            context: &Context,
+            host: &Iroha,
+        ) -> Result {
+            OnlyGenesis::from(self).validate(authority, host, context)
+        }
+    }
+}
+
 mod role {
     use iroha_executor_data_model::permission::role::CanManageRoles;
 
@@ -290,8 +314,8 @@ pub mod asset {
     //! Module with pass conditions for asset related tokens
 
     use iroha_executor_data_model::permission::asset::{
-        CanBurnAsset, CanBurnAssetWithDefinition, CanMintAsset, CanMintAssetWithDefinition,
-        CanTransferAsset, CanTransferAssetWithDefinition,
+        CanBurnAsset, CanBurnAssetWithDefinition, CanFreezeAsset, CanFreezeAssetWithDefinition,
+        CanMintAsset, CanMintAssetWithDefinition, CanTransferAsset, CanTransferAssetWithDefinition,
     };
 
     use super::*;
@@ -412,6 +436,42 @@ pub mod asset {
         }
     }
 
+    impl ValidateGrantRevoke for CanFreezeAssetWithDefinition {
+        fn validate_grant(&self, authority: &AccountId, context: &Context, host: &Iroha) -> Result {
+            super::asset_definition::Owner::from(self).validate(authority, host, context)
+        }
+        fn validate_revoke(
+            &self,
+            authority: &AccountId,
+            context: &Context,
+            host: &Iroha,
+        ) -> Result {
+            super::asset_definition::Owner::from(self).validate(authority, host, context)
+        }
+    }
+
+    // Freezing is an issuer capability, so even for a single asset
+    // it is the asset definition owner who hands it out.
+    impl ValidateGrantRevoke for CanFreezeAsset {
+        fn validate_grant(&self, authority: &AccountId, context: &Context, host: &Iroha) -> Result {
+            super::asset_definition::Owner {
+                asset_definition: self.asset.definition(),
+            }
+            .validate(authority, host, context)
+        }
+        fn validate_revoke(
+            &self,
+            authority: &AccountId,
+            context: &Context,
+            host: &Iroha,
+        ) -> Result {
+            super::asset_definition::Owner {
+                asset_definition: self.asset.definition(),
+            }
+            .validate(authority, host, context)
+        }
+    }
+
     macro_rules! impl_froms {
         ($($name:ty),+ $(,)?) => {$(
             impl<'t> From<&'t $name> for Owner<'t> {
@@ -557,6 +617,7 @@ pub mod asset_definition {
         iroha_executor_data_model::permission::asset::CanMintAssetWithDefinition,
         iroha_executor_data_model::permission::asset::CanBurnAssetWithDefinition,
         iroha_executor_data_model::permission::asset::CanTransferAssetWithDefinition,
+        iroha_executor_data_model::permission::asset::CanFreezeAssetWithDefinition,
     );
 }
 
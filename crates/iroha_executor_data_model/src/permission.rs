@@ -143,6 +143,18 @@ pub mod asset {
             pub asset: AssetId,
         }
     }
+
+    permission! {
+        pub struct CanFreezeAssetWithDefinition {
+            pub asset_definition: AssetDefinitionId,
+        }
+    }
+
+    permission! {
+        pub struct CanFreezeAsset {
+            pub asset: AssetId,
+        }
+    }
 }
 
 pub mod nft {
@@ -225,6 +237,15 @@ pub mod role {
     }
 }
 
+pub mod transaction {
+    use super::*;
+
+    permission! {
+        #[derive(Copy)]
+        pub struct CanPrioritizeTransactions;
+    }
+}
+
 pub mod executor {
     use super::*;
 
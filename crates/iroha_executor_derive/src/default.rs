@@ -129,6 +129,10 @@ pub fn impl_derive_visit(emitter: &mut Emitter, input: &syn::DeriveInput) -> Tok
         "fn visit_mint_asset_numeric(operation: &Mint<Numeric, Asset>)",
         "fn visit_burn_asset_numeric(operation: &Burn<Numeric, Asset>)",
         "fn visit_transfer_asset_numeric(operation: &Transfer<Asset, Numeric, Account>)",
+        "fn visit_freeze_asset(operation: &Freeze<Asset>)",
+        "fn visit_unfreeze_asset(operation: &Unfreeze<Asset>)",
+        "fn visit_hold_asset_numeric(operation: &Hold<Numeric, Asset>)",
+        "fn visit_release_asset_numeric(operation: &Release<Numeric, Asset>)",
         "fn visit_transfer_nft(operation: &Transfer<Account, NftId, Account>)",
         "fn visit_set_nft_key_value(operation: &SetKeyValue<Nft>)",
         "fn visit_remove_nft_key_value(operation: &RemoveKeyValue<Nft>)",
@@ -139,6 +143,7 @@ pub fn impl_derive_visit(emitter: &mut Emitter, input: &syn::DeriveInput) -> Tok
         "fn visit_transfer_asset_definition(operation: &Transfer<Account, AssetDefinitionId, Account>)",
         "fn visit_set_asset_definition_key_value(operation: &SetKeyValue<AssetDefinition>)",
         "fn visit_remove_asset_definition_key_value(operation: &RemoveKeyValue<AssetDefinition>)",
+        "fn visit_set_asset_definition_limits(operation: &SetAssetDefinitionLimits)",
         "fn visit_grant_account_permission(operation: &Grant<Permission, Account>)",
         "fn visit_revoke_account_permission(operation: &Revoke<Permission, Account>)",
         "fn visit_register_role(operation: &Register<Role>)",
@@ -60,6 +60,14 @@ ffi_type! {
         type Target = T;
     }
 }
+ffi_type! {
+    unsafe impl Transparent for core::num::NonZeroU32 {
+        type Target = u32;
+
+        validation_fn=unsafe {|target: &u32| *target != 0},
+        niche_value=0
+    }
+}
 ffi_type! {
     unsafe impl Transparent for core::num::NonZeroU64 {
         type Target = u64;
@@ -22,7 +22,8 @@ pub static GENESIS_DOMAIN_ID: LazyLock<DomainId> = LazyLock::new(|| "genesis".pa
 /// Genesis block.
 ///
 /// First transaction must contain single [`Upgrade`] instruction to set executor.
-/// Subsequent transactions can be parameter settings, instructions, topology change, in this order if they exist.
+/// Subsequent transactions can be parameter settings, instructions, topology change
+/// and peer weights, in this order if they exist.
 #[derive(Debug, Clone)]
 #[repr(transparent)]
 pub struct GenesisBlock(pub SignedBlock);
@@ -151,13 +152,20 @@ impl RawGenesisTransaction {
         let upgrade_executor = Upgrade::new(Executor::new(self.executor.try_into()?)).into();
         instructions_list.push(vec![upgrade_executor]);
 
+        let mut peer_weights = Vec::new();
         if let Some(parameters) = self.parameters {
-            let instructions = parameters
+            let (weights, parameters): (Vec<_>, Vec<_>) = parameters
                 .parameters()
-                .map(SetParameter::new)
-                .map(InstructionBox::from)
-                .collect();
-            instructions_list.push(instructions);
+                .partition(|parameter| matches!(parameter, Parameter::PeerWeight(_)));
+            peer_weights = weights;
+            if !parameters.is_empty() {
+                let instructions = parameters
+                    .into_iter()
+                    .map(SetParameter::new)
+                    .map(InstructionBox::from)
+                    .collect();
+                instructions_list.push(instructions);
+            }
         }
 
         if !self.instructions.is_empty() {
@@ -187,6 +195,16 @@ impl RawGenesisTransaction {
             instructions_list.push(instructions)
         }
 
+        // NOTE: Weights can only be assigned to the registered peers
+        if !peer_weights.is_empty() {
+            let instructions = peer_weights
+                .into_iter()
+                .map(SetParameter::new)
+                .map(InstructionBox::from)
+                .collect();
+            instructions_list.push(instructions);
+        }
+
         Ok(instructions_list)
     }
 }
@@ -5,9 +5,12 @@ use std::{
 
 use clap::{Args as ClapArgs, Subcommand};
 use color_eyre::eyre::{eyre, WrapErr as _};
-use iroha_core::kura::{BlockIndex, BlockStore};
-use iroha_data_model::block::SignedBlock;
-use iroha_version::scale::DecodeVersioned;
+use iroha_core::{
+    kura::{BlockIndex, BlockStore},
+    sumeragi::TopologyReplay,
+};
+use iroha_crypto::{HashOf, PublicKey};
+use iroha_data_model::block::{BlockHeader, SignedBlock};
 
 use crate::{Outcome, RunArgs};
 
@@ -33,6 +36,17 @@ enum Command {
         #[clap(short = 'n', long, default_value_t = 1)]
         length: u64,
     },
+    /// Check that the stored blocks are intact, chained and signed by the peers in the topology.
+    /// The topology is rebuilt by replaying the blocks, so signatures are checked
+    /// only if no blocks are pruned
+    Verify {
+        /// Public key of the genesis account, used to check the genesis block signature
+        #[clap(long)]
+        genesis_public_key: Option<PublicKey>,
+    },
+    /// Cut the block store back to the last block which is intact and chained to the previous ones.
+    /// Pruned blocks are never cut
+    Repair,
 }
 
 impl<T: Write> RunArgs<T> for Args {
@@ -55,26 +69,45 @@ impl<T: Write> RunArgs<T> for Args {
                 length,
             )
             .wrap_err("failed to print blockchain"),
+            Command::Verify { genesis_public_key } => verify_blockchain(
+                writer,
+                &args.path_to_block_store,
+                genesis_public_key.as_ref(),
+            )
+            .wrap_err("failed to verify blockchain"),
+            Command::Repair => repair_blockchain(writer, &args.path_to_block_store)
+                .wrap_err("failed to repair blockchain"),
         }
     }
 }
 
-fn print_blockchain(
-    writer: &mut dyn Write,
-    block_store_path: &Path,
-    from_height: u64,
-    block_count: u64,
-) -> Outcome {
+fn open_block_store(block_store_path: &Path) -> color_eyre::Result<BlockStore> {
     let mut block_store_path: std::borrow::Cow<'_, Path> = block_store_path.into();
 
     if let Some(os_str_file_name) = block_store_path.file_name() {
         let file_name_str = os_str_file_name.to_str().unwrap_or("");
-        if file_name_str == "blocks.data" || file_name_str == "blocks.index" {
+        if matches!(
+            file_name_str,
+            "blocks.manifest" | "blocks.index" | "blocks.hashes" | "blocks.checksums" | "segments"
+        ) {
             block_store_path.to_mut().pop();
         }
     }
 
-    let block_store = BlockStore::new(&block_store_path);
+    let mut block_store = BlockStore::new(&block_store_path);
+    block_store
+        .load_layout()
+        .wrap_err("failed to read block store manifest")?;
+    Ok(block_store)
+}
+
+fn print_blockchain(
+    writer: &mut dyn Write,
+    block_store_path: &Path,
+    from_height: u64,
+    block_count: u64,
+) -> Outcome {
+    let block_store = open_block_store(block_store_path)?;
 
     let index_count = block_store
         .read_index_count()
@@ -130,16 +163,408 @@ fn print_blockchain(
             idx.start,
             idx.length
         )?;
-        let mut block_buf =
-            vec![0_u8; usize::try_from(idx.length).wrap_err("index_len didn't fit in 32-bits")?];
-        block_store
-            .read_block_data(idx.start, &mut block_buf)
-            .wrap_err(format!("failed to read block № {} data.", meta_index + 1))?;
-        let block = SignedBlock::decode_all_versioned(&block_buf)
-            .wrap_err(format!("Failed to decode block № {}", meta_index + 1))?;
+        let block = block_store
+            .read_block_at(meta_index, idx)
+            .wrap_err(format!("failed to read block № {}", meta_index + 1))?;
         writeln!(writer, "Block#{} :", meta_index + 1)?;
         writeln!(writer, "{block:#?}")?;
     }
 
     Ok(())
 }
+
+/// Inconsistency found in the block store
+#[derive(Debug, derive_more::Display)]
+enum Problem {
+    /// Block can't be located or decoded
+    #[display(fmt = "block can't be read: {_0}")]
+    Unreadable(String),
+    /// Hash of the block is missing from the hashes file
+    #[display(fmt = "block hash is missing from the hashes file")]
+    MissingHash,
+    /// Height in the block header doesn't match the position of the block
+    #[display(fmt = "block header has height {_0}")]
+    HeightMismatch(u64),
+    /// Hash of the block doesn't match the one in the hashes file
+    #[display(fmt = "block hash {actual} doesn't match the stored hash {stored}")]
+    HashMismatch {
+        actual: HashOf<BlockHeader>,
+        stored: HashOf<BlockHeader>,
+    },
+    /// Block doesn't refer to the previous block
+    #[display(fmt = "previous block hash {actual:?} doesn't match {expected:?}")]
+    BrokenChain {
+        actual: Option<HashOf<BlockHeader>>,
+        expected: Option<HashOf<BlockHeader>>,
+    },
+    /// Block signatures don't match the topology
+    #[display(fmt = "invalid signature: {_0}")]
+    Signature(String),
+}
+
+impl Problem {
+    /// Whether the problem is caused by damaged storage rather than by the block contents
+    fn is_storage(&self) -> bool {
+        !matches!(self, Self::Signature(_))
+    }
+}
+
+/// Block store inconsistency at a certain block
+struct Inconsistency {
+    /// Height (counting from 0) of the block
+    height: u64,
+    /// Offset of the block data, if the block index is readable
+    offset: Option<u64>,
+    problem: Problem,
+}
+
+/// Walk through all the blocks stored in `block_store` and collect inconsistencies.
+///
+/// Signatures are checked only if the store keeps the whole chain starting from the genesis block
+/// and only up to the first unreadable block, since the topology can't be followed beyond it.
+fn find_inconsistencies(
+    block_store: &BlockStore,
+    genesis_public_key: Option<&PublicKey>,
+) -> color_eyre::Result<Vec<Inconsistency>> {
+    let index_count = block_store
+        .read_index_count()
+        .wrap_err("failed to read index count")?;
+    let hashes_count = block_store
+        .read_hashes_count()
+        .wrap_err("failed to read hashes count")?;
+    let hashes = block_store
+        .read_block_hashes(
+            0,
+            index_count
+                .min(hashes_count)
+                .try_into()
+                .wrap_err("hashes count didn't fit in usize")?,
+        )
+        .wrap_err("failed to read block hashes")?;
+    let first_block = block_store.first_block();
+
+    let mut inconsistencies = Vec::new();
+    let mut prev_hash = first_block
+        .checked_sub(1)
+        .and_then(|height| usize::try_from(height).ok())
+        .and_then(|height| hashes.get(height).copied());
+    // Topology is followed only if the store isn't pruned
+    let mut replay = (first_block == 0).then(TopologyReplay::new);
+
+    for height in first_block..index_count {
+        let mut report = |offset, problem| {
+            inconsistencies.push(Inconsistency {
+                height,
+                offset,
+                problem,
+            })
+        };
+        let stored_hash = usize::try_from(height)
+            .ok()
+            .and_then(|height| hashes.get(height).copied());
+        let Some(stored_hash) = stored_hash else {
+            report(None, Problem::MissingHash);
+            prev_hash = None;
+            continue;
+        };
+
+        let index = match block_store.read_block_index(height) {
+            Ok(index) => index,
+            Err(error) => {
+                report(None, Problem::Unreadable(error.to_string()));
+                prev_hash = Some(stored_hash);
+                replay = None;
+                continue;
+            }
+        };
+        let offset = Some(index.start);
+        let block = match block_store.read_block_at(height, index) {
+            Ok(block) => block,
+            Err(error) => {
+                report(offset, Problem::Unreadable(error.to_string()));
+                prev_hash = Some(stored_hash);
+                replay = None;
+                continue;
+            }
+        };
+
+        let header = block.header();
+        if header.height().get() != height + 1 {
+            report(offset, Problem::HeightMismatch(header.height().get()));
+        }
+        if block.hash() != stored_hash {
+            report(
+                offset,
+                Problem::HashMismatch {
+                    actual: block.hash(),
+                    stored: stored_hash,
+                },
+            );
+        }
+        if header.prev_block_hash() != prev_hash {
+            report(
+                offset,
+                Problem::BrokenChain {
+                    actual: header.prev_block_hash(),
+                    expected: prev_hash,
+                },
+            );
+        }
+        prev_hash = Some(stored_hash);
+
+        let Some(replay) = replay.as_mut() else {
+            continue;
+        };
+        let commit_topology = replay.replay(&block);
+        if header.is_genesis() {
+            if let Some(genesis_public_key) = genesis_public_key {
+                if let Err(error) =
+                    verify_signatures(&block, std::slice::from_ref(genesis_public_key))
+                {
+                    report(offset, Problem::Signature(error));
+                }
+            }
+        } else if let Some(commit_topology) = commit_topology {
+            let public_keys = commit_topology
+                .into_iter()
+                .map(|peer| peer.public_key().clone())
+                .collect::<Vec<_>>();
+            if let Err(error) = verify_signatures(&block, &public_keys) {
+                report(offset, Problem::Signature(error));
+            }
+        } else {
+            report(
+                offset,
+                Problem::Signature("no peers are registered in the genesis block".to_owned()),
+            );
+        }
+    }
+
+    if hashes_count > index_count {
+        inconsistencies.push(Inconsistency {
+            height: index_count,
+            offset: None,
+            problem: Problem::Unreadable(format!(
+                "hashes file has {hashes_count} hashes, but index file has {index_count} blocks"
+            )),
+        });
+    }
+
+    Ok(inconsistencies)
+}
+
+/// Check that every signature of the `block` is made by the peer at its index in the topology.
+fn verify_signatures(block: &SignedBlock, public_keys: &[PublicKey]) -> Result<(), String> {
+    if let Some(certificate) = block.commit_certificate() {
+        let signatories = certificate
+            .signatories()
+            .map(|index| {
+                usize::try_from(index)
+                    .ok()
+                    .and_then(|index| public_keys.get(index))
+                    .ok_or_else(|| format!("unknown signatory {index}"))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        return certificate
+            .signature()
+            .verify_aggregate_hash(signatories, block.header().hash())
+            .map_err(|_err| "commit certificate doesn't match".to_owned());
+    }
+    if block.signatures().next().is_none() {
+        return Err("block is not signed".to_owned());
+    }
+    for signature in block.signatures() {
+        let public_key = usize::try_from(signature.index)
+            .ok()
+            .and_then(|index| public_keys.get(index))
+            .ok_or_else(|| format!("unknown signatory {}", signature.index))?;
+        signature
+            .signature
+            .verify_hash(public_key, block.header().hash())
+            .map_err(|_err| format!("signature of the peer {} doesn't match", signature.index))?;
+    }
+    Ok(())
+}
+
+fn verify_blockchain(
+    writer: &mut dyn Write,
+    block_store_path: &Path,
+    genesis_public_key: Option<&PublicKey>,
+) -> Outcome {
+    let block_store = open_block_store(block_store_path)?;
+    if block_store.first_block() > 0 {
+        writeln!(
+            writer,
+            "Blocks 1-{} are pruned, signatures are not checked.",
+            block_store.first_block()
+        )?;
+    }
+
+    let inconsistencies = find_inconsistencies(&block_store, genesis_public_key)?;
+    for Inconsistency {
+        height,
+        offset,
+        problem,
+    } in &inconsistencies
+    {
+        match offset {
+            Some(offset) => writeln!(
+                writer,
+                "Block#{} at byte offset {offset}: {problem}",
+                height + 1
+            )?,
+            None => writeln!(writer, "Block#{}: {problem}", height + 1)?,
+        }
+    }
+
+    if inconsistencies.is_empty() {
+        writeln!(writer, "Block store is consistent.")?;
+        Ok(())
+    } else {
+        Err(eyre!(
+            "found {} inconsistencies in the block store",
+            inconsistencies.len()
+        ))
+    }
+}
+
+fn repair_blockchain(writer: &mut dyn Write, block_store_path: &Path) -> Outcome {
+    let block_store = open_block_store(block_store_path)?;
+    let index_count = block_store
+        .read_index_count()
+        .wrap_err("failed to read index count")?;
+
+    let Some(height) = find_inconsistencies(&block_store, None)?
+        .into_iter()
+        .find(|inconsistency| inconsistency.problem.is_storage())
+        .map(|inconsistency| inconsistency.height)
+    else {
+        writeln!(writer, "Block store is consistent, nothing to repair.")?;
+        return Ok(());
+    };
+    let first_block = block_store.first_block();
+    if first_block > 0 && height <= first_block {
+        return Err(eyre!(
+            "block #{} is the first one kept after pruning and it is damaged, \
+             cutting the store would leave no blocks. \
+             Restore the store from a snapshot or another peer instead",
+            height + 1
+        ));
+    }
+
+    block_store
+        .prune(height)
+        .wrap_err("failed to cut the block store")?;
+    writeln!(
+        writer,
+        "Block store is cut back to {height} blocks, {} blocks are removed.",
+        index_count.saturating_sub(height)
+    )?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use iroha_core::{block::BlockBuilder, tx::AcceptedTransaction};
+    use iroha_data_model::{isi::Log, transaction::TransactionBuilder, ChainId, Level};
+    use iroha_test_samples::{SAMPLE_GENESIS_ACCOUNT_ID, SAMPLE_GENESIS_ACCOUNT_KEYPAIR};
+    use tempfile::TempDir;
+
+    use super::*;
+
+    fn blocks(count: usize) -> Vec<SignedBlock> {
+        let mut blocks: Vec<SignedBlock> = Vec::with_capacity(count);
+        for _ in 0..count {
+            let tx = TransactionBuilder::new(
+                ChainId::from("test"),
+                SAMPLE_GENESIS_ACCOUNT_ID.to_owned(),
+            )
+            .with_instructions([Log::new(Level::INFO, "test".to_owned())])
+            .sign(SAMPLE_GENESIS_ACCOUNT_KEYPAIR.private_key());
+            let block = BlockBuilder::new(vec![AcceptedTransaction::new_unchecked(tx)])
+                .chain(0, blocks.last())
+                .sign(SAMPLE_GENESIS_ACCOUNT_KEYPAIR.private_key())
+                .unpack(|_| {})
+                .into();
+            blocks.push(block);
+        }
+        blocks
+    }
+
+    /// Store of `count` blocks with the block at `corrupted_height` damaged.
+    fn corrupted_store(
+        count: usize,
+        segment_size: u64,
+        corrupted_height: u64,
+    ) -> color_eyre::Result<TempDir> {
+        let temp = TempDir::new()?;
+        let mut block_store = BlockStore::new(temp.path()).with_segment_size(segment_size);
+        block_store.create_files_if_they_do_not_exist()?;
+        for block in blocks(count) {
+            block_store.append_block_to_chain(&block)?;
+        }
+
+        let BlockIndex { start, length } = block_store.read_block_index(corrupted_height)?;
+        let mut data = vec![0_u8; length.try_into()?];
+        block_store.read_block_data(start, &mut data)?;
+        data[data.len() / 2] ^= 0xFF;
+        block_store.write_block_data(start, &data)?;
+
+        Ok(temp)
+    }
+
+    #[test]
+    fn verify_reports_corrupted_block() -> color_eyre::Result<()> {
+        let temp = corrupted_store(4, 1 << 20, 2)?;
+        let block_store = open_block_store(temp.path())?;
+
+        let storage_problems = find_inconsistencies(&block_store, None)?
+            .into_iter()
+            .filter(|inconsistency| inconsistency.problem.is_storage())
+            .collect::<Vec<_>>();
+
+        assert_eq!(storage_problems.len(), 1);
+        assert_eq!(storage_problems[0].height, 2);
+        assert!(matches!(
+            storage_problems[0].problem,
+            Problem::Unreadable(_)
+        ));
+        assert!(verify_blockchain(&mut Vec::new(), temp.path(), None).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn repair_cuts_store_before_corrupted_block() -> color_eyre::Result<()> {
+        let temp = corrupted_store(4, 1 << 20, 2)?;
+
+        repair_blockchain(&mut Vec::new(), temp.path())?;
+
+        let block_store = open_block_store(temp.path())?;
+        assert_eq!(block_store.read_index_count()?, 2);
+        assert_eq!(block_store.read_hashes_count()?, 2);
+        assert!(find_inconsistencies(&block_store, None)?
+            .iter()
+            .all(|inconsistency| !inconsistency.problem.is_storage()));
+
+        Ok(())
+    }
+
+    #[test]
+    fn repair_keeps_pruned_blocks() -> color_eyre::Result<()> {
+        // Every block takes a segment on its own, so that blocks can be pruned one by one
+        let temp = corrupted_store(4, 1, 2)?;
+        let mut block_store = open_block_store(temp.path())?;
+        assert_eq!(block_store.prune_below(2)?, 2);
+
+        assert!(repair_blockchain(&mut Vec::new(), temp.path()).is_err());
+        assert_eq!(block_store.read_index_count()?, 4);
+        assert!(matches!(
+            block_store.prune(1),
+            Err(iroha_core::kura::Error::CutPrunedBlocks(1))
+        ));
+
+        Ok(())
+    }
+}
@@ -12,6 +12,7 @@ mod crypto;
 mod genesis;
 mod kura;
 mod schema;
+mod snapshot;
 mod swarm;
 mod wasm;
 
@@ -50,6 +51,8 @@ enum Args {
     Codec(codec::Args),
     /// Commands related to block inspection
     Kura(kura::Args),
+    /// Commands related to state snapshots
+    Snapshot(snapshot::Args),
     /// Commands related to Docker Compose configuration generation
     Swarm(swarm::Args),
     /// Commands related to building wasm smartcontracts
@@ -69,6 +72,7 @@ impl<T: Write> RunArgs<T> for Args {
             Genesis(args) => args.run(writer),
             Codec(args) => args.run(writer),
             Kura(args) => args.run(writer),
+            Snapshot(args) => args.run(writer),
             Swarm(args) => args.run(writer),
             Wasm(args) => args.run(writer),
             MarkdownHelp(args) => args.run(writer),
@@ -0,0 +1,44 @@
+use std::{
+    io::{BufWriter, Write},
+    path::PathBuf,
+};
+
+use clap::{Args as ClapArgs, Subcommand};
+use color_eyre::eyre::WrapErr as _;
+use iroha_core::snapshot::migrate_legacy_snapshot;
+
+use crate::{Outcome, RunArgs};
+
+/// State snapshot tools
+#[derive(Debug, ClapArgs, Clone)]
+pub struct Args {
+    /// Path to the directory where snapshots are stored
+    #[clap()]
+    store_dir: PathBuf,
+    #[clap(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand, Debug, Clone)]
+enum Command {
+    /// Rewrite a snapshot stored in the legacy JSON format into the binary format
+    Migrate,
+}
+
+impl<T: Write> RunArgs<T> for Args {
+    fn run(self, writer: &mut BufWriter<T>) -> Outcome {
+        match self.command {
+            Command::Migrate => {
+                let migrated = migrate_legacy_snapshot(&self.store_dir).wrap_err_with(|| {
+                    format!("failed to migrate snapshot in {}", self.store_dir.display())
+                })?;
+                if migrated {
+                    writeln!(writer, "Snapshot is migrated to the binary format.")?;
+                } else {
+                    writeln!(writer, "Snapshot is already in the binary format.")?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
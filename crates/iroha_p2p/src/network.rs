@@ -81,6 +81,7 @@ impl<T: Pload, K: Kex + Sync, E: Enc + Sync> NetworkBaseHandle<T, K, E> {
             address: listen_addr,
             public_address,
             idle_timeout,
+            read_replicas,
         }: Config,
         shutdown_signal: ShutdownSignal,
     ) -> Result<(Self, Child), Error> {
@@ -116,6 +117,7 @@ impl<T: Pload, K: Kex + Sync, E: Enc + Sync> NetworkBaseHandle<T, K, E> {
             current_conn_id: 0,
             current_topology: HashSet::new(),
             current_peers_addresses: Vec::new(),
+            read_replicas: read_replicas.into_iter().map(PeerId::new).collect(),
             idle_timeout,
             _key_exchange: core::marker::PhantomData::<K>,
             _encryptor: core::marker::PhantomData::<E>,
@@ -244,6 +246,10 @@ struct NetworkBase<T: Pload, K: Kex, E: Enc> {
     ///
     /// Will try to establish connection via both addresses.
     current_peers_addresses: Vec<(PeerId, SocketAddr)>,
+    /// Read-replica peers which are allowed to connect without being in the topology.
+    ///
+    /// They are never dialed and don't receive broadcasts.
+    read_replicas: HashSet<PeerId>,
     /// Duration after which terminate connection with idle peer
     idle_timeout: Duration,
     /// Key exchange used by network
@@ -381,7 +387,7 @@ impl<T: Pload, K: Kex, E: Enc> NetworkBase<T, K, E> {
             .peers
             .keys()
             // Peer is connected but shouldn't
-            .filter(|&peer_id| !self.current_topology.contains(peer_id))
+            .filter(|&peer_id| !self.is_allowed(peer_id))
             .cloned()
             .collect::<Vec<_>>();
 
@@ -394,6 +400,10 @@ impl<T: Pload, K: Kex, E: Enc> NetworkBase<T, K, E> {
         }
     }
 
+    fn is_allowed(&self, peer_id: &PeerId) -> bool {
+        self.current_topology.contains(peer_id) || self.read_replicas.contains(peer_id)
+    }
+
     fn connect_peer(&mut self, peer: &Peer) {
         iroha_logger::trace!(
             listen_addr = %self.listen_addr, peer.id.address = %peer.address(),
@@ -437,7 +447,7 @@ impl<T: Pload, K: Kex, E: Enc> NetworkBase<T, K, E> {
     ) {
         self.connecting_peers.remove(&connection_id);
 
-        if !self.current_topology.contains(peer.id()) {
+        if !self.is_allowed(peer.id()) {
             iroha_logger::warn!(peer=%peer.id(), topology=?self.current_topology, "Peer not present in topology is trying to connect");
             return;
         }
@@ -505,9 +515,14 @@ impl<T: Pload, K: Kex, E: Enc> NetworkBase<T, K, E> {
         let Self {
             peers,
             online_peers_sender,
+            current_topology,
             ..
         } = self;
         peers.retain(|public_key, ref_peer| {
+            // Read replicas only follow the chain via block sync
+            if !current_topology.contains(public_key) {
+                return true;
+            }
             if ref_peer.handle.post(data.clone()).is_err() {
                 let peer = Peer::new(ref_peer.p2p_addr.clone(), public_key.clone());
                 iroha_logger::error!(peer=%peer, "Failed to send message to peer");
@@ -46,6 +46,7 @@ async fn network_create() {
         address: WithOrigin::inline(address.clone()),
         public_address: WithOrigin::inline(address.clone()),
         idle_timeout,
+        read_replicas: Vec::new(),
     };
     let (network, _) = NetworkHandle::start(key_pair, config, ShutdownSignal::new())
         .await
@@ -159,6 +160,7 @@ async fn two_networks() {
         address: WithOrigin::inline(address1.clone()),
         public_address: WithOrigin::inline(address1.clone()),
         idle_timeout,
+        read_replicas: Vec::new(),
     };
     let (mut network1, _) = NetworkHandle::start(key_pair1, config1, ShutdownSignal::new())
         .await
@@ -170,6 +172,7 @@ async fn two_networks() {
         address: WithOrigin::inline(address2.clone()),
         public_address: WithOrigin::inline(address2.clone()),
         idle_timeout,
+        read_replicas: Vec::new(),
     };
     let (network2, _) = NetworkHandle::start(key_pair2, config2, ShutdownSignal::new())
         .await
@@ -305,6 +308,7 @@ async fn start_network(
         address: WithOrigin::inline(address.clone()),
         public_address: WithOrigin::inline(address.clone()),
         idle_timeout,
+        read_replicas: Vec::new(),
     };
     let (mut network, _) = NetworkHandle::start(key_pair, config, shutdown_signal)
         .await
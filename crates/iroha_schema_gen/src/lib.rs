@@ -59,6 +59,10 @@ pub fn build_schemas() -> MetaMap {
         BlockMessage,
         BlockSubscriptionRequest,
 
+        // Query subscription
+        QuerySubscriptionMessage,
+        QuerySubscriptionRequest,
+
         // Never referenced, but present in type signature. Like `PhantomData<X>`
         MerkleTree<SignedTransaction>,
 
@@ -83,6 +87,8 @@ pub fn build_schemas() -> MetaMap {
         permission::asset::CanMintAsset,
         permission::asset::CanBurnAsset,
         permission::asset::CanTransferAsset,
+        permission::asset::CanFreezeAssetWithDefinition,
+        permission::asset::CanFreezeAsset,
 
         permission::nft::CanRegisterNft,
         permission::nft::CanUnregisterNft,
@@ -98,6 +104,8 @@ pub fn build_schemas() -> MetaMap {
         permission::trigger::CanModifyTrigger,
         permission::trigger::CanModifyTriggerMetadata,
 
+        permission::transaction::CanPrioritizeTransactions,
+
         permission::executor::CanUpgradeExecutor,
 
         // Multi-signature operations
@@ -129,10 +137,39 @@ types!(
     AccountProjection<PredicateMarker>,
     AccountProjection<SelectorMarker>,
     AccountRoleChanged,
+    AccountSequenceChanged,
     Action,
     ActionPredicateAtom,
     ActionProjection<PredicateMarker>,
     ActionProjection<SelectorMarker>,
+    Aggregate<Account>,
+    Aggregate<AssetDefinition>,
+    Aggregate<Asset>,
+    Aggregate<BlockHeader>,
+    Aggregate<CommittedTransaction>,
+    Aggregate<Domain>,
+    Aggregate<Nft>,
+    Aggregate<PeerId>,
+    Aggregate<Permission>,
+    Aggregate<RoleId>,
+    Aggregate<Role>,
+    Aggregate<SignedBlock>,
+    Aggregate<TriggerId>,
+    Aggregate<Trigger>,
+    AggregateFunction<Account>,
+    AggregateFunction<AssetDefinition>,
+    AggregateFunction<Asset>,
+    AggregateFunction<BlockHeader>,
+    AggregateFunction<CommittedTransaction>,
+    AggregateFunction<Domain>,
+    AggregateFunction<Nft>,
+    AggregateFunction<PeerId>,
+    AggregateFunction<Permission>,
+    AggregateFunction<RoleId>,
+    AggregateFunction<Role>,
+    AggregateFunction<SignedBlock>,
+    AggregateFunction<TriggerId>,
+    AggregateFunction<Trigger>,
     Algorithm,
     Asset,
     AssetChanged,
@@ -162,10 +199,12 @@ types!(
     BTreeMap<AccountId, u8>,
     BTreeMap<CustomParameterId, CustomParameter>,
     BTreeMap<Name, Json>,
+    BTreeMap<PeerId, NonZeroU64>,
     BTreeSet<AccountId>,
     BTreeSet<Permission>,
     BTreeSet<BlockSignature>,
     BTreeSet<String>,
+    BlockCommitCertificate,
     BlockEvent,
     BlockEventFilter,
     BlockHeaderHashPredicateAtom,
@@ -287,6 +326,7 @@ types!(
     FindTransactions,
     FindTriggers,
     ForwardCursor,
+    Freeze<Asset>,
     GenesisWasmAction,
     GenesisWasmTrigger,
     Grant<Permission, Account>,
@@ -295,12 +335,14 @@ types!(
     GrantBox,
     Hash,
     HashOf<BlockHeader>,
+    HashOf<LeaderSeed>,
     HashOf<MerkleTree<TransactionEntrypoint>>,
     HashOf<MerkleTree<TransactionResult>>,
     HashOf<SignedTransaction>,
     HashOf<TransactionEntrypoint>,
     HashOf<TransactionResult>,
     HashOf<Vec<InstructionBox>>,
+    Hold<Numeric, Asset>,
     IdBox,
     InstructionBox,
     InstructionEvaluationError,
@@ -312,9 +354,12 @@ types!(
     Ipv6Addr,
     Ipv4Addr,
     Json,
+    JsonPointerPredicate,
     JsonPredicateAtom,
     JsonProjection<PredicateMarker>,
     JsonProjection<SelectorMarker>,
+    JsonValuePredicate,
+    LeaderSeed,
     Level,
     Log,
     MathError,
@@ -368,26 +413,46 @@ types!(
     NumericProjection<PredicateMarker>,
     NumericProjection<SelectorMarker>,
     NumericSpec,
+    Option<Aggregate<Account>>,
+    Option<Aggregate<AssetDefinition>>,
+    Option<Aggregate<Asset>>,
+    Option<Aggregate<BlockHeader>>,
+    Option<Aggregate<CommittedTransaction>>,
+    Option<Aggregate<Domain>>,
+    Option<Aggregate<Nft>>,
+    Option<Aggregate<PeerId>>,
+    Option<Aggregate<Permission>>,
+    Option<Aggregate<RoleId>>,
+    Option<Aggregate<Role>>,
+    Option<Aggregate<SignedBlock>>,
+    Option<Aggregate<TriggerId>>,
+    Option<Aggregate<Trigger>>,
     Option<AccountId>,
     Option<AssetDefinitionId>,
     Option<AssetId>,
+    Option<BlockCommitCertificate>,
     Option<BlockStatus>,
     Option<DomainId>,
     Option<ForwardCursor>,
     Option<HashOf<BlockHeader>>,
+    Option<HashOf<LeaderSeed>>,
     Option<HashOf<MerkleTree<TransactionEntrypoint>>>,
     Option<HashOf<MerkleTree<TransactionResult>>>,
     Option<HashOf<SignedTransaction>>,
     Option<HashOf<TransactionEntrypoint>>,
     Option<HashOf<TransactionResult>>,
     Option<IpfsPath>,
+    Option<Json>,
+    Option<LeaderSeed>,
     Option<Name>,
     Option<NftId>,
+    Option<Numeric>,
     Option<NonZeroU32>,
     Option<NonZeroU64>,
     Option<Option<NonZeroU64>>,
     Option<Parameters>,
     Option<PeerId>,
+    Option<QueryContinuation>,
     Option<RoleId>,
     Option<TransactionStatus>,
     Option<TriggerCompletedOutcomeType>,
@@ -407,6 +472,7 @@ types!(
     PeerIdPredicateAtom,
     PeerIdProjection<PredicateMarker>,
     PeerIdProjection<SelectorMarker>,
+    PeerWeight,
     Permission,
     PermissionPredicateAtom,
     PermissionProjection<PredicateMarker>,
@@ -418,6 +484,7 @@ types!(
     PublicKeyProjection<PredicateMarker>,
     PublicKeyProjection<SelectorMarker>,
     QueryBox,
+    QueryContinuation,
     QueryExecutionFail,
     QueryOutput,
     QueryOutputBatchBox,
@@ -426,7 +493,10 @@ types!(
     QueryRequest,
     QueryRequestWithAuthority,
     QueryResponse,
+    QueryResultsDelta,
     QuerySignature,
+    QuerySubscriptionMessage,
+    QuerySubscriptionRequest,
     QueryWithFilter<FindAccounts>,
     QueryWithFilter<FindAccountsWithAsset>,
     QueryWithFilter<FindActiveTriggerIds>,
@@ -444,6 +514,7 @@ types!(
     QueryWithFilter<FindTransactions>,
     QueryWithFilter<FindTriggers>,
     QueryWithParams,
+    RangeInclusive<Numeric>,
     Register<Account>,
     Register<AssetDefinition>,
     Register<Domain>,
@@ -452,6 +523,7 @@ types!(
     Register<Role>,
     Register<Trigger>,
     RegisterBox,
+    Release<Numeric, Asset>,
     RemoveKeyValue<Account>,
     RemoveKeyValue<AssetDefinition>,
     RemoveKeyValue<Domain>,
@@ -491,6 +563,8 @@ types!(
     SelectorTuple<SignedBlock>,
     SelectorTuple<TriggerId>,
     SelectorTuple<Trigger>,
+    SequenceMismatch,
+    SetAssetDefinitionLimits,
     SetKeyValue<Account>,
     SetKeyValue<AssetDefinition>,
     SetKeyValue<Domain>,
@@ -519,6 +593,21 @@ types!(
     SocketAddrHost,
     SocketAddrV4,
     SocketAddrV6,
+    SortKey<Account>,
+    SortKey<AssetDefinition>,
+    SortKey<Asset>,
+    SortKey<BlockHeader>,
+    SortKey<CommittedTransaction>,
+    SortKey<Domain>,
+    SortKey<Nft>,
+    SortKey<PeerId>,
+    SortKey<Permission>,
+    SortKey<RoleId>,
+    SortKey<Role>,
+    SortKey<SignedBlock>,
+    SortKey<TriggerId>,
+    SortKey<Trigger>,
+    SortOrder,
     Sorting,
     Status,
     String,
@@ -577,6 +666,7 @@ types!(
     TriggerProjection<SelectorMarker>,
     DataTriggerSequence,
     TypeError,
+    Unfreeze<Asset>,
     Unregister<Account>,
     Unregister<AssetDefinition>,
     Unregister<Domain>,
@@ -628,6 +718,20 @@ types!(
     Vec<RoleId>,
     Vec<SignedBlock>,
     Vec<SignedTransaction>,
+    Vec<SortKey<Account>>,
+    Vec<SortKey<AssetDefinition>>,
+    Vec<SortKey<Asset>>,
+    Vec<SortKey<BlockHeader>>,
+    Vec<SortKey<CommittedTransaction>>,
+    Vec<SortKey<Domain>>,
+    Vec<SortKey<Nft>>,
+    Vec<SortKey<PeerId>>,
+    Vec<SortKey<Permission>>,
+    Vec<SortKey<RoleId>>,
+    Vec<SortKey<Role>>,
+    Vec<SortKey<SignedBlock>>,
+    Vec<SortKey<TriggerId>>,
+    Vec<SortKey<Trigger>>,
     Vec<AccountProjection<SelectorMarker>>,
     Vec<AssetDefinitionProjection<SelectorMarker>>,
     Vec<AssetProjection<SelectorMarker>>,
@@ -676,7 +780,10 @@ types!(
 pub mod complete_data_model {
     //! Complete set of types participating in the schema
 
-    pub use core::num::{NonZeroU16, NonZeroU32, NonZeroU64};
+    pub use core::{
+        num::{NonZeroU16, NonZeroU32, NonZeroU64},
+        ops::RangeInclusive,
+    };
     pub use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 
     pub use iroha_crypto::*;
@@ -686,7 +793,8 @@ pub mod complete_data_model {
         block::{
             error::BlockRejectionReason,
             stream::{BlockMessage, BlockSubscriptionRequest},
-            BlockHeader, BlockPayload, BlockResult, BlockSignature, SignedBlock, SignedBlockV1,
+            BlockCommitCertificate, BlockHeader, BlockPayload, BlockResult, BlockSignature,
+            LeaderSeed, SignedBlock, SignedBlockV1,
         },
         domain::NewDomain,
         events::pipeline::{BlockEventFilter, TransactionEventFilter},
@@ -701,14 +809,17 @@ pub mod complete_data_model {
         },
         parameter::{
             BlockParameter, BlockParameters, CustomParameter, CustomParameterId, Parameter,
-            Parameters, SmartContractParameter, SmartContractParameters, SumeragiParameter,
-            SumeragiParameters, TransactionParameter, TransactionParameters,
+            Parameters, PeerWeight, SmartContractParameter, SmartContractParameters,
+            SumeragiParameter, SumeragiParameters, TransactionParameter, TransactionParameters,
         },
         prelude::*,
         query::{
-            dsl::{CompoundPredicate, PredicateMarker, SelectorMarker},
+            dsl::{
+                Aggregate, AggregateFunction, CompoundPredicate, PredicateMarker, SelectorMarker,
+                SortKey,
+            },
             error::{FindError, QueryExecutionFail},
-            parameters::{ForwardCursor, QueryParams},
+            parameters::{ForwardCursor, QueryContinuation, QueryParams},
             CommittedTransaction, QueryOutput, QueryOutputBatchBox, QueryOutputBatchBoxTuple,
             QueryRequestWithAuthority, QueryResponse, QuerySignature, QueryWithFilter,
             QueryWithParams, SignedQuery, SignedQueryV1, SingularQueryOutputBox,
@@ -792,6 +903,10 @@ mod tests {
         insert_into_test_map!(iroha_executor_data_model::permission::asset::CanMintAsset);
         insert_into_test_map!(iroha_executor_data_model::permission::asset::CanBurnAsset);
         insert_into_test_map!(iroha_executor_data_model::permission::asset::CanTransferAsset);
+        insert_into_test_map!(
+            iroha_executor_data_model::permission::asset::CanFreezeAssetWithDefinition
+        );
+        insert_into_test_map!(iroha_executor_data_model::permission::asset::CanFreezeAsset);
 
         insert_into_test_map!(iroha_executor_data_model::permission::nft::CanRegisterNft);
         insert_into_test_map!(iroha_executor_data_model::permission::nft::CanUnregisterNft);
@@ -808,6 +923,9 @@ mod tests {
         insert_into_test_map!(
             iroha_executor_data_model::permission::trigger::CanModifyTriggerMetadata
         );
+        insert_into_test_map!(
+            iroha_executor_data_model::permission::transaction::CanPrioritizeTransactions
+        );
         insert_into_test_map!(iroha_executor_data_model::permission::executor::CanUpgradeExecutor);
 
         insert_into_test_map!(iroha_executor_data_model::isi::multisig::MultisigInstructionBox);
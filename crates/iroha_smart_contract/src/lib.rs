@@ -271,6 +271,8 @@ mod tests {
                 FindAssets,
                 CompoundPredicate::<Asset>::build(|asset| asset.id.eq(asset_id)),
                 SelectorTuple::<Asset>::build(|asset| asset.value),
+                None,
+                vec![],
             )),
             QueryParams::default(),
         )
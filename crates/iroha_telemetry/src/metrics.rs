@@ -157,6 +157,8 @@ pub struct Metrics {
     pub queue_size: GenericGauge<AtomicU64>,
     /// Number of sumeragi dropped messages
     pub dropped_messages: DroppedMessagesCounter,
+    /// Number of requests rejected by the Torii rate limits
+    pub rejected_requests: IntCounterVec,
     /// Internal use only. Needed for generating the response.
     registry: Registry,
 }
@@ -244,6 +246,14 @@ impl Default for Metrics {
             .expect("Infallible");
         let dropped_messages =
             IntCounter::new("dropped_messages", "Sumeragi dropped messages").expect("Infallible");
+        let rejected_requests = IntCounterVec::new(
+            Opts::new(
+                "rejected_requests",
+                "Requests rejected by the rate limits of this peer",
+            ),
+            &["route", "key"],
+        )
+        .expect("Infallible");
         let registry = Registry::new();
 
         macro_rules! register {
@@ -271,7 +281,8 @@ impl Default for Metrics {
             isi_times,
             view_changes,
             queue_size,
-            dropped_messages
+            dropped_messages,
+            rejected_requests
         );
 
         Self {
@@ -290,6 +301,7 @@ impl Default for Metrics {
             view_changes,
             queue_size,
             dropped_messages,
+            rejected_requests,
             registry,
         }
     }
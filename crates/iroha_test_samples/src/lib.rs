@@ -136,6 +136,17 @@ pub fn load_sample_wasm(name: impl AsRef<str>) -> WasmSmartContract {
     }
 }
 
+/// Resolve the path of a sample TLS file in `tls`, e.g. `ca.crt` or `server.key`.
+///
+/// The server certificates are issued for `localhost` and `127.0.0.1`.
+/// All the certificates are issued by the certificate authority in `ca.crt`,
+/// except for `untrusted_client.crt` issued by an unrelated one.
+pub fn sample_tls_path(name: impl AsRef<str>) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tls")
+        .join(name.as_ref())
+}
+
 #[derive(Deserialize)]
 struct WasmBuildConfiguration {
     profile: Profile,
@@ -1,4 +1,7 @@
-use std::{num::NonZeroU64, sync::Arc};
+use std::{
+    num::{NonZeroU64, NonZeroUsize},
+    sync::Arc,
+};
 
 use iroha_core::kura::Kura;
 use iroha_data_model::block::{
@@ -14,6 +17,9 @@ pub enum Error {
     /// Error from provided stream/websocket
     #[error("Stream error: {0}")]
     Stream(Box<stream::Error>),
+    /// Requested block is pruned from the storage
+    #[error("Block at height {0} is pruned on this peer")]
+    BlockPruned(NonZeroU64),
 }
 
 impl From<stream::Error> for Error {
@@ -52,14 +58,18 @@ impl<'ws> Consumer<'ws> {
     /// Forwards block if block for given height already exists
     ///
     /// # Errors
-    /// Can fail due to timeout. Also receiving might fail
+    /// - Can fail due to timeout. Also receiving might fail
+    /// - Requested block is pruned from the storage
     #[iroha_futures::telemetry_future]
     pub async fn consume(&mut self) -> Result<()> {
-        if let Some(block) = self.kura.get_block(
-            self.height
-                .try_into()
-                .expect("INTERNAL BUG: Number of blocks exceeds usize::MAX"),
-        ) {
+        let height: NonZeroUsize = self
+            .height
+            .try_into()
+            .expect("INTERNAL BUG: Number of blocks exceeds usize::MAX");
+        if height.get() <= self.kura.pruned_blocks_count() {
+            return Err(Error::BlockPruned(self.height));
+        }
+        if let Some(block) = self.kura.get_block(height) {
             // TODO: to avoid clone `BlockMessage` could be split into sending and receiving parts
             self.stream
                 .send(BlockMessage(SignedBlock::clone(&block)))
@@ -10,6 +10,7 @@ use std::{collections::HashSet, fmt::Debug, sync::Arc, time::Duration};
 use axum::{
     extract::{DefaultBodyLimit, WebSocketUpgrade},
     http::StatusCode,
+    middleware,
     response::{IntoResponse, Json, Response},
     routing::{get, post},
     Router,
@@ -17,7 +18,7 @@ use axum::{
 use error_stack::ResultExt;
 use iroha_config::{
     base::{util::Bytes, WithOrigin},
-    parameters::actual::Torii as Config,
+    parameters::actual::{Torii as Config, ToriiTls},
 };
 #[cfg(feature = "telemetry")]
 use iroha_core::telemetry::Telemetry;
@@ -27,13 +28,15 @@ use iroha_core::{
     prelude::*,
     query::store::LiveQueryStoreHandle,
     queue::{self, Queue},
+    snapshot::history::StateHistory,
     state::State,
     EventsSender,
 };
-use iroha_data_model::{peer::Peer, ChainId};
+use iroha_data_model::{account::AccountId, peer::Peer, ChainId};
 use iroha_futures::supervisor::ShutdownSignal;
 use iroha_primitives::addr::SocketAddr;
 use iroha_torii_shared::uri;
+use limits::{Limits, Route};
 use tokio::{net::TcpListener, sync::watch};
 use tower_http::{
     timeout::TimeoutLayer,
@@ -45,8 +48,11 @@ use utils::{extractors::ScaleVersioned, Scale};
 pub(crate) mod utils;
 mod block;
 mod event;
+mod limits;
+mod query_subscription;
 mod routing;
 mod stream;
+mod tls;
 
 const SERVER_SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(60);
 
@@ -59,8 +65,13 @@ pub struct Torii {
     query_service: LiveQueryStoreHandle,
     kura: Arc<Kura>,
     transaction_max_content_len: Bytes<u64>,
+    query_gateway_account: Option<AccountId>,
+    query_subscriptions: Arc<query_subscription::Subscriptions>,
+    limits: Arc<Limits>,
+    tls: Option<ToriiTls>,
     address: WithOrigin<SocketAddr>,
     state: Arc<State>,
+    state_history: Option<Arc<StateHistory>>,
     #[cfg(feature = "telemetry")]
     telemetry: Telemetry,
     online_peers: OnlinePeersProvider,
@@ -78,6 +89,7 @@ impl Torii {
         query_service: LiveQueryStoreHandle,
         kura: Arc<Kura>,
         state: Arc<State>,
+        state_history: Option<Arc<StateHistory>>,
         online_peers: OnlinePeersProvider,
         #[cfg(feature = "telemetry")] telemetry: Telemetry,
     ) -> Self {
@@ -89,11 +101,23 @@ impl Torii {
             query_service,
             kura,
             state,
+            state_history,
             online_peers,
+            limits: Arc::new(Limits::new(
+                config.rate_limit,
+                #[cfg(feature = "telemetry")]
+                telemetry.clone(),
+            )),
             #[cfg(feature = "telemetry")]
             telemetry,
             address: config.address,
             transaction_max_content_len: config.max_content_len,
+            query_gateway_account: config.query_gateway_account,
+            query_subscriptions: Arc::new(query_subscription::Subscriptions::new(
+                config.query_subscription_capacity,
+                config.query_subscription_capacity_per_user,
+            )),
+            tls: config.tls,
         }
     }
 
@@ -190,8 +214,9 @@ impl Torii {
                     let chain_id = self.chain_id.clone();
                     let queue = self.queue.clone();
                     let state = self.state.clone();
+                    let limits = self.limits.clone();
                     move |ScaleVersioned(transaction): ScaleVersioned<_>| {
-                        routing::handle_transaction(chain_id, queue, state, transaction)
+                        routing::handle_transaction(chain_id, queue, state, limits, transaction)
                     }
                 })
                 .layer(DefaultBodyLimit::max(
@@ -199,6 +224,10 @@ impl Torii {
                         .get()
                         .try_into()
                         .expect("should't exceed usize"),
+                ))
+                .layer(middleware::from_fn_with_state(
+                    (self.limits.clone(), Route::Transaction),
+                    limits::limit_by_address,
                 )),
             )
             .route(
@@ -206,10 +235,22 @@ impl Torii {
                 post({
                     let query_service = self.query_service.clone();
                     let state = self.state.clone();
+                    let state_history = self.state_history.clone();
+                    let limits = self.limits.clone();
                     move |ScaleVersioned(query_request): ScaleVersioned<_>| {
-                        routing::handle_queries(query_service, state, query_request)
+                        routing::handle_queries(
+                            query_service,
+                            state,
+                            state_history,
+                            limits,
+                            query_request,
+                        )
                     }
-                }),
+                })
+                .layer(middleware::from_fn_with_state(
+                    (self.limits.clone(), Route::Query),
+                    limits::limit_by_address,
+                )),
             )
             .route(
                 uri::CONFIGURATION,
@@ -219,6 +260,38 @@ impl Torii {
                 }),
             );
 
+        let router = if let Some(account) = self.query_gateway_account.clone() {
+            router
+                .route(
+                    uri::QUERY_JSON_SCHEMA,
+                    get(routing::handle_json_query_schema).layer(middleware::from_fn_with_state(
+                        (self.limits.clone(), Route::Query),
+                        limits::limit_by_address,
+                    )),
+                )
+                .route(
+                    uri::QUERY_JSON,
+                    post({
+                        let state = self.state.clone();
+                        let state_history = self.state_history.clone();
+                        move |Json(query_request): Json<_>| {
+                            routing::handle_json_queries(
+                                state,
+                                state_history,
+                                account,
+                                query_request,
+                            )
+                        }
+                    })
+                    .layer(middleware::from_fn_with_state(
+                        (self.limits.clone(), Route::Query),
+                        limits::limit_by_address,
+                    )),
+                )
+        } else {
+            router
+        };
+
         let router = router
             .route(
                 uri::SUBSCRIPTION,
@@ -233,7 +306,11 @@ impl Torii {
                             }
                         }))
                     }
-                }),
+                })
+                .layer(middleware::from_fn_with_state(
+                    (self.limits.clone(), Route::Stream),
+                    limits::limit_by_address,
+                )),
             )
             .route(
                 uri::BLOCKS_STREAM,
@@ -247,7 +324,38 @@ impl Torii {
                             }
                         }))
                     }
-                }),
+                })
+                .layer(middleware::from_fn_with_state(
+                    (self.limits.clone(), Route::Stream),
+                    limits::limit_by_address,
+                )),
+            )
+            .route(
+                uri::QUERY_SUBSCRIPTION,
+                get({
+                    let state = self.state.clone();
+                    let events = self.events.clone();
+                    let subscriptions = self.query_subscriptions.clone();
+                    move |ws: WebSocketUpgrade| {
+                        core::future::ready(ws.on_upgrade(|ws| async move {
+                            if let Err(error) =
+                                routing::query_subscription::handle_query_subscription(
+                                    state,
+                                    events,
+                                    subscriptions,
+                                    ws,
+                                )
+                                .await
+                            {
+                                iroha_logger::error!(%error, "Failure during query subscription");
+                            }
+                        }))
+                    }
+                })
+                .layer(middleware::from_fn_with_state(
+                    (self.limits.clone(), Route::Stream),
+                    limits::limit_by_address,
+                )),
             );
 
         let router = router.route(
@@ -282,10 +390,21 @@ impl Torii {
         .attach_printable_lazy(|| self.address.clone().into_attachment())?;
         let api_router = self.create_api_router();
 
-        axum::serve(listener, api_router)
-            .with_graceful_shutdown(async move { shutdown_signal.receive().await })
-            .await
-            .change_context(Error::FailedExit)
+        if let Some(config) = &self.tls {
+            let tls = tls::Tls::load(config)
+                .change_context(Error::StartServer)
+                .attach_printable("failed to load the TLS certificates")?;
+            tls::serve(listener, api_router, tls, shutdown_signal).await;
+            return Ok(());
+        }
+
+        axum::serve(
+            listener,
+            api_router.into_make_service_with_connect_info::<std::net::SocketAddr>(),
+        )
+        .with_graceful_shutdown(async move { shutdown_signal.receive().await })
+        .await
+        .change_context(Error::FailedExit)
     }
 }
 
@@ -317,6 +436,8 @@ pub enum Error {
     StartServer,
     /// Torii server terminated with an error
     FailedExit,
+    /// Too many requests, the rate limit is exceeded
+    TooManyRequests,
 }
 
 impl IntoResponse for Error {
@@ -345,6 +466,7 @@ impl Error {
             #[cfg(feature = "profiling")]
             Pprof(_) => StatusCode::INTERNAL_SERVER_ERROR,
             ConfigurationFailure(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            TooManyRequests => StatusCode::TOO_MANY_REQUESTS,
             StartServer | FailedExit => unreachable!("these never occur during request handling"),
         }
     }
@@ -365,8 +487,12 @@ impl Error {
                 | CursorDone
                 | NotFound
                 | FetchSizeTooBig
-                | InvalidSingularParameters => StatusCode::BAD_REQUEST,
-                Find(_) => StatusCode::NOT_FOUND,
+                | InvalidSingularParameters
+                | NonNumericAggregate
+                | AggregateOverflow
+                | UnorderedSortKey
+                | InvalidSubscriptionParameters => StatusCode::BAD_REQUEST,
+                Find(_) | HeightUnavailable | BlocksPruned(_) => StatusCode::NOT_FOUND,
                 CapacityLimit => StatusCode::TOO_MANY_REQUESTS,
             },
             TooComplex => StatusCode::UNPROCESSABLE_ENTITY,
@@ -0,0 +1,311 @@
+//! Rate limiting of the requests with token buckets,
+//! keyed by the remote address and by the signing account once the request is decoded.
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    net::{IpAddr, Ipv6Addr},
+    sync::{Arc, Mutex},
+    time::Instant,
+};
+
+use axum::{
+    extract::{ConnectInfo, Request, State},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use iroha_config::parameters::actual::{RateLimit, ToriiRateLimit};
+#[cfg(feature = "telemetry")]
+use iroha_core::telemetry::Telemetry;
+use iroha_data_model::account::AccountId;
+
+use crate::Error;
+
+/// The number of keys tracked by a limiter, after which the least recently used ones are forgotten
+const MAX_TRACKED_KEYS: usize = 1 << 16;
+/// Length of the prefix identifying an IPv6 client, as a single host is usually given a whole `/64`
+const IPV6_CLIENT_PREFIX_LEN: u32 = 64;
+
+/// Routes limited separately
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Route {
+    /// Submitting transactions
+    Transaction,
+    /// Executing queries
+    Query,
+    /// Opening event, block and query subscription streams
+    Stream,
+}
+
+impl Route {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Transaction => "transaction",
+            Self::Query => "query",
+            Self::Stream => "stream",
+        }
+    }
+}
+
+/// The client the requests are counted for
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Key {
+    /// Remote address of the connection
+    Address(IpAddr),
+    /// Account signing the request
+    Account(AccountId),
+}
+
+impl Key {
+    /// Key of the client connecting from the `address`.
+    ///
+    /// IPv6 clients are identified by the network prefix of the address,
+    /// since a single client can freely pick any address within it.
+    pub fn address(address: IpAddr) -> Self {
+        match address.to_canonical() {
+            IpAddr::V4(address) => Self::Address(address.into()),
+            IpAddr::V6(address) => {
+                let mask = u128::MAX << (128 - IPV6_CLIENT_PREFIX_LEN);
+                Self::Address(Ipv6Addr::from(address.to_bits() & mask).into())
+            }
+        }
+    }
+
+    #[cfg(feature = "telemetry")]
+    fn kind(&self) -> &'static str {
+        match self {
+            Self::Address(_) => "address",
+            Self::Account(_) => "account",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Bucket {
+    tokens: f64,
+    updated: Instant,
+    /// Position of the bucket in the order of use
+    used: u64,
+}
+
+/// Buckets of the clients, forgetting the least recently used ones beyond the capacity
+#[derive(Debug, Default)]
+struct Buckets {
+    by_key: HashMap<Key, Bucket>,
+    /// Keys of the buckets ordered by their last use
+    by_use: BTreeMap<u64, Key>,
+    next_use: u64,
+}
+
+impl Buckets {
+    /// Get the bucket of the `key` marking it as the most recently used one,
+    /// making room for it by evicting the least recently used buckets if it is new
+    fn touch(&mut self, key: &Key, new: impl FnOnce() -> (f64, Instant)) -> &mut Bucket {
+        let used = self.next_use;
+        self.next_use += 1;
+
+        if let Some(bucket) = self.by_key.get(key) {
+            self.by_use.remove(&bucket.used);
+        } else {
+            while self.by_key.len() >= MAX_TRACKED_KEYS {
+                let (_, evicted) = self
+                    .by_use
+                    .pop_first()
+                    .expect("Every tracked key has its use recorded");
+                self.by_key.remove(&evicted);
+            }
+        }
+        self.by_use.insert(used, key.clone());
+
+        let bucket = self.by_key.entry(key.clone()).or_insert_with(|| {
+            let (tokens, updated) = new();
+            Bucket {
+                tokens,
+                updated,
+                used,
+            }
+        });
+        bucket.used = used;
+        bucket
+    }
+}
+
+/// Token-bucket limiter of the requests of each client
+#[derive(Debug)]
+struct RateLimiter {
+    /// Tokens added per second
+    rate: f64,
+    /// Maximum number of tokens
+    burst: f64,
+    buckets: Mutex<Buckets>,
+}
+
+impl RateLimiter {
+    fn new(limit: RateLimit) -> Self {
+        Self {
+            rate: f64::from(limit.per_second.get()),
+            burst: f64::from(limit.burst.get()),
+            buckets: Mutex::default(),
+        }
+    }
+
+    fn refilled(&self, bucket: Bucket, now: Instant) -> f64 {
+        let elapsed = now.saturating_duration_since(bucket.updated).as_secs_f64();
+        (bucket.tokens + elapsed * self.rate).min(self.burst)
+    }
+
+    /// Takes a token from the bucket of the `key`, returning `false` if there are none left
+    fn try_acquire(&self, key: &Key, now: Instant) -> bool {
+        let mut buckets = self.buckets.lock().expect("Rate limiter lock poisoned");
+
+        let bucket = buckets.touch(key, || (self.burst, now));
+        let tokens = self.refilled(*bucket, now);
+        bucket.updated = now;
+        if tokens < 1.0 {
+            bucket.tokens = tokens;
+            return false;
+        }
+        bucket.tokens = tokens - 1.0;
+        true
+    }
+}
+
+/// Rate limits of all the routes
+pub struct Limits {
+    transactions: Option<RateLimiter>,
+    queries: Option<RateLimiter>,
+    streams: Option<RateLimiter>,
+    #[cfg(feature = "telemetry")]
+    telemetry: Telemetry,
+}
+
+impl Limits {
+    /// Construct [`Limits`] from the configuration
+    pub fn new(config: ToriiRateLimit, #[cfg(feature = "telemetry")] telemetry: Telemetry) -> Self {
+        Self {
+            transactions: config.transactions.map(RateLimiter::new),
+            queries: config.queries.map(RateLimiter::new),
+            streams: config.streams.map(RateLimiter::new),
+            #[cfg(feature = "telemetry")]
+            telemetry,
+        }
+    }
+
+    /// Counts a request to the `route` from the client identified by the `key`.
+    ///
+    /// # Errors
+    /// Fails if the client exceeded the limit of the route
+    pub fn check(&self, route: Route, key: Key) -> Result<(), Error> {
+        let limiter = match route {
+            Route::Transaction => &self.transactions,
+            Route::Query => &self.queries,
+            Route::Stream => &self.streams,
+        };
+        let Some(limiter) = limiter else {
+            return Ok(());
+        };
+        if limiter.try_acquire(&key, Instant::now()) {
+            return Ok(());
+        }
+
+        iroha_logger::debug!(route = route.as_str(), ?key, "Request rate limited");
+        #[cfg(feature = "telemetry")]
+        self.telemetry
+            .inc_rejected_requests(route.as_str(), key.kind());
+        Err(Error::TooManyRequests)
+    }
+
+    /// Counts a request to the `route` from the signing `account`.
+    ///
+    /// # Errors
+    /// Fails if the account exceeded the limit of the route
+    pub fn check_account(&self, route: Route, account: &AccountId) -> Result<(), Error> {
+        self.check(route, Key::Account(account.clone()))
+    }
+}
+
+/// Middleware limiting the requests to the `route` by the remote address of the connection
+pub async fn limit_by_address(
+    State((limits, route)): State<(Arc<Limits>, Route)>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let address = request
+        .extensions()
+        .get::<ConnectInfo<std::net::SocketAddr>>()
+        .map(|ConnectInfo(address)| address.ip());
+    if let Some(address) = address {
+        if let Err(error) = limits.check(route, Key::address(address)) {
+            return error.into_response();
+        }
+    }
+
+    next.run(request).await
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use nonzero_ext::nonzero;
+
+    use super::*;
+
+    #[test]
+    fn bucket_allows_bursts_and_refills_over_time() {
+        let limiter = RateLimiter::new(RateLimit {
+            per_second: nonzero!(2_u32),
+            burst: nonzero!(3_u32),
+        });
+        let key = Key::Address([127, 0, 0, 1].into());
+        let other = Key::Address([127, 0, 0, 2].into());
+        let start = Instant::now();
+
+        for _ in 0..3 {
+            assert!(limiter.try_acquire(&key, start));
+        }
+        assert!(!limiter.try_acquire(&key, start));
+        // clients are limited independently
+        assert!(limiter.try_acquire(&other, start));
+
+        let later = start + Duration::from_millis(500);
+        assert!(limiter.try_acquire(&key, later));
+        assert!(!limiter.try_acquire(&key, later));
+    }
+
+    #[test]
+    fn least_recently_used_buckets_are_evicted_beyond_capacity() {
+        let limiter = RateLimiter::new(RateLimit {
+            per_second: nonzero!(1_u32),
+            burst: nonzero!(1_u32),
+        });
+        let now = Instant::now();
+        let key = |i: usize| Key::Address(IpAddr::from(u32::try_from(i).unwrap().to_be_bytes()));
+
+        assert!(limiter.try_acquire(&key(0), now));
+        for i in 1..MAX_TRACKED_KEYS {
+            assert!(limiter.try_acquire(&key(i), now));
+        }
+        // the first key is the most recently used one now
+        assert!(!limiter.try_acquire(&key(0), now));
+        assert!(limiter.try_acquire(&key(MAX_TRACKED_KEYS), now));
+
+        let buckets = limiter.buckets.lock().unwrap();
+        assert_eq!(buckets.by_key.len(), MAX_TRACKED_KEYS);
+        assert_eq!(buckets.by_use.len(), MAX_TRACKED_KEYS);
+        assert!(buckets.by_key.contains_key(&key(0)));
+        assert!(!buckets.by_key.contains_key(&key(1)));
+    }
+
+    #[test]
+    fn ipv6_clients_are_keyed_by_prefix() {
+        let address = |s: &str| Key::address(s.parse().unwrap());
+
+        assert_eq!(
+            address("2001:db8:1:2:aaaa::1"),
+            address("2001:db8:1:2:ffff:ffff:ffff:ffff")
+        );
+        assert_ne!(address("2001:db8:1:2::1"), address("2001:db8:1:3::1"));
+        assert_eq!(address("::ffff:192.0.2.1"), address("192.0.2.1"));
+        assert_ne!(address("192.0.2.1"), address("192.0.2.2"));
+    }
+}
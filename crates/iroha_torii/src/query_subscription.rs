@@ -0,0 +1,264 @@
+//! Subscriptions to the results of iterable queries.
+//! The initial results are sent right away, then the changes are pushed after each committed block.
+
+use std::{
+    collections::HashMap,
+    num::{NonZeroU64, NonZeroUsize},
+    sync::{Arc, Mutex},
+};
+
+use iroha_core::{
+    query::subscription::QuerySubscription, smartcontracts::query::ValidQueryRequest, state::State,
+};
+use iroha_data_model::{
+    account::AccountId,
+    events::prelude::*,
+    query::{
+        error::QueryExecutionFail,
+        subscription::{QuerySubscriptionMessage, QuerySubscriptionRequest},
+        QueryRequestWithAuthority, SignedQuery,
+    },
+    ValidationFail,
+};
+use iroha_logger::warn;
+use tokio::task;
+
+use crate::stream::{self, WebSocketScale};
+
+/// Type of error for `Consumer`
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    /// Error from provided stream/websocket
+    #[error("Stream error: {0}")]
+    Stream(Box<stream::Error>),
+    /// The subscription was rejected or its query failed, which was reported to the subscriber
+    #[error("Query subscription failed: {0}")]
+    Failed(ValidationFail),
+}
+
+impl From<stream::Error> for Error {
+    fn from(error: stream::Error) -> Self {
+        Self::Stream(Box::new(error))
+    }
+}
+
+/// Result type for `Consumer`
+pub type Result<T> = core::result::Result<T, Error>;
+
+/// Numbers of the open query subscriptions, limited in total and for a single user like the live queries are
+#[derive(Debug)]
+pub struct Subscriptions {
+    capacity: NonZeroUsize,
+    capacity_per_user: NonZeroUsize,
+    open: Mutex<OpenSubscriptions>,
+}
+
+#[derive(Debug, Default)]
+struct OpenSubscriptions {
+    total: usize,
+    per_user: HashMap<AccountId, usize>,
+}
+
+impl Subscriptions {
+    /// Construct [`Subscriptions`] allowing up to `capacity` subscriptions, up to `capacity_per_user` of them for a single user
+    pub fn new(capacity: NonZeroUsize, capacity_per_user: NonZeroUsize) -> Self {
+        Self {
+            capacity,
+            capacity_per_user,
+            open: Mutex::default(),
+        }
+    }
+
+    /// Opens a subscription for the `authority`, which stays open until the returned [`Slot`] is dropped
+    ///
+    /// # Errors
+    /// Returns [`QueryExecutionFail::CapacityLimit`] if the total or the user's capacity is reached
+    pub fn open(
+        self: &Arc<Self>,
+        authority: &AccountId,
+    ) -> core::result::Result<Slot, QueryExecutionFail> {
+        let mut open = self.open.lock().expect("Subscriptions lock poisoned");
+        if open.total >= self.capacity.get() {
+            warn!(
+                max_subscriptions = self.capacity,
+                "Reached maximum allowed number of query subscriptions"
+            );
+            return Err(QueryExecutionFail::CapacityLimit);
+        }
+        let of_user = open.per_user.entry(authority.clone()).or_default();
+        if *of_user >= self.capacity_per_user.get() {
+            warn!(
+                max_subscriptions_per_user = self.capacity_per_user,
+                %authority,
+                "Account reached maximum allowed number of query subscriptions"
+            );
+            return Err(QueryExecutionFail::CapacityLimit);
+        }
+        *of_user += 1;
+        open.total += 1;
+
+        Ok(Slot {
+            subscriptions: Arc::clone(self),
+            authority: authority.clone(),
+        })
+    }
+
+    fn close(&self, authority: &AccountId) {
+        let mut open = self.open.lock().expect("Subscriptions lock poisoned");
+        open.total -= 1;
+        if let Some(of_user) = open.per_user.get_mut(authority) {
+            *of_user -= 1;
+            if *of_user == 0 {
+                open.per_user.remove(authority);
+            }
+        }
+    }
+}
+
+/// An open subscription counted by [`Subscriptions`], closed once dropped
+#[derive(Debug)]
+pub struct Slot {
+    subscriptions: Arc<Subscriptions>,
+    authority: AccountId,
+}
+
+impl Drop for Slot {
+    fn drop(&mut self) {
+        self.subscriptions.close(&self.authority);
+    }
+}
+
+/// Consumer for the results of a subscribed query.
+/// Passes the changes of the results over the corresponding connection `stream` after each committed block.
+///
+/// The results are evaluated on the blocking threads, the same way the queries are.
+#[derive(Debug)]
+pub struct Consumer<'ws> {
+    pub stream: &'ws mut WebSocketScale,
+    state: Arc<State>,
+    subscription: Arc<Mutex<QuerySubscription>>,
+    _slot: Slot,
+}
+
+impl<'ws> Consumer<'ws> {
+    /// Constructs [`Consumer`] from the query received through the `stream`, sending the initial results of the query.
+    ///
+    /// # Errors
+    /// Can fail due to timeout or without message at websocket or during decoding request.
+    /// Also fails if the query is rejected or there are too many subscriptions, which is reported to the subscriber.
+    #[iroha_futures::telemetry_future]
+    pub async fn new(
+        stream: &'ws mut WebSocketScale,
+        state: Arc<State>,
+        subscriptions: &Arc<Subscriptions>,
+    ) -> Result<Self> {
+        let QuerySubscriptionRequest(SignedQuery::V1(query)) =
+            stream.recv::<QuerySubscriptionRequest>().await?;
+        let query: QueryRequestWithAuthority = query.payload;
+        let authority = query.authority.clone();
+
+        let slot = match subscriptions.open(&authority) {
+            Ok(slot) => slot,
+            Err(error) => return Err(fail(stream, ValidationFail::QueryFailed(error)).await),
+        };
+        let started = task::spawn_blocking({
+            let state = Arc::clone(&state);
+            move || {
+                let state_view = state.view();
+                ValidQueryRequest::validate_for_client(query, &state_view).and_then(|valid_query| {
+                    valid_query
+                        .subscribe(&state_view, authority)
+                        .map_err(ValidationFail::QueryFailed)
+                })
+            }
+        })
+        .await
+        .expect("Failed to join query subscription task");
+        let (subscription, batches) = match started {
+            Ok(started) => started,
+            Err(error) => return Err(fail(stream, error).await),
+        };
+
+        for batch in batches {
+            stream
+                .send(QuerySubscriptionMessage::Initial(batch))
+                .await?;
+        }
+        // there are no results to subscribe to before the genesis
+        if let Some(height) = NonZeroU64::new(subscription.height() as u64) {
+            stream
+                .send(QuerySubscriptionMessage::Synced(height))
+                .await?;
+        }
+
+        Ok(Consumer {
+            stream,
+            state,
+            subscription: Arc::new(Mutex::new(subscription)),
+            _slot: slot,
+        })
+    }
+
+    /// Takes note of the `event` and, once the block producing it is applied,
+    /// sends the changes of the results over the `stream`.
+    ///
+    /// # Errors
+    /// Can fail due to timeout or sending the changes.
+    /// Also fails if the query is not permitted anymore or fails, which is reported to the subscriber.
+    #[iroha_futures::telemetry_future]
+    pub async fn consume(&mut self, event: EventBox) -> Result<()> {
+        match event {
+            EventBox::Pipeline(PipelineEventBox::Block(event))
+                if matches!(event.status(), BlockStatus::Applied) =>
+            {
+                let state = Arc::clone(&self.state);
+                let subscription = Arc::clone(&self.subscription);
+                let delta = task::spawn_blocking(move || {
+                    let state_view = state.view();
+                    subscription
+                        .lock()
+                        .expect("Query subscription lock poisoned")
+                        .update(&state_view)
+                })
+                .await
+                .expect("Failed to join query subscription task");
+
+                match delta {
+                    Ok(Some(delta)) => self
+                        .stream
+                        .send(QuerySubscriptionMessage::Delta(delta))
+                        .await
+                        .map_err(Into::into),
+                    Ok(None) => Ok(()),
+                    Err(error) => Err(fail(self.stream, error).await),
+                }
+            }
+            event => {
+                self.subscription
+                    .lock()
+                    .expect("Query subscription lock poisoned")
+                    .observe(&event);
+                Ok(())
+            }
+        }
+    }
+
+    /// Takes note of events that were missed, so that the results are evaluated again after the next block.
+    pub fn invalidate(&mut self) {
+        self.subscription
+            .lock()
+            .expect("Query subscription lock poisoned")
+            .invalidate();
+    }
+}
+
+/// Reports the `error` to the subscriber
+async fn fail(stream: &mut WebSocketScale, error: ValidationFail) -> Error {
+    match stream
+        .send(QuerySubscriptionMessage::Failed(error.clone()))
+        .await
+    {
+        Ok(()) => Error::Failed(error),
+        Err(error) => error.into(),
+    }
+}
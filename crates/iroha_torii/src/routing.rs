@@ -2,30 +2,42 @@
 //! Iroha you should add it here by creating a `handle_*` function,
 //! and add it to impl Torii.
 
+use std::num::{NonZeroU64, NonZeroUsize};
+
 use axum::extract::ws::WebSocket;
 #[cfg(feature = "telemetry")]
 use eyre::{eyre, WrapErr};
 use iroha_config::client_api::{ConfigGetDTO, ConfigUpdateDTO};
 #[cfg(feature = "telemetry")]
 use iroha_core::telemetry::Telemetry;
-use iroha_core::{query::store::LiveQueryStoreHandle, smartcontracts::query::ValidQueryRequest};
+use iroha_core::{
+    query::store::LiveQueryStoreHandle,
+    smartcontracts::query::ValidQueryRequest,
+    snapshot::{history::StateHistory, TryReadError},
+};
 use iroha_data_model::{
     self,
     prelude::*,
-    query::{QueryRequestWithAuthority, QueryResponse, SignedQuery},
+    query::{
+        error::QueryExecutionFail, QueryRequest, QueryRequestWithAuthority, QueryResponse,
+        SignedQuery,
+    },
 };
+use iroha_schema::IntoSchema as _;
 #[cfg(feature = "telemetry")]
 use iroha_telemetry::metrics::Status;
 use iroha_torii_shared::Version;
 use tokio::task;
 
 use super::*;
+use crate::limits::{Limits, Route};
 
 #[iroha_futures::telemetry_future]
 pub async fn handle_transaction(
     chain_id: Arc<ChainId>,
     queue: Arc<Queue>,
     state: Arc<State>,
+    limits: Arc<Limits>,
     tx: SignedTransaction,
 ) -> Result<()> {
     let (max_clock_drift, tx_limits) = {
@@ -36,6 +48,7 @@ pub async fn handle_transaction(
 
     let accepted_tx = AcceptedTransaction::accept(tx, &chain_id, max_clock_drift, tx_limits)
         .map_err(Error::AcceptTransaction)?;
+    limits.check_account(Route::Transaction, accepted_tx.as_ref().authority())?;
 
     queue
         .push(accepted_tx, state.view())
@@ -54,19 +67,20 @@ pub async fn handle_transaction(
 pub async fn handle_queries(
     live_query_store: LiveQueryStoreHandle,
     state: Arc<State>,
+    state_history: Option<Arc<StateHistory>>,
+    limits: Arc<Limits>,
     query: SignedQuery,
 ) -> Result<Scale<QueryResponse>> {
-    let handle = task::spawn_blocking(move || {
-        let state_view = state.view();
+    let SignedQuery::V1(query) = query;
+    limits.check_account(Route::Query, &query.payload.authority)?;
 
-        let SignedQuery::V1(query) = query;
-        let query: QueryRequestWithAuthority = query.payload;
-        let authority = query.authority.clone();
-
-        let valid_query = ValidQueryRequest::validate_for_client(query, &state_view)?;
-        let response = valid_query.execute(&live_query_store, &state_view, &authority)?;
-
-        Ok::<_, ValidationFail>(response)
+    let handle = task::spawn_blocking(move || {
+        execute_query(
+            Some(&live_query_store),
+            &state,
+            state_history,
+            query.payload,
+        )
     });
     handle
         .await
@@ -75,6 +89,110 @@ pub async fn handle_queries(
         .map_err(Into::into)
 }
 
+/// Handles a query received as JSON, executing it on behalf of the configured gateway `account`.
+///
+/// The gateway account is shared by all the JSON clients, so the requests are only limited
+/// by the remote address, and no live queries are kept for them: the iterable queries are
+/// continued by starting them again from the continuation of the returned cursor,
+/// or with a pagination offset.
+///
+/// The errors are returned as JSON as well.
+#[iroha_futures::telemetry_future]
+pub async fn handle_json_queries(
+    state: Arc<State>,
+    state_history: Option<Arc<StateHistory>>,
+    account: AccountId,
+    request: QueryRequest,
+) -> Response {
+    let handle = task::spawn_blocking(move || {
+        execute_query(None, &state, state_history, request.with_authority(account))
+    });
+    match handle.await.expect("Failed to join query handling task") {
+        Ok(response) => axum::Json(response).into_response(),
+        Err(error) => (Error::query_status_code(&error), axum::Json(error)).into_response(),
+    }
+}
+
+/// Describes the JSON query requests, responses and errors with their schema.
+///
+/// The JSON representation of the types follows their `serde` implementations.
+#[iroha_futures::telemetry_future]
+pub async fn handle_json_query_schema() -> axum::Json<iroha_schema::MetaMap> {
+    let mut schema = iroha_schema::MetaMap::new();
+    QueryRequest::update_schema_map(&mut schema);
+    QueryResponse::update_schema_map(&mut schema);
+    ValidationFail::update_schema_map(&mut schema);
+
+    axum::Json(schema)
+}
+
+/// Validates and executes an authorized query, blocking on the state access.
+///
+/// The rest of the iterable query output is not kept if there is no `live_query_store`.
+fn execute_query(
+    live_query_store: Option<&LiveQueryStoreHandle>,
+    state: &State,
+    state_history: Option<Arc<StateHistory>>,
+    query: QueryRequestWithAuthority,
+) -> core::result::Result<QueryResponse, ValidationFail> {
+    let state_view = state.view();
+    let authority = query.authority.clone();
+
+    // Resumed queries are answered at the height their continuation is pinned to
+    let at_height = match &query.request {
+        QueryRequest::Start(query) => query
+            .params
+            .continuation
+            .as_ref()
+            .map(|continuation| continuation.height)
+            .or(query.params.at_height.map(NonZeroU64::get))
+            .and_then(|height| usize::try_from(height).ok())
+            .and_then(NonZeroUsize::new)
+            .filter(|&height| height.get() != state_view.height()),
+        _ => None,
+    };
+
+    let valid_query = ValidQueryRequest::validate_for_client(query, &state_view)?;
+    let response = match at_height {
+        None => execute_valid_query(valid_query, live_query_store, &state_view, &authority)?,
+        Some(height) => {
+            // Past state is validated against the current one,
+            // so permissions revoked since then are respected
+            drop(state_view);
+            let state_history = state_history.ok_or(ValidationFail::QueryFailed(
+                QueryExecutionFail::HeightUnavailable,
+            ))?;
+            state_history
+                .with_state_at(height, |state_view| {
+                    execute_valid_query(valid_query, live_query_store, state_view, &authority)
+                })
+                .map_err(|err| match err {
+                    TryReadError::HeightUnavailable(_) => {
+                        ValidationFail::QueryFailed(QueryExecutionFail::HeightUnavailable)
+                    }
+                    err => {
+                        iroha_logger::error!(%height, ?err, "Failed to restore state at height");
+                        ValidationFail::InternalError(err.to_string())
+                    }
+                })??
+        }
+    };
+
+    Ok(response)
+}
+
+fn execute_valid_query(
+    valid_query: ValidQueryRequest,
+    live_query_store: Option<&LiveQueryStoreHandle>,
+    state: &impl StateReadOnly,
+    authority: &AccountId,
+) -> core::result::Result<QueryResponse, QueryExecutionFail> {
+    match live_query_store {
+        Some(live_query_store) => valid_query.execute(live_query_store, state, authority),
+        None => valid_query.execute_detached(state),
+    }
+}
+
 pub async fn handle_health() -> &'static str {
     "Healthy"
 }
@@ -248,6 +366,108 @@ pub mod event {
     }
 }
 
+pub mod query_subscription {
+    //! Query subscriptions handler
+
+    use stream::WebSocketScale;
+    use tokio::sync::broadcast::{self, error::RecvError};
+
+    use super::*;
+    use crate::query_subscription;
+
+    /// Type for any error during query subscriptions
+    #[derive(Debug, displaydoc::Display, thiserror::Error)]
+    enum Error {
+        /// Query subscription resulted in an error: {_0}
+        Consumer(#[from] Box<query_subscription::Error>),
+        /// Event reception error
+        Event(#[from] RecvError),
+        /// Connection is closed
+        Close,
+    }
+
+    impl From<query_subscription::Error> for Error {
+        fn from(error: query_subscription::Error) -> Self {
+            match error {
+                query_subscription::Error::Stream(err) if matches!(*err, stream::Error::Closed) => {
+                    Self::Close
+                }
+                error => Self::Consumer(Box::new(error)),
+            }
+        }
+    }
+
+    type Result<T> = core::result::Result<T, Error>;
+
+    /// Subscribes `stream` to the changes of the results of the query
+    /// received through the `stream`
+    #[iroha_futures::telemetry_future]
+    pub async fn handle_query_subscription(
+        state: Arc<State>,
+        events: EventsSender,
+        subscriptions: Arc<query_subscription::Subscriptions>,
+        stream: WebSocket,
+    ) -> eyre::Result<()> {
+        let mut stream = WebSocketScale(stream);
+        let init_and_subscribe = async {
+            // subscribe before evaluating the initial results, so that no block is missed
+            let events = events.subscribe();
+            let mut consumer =
+                query_subscription::Consumer::new(&mut stream, state, &subscriptions).await?;
+            subscribe_forever(events, &mut consumer).await
+        };
+
+        match init_and_subscribe.await {
+            Ok(()) => stream.close().await.map_err(Into::into),
+            Err(Error::Close) => Ok(()),
+            Err(Error::Consumer(error))
+                if matches!(*error, query_subscription::Error::Failed(_)) =>
+            {
+                // the failure was already reported to the subscriber
+                iroha_logger::debug!(%error, "Query subscription failed");
+                stream.close().await.map_err(Into::into)
+            }
+            Err(err) => {
+                // NOTE: try close websocket and return initial error
+                let _ = stream.close().await;
+                Err(err.into())
+            }
+        }
+    }
+
+    /// Make endless `consumer` subscription for `events`
+    ///
+    /// Ideally should return `Result<!>` cause it either runs forever or returns error
+    async fn subscribe_forever(
+        mut events: broadcast::Receiver<EventBox>,
+        consumer: &mut query_subscription::Consumer<'_>,
+    ) -> Result<()> {
+        loop {
+            tokio::select! {
+                // Wait for stream to be closed by client
+                closed = consumer.stream.closed() => {
+                    match closed {
+                        Ok(()) => return Err(Error::Close),
+                        Err(err) => return Err(query_subscription::Error::from(err).into())
+                    }
+                }
+                // This branch catches events and sends the changes of the results
+                event = events.recv() => {
+                    match event {
+                        Ok(event) => consumer.consume(event).await?,
+                        // the results are evaluated again instead of tracking the missed events
+                        Err(RecvError::Lagged(skipped)) => {
+                            iroha_logger::debug!(skipped, "Query subscription lagged behind events");
+                            consumer.invalidate();
+                        }
+                        Err(err) => return Err(err.into()),
+                    }
+                }
+            }
+        }
+    }
+}
+
 #[iroha_futures::telemetry_future]
 pub async fn handle_version(state: Arc<State>) -> String {
     use iroha_version::Version;
@@ -0,0 +1,479 @@
+//! TLS termination of the Torii connections.
+//! The certificates are reloaded once their files change, so that they can be renewed without a restart.
+
+use std::{
+    fs, io,
+    net::SocketAddr,
+    path::{Path, PathBuf},
+    sync::{Arc, RwLock},
+    time::{Duration, SystemTime},
+};
+
+use axum::{extract::ConnectInfo, Router};
+use hyper::{body::Incoming, server::conn::http1, service::Service as _};
+use hyper_util::{rt::TokioIo, service::TowerToHyperService};
+use iroha_config::parameters::actual::ToriiTls as Config;
+use iroha_futures::supervisor::ShutdownSignal;
+use rustls::{
+    pki_types::{CertificateDer, PrivateKeyDer},
+    server::{VerifierBuilderError, WebPkiClientVerifier},
+    RootCertStore, ServerConfig,
+};
+use tokio::{
+    net::{TcpListener, TcpStream},
+    task::JoinSet,
+};
+use tokio_rustls::TlsAcceptor;
+
+use crate::SERVER_SHUTDOWN_TIMEOUT;
+
+/// Time given to a client to complete the TLS handshake
+const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Errors of loading the TLS configuration
+#[derive(thiserror::Error, displaydoc::Display, Debug)]
+pub enum Error {
+    /// Failed to read `{0}`
+    Read(String, #[source] io::Error),
+    /// No certificates found in `{0}`
+    NoCertificates(String),
+    /// No private key found in `{0}`
+    NoPrivateKey(String),
+    /// Invalid certificate of the client certificate authority
+    ClientCa(#[source] rustls::Error),
+    /// Failed to set up the verification of client certificates
+    ClientVerifier(#[source] VerifierBuilderError),
+    /// Certificate doesn't match the private key or is invalid
+    Certificate(#[source] rustls::Error),
+}
+
+/// TLS configuration of the server, reloaded once the files it is read from change
+pub struct Tls {
+    cert_file: PathBuf,
+    key_file: PathBuf,
+    client_ca_file: Option<PathBuf>,
+    reload_period: Duration,
+    server_config: RwLock<Arc<ServerConfig>>,
+}
+
+impl Tls {
+    /// Load the certificates and the key from the files in the `config`.
+    ///
+    /// # Errors
+    /// Fails if the files can't be read or don't contain a valid certificate and key
+    pub fn load(config: &Config) -> Result<Self, Error> {
+        let cert_file = config.cert_file.resolve_relative_path();
+        let key_file = config.key_file.resolve_relative_path();
+        let client_ca_file = config
+            .client_ca_file
+            .as_ref()
+            .map(iroha_config::base::WithOrigin::resolve_relative_path);
+        let server_config = server_config(&cert_file, &key_file, client_ca_file.as_deref())?;
+
+        Ok(Self {
+            cert_file,
+            key_file,
+            client_ca_file,
+            reload_period: config.reload_period,
+            server_config: RwLock::new(server_config),
+        })
+    }
+
+    fn acceptor(&self) -> TlsAcceptor {
+        TlsAcceptor::from(
+            self.server_config
+                .read()
+                .expect("TLS config lock poisoned")
+                .clone(),
+        )
+    }
+
+    /// Modification times and sizes of the files, telling when they change
+    fn versions(&self) -> Vec<Option<(SystemTime, u64)>> {
+        [Some(&self.cert_file), Some(&self.key_file)]
+            .into_iter()
+            .chain([self.client_ca_file.as_ref()])
+            .flatten()
+            .map(|path| {
+                fs::metadata(path)
+                    .and_then(|meta| Ok((meta.modified()?, meta.len())))
+                    .ok()
+            })
+            .collect()
+    }
+
+    /// Reload the configuration every time the files change and then stay the same
+    /// for a reload period, so that the files being written aren't read partially.
+    /// The previous configuration stays in use if the new one is invalid.
+    async fn reload_on_changes(self: Arc<Self>) {
+        let mut changes = Debounce::new(self.versions());
+        let mut interval = tokio::time::interval(self.reload_period);
+        interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+        loop {
+            interval.tick().await;
+            if !changes.settled(self.versions()) {
+                continue;
+            }
+
+            match server_config(
+                &self.cert_file,
+                &self.key_file,
+                self.client_ca_file.as_deref(),
+            ) {
+                Ok(server_config) => {
+                    *self
+                        .server_config
+                        .write()
+                        .expect("TLS config lock poisoned") = server_config;
+                    iroha_logger::info!("Reloaded TLS certificates");
+                }
+                Err(error) => {
+                    iroha_logger::error!(%error, "Failed to reload TLS certificates, keeping the previous ones");
+                }
+            }
+        }
+    }
+}
+
+/// Tells when the observed versions of the files settle after a change
+struct Debounce<T> {
+    loaded: T,
+    pending: Option<T>,
+}
+
+impl<T: PartialEq> Debounce<T> {
+    fn new(loaded: T) -> Self {
+        Self {
+            loaded,
+            pending: None,
+        }
+    }
+
+    /// Returns `true` if the `current` versions differ from the loaded ones
+    /// and are the same as the previously observed ones
+    fn settled(&mut self, current: T) -> bool {
+        if current == self.loaded {
+            self.pending = None;
+            return false;
+        }
+        if self.pending.as_ref() != Some(&current) {
+            self.pending = Some(current);
+            return false;
+        }
+        self.loaded = current;
+        self.pending = None;
+        true
+    }
+}
+
+fn server_config(
+    cert_file: &Path,
+    key_file: &Path,
+    client_ca_file: Option<&Path>,
+) -> Result<Arc<ServerConfig>, Error> {
+    let certs = read_certs(cert_file)?;
+    let key = read_key(key_file)?;
+
+    let builder = ServerConfig::builder();
+    let builder = if let Some(client_ca_file) = client_ca_file {
+        let mut roots = RootCertStore::empty();
+        for cert in read_certs(client_ca_file)? {
+            roots.add(cert).map_err(Error::ClientCa)?;
+        }
+        let verifier = WebPkiClientVerifier::builder(Arc::new(roots))
+            .build()
+            .map_err(Error::ClientVerifier)?;
+        builder.with_client_cert_verifier(verifier)
+    } else {
+        builder.with_no_client_auth()
+    };
+
+    let mut config = builder
+        .with_single_cert(certs, key)
+        .map_err(Error::Certificate)?;
+    config.alpn_protocols = vec![b"http/1.1".to_vec()];
+    Ok(Arc::new(config))
+}
+
+fn open(path: &Path) -> Result<io::BufReader<fs::File>, Error> {
+    fs::File::open(path)
+        .map(io::BufReader::new)
+        .map_err(|error| Error::Read(path.display().to_string(), error))
+}
+
+fn read_certs(path: &Path) -> Result<Vec<CertificateDer<'static>>, Error> {
+    let certs = rustls_pemfile::certs(&mut open(path)?)
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|error| Error::Read(path.display().to_string(), error))?;
+    if certs.is_empty() {
+        return Err(Error::NoCertificates(path.display().to_string()));
+    }
+    Ok(certs)
+}
+
+fn read_key(path: &Path) -> Result<PrivateKeyDer<'static>, Error> {
+    rustls_pemfile::private_key(&mut open(path)?)
+        .map_err(|error| Error::Read(path.display().to_string(), error))?
+        .ok_or_else(|| Error::NoPrivateKey(path.display().to_string()))
+}
+
+/// Serve the `router` over TLS connections accepted by the `listener` until the shutdown is signaled.
+pub async fn serve(
+    listener: TcpListener,
+    router: Router,
+    tls: Tls,
+    shutdown_signal: ShutdownSignal,
+) {
+    let tls = Arc::new(tls);
+    let reload = tokio::spawn(Arc::clone(&tls).reload_on_changes());
+    let mut connections = JoinSet::new();
+
+    loop {
+        let (stream, remote) = tokio::select! {
+            accepted = listener.accept() => match accepted {
+                Ok(accepted) => accepted,
+                Err(error) => {
+                    iroha_logger::warn!(%error, "Failed to accept connection");
+                    // e.g. too many open files, give other connections time to close
+                    tokio::time::sleep(Duration::from_secs(1)).await;
+                    continue;
+                }
+            },
+            () = shutdown_signal.receive() => break,
+        };
+
+        connections.spawn(serve_connection(
+            stream,
+            remote,
+            tls.acceptor(),
+            router.clone(),
+            shutdown_signal.clone(),
+        ));
+        while connections.try_join_next().is_some() {}
+    }
+
+    reload.abort();
+    // Graceful shutdown waits for outstanding requests to complete, but not forever
+    let _ = tokio::time::timeout(SERVER_SHUTDOWN_TIMEOUT, async {
+        while connections.join_next().await.is_some() {}
+    })
+    .await;
+}
+
+async fn serve_connection(
+    stream: TcpStream,
+    remote: SocketAddr,
+    acceptor: TlsAcceptor,
+    router: Router,
+    shutdown_signal: ShutdownSignal,
+) {
+    let stream = match tokio::time::timeout(HANDSHAKE_TIMEOUT, acceptor.accept(stream)).await {
+        Ok(Ok(stream)) => stream,
+        Ok(Err(error)) => {
+            iroha_logger::debug!(%error, %remote, "TLS handshake failed");
+            return;
+        }
+        Err(_) => {
+            iroha_logger::debug!(%remote, "TLS handshake timed out");
+            return;
+        }
+    };
+
+    let service = TowerToHyperService::new(router);
+    let service = hyper::service::service_fn(move |mut request: hyper::Request<Incoming>| {
+        request.extensions_mut().insert(ConnectInfo(remote));
+        service.call(request)
+    });
+    let connection = http1::Builder::new()
+        .serve_connection(TokioIo::new(stream), service)
+        .with_upgrades();
+    tokio::pin!(connection);
+
+    let result = tokio::select! {
+        result = connection.as_mut() => result,
+        () = shutdown_signal.receive() => {
+            connection.as_mut().graceful_shutdown();
+            connection.await
+        }
+    };
+    if let Err(error) = result {
+        iroha_logger::debug!(%error, %remote, "Failed to serve connection");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::routing::get;
+    use iroha_config::base::WithOrigin;
+    use iroha_test_samples::sample_tls_path;
+    use rustls::{pki_types::ServerName, ClientConfig};
+    use tokio::io::{AsyncReadExt as _, AsyncWriteExt as _};
+    use tokio_rustls::TlsConnector;
+
+    use super::*;
+
+    const RELOAD_PERIOD: Duration = Duration::from_millis(50);
+
+    /// Torii serving the sample certificates copied to a temporary directory
+    struct Server {
+        dir: tempfile::TempDir,
+        address: SocketAddr,
+        shutdown_signal: ShutdownSignal,
+    }
+
+    impl Server {
+        async fn start(client_auth: bool) -> Self {
+            let dir = tempfile::tempdir().unwrap();
+            for name in ["server.crt", "server.key", "ca.crt"] {
+                fs::copy(sample_tls_path(name), dir.path().join(name)).unwrap();
+            }
+            let tls = Tls::load(&Config {
+                cert_file: WithOrigin::inline(dir.path().join("server.crt")),
+                key_file: WithOrigin::inline(dir.path().join("server.key")),
+                client_ca_file: client_auth.then(|| WithOrigin::inline(dir.path().join("ca.crt"))),
+                reload_period: RELOAD_PERIOD,
+            })
+            .unwrap();
+
+            let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+            let address = listener.local_addr().unwrap();
+            let router = Router::new().route("/", get(|| async { "ok" }));
+            let shutdown_signal = ShutdownSignal::new();
+            tokio::spawn(serve(listener, router, tls, shutdown_signal.clone()));
+
+            Self {
+                dir,
+                address,
+                shutdown_signal,
+            }
+        }
+
+        fn replace_file(&self, name: &str, sample: &str) {
+            fs::copy(sample_tls_path(sample), self.dir.path().join(name)).unwrap();
+        }
+
+        /// Request `/` presenting the sample client certificate `identity`, if any.
+        /// Returns the response along with the certificate presented by the server.
+        async fn get(
+            &self,
+            identity: Option<&str>,
+        ) -> io::Result<(String, CertificateDer<'static>)> {
+            let mut roots = RootCertStore::empty();
+            roots.add_parsable_certificates(read_certs(&sample_tls_path("ca.crt")).unwrap());
+            let builder = ClientConfig::builder().with_root_certificates(roots);
+            let config = match identity {
+                Some(name) => builder
+                    .with_client_auth_cert(
+                        read_certs(&sample_tls_path(format!("{name}.crt"))).unwrap(),
+                        read_key(&sample_tls_path(format!("{name}.key"))).unwrap(),
+                    )
+                    .unwrap(),
+                None => builder.with_no_client_auth(),
+            };
+
+            let stream = TcpStream::connect(self.address).await?;
+            let mut stream = TlsConnector::from(Arc::new(config))
+                .connect(ServerName::try_from("localhost").unwrap(), stream)
+                .await?;
+            let certificate = stream.get_ref().1.peer_certificates().unwrap()[0].clone();
+            stream
+                .write_all(b"GET / HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n")
+                .await?;
+            let mut response = Vec::new();
+            match stream.read_to_end(&mut response).await {
+                // the connection may be closed without the TLS close notification
+                Err(error)
+                    if error.kind() == io::ErrorKind::UnexpectedEof && !response.is_empty() => {}
+                result => {
+                    result?;
+                }
+            }
+            Ok((String::from_utf8_lossy(&response).into_owned(), certificate))
+        }
+    }
+
+    impl Drop for Server {
+        fn drop(&mut self) {
+            self.shutdown_signal.send();
+        }
+    }
+
+    fn is_ok(response: &io::Result<(String, CertificateDer<'static>)>) -> bool {
+        response.as_ref().is_ok_and(|(response, _)| {
+            response.starts_with("HTTP/1.1 200") && response.ends_with("ok")
+        })
+    }
+
+    #[test]
+    fn files_without_certificates_are_rejected() {
+        let dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+        let not_pem = dir.join("Cargo.toml");
+
+        assert!(matches!(
+            server_config(&not_pem, &not_pem, None),
+            Err(Error::NoCertificates(_))
+        ));
+        assert!(matches!(
+            server_config(&dir.join("missing.pem"), &not_pem, None),
+            Err(Error::Read(..))
+        ));
+    }
+
+    #[tokio::test]
+    async fn requests_are_served_over_tls() {
+        let server = Server::start(false).await;
+
+        let response = server.get(None).await;
+        assert!(is_ok(&response), "{response:?}");
+    }
+
+    #[tokio::test]
+    async fn client_certificates_are_verified() {
+        let server = Server::start(true).await;
+
+        let response = server.get(Some("client")).await;
+        assert!(is_ok(&response), "{response:?}");
+        assert!(!is_ok(&server.get(None).await));
+        assert!(!is_ok(&server.get(Some("untrusted_client")).await));
+    }
+
+    #[tokio::test]
+    async fn certificates_are_reloaded_once_changed() {
+        let server = Server::start(false).await;
+        let renewed = read_certs(&sample_tls_path("server_renewed.crt")).unwrap();
+
+        server.replace_file("server.crt", "server_renewed.crt");
+        server.replace_file("server.key", "server_renewed.key");
+
+        let reloaded = tokio::time::timeout(Duration::from_secs(5), async {
+            loop {
+                if let Ok((_, certificate)) = server.get(None).await {
+                    if certificate == renewed[0] {
+                        break;
+                    }
+                }
+                tokio::time::sleep(RELOAD_PERIOD).await;
+            }
+        })
+        .await;
+        assert!(reloaded.is_ok(), "The renewed certificate isn't served");
+        assert!(is_ok(&server.get(None).await));
+    }
+
+    #[test]
+    fn reloads_wait_for_changes_to_settle() {
+        let mut changes = Debounce::new(0);
+
+        assert!(!changes.settled(0));
+        // the file is being written
+        assert!(!changes.settled(1));
+        assert!(!changes.settled(2));
+        assert!(changes.settled(2));
+        assert!(!changes.settled(2));
+        // the change is reverted before it settles
+        assert!(!changes.settled(3));
+        assert!(!changes.settled(2));
+        assert!(!changes.settled(2));
+    }
+}
@@ -6,6 +6,10 @@ pub mod uri {
 
     /// Query URI is used to handle incoming Query requests.
     pub const QUERY: &str = "/query";
+    /// Query URI used to handle incoming JSON Query requests on behalf of the configured gateway account.
+    pub const QUERY_JSON: &str = "/query/json";
+    /// URI for retrieving the schema of the JSON Query requests, responses and errors.
+    pub const QUERY_JSON_SCHEMA: &str = "/query/json/schema";
     /// Transaction URI is used to handle incoming ISI requests.
     pub const TRANSACTION: &str = "/transaction";
     /// Health URI is used to handle incoming Healthcheck requests.
@@ -16,6 +20,8 @@ pub mod uri {
     pub const SUBSCRIPTION: &str = "/events";
     /// The web socket uri used to subscribe to blocks stream.
     pub const BLOCKS_STREAM: &str = "/block/stream";
+    /// The web socket uri used to subscribe to the results of a query.
+    pub const QUERY_SUBSCRIPTION: &str = "/query/subscription";
     /// The URI for local config changing inspecting
     pub const CONFIGURATION: &str = "/configuration";
     /// URI to report status for administration
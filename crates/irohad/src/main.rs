@@ -13,6 +13,7 @@ use error_stack::{IntoReportCompat, Report, Result, ResultExt};
 use iroha_config::{
     base::{read::ConfigReader, util::Emitter, WithOrigin},
     parameters::{actual::Root as Config, user::Root as UserConfig},
+    snapshot::Mode as SnapshotMode,
 };
 #[cfg(feature = "telemetry")]
 use iroha_core::telemetry::StateTelemetry;
@@ -25,7 +26,10 @@ use iroha_core::{
     query::store::LiveQueryStore,
     queue::Queue,
     smartcontracts::isi::Registrable as _,
-    snapshot::{try_read_snapshot, SnapshotMaker, TryReadError as TryReadSnapshotError},
+    snapshot::{
+        history::StateHistory, try_read_snapshot, SnapshotMaker,
+        TryReadError as TryReadSnapshotError,
+    },
     state::{State, StateReadOnly, World},
     sumeragi::{GenesisWithPubKey, SumeragiHandle, SumeragiStartArgs},
     IrohaNetwork,
@@ -129,6 +133,8 @@ pub enum StartError {
     ListenOsSignal,
     #[error("Unable to start Torii (Iroha HTTP API Gateway)")]
     StartTorii,
+    #[error("Kura has pruned blocks not covered by the state snapshot")]
+    PrunedBlocksNotCovered,
 }
 
 struct NetworkRelay {
@@ -207,6 +213,16 @@ impl Iroha {
             (metrics.clone(), StateTelemetry::new(metrics))
         };
 
+        let state_history = StateHistory::from_config(
+            &config.snapshot,
+            Arc::clone(&kura),
+            live_query_store.clone(),
+            // Replaying past blocks shouldn't affect metrics of the live state
+            #[cfg(feature = "telemetry")]
+            StateTelemetry::new(Arc::default()),
+        )
+        .map(Arc::new);
+
         let state = match try_read_snapshot(
             config.snapshot.store_dir.resolve_relative_path(),
             &kura,
@@ -245,10 +261,22 @@ impl Iroha {
                 state_telemetry
             )
         });
+        let pruned_blocks_count = kura.pruned_blocks_count();
+        if state.view().height() < pruned_blocks_count {
+            return Err(Report::new(StartError::PrunedBlocksNotCovered)
+                .attach_printable(format!(
+                    "state height: {}, pruned blocks: {pruned_blocks_count}",
+                    state.view().height()
+                ))
+                .attach_printable("Restore the snapshot store or the full block store"));
+        }
         let state = Arc::new(state);
 
         let (events_sender, _) = broadcast::channel(EVENTS_BUFFER_CAPACITY);
-        let queue = Arc::new(Queue::from_config(config.queue, events_sender.clone()));
+        let queue = Arc::new(Queue::from_config(
+            config.queue.clone(),
+            events_sender.clone(),
+        ));
 
         let (network, child) = IrohaNetwork::start(
             config.common.key_pair.clone(),
@@ -345,6 +373,7 @@ impl Iroha {
             live_query_store,
             kura.clone(),
             state.clone(),
+            state_history,
             iroha_torii::OnlinePeersProvider::new(network.online_peers_receiver()),
             #[cfg(feature = "telemetry")]
             telemetry,
@@ -509,6 +538,8 @@ pub enum ConfigError {
     InvalidDirPath,
     #[error("Network error: cannot listen to address `{addr}`")]
     CannotBindAddress { addr: SocketAddr },
+    #[error("Blocks pruning requires snapshots to be written")]
+    PruningWithoutSnapshots,
 }
 
 /// Read the configuration and then a genesis block if specified.
@@ -585,6 +616,16 @@ fn validate_config(config: &Config) -> Result<(), ConfigError> {
         ").attach_printable(config.common.trusted_peers.clone().into_attachment().display_as_debug()));
     }
 
+    if config.kura.blocks_to_keep.is_some()
+        && !matches!(config.snapshot.mode, SnapshotMode::ReadWrite)
+    {
+        emitter.emit(
+            Report::new(ConfigError::PruningWithoutSnapshots).attach_printable(
+                "`kura.blocks_to_keep` is set, but `snapshot.mode` is not `read_write`",
+            ),
+        );
+    }
+
     if config.network.address.value() == config.torii.address.value() {
         emitter.emit(
             Report::new(ConfigError::SameNetworkAndToriiAddrs)
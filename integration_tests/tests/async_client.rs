@@ -0,0 +1,62 @@
+#![allow(missing_docs)]
+
+use eyre::Result;
+use futures_util::TryStreamExt;
+use iroha::{
+    async_client::{AsyncClient, QueryStream},
+    data_model::{prelude::*, query::parameters::FetchSize},
+};
+use iroha_test_network::*;
+use nonzero_ext::nonzero;
+
+#[tokio::test]
+async fn submits_transactions_and_waits_for_their_status() -> Result<()> {
+    let network = NetworkBuilder::new().start().await?;
+    let client = AsyncClient::from_client(network.client())?;
+    let domain_id: DomainId = "looking_glass".parse()?;
+
+    let status = client
+        .submit_all_and_wait([Register::domain(Domain::new(domain_id.clone()))])
+        .await?;
+    assert_eq!(status, TransactionStatus::Approved);
+
+    // the domain exists already
+    let status = client
+        .submit_all_and_wait([Register::domain(Domain::new(domain_id.clone()))])
+        .await?;
+    assert!(matches!(status, TransactionStatus::Rejected(_)));
+
+    let domain = client
+        .query(FindDomains::new())
+        .filter_with(|domain| domain.id.eq(domain_id.clone()))
+        .execute_all_async()
+        .await?;
+    assert_eq!(domain.len(), 1);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn streams_query_results_batch_by_batch() -> Result<()> {
+    let network = NetworkBuilder::new().start().await?;
+    let client = AsyncClient::from_client(network.client())?;
+
+    let expected = network
+        .client()
+        .query(FindDomains::new())
+        .select_with(|domain| domain.id)
+        .execute_all()?;
+    assert!(expected.len() > 1, "multiple batches should be fetched");
+
+    let iter = client
+        .query(FindDomains::new())
+        .select_with(|domain| domain.id)
+        .with_fetch_size(FetchSize::new(Some(nonzero!(1_u64))))
+        .execute_async()
+        .await?;
+    let streamed: Vec<DomainId> = QueryStream::from(iter).try_collect().await?;
+
+    assert_eq!(streamed, expected);
+
+    Ok(())
+}
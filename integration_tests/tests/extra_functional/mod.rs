@@ -3,5 +3,6 @@ mod genesis;
 mod multiple_blocks_created;
 mod normal;
 mod offline_peers;
+mod read_replica;
 mod restart_peer;
 mod unregister_peer;
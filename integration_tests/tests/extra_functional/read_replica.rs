@@ -0,0 +1,64 @@
+use std::borrow::Cow;
+
+use eyre::Result;
+use iroha::{
+    crypto::{Algorithm, KeyPair},
+    data_model::prelude::*,
+};
+use iroha_config_base::toml::WriteExt;
+use iroha_test_network::*;
+use tokio::{task::spawn_blocking, time::timeout};
+
+/// A replica follows the chain of the network it is allowed into
+/// and serves queries without ever being registered as a peer.
+#[tokio::test]
+async fn read_replica_follows_the_chain_without_registration() -> Result<()> {
+    const REPLICA_SEED: &[u8] = b"read_replica";
+
+    let replica_key = KeyPair::from_seed(REPLICA_SEED.to_vec(), Algorithm::Ed25519);
+    let network = NetworkBuilder::new()
+        .with_peers(4)
+        .with_config_layer(|c| {
+            c.write(
+                ["network", "read_replicas"],
+                [replica_key.public_key().clone()],
+            );
+        })
+        .start()
+        .await?;
+
+    let replica = NetworkPeerBuilder::new()
+        .with_seed(Some(REPLICA_SEED))
+        .build(network.env());
+    replica
+        .start(
+            network.config_layers().chain(Some(Cow::Owned(
+                toml::Table::new().write(["sumeragi", "read_replica"], true),
+            ))),
+            None,
+        )
+        .await;
+    timeout(network.sync_timeout(), replica.once_block(1)).await?;
+
+    let domain_id: DomainId = "replicated".parse()?;
+    let client = network.client();
+    let register = Register::domain(Domain::new(domain_id.clone()));
+    spawn_blocking(move || client.submit_blocking(register)).await??;
+    timeout(network.sync_timeout(), replica.once_block(2)).await?;
+
+    let client = replica.client();
+    let (domains, peers) = spawn_blocking(move || {
+        let domains = client
+            .query(FindDomains::new())
+            .filter_with(|domain| domain.id.eq(domain_id))
+            .execute_all()?;
+        let peers = client.query(FindPeers).execute_all()?;
+        Ok::<_, eyre::Report>((domains, peers))
+    })
+    .await??;
+    assert_eq!(domains.len(), 1);
+    assert_eq!(peers.len(), network.peers().len());
+    assert!(!peers.contains(&replica.id()));
+
+    Ok(())
+}
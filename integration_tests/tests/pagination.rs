@@ -67,12 +67,16 @@ fn fetch_size_should_work() -> Result<()> {
             FindAssetsDefinitions::new(),
             CompoundPredicate::PASS,
             SelectorTuple::default(),
+            None,
+            Vec::new(),
         )
         .into(),
         QueryParams::new(
             Pagination::new(Some(nonzero!(7_u64)), 1),
             Sorting::default(),
             FetchSize::new(Some(nonzero!(3_u64))),
+            None,
+            None,
         ),
     );
     let (first_batch, remaining_items, _continue_cursor) = client.start_query(query)?;
@@ -83,6 +87,149 @@ fn fetch_size_should_work() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn cursor_can_be_resumed_on_another_peer() -> Result<()> {
+    use iroha::data_model::query::{builder::QueryExecutor as _, QueryResponse};
+
+    let (network, rt) = NetworkBuilder::new().with_peers(4).start_blocking()?;
+    let client = network.peers()[0].client();
+    let other_client = network.peers()[1].client();
+
+    register_assets(&client)?;
+    rt.block_on(network.ensure_blocks(2))?;
+
+    let fetch_size = FetchSize::new(Some(nonzero!(3_u64)));
+    let query = client
+        .query(FindAssetsDefinitions::new())
+        .with_fetch_size(fetch_size)
+        .into_query_with_params();
+    let (_first_batch, _remaining_items, continue_cursor) = client.start_query(query)?;
+    let cursor = continue_cursor.expect("more batches").raw().clone();
+    let continuation = cursor
+        .continuation
+        .clone()
+        .expect("asset definitions can be resumed");
+
+    let QueryResponse::Iterable(expected) = client.raw_continue_iterable_query(cursor)? else {
+        panic!("iterable query response expected");
+    };
+    // the other peer has never seen the query
+    let query = other_client
+        .query(FindAssetsDefinitions::new())
+        .with_fetch_size(fetch_size)
+        .continue_after(continuation)
+        .into_query_with_params();
+    let (batch, remaining_items, _continue_cursor) = other_client.start_query(query)?;
+
+    assert_eq!(batch, expected.batch);
+    assert_eq!(remaining_items, expected.remaining_items);
+
+    Ok(())
+}
+
+#[test]
+fn cursor_can_be_resumed_after_new_blocks() -> Result<()> {
+    use iroha::data_model::query::{builder::QueryExecutor as _, QueryOutputBatchBox};
+
+    let (network, rt) = NetworkBuilder::new()
+        .with_peers(4)
+        .with_config_layer(|c| {
+            c.write(["snapshot", "mode"], "read_write")
+                .write(["snapshot", "create_every_ms"], 100)
+                .write(["snapshot", "deltas_per_base"], 0)
+                .write(["snapshot", "history_size"], 10);
+        })
+        .start_blocking()?;
+    let client = network.peers()[0].client();
+    let other_client = network.peers()[1].client();
+
+    register_assets(&client)?;
+    rt.block_on(network.ensure_blocks(2))?;
+    // let the peers record a snapshot of the state the query is pinned to
+    std::thread::sleep(std::time::Duration::from_secs(1));
+
+    let before = client.query(FindAssetsDefinitions::new()).execute_all()?;
+    let query = client
+        .query(FindAssetsDefinitions::new())
+        .with_fetch_size(FetchSize::new(Some(nonzero!(3_u64))))
+        .into_query_with_params();
+    let (_first_batch, _remaining_items, continue_cursor) = client.start_query(query)?;
+    let continuation = continue_cursor
+        .expect("more batches")
+        .raw()
+        .continuation
+        .clone()
+        .expect("asset definitions can be resumed");
+    let last_returned = before[2].id().clone();
+    assert_eq!(
+        continuation.id,
+        QueryOutputBatchBox::from(vec![last_returned.clone()])
+    );
+
+    // asset definitions registered after the pinned height are not returned
+    let register = ["0#wonderland", "zz#wonderland"].map(|asset_definition_id| {
+        Register::asset_definition(AssetDefinition::numeric(
+            asset_definition_id.parse().expect("Valid"),
+        ))
+    });
+    client.submit_all_blocking(register)?;
+    rt.block_on(network.ensure_blocks(3))?;
+
+    let resumed = other_client
+        .query(FindAssetsDefinitions::new())
+        .continue_after(continuation)
+        .execute_all()?;
+    assert_eq!(resumed, before[3..]);
+
+    Ok(())
+}
+
+#[test]
+fn cursor_pinned_to_unavailable_height_fails() -> Result<()> {
+    use iroha::{
+        client::QueryError,
+        data_model::query::{builder::QueryExecutor as _, error::QueryExecutionFail},
+    };
+
+    // snapshots are disabled, so past states can't be rebuilt
+    let (network, rt) = NetworkBuilder::new().start_blocking()?;
+    let client = network.client();
+
+    register_assets(&client)?;
+    rt.block_on(network.ensure_blocks(2))?;
+
+    let query = client
+        .query(FindAssetsDefinitions::new())
+        .with_fetch_size(FetchSize::new(Some(nonzero!(3_u64))))
+        .into_query_with_params();
+    let (_first_batch, _remaining_items, continue_cursor) = client.start_query(query)?;
+    let continuation = continue_cursor
+        .expect("more batches")
+        .raw()
+        .continuation
+        .clone()
+        .expect("asset definitions can be resumed");
+
+    client.submit_blocking(Register::asset_definition(AssetDefinition::numeric(
+        "zz#wonderland".parse().expect("Valid"),
+    )))?;
+    rt.block_on(network.ensure_blocks(3))?;
+
+    let err = client
+        .query(FindAssetsDefinitions::new())
+        .continue_after(continuation)
+        .execute_all()
+        .expect_err("the pinned height is no longer available");
+    assert!(matches!(
+        err,
+        QueryError::Validation(ValidationFail::QueryFailed(
+            QueryExecutionFail::HeightUnavailable
+        ))
+    ));
+
+    Ok(())
+}
+
 fn register_assets(client: &Client) -> Result<()> {
     // FIXME transaction is rejected for more than a certain number of instructions
     let register: Vec<_> = ('a'..='j')
@@ -142,3 +142,141 @@ fn test_total_quantity(
 
     Ok(())
 }
+
+#[test]
+fn filter_by_numeric_value() -> Result<()> {
+    let (network, _rt) = NetworkBuilder::new().start_blocking().unwrap();
+    let test_client = network.client();
+
+    let accounts: [AccountId; 3] = [
+        ALICE_ID.clone(),
+        gen_account_in("wonderland").0,
+        gen_account_in("wonderland").0,
+    ];
+    let register_accounts = accounts
+        .iter()
+        .skip(1) // Alice has already been registered in genesis
+        .cloned()
+        .map(|account_id| Register::account(Account::new(account_id)))
+        .collect::<Vec<_>>();
+    test_client.submit_all_blocking(register_accounts)?;
+
+    let definition_id: AssetDefinitionId = "quantity#wonderland".parse()?;
+    test_client.submit_blocking(Register::asset_definition(AssetDefinition::numeric(
+        definition_id.clone(),
+    )))?;
+    let mint_assets = accounts
+        .iter()
+        .zip([numeric!(1), numeric!(5), numeric!(10)])
+        .map(|(account_id, quantity)| {
+            Mint::asset_numeric(
+                quantity,
+                AssetId::new(definition_id.clone(), account_id.clone()),
+            )
+        })
+        .collect::<Vec<_>>();
+    test_client.submit_all_blocking(mint_assets)?;
+
+    let find_values = |predicate: CompoundPredicate<Asset>| {
+        let mut values = test_client
+            .query(FindAssets::new())
+            .filter_with(|asset| asset.id.definition.eq(definition_id.clone()))
+            .filter(predicate)
+            .select_with(|asset| asset.value)
+            .execute_all()?;
+        values.sort();
+        Ok::<_, QueryError>(values)
+    };
+
+    let greater = CompoundPredicate::<Asset>::build(|asset| asset.value.gt(numeric!(1)));
+    assert_eq!(find_values(greater)?, [numeric!(5), numeric!(10)]);
+    let greater_or_equal = CompoundPredicate::<Asset>::build(|asset| asset.value.ge(numeric!(5)));
+    assert_eq!(find_values(greater_or_equal)?, [numeric!(5), numeric!(10)]);
+    let less = CompoundPredicate::<Asset>::build(|asset| asset.value.lt(numeric!(5)));
+    assert_eq!(find_values(less)?, [numeric!(1)]);
+    let less_or_equal = CompoundPredicate::<Asset>::build(|asset| asset.value.le(numeric!(5)));
+    assert_eq!(find_values(less_or_equal)?, [numeric!(1), numeric!(5)]);
+    let equal = CompoundPredicate::<Asset>::build(|asset| asset.value.eq(numeric!(10)));
+    assert_eq!(find_values(equal)?, [numeric!(10)]);
+    let between =
+        CompoundPredicate::<Asset>::build(|asset| asset.value.between(numeric!(2)..=numeric!(10)));
+    assert_eq!(find_values(between)?, [numeric!(5), numeric!(10)]);
+
+    let total_quantity = test_client
+        .query(FindAssetsDefinitions::new())
+        .filter_with(|asset_definition| asset_definition.total_quantity.ge(numeric!(16)))
+        .select_with(|asset_definition| asset_definition.id)
+        .execute_all()?;
+    assert_eq!(total_quantity, [definition_id]);
+
+    Ok(())
+}
+
+#[test]
+fn aggregate_balances() -> Result<()> {
+    let (network, _rt) = NetworkBuilder::new().start_blocking().unwrap();
+    let test_client = network.client();
+
+    let domain_id: DomainId = "looking_glass".parse()?;
+    test_client.submit_blocking(Register::domain(Domain::new(domain_id.clone())))?;
+    let (mouse_id, _mouse_keypair) = gen_account_in("looking_glass");
+    test_client.submit_blocking(Register::account(Account::new(mouse_id.clone())))?;
+    let gold: AssetDefinitionId = "gold#looking_glass".parse()?;
+    let silver: AssetDefinitionId = "silver#looking_glass".parse()?;
+    test_client.submit_all_blocking([
+        Register::asset_definition(AssetDefinition::numeric(gold.clone())),
+        Register::asset_definition(AssetDefinition::numeric(silver.clone())),
+    ])?;
+    test_client.submit_all_blocking([
+        Mint::asset_numeric(numeric!(3), AssetId::new(gold.clone(), ALICE_ID.clone())),
+        Mint::asset_numeric(numeric!(4), AssetId::new(gold.clone(), mouse_id.clone())),
+        Mint::asset_numeric(numeric!(10), AssetId::new(silver.clone(), mouse_id)),
+    ])?;
+
+    let in_looking_glass =
+        CompoundPredicate::<Asset>::build(|asset| asset.id.definition.domain.eq(domain_id.clone()));
+    let assets = || {
+        test_client
+            .query(FindAssets::new())
+            .filter(in_looking_glass.clone())
+    };
+
+    let mut totals = assets()
+        .group_by_with(|asset| asset.id.definition)
+        .sum_with(|asset| asset.value)
+        .execute_all()?;
+    totals.sort();
+    assert_eq!(
+        totals,
+        [(gold.clone(), numeric!(7)), (silver, numeric!(10))]
+    );
+
+    assert_eq!(assets().count().execute_single()?, numeric!(3));
+    assert_eq!(
+        assets().max_with(|asset| asset.value).execute_single()?,
+        numeric!(10)
+    );
+    assert_eq!(
+        assets()
+            .filter_with(|asset| asset.id.definition.eq(gold))
+            .min_with(|asset| asset.value)
+            .execute_single()?,
+        numeric!(3)
+    );
+    assert_eq!(
+        assets()
+            .filter(CompoundPredicate::FAIL)
+            .min_with(|asset| asset.value)
+            .execute_single_opt()?,
+        None
+    );
+
+    let accounts_per_domain = test_client
+        .query(FindAccounts::new())
+        .group_by_with(|account| account.id.domain)
+        .count()
+        .execute_all()?;
+    assert!(accounts_per_domain.contains(&(domain_id, numeric!(1))));
+
+    Ok(())
+}
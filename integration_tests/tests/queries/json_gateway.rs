@@ -0,0 +1,122 @@
+use eyre::Result;
+use iroha::data_model::{
+    prelude::*,
+    query::{QueryOutputBatchBox, QueryResponse},
+};
+use iroha_test_network::*;
+use iroha_test_samples::BOB_ID;
+use reqwest::StatusCode;
+
+#[tokio::test]
+async fn json_query_is_executed_by_gateway_account() -> Result<()> {
+    let network = NetworkBuilder::new()
+        .with_config_layer(|c| {
+            c.write(["torii", "query_gateway_account"], BOB_ID.clone());
+        })
+        .start()
+        .await?;
+    let client = network.client();
+
+    let query = client
+        .query(FindDomains::new())
+        .filter_with(|domain| domain.id.eq("wonderland".parse().unwrap()))
+        .into_query_with_params();
+    let response = reqwest::Client::new()
+        .post(client.torii_url.join("/query/json")?)
+        .json(&QueryRequest::Start(query))
+        .send()
+        .await?;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let QueryResponse::Iterable(output) = response.json::<QueryResponse>().await? else {
+        panic!("Expected an iterable query response");
+    };
+    let (batch, remaining_items, _cursor) = output.into_parts();
+    assert_eq!(remaining_items, 0);
+    match batch.tuple.as_slice() {
+        [QueryOutputBatchBox::Domain(domains)] => {
+            assert_eq!(domains.len(), 1);
+            assert_eq!(domains[0].id(), &"wonderland".parse::<DomainId>()?);
+        }
+        other => panic!("Unexpected batch: {other:?}"),
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn json_gateway_is_disabled_by_default() -> Result<()> {
+    let network = NetworkBuilder::new().start().await?;
+    let client = network.client();
+
+    let query = client.query(FindDomains::new()).into_query_with_params();
+    let response = reqwest::Client::new()
+        .post(client.torii_url.join("/query/json")?)
+        .json(&QueryRequest::Start(query))
+        .send()
+        .await?;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn json_gateway_serves_query_schema() -> Result<()> {
+    let network = NetworkBuilder::new()
+        .with_config_layer(|c| {
+            c.write(["torii", "query_gateway_account"], BOB_ID.clone());
+        })
+        .start()
+        .await?;
+    let client = network.client();
+
+    let response = reqwest::Client::new()
+        .get(client.torii_url.join("/query/json/schema")?)
+        .send()
+        .await?;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let schema = response.json::<serde_json::Value>().await?;
+    for name in ["QueryRequest", "QueryResponse", "ValidationFail"] {
+        assert!(schema.get(name).is_some(), "Schema lacks `{name}`");
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn json_gateway_does_not_keep_live_queries() -> Result<()> {
+    let network = NetworkBuilder::new()
+        .with_config_layer(|c| {
+            c.write(["torii", "query_gateway_account"], BOB_ID.clone());
+        })
+        .start()
+        .await?;
+    let client = network.client();
+
+    let query = client
+        .query(FindDomains::new())
+        .with_fetch_size(FetchSize::new(Some(nonzero_ext::nonzero!(1_u64))))
+        .into_query_with_params();
+    let response = reqwest::Client::new()
+        .post(client.torii_url.join("/query/json")?)
+        .json(&QueryRequest::Start(query))
+        .send()
+        .await?;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let QueryResponse::Iterable(output) = response.json::<QueryResponse>().await? else {
+        panic!("Expected an iterable query response");
+    };
+    let (_batch, _remaining_items, cursor) = output.into_parts();
+    let cursor = cursor.expect("More domains than the fetch size");
+
+    let response = reqwest::Client::new()
+        .post(client.torii_url.join("/query/json")?)
+        .json(&QueryRequest::Continue(cursor))
+        .send()
+        .await?;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    Ok(())
+}
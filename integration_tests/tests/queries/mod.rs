@@ -9,10 +9,13 @@ use iroha_test_network::*;
 
 mod account;
 mod asset;
+mod json_gateway;
 mod metadata;
 mod query_errors;
+mod rate_limit;
 mod role;
 mod smart_contract;
+mod subscription;
 
 #[test]
 fn too_big_fetch_size_is_not_allowed() {
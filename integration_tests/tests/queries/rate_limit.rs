@@ -0,0 +1,40 @@
+use eyre::Result;
+use iroha::data_model::prelude::*;
+use iroha_test_network::*;
+use iroha_test_samples::BOB_ID;
+use reqwest::StatusCode;
+
+#[tokio::test]
+async fn queries_exceeding_rate_limit_are_rejected() -> Result<()> {
+    let network = NetworkBuilder::new()
+        .with_config_layer(|c| {
+            c.write(["torii", "query_gateway_account"], BOB_ID.clone())
+                .write(["torii", "rate_limit", "queries_per_sec"], 1)
+                .write(["torii", "rate_limit", "queries_burst"], 2);
+        })
+        .start()
+        .await?;
+    let client = network.client();
+
+    let query = QueryRequest::Start(client.query(FindDomains::new()).into_query_with_params());
+    let http = reqwest::Client::new();
+    let mut statuses = Vec::new();
+    for _ in 0..3 {
+        let response = http
+            .post(client.torii_url.join("/query/json")?)
+            .json(&query)
+            .send()
+            .await?;
+        statuses.push(response.status());
+    }
+    assert_eq!(
+        statuses,
+        [
+            StatusCode::OK,
+            StatusCode::OK,
+            StatusCode::TOO_MANY_REQUESTS
+        ]
+    );
+
+    Ok(())
+}
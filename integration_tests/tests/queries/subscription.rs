@@ -0,0 +1,133 @@
+use eyre::Result;
+use futures_util::StreamExt;
+use iroha::{
+    client::{query_subscription_api::AsyncQuerySubscriptionStream, Client},
+    data_model::{
+        prelude::*,
+        query::{error::QueryExecutionFail, QueryOutputBatchBox, QueryOutputBatchBoxTuple},
+    },
+};
+use iroha_test_network::*;
+use iroha_test_samples::ALICE_ID;
+use tokio::task::spawn_blocking;
+
+fn assets(batch: &QueryOutputBatchBoxTuple) -> Vec<Asset> {
+    match batch.tuple.as_slice() {
+        [QueryOutputBatchBox::Asset(assets)] => assets.clone(),
+        other => panic!("Unexpected batch: {other:?}"),
+    }
+}
+
+async fn submit(client: &Client, instruction: impl Into<InstructionBox>) -> Result<()> {
+    let client = client.clone();
+    let instruction = instruction.into();
+    spawn_blocking(move || client.submit_blocking(instruction)).await??;
+    Ok(())
+}
+
+async fn next_delta(stream: &mut AsyncQuerySubscriptionStream) -> Result<QueryResultsDelta> {
+    match stream.next().await.expect("Stream closed")? {
+        QuerySubscriptionMessage::Delta(delta) => Ok(delta),
+        other => panic!("Unexpected message: {other:?}"),
+    }
+}
+
+#[tokio::test]
+async fn subscription_pushes_changes_of_matching_assets() -> Result<()> {
+    let network = NetworkBuilder::new().start().await?;
+    let client = network.client();
+    let tulip: AssetDefinitionId = "tulip#wonderland".parse()?;
+    let tulip_of_alice = AssetId::new(tulip.clone(), ALICE_ID.clone());
+    submit(
+        &client,
+        Register::asset_definition(AssetDefinition::numeric(tulip.clone())),
+    )
+    .await?;
+
+    let query = client
+        .query(FindAssets::new())
+        .filter_with(|asset| asset.id.definition.eq(tulip.clone()))
+        .into_query_with_params();
+    let mut stream = client.listen_for_query_async(query).await?;
+    // nobody has tulips yet, so there are no initial batches
+    let message = stream.next().await.expect("Stream closed")?;
+    assert!(matches!(message, QuerySubscriptionMessage::Synced(_)));
+
+    submit(&client, Mint::asset_numeric(5_u32, tulip_of_alice.clone())).await?;
+    let delta = next_delta(&mut stream).await?;
+    assert_eq!(
+        assets(&delta.inserted),
+        [Asset::new(tulip_of_alice.clone(), 5_u32)]
+    );
+    assert!(assets(&delta.updated).is_empty());
+
+    submit(&client, Mint::asset_numeric(3_u32, tulip_of_alice.clone())).await?;
+    let delta = next_delta(&mut stream).await?;
+    assert!(assets(&delta.inserted).is_empty());
+    assert_eq!(
+        assets(&delta.updated),
+        [Asset::new(tulip_of_alice.clone(), 8_u32)]
+    );
+    assert_eq!(
+        assets(&delta.replaced),
+        [Asset::new(tulip_of_alice.clone(), 5_u32)]
+    );
+
+    submit(&client, Unregister::asset_definition(tulip)).await?;
+    let delta = next_delta(&mut stream).await?;
+    assert_eq!(assets(&delta.removed), [Asset::new(tulip_of_alice, 8_u32)]);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn sorted_subscription_is_rejected() -> Result<()> {
+    let network = NetworkBuilder::new().start().await?;
+    let client = network.client();
+
+    let query = client
+        .query(FindAssets::new())
+        .with_sorting(Sorting::by_metadata_key("key".parse()?))
+        .into_query_with_params();
+    let mut stream = client.listen_for_query_async(query).await?;
+
+    let message = stream.next().await.expect("Stream closed")?;
+    assert!(matches!(
+        message,
+        QuerySubscriptionMessage::Failed(ValidationFail::QueryFailed(
+            QueryExecutionFail::InvalidSubscriptionParameters
+        ))
+    ));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn subscriptions_beyond_user_capacity_are_rejected() -> Result<()> {
+    let network = NetworkBuilder::new()
+        .with_config_layer(|c| {
+            c.write(["torii", "query_subscription_capacity_per_user"], 1);
+        })
+        .start()
+        .await?;
+    let client = network.client();
+    let query = || client.query(FindDomains::new()).into_query_with_params();
+
+    let mut first = client.listen_for_query_async(query()).await?;
+    while !matches!(
+        first.next().await.expect("Stream closed")?,
+        QuerySubscriptionMessage::Synced(_)
+    ) {}
+
+    let mut second = client.listen_for_query_async(query()).await?;
+    let message = second.next().await.expect("Stream closed")?;
+    assert!(matches!(
+        message,
+        QuerySubscriptionMessage::Failed(ValidationFail::QueryFailed(
+            QueryExecutionFail::CapacityLimit
+        ))
+    ));
+
+    drop(first);
+    Ok(())
+}
@@ -342,3 +342,62 @@ fn sort_only_elements_which_have_sorting_key() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn sort_by_selected_values_in_both_directions() -> Result<()> {
+    let (network, _rt) = NetworkBuilder::new().start_blocking()?;
+    let test_client = network.client();
+
+    let balances = [("xor_a", 5_u32), ("xor_b", 13), ("xor_c", 7), ("xor_d", 13)];
+    let definitions = balances
+        .iter()
+        .map(|(name, _)| format!("{name}#wonderland").parse::<AssetDefinitionId>())
+        .collect::<Result<Vec<_>, _>>()?;
+    test_client.submit_all_blocking(
+        definitions
+            .iter()
+            .map(|id| Register::asset_definition(AssetDefinition::numeric(id.clone()))),
+    )?;
+    test_client.submit_all_blocking(definitions.iter().zip(balances).map(
+        |(definition, (_, value))| {
+            Mint::asset_numeric(value, AssetId::new(definition.clone(), ALICE_ID.clone()))
+        },
+    ))?;
+
+    let top_holdings = test_client
+        .query(FindAssets::new())
+        .filter_with(|asset| asset.id.definition.name.starts_with("xor_"))
+        .sort_by_with(SortOrder::Descending, |asset| asset.value)
+        .sort_by_with(SortOrder::Ascending, |asset| asset.id.definition)
+        .with_pagination(Pagination::new(Some(nonzero!(3_u64)), 0))
+        .select_with(|asset| asset.id.definition)
+        .execute_all()?;
+    assert_eq!(
+        top_holdings,
+        vec![
+            definitions[1].clone(),
+            definitions[3].clone(),
+            definitions[2].clone()
+        ]
+    );
+
+    let sort_by_metadata_key = "test_sort".parse::<Name>()?;
+    let domains = (0..3_u32)
+        .map(|i| format!("descending{i}").parse::<DomainId>())
+        .collect::<Result<Vec<_>, _>>()?;
+    test_client.submit_all_blocking(domains.iter().zip(0_u32..).map(|(id, i)| {
+        let mut metadata = Metadata::default();
+        metadata.insert(sort_by_metadata_key.clone(), i);
+        Register::domain(Domain::new(id.clone()).with_metadata(metadata))
+    }))?;
+
+    let res = test_client
+        .query(FindDomains::new())
+        .filter_with(|domain| domain.id.name.starts_with("descending"))
+        .with_sorting(Sorting::by_metadata_key(sort_by_metadata_key).descending())
+        .select_with(|domain| domain.id)
+        .execute_all()?;
+    assert!(res.iter().eq(domains.iter().rev()));
+
+    Ok(())
+}
@@ -36,12 +36,16 @@ fn main(host: Iroha, context: Context) {
                 FindAssets,
                 CompoundPredicate::PASS,
                 SelectorTuple::default(),
+                None,
+                Default::default(),
             )
             .into(),
             QueryParams::new(
                 Default::default(),
                 Default::default(),
                 FetchSize::new(Some(nonzero!(1_u64))),
+                None,
+                None,
             ),
         ))
         .dbg_unwrap();